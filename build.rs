@@ -0,0 +1,19 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "proto")]
+    compile_protos();
+}
+
+/// Generates the prost messages for the protobuf definitions of the typed events.  The definitions
+/// are compiled with protox, so no protoc installation is required.
+#[cfg(feature = "proto")]
+fn compile_protos() {
+    const PROTO_FILE: &str = "proto/object_store_gateway.proto";
+    println!("cargo:rerun-if-changed={PROTO_FILE}");
+    let descriptors =
+        protox::compile([PROTO_FILE], ["proto"]).expect("the proto definitions should compile");
+    prost_build::Config::new()
+        .btree_map(["."])
+        .compile_fds(descriptors)
+        .expect("the prost messages should generate");
+}
@@ -0,0 +1,44 @@
+#![no_main]
+
+//! Emits arbitrary events and generators in both the flat and custom event forms, and asserts that
+//! parsing them never panics and that valid events are parsed back unchanged.
+//!
+//! Run with `cargo fuzz run parse_after_emit` from the repository root.
+
+use cosmwasm_std::Event;
+use libfuzzer_sys::fuzz_target;
+use os_gateway_contract_attributes::{
+    parse_gateway_event, OsGatewayAttributeGenerator, OsGatewayEvent, OS_GATEWAY_ALL_KEYS,
+    OS_GATEWAY_PAYLOAD_KEY,
+};
+
+/// Whether the event is expected to survive being emitted and parsed unchanged: its values must be
+/// valid, and its extra attributes must be prefixed keys that are not otherwise recognized.
+fn round_trips(event: &OsGatewayEvent) -> bool {
+    let extra_attributes = match event {
+        OsGatewayEvent::AccessGrant(grant) => &grant.extra_attributes,
+        OsGatewayEvent::AccessRevoke(revoke) => &revoke.extra_attributes,
+        _ => return false,
+    };
+    event.validate().is_ok()
+        && extra_attributes.keys().all(|key| {
+            key.starts_with("object_store_gateway_")
+                && !OS_GATEWAY_ALL_KEYS.contains(&key.as_str())
+                && key != OS_GATEWAY_PAYLOAD_KEY
+        })
+}
+
+fuzz_target!(|input: (OsGatewayEvent, OsGatewayAttributeGenerator)| {
+    let (event, generator) = input;
+    let emitted = OsGatewayAttributeGenerator::from(event.clone());
+    for parsed in [
+        parse_gateway_event(&Event::new("wasm").add_attributes(&emitted)),
+        parse_gateway_event(&emitted.to_event()),
+    ] {
+        if round_trips(&event) {
+            assert_eq!(vec![Ok(event.clone())], parsed);
+        }
+    }
+    parse_gateway_event(&Event::new("wasm").add_attributes(&generator));
+    parse_gateway_event(&generator.to_event());
+});
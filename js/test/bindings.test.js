@@ -0,0 +1,57 @@
+const assert = require("node:assert/strict");
+const { test } = require("node:test");
+const { OsGatewayAttributeGenerator } = require("../pkg/os_gateway_contract_attributes.js");
+
+const SCOPE_ADDRESS = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+test("grants produce every attribute in emission order", () => {
+    const generator = OsGatewayAttributeGenerator.accessGrant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    generator.setAccessGrantId("my_unique_id");
+    generator.setExpiration("height:100");
+    generator.setAccessLevel("read_write");
+    generator.validate();
+    assert.deepEqual(generator.toAttributesJson(), [
+        { key: "object_store_gateway_event_type", value: "access_grant" },
+        { key: "object_store_gateway_scope_address", value: SCOPE_ADDRESS },
+        { key: "object_store_gateway_target_account_address", value: ACCOUNT_ADDRESS },
+        { key: "object_store_gateway_access_grant_id", value: "my_unique_id" },
+        { key: "object_store_gateway_expiration", value: "height:100" },
+        { key: "object_store_gateway_access_level", value: "read_write" },
+    ]);
+});
+
+test("revokes produce only their required attributes", () => {
+    const generator = OsGatewayAttributeGenerator.accessRevoke(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    generator.validate();
+    assert.deepEqual(generator.toAttributesJson(), [
+        { key: "object_store_gateway_event_type", value: "access_revoke" },
+        { key: "object_store_gateway_scope_address", value: SCOPE_ADDRESS },
+        { key: "object_store_gateway_target_account_address", value: ACCOUNT_ADDRESS },
+    ]);
+});
+
+test("the payload version can be stamped", () => {
+    const generator = OsGatewayAttributeGenerator.accessGrant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    generator.setPayloadVersion();
+    assert.ok(
+        generator
+            .toAttributesJson()
+            .some(({ key }) => key === "object_store_gateway_payload_version"),
+    );
+});
+
+test("unparseable optional values throw", () => {
+    const generator = OsGatewayAttributeGenerator.accessGrant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    assert.throws(() => generator.setExpiration("soon"), /soon/);
+    assert.throws(() => generator.setAccessLevel("admin"), /admin/);
+});
+
+test("validation reports every invalid attribute", () => {
+    const generator = OsGatewayAttributeGenerator.accessGrant("", ACCOUNT_ADDRESS);
+    generator.setAccessGrantId("");
+    assert.throws(
+        () => generator.validate(),
+        /object_store_gateway_scope_address.*object_store_gateway_access_grant_id/,
+    );
+});
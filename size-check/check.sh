@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Builds the minimal contract and fails if its wasm exceeds the budget in bytes pinned in
+# size-check/budget.  Raise the budget deliberately, in the change that causes the growth.
+set -euo pipefail
+
+cd "$(dirname "$0")"
+cargo build --release --target wasm32-unknown-unknown
+wasm=target/wasm32-unknown-unknown/release/os_gateway_contract_attributes_size_check.wasm
+size=$(wc -c < "$wasm")
+budget=$(cat budget)
+echo "contract size: ${size} bytes, budget: ${budget} bytes"
+if [ "$size" -gt "$budget" ]; then
+  echo "the contract exceeds its size budget" >&2
+  exit 1
+fi
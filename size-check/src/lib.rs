@@ -0,0 +1,20 @@
+//! A minimal contract that emits gateway attributes using only the default features, built by CI
+//! to catch growth in the wasm that the crate contributes to contracts.  The default features pull
+//! in no dependencies, so the contract's own cosmwasm-std converts the generator's key and value
+//! pairs into attributes.
+
+use cosmwasm_std::{entry_point, DepsMut, Env, MessageInfo, Response, StdResult};
+use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+
+#[entry_point]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    scope_address: String,
+) -> StdResult<Response> {
+    Ok(Response::new().add_attributes(
+        OsGatewayAttributeGenerator::access_grant(scope_address, info.sender)
+            .with_access_grant_id("size_check"),
+    ))
+}
@@ -0,0 +1,272 @@
+use crate::fingerprint::hash_fingerprint;
+use crate::OsGatewayError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+
+/// The maximum number of characters in an access grant id.
+pub(crate) const MAX_ACCESS_GRANT_ID_LENGTH: usize = 128;
+
+/// Every non-alphanumeric character that may appear in an access grant id.
+const ACCESS_GRANT_ID_PUNCTUATION: [char; 4] = ['-', '_', '.', ':'];
+
+/// A validated access grant id.  Access grant ids are the handle by which a grant is later revoked,
+/// so they are restricted to 1 to 128 ASCII alphanumeric characters, hyphens, underscores, periods,
+/// and colons, which are safe to store and compare across systems.
+///
+/// Contracts without a source of randomness can derive a stable id from the grant's contents with
+/// [derived](self::AccessGrantId::derived), and the `uuid` feature enables [new_uuid_v4](self::AccessGrantId::new_uuid_v4)
+/// for contracts that can provide their own entropy.  When the `storage` feature is enabled, the id
+/// can be used directly as a [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus) map key
+/// or prefix.  The id is accepted anywhere an access grant id string is:
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrantId, OsGatewayAttributeGenerator};
+///
+/// let access_grant_id = AccessGrantId::derived(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     "my_contract",
+/// );
+/// let generator = OsGatewayAttributeGenerator::access_grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .with_access_grant_id(access_grant_id);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccessGrantId(String);
+impl AccessGrantId {
+    /// Validates the provided value as an access grant id.  Produces an [OsGatewayError::InvalidAccessGrantId](crate::OsGatewayError::InvalidAccessGrantId)
+    /// if the value is empty, too long, or contains a disallowed character.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The access grant id to validate.
+    pub fn new<S: Into<String>>(access_grant_id: S) -> Result<Self, OsGatewayError> {
+        let access_grant_id = access_grant_id.into();
+        let invalid = |reason: String| OsGatewayError::InvalidAccessGrantId {
+            access_grant_id: access_grant_id.clone(),
+            reason,
+        };
+        if access_grant_id.is_empty() {
+            return Err(invalid("the id is empty".to_string()));
+        }
+        if access_grant_id.len() > MAX_ACCESS_GRANT_ID_LENGTH {
+            return Err(invalid(format!(
+                "the id exceeds {MAX_ACCESS_GRANT_ID_LENGTH} characters",
+            )));
+        }
+        if let Some(character) = access_grant_id.chars().find(|character| {
+            !character.is_ascii_alphanumeric() && !ACCESS_GRANT_ID_PUNCTUATION.contains(character)
+        }) {
+            return Err(invalid(format!("invalid character [{character}]")));
+        }
+        Ok(Self(access_grant_id))
+    }
+
+    /// Deterministically derives an access grant id from the grant's scope and target account, so
+    /// that contracts without a source of randomness can still produce stable ids.  The same inputs
+    /// always produce the same id, and any change to the inputs produces a different id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope to which the grant refers.
+    /// * `target_account_address` The bech32 address of the account to which the grant refers.
+    /// * `salt` An arbitrary value that distinguishes otherwise identical grants, such as a contract
+    ///   name or a sequence number.
+    pub fn derived(scope_address: &str, target_account_address: &str, salt: &str) -> Self {
+        // Length prefixes ensure that shifting characters between the inputs changes the hash
+        let input = format!(
+            "{}:{scope_address}{}:{target_account_address}{}:{salt}",
+            scope_address.len(),
+            target_account_address.len(),
+            salt.len(),
+        );
+        Self(
+            hash_fingerprint(input.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        )
+    }
+
+    /// Produces a random, hyphenated version 4 UUID access grant id from the provided bytes.  No
+    /// entropy is gathered by this function, which keeps it usable within wasm.  The caller must
+    /// provide bytes from a suitable source of randomness.
+    ///
+    /// # Parameters
+    ///
+    /// * `rng_bytes` Sixteen random bytes from which the UUID is built.
+    #[cfg(feature = "uuid")]
+    pub fn new_uuid_v4(rng_bytes: [u8; 16]) -> Self {
+        Self(
+            uuid::Builder::from_random_bytes(rng_bytes)
+                .into_uuid()
+                .hyphenated()
+                .to_string(),
+        )
+    }
+
+    /// Exposes the validated id as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the value, producing the validated id.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+impl Display for AccessGrantId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for AccessGrantId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl TryFrom<&str> for AccessGrantId {
+    type Error = OsGatewayError;
+
+    fn try_from(access_grant_id: &str) -> Result<Self, Self::Error> {
+        Self::new(access_grant_id)
+    }
+}
+impl TryFrom<String> for AccessGrantId {
+    type Error = OsGatewayError;
+
+    fn try_from(access_grant_id: String) -> Result<Self, Self::Error> {
+        Self::new(access_grant_id)
+    }
+}
+impl From<AccessGrantId> for String {
+    fn from(access_grant_id: AccessGrantId) -> Self {
+        access_grant_id.into_string()
+    }
+}
+/// Serializes the id as a plain string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AccessGrantId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+/// Deserializes the id from a plain string, rejecting values that are not valid access grant ids.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AccessGrantId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+#[cfg(feature = "storage")]
+crate::storage_keys::string_storage_key!(AccessGrantId);
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrantId, OsGatewayAttributeGenerator, OsGatewayError, OS_GATEWAY_KEYS};
+
+    fn assert_invalid(access_grant_id: &str, expected_reason: &str) {
+        assert_eq!(
+            Err(OsGatewayError::InvalidAccessGrantId {
+                access_grant_id: access_grant_id.to_string(),
+                reason: expected_reason.to_string(),
+            }),
+            AccessGrantId::new(access_grant_id),
+            "the id [{access_grant_id}] should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_valid_access_grant_ids() {
+        for access_grant_id in ["my_unique_id", "grant-1", "a.b:c", &"x".repeat(128)] {
+            assert_eq!(
+                access_grant_id,
+                AccessGrantId::new(access_grant_id)
+                    .expect("the id should be valid")
+                    .as_str(),
+                "the id [{access_grant_id}] should be accepted verbatim",
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_access_grant_ids() {
+        assert_invalid("", "the id is empty");
+        assert_invalid(&"x".repeat(129), "the id exceeds 128 characters");
+        assert_invalid("has space", "invalid character [ ]");
+        assert_invalid("semi;colon", "invalid character [;]");
+        assert_invalid("ünïcode", "invalid character [ü]");
+    }
+
+    #[test]
+    fn test_derived_access_grant_ids_are_stable() {
+        let access_grant_id = AccessGrantId::derived("scope", "target", "salt");
+        assert_eq!(
+            access_grant_id,
+            AccessGrantId::derived("scope", "target", "salt"),
+            "the same inputs should derive the same id",
+        );
+        assert_eq!(
+            Ok(access_grant_id.clone()),
+            AccessGrantId::new(access_grant_id.as_str()),
+            "a derived id should pass validation",
+        );
+        for other in [
+            AccessGrantId::derived("scope", "target", "other_salt"),
+            AccessGrantId::derived("scope", "other_target", "salt"),
+            AccessGrantId::derived("other_scope", "target", "salt"),
+            AccessGrantId::derived("scopet", "arget", "salt"),
+        ] {
+            assert_ne!(
+                access_grant_id, other,
+                "any change to the inputs should derive a different id",
+            );
+        }
+    }
+
+    #[test]
+    fn test_generator_accepts_access_grant_ids() {
+        let access_grant_id = AccessGrantId::new("my_unique_id").unwrap();
+        let generator = OsGatewayAttributeGenerator::access_grant("scope", "target")
+            .with_access_grant_id(access_grant_id);
+        assert_eq!(
+            Some("my_unique_id"),
+            generator.get(OS_GATEWAY_KEYS.access_grant_id),
+            "the generator should accept a validated id",
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_v4_access_grant_ids() {
+        let access_grant_id = AccessGrantId::new_uuid_v4([0xff; 16]);
+        assert_eq!(
+            "ffffffff-ffff-4fff-bfff-ffffffffffff",
+            access_grant_id.as_str(),
+            "the id should be a hyphenated uuid with the version and variant bits set",
+        );
+        assert!(
+            AccessGrantId::new(access_grant_id.as_str()).is_ok(),
+            "a uuid id should pass validation",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates_access_grant_ids() {
+        let access_grant_id = AccessGrantId::new("my_unique_id").unwrap();
+        let json = cosmwasm_std::to_json_binary(&access_grant_id).unwrap();
+        assert_eq!(
+            access_grant_id,
+            cosmwasm_std::from_json::<AccessGrantId>(&json).unwrap(),
+            "the id should survive a serde round trip",
+        );
+        assert!(
+            cosmwasm_std::from_json::<AccessGrantId>(b"\"has space\"").is_err(),
+            "an invalid id should not deserialize",
+        );
+    }
+}
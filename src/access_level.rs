@@ -0,0 +1,180 @@
+use crate::OsGatewayError;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// The encoded value of read-only access.
+const READ_VALUE: &str = "read";
+
+/// The encoded value of read and write access.
+const READ_WRITE_VALUE: &str = "read_write";
+
+/// The level of access that an access grant gives its target account, encoded in the
+/// [access level attribute](crate::OsGatewayKeys).  Grants without an access level are given the
+/// gateway's default access.
+///
+/// Only recognized levels can be constructed, so generators can never emit an unrecognized level.
+/// Consumers that must tolerate levels introduced by newer releases can use
+/// [parse_lenient](self::AccessLevel::parse_lenient) rather than [FromStr].
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessLevel, OsGatewayAttributeGenerator};
+///
+/// let generator = OsGatewayAttributeGenerator::grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .with_access_level(AccessLevel::ReadWrite)
+/// .build();
+/// assert_eq!(Some("read_write"), generator.get("object_store_gateway_access_level"));
+/// ```
+///
+/// # Variants
+///
+/// * `Read` The target account may read the records of the scope.
+///
+/// * `ReadWrite` The target account may read and modify the records of the scope.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum AccessLevel {
+    Read,
+    ReadWrite,
+}
+impl AccessLevel {
+    /// Every variant, in declaration order.
+    pub const ALL: [AccessLevel; 2] = [Self::Read, Self::ReadWrite];
+
+    /// The exact attribute value that denotes this level.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => READ_VALUE,
+            Self::ReadWrite => READ_WRITE_VALUE,
+        }
+    }
+
+    /// Parses an attribute value without rejecting unrecognized levels, which are instead produced
+    /// as [LenientAccessLevel::Unknown](crate::LenientAccessLevel::Unknown).
+    ///
+    /// # Parameters
+    ///
+    /// * `value` The attribute value to parse.
+    pub fn parse_lenient<S: Into<String>>(value: S) -> LenientAccessLevel {
+        let value = value.into();
+        match value.parse() {
+            Ok(access_level) => LenientAccessLevel::Known(access_level),
+            Err(_) => LenientAccessLevel::Unknown(value),
+        }
+    }
+}
+impl Display for AccessLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+/// Parses an exact attribute value into its level, producing an [OsGatewayError::UnknownAccessLevel](crate::OsGatewayError::UnknownAccessLevel)
+/// if the value is not recognized.
+impl FromStr for AccessLevel {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|access_level| access_level.as_str() == s)
+            .ok_or_else(|| OsGatewayError::UnknownAccessLevel {
+                access_level: s.to_string(),
+            })
+    }
+}
+/// Serializes the level as its attribute value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AccessLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+/// Deserializes the level from its attribute value, rejecting unrecognized levels.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AccessLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// The result of leniently parsing an access level with [parse_lenient](crate::AccessLevel::parse_lenient).
+///
+/// # Variants
+///
+/// * `Known` A level recognized by this release.
+///
+/// * `Unknown` An unrecognized level, containing the raw attribute value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LenientAccessLevel {
+    Known(AccessLevel),
+    Unknown(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessLevel, LenientAccessLevel, OsGatewayError};
+
+    #[test]
+    fn test_every_variant_round_trips() {
+        for access_level in AccessLevel::ALL {
+            assert_eq!(
+                access_level.as_str(),
+                access_level.to_string(),
+                "the level [{access_level:?}] should display as its attribute value",
+            );
+            assert_eq!(
+                Ok(access_level),
+                access_level.as_str().parse::<AccessLevel>(),
+                "the level [{access_level}] should parse back into the same variant",
+            );
+            assert_eq!(
+                LenientAccessLevel::Known(access_level),
+                AccessLevel::parse_lenient(access_level.as_str()),
+                "the level [{access_level}] should leniently parse as a known level",
+            );
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_strings_are_rejected() {
+        for value in ["", "Read", "READ_WRITE", "readwrite", " read", "admin"] {
+            assert_eq!(
+                Err(OsGatewayError::UnknownAccessLevel {
+                    access_level: value.to_string(),
+                }),
+                value.parse::<AccessLevel>(),
+                "the value [{value}] should be rejected",
+            );
+            assert_eq!(
+                LenientAccessLevel::Unknown(value.to_string()),
+                AccessLevel::parse_lenient(value),
+                "the value [{value}] should leniently parse as an unknown level",
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_attribute_value() {
+        let json = cosmwasm_std::to_json_vec(&AccessLevel::ReadWrite).unwrap();
+        assert_eq!(
+            b"\"read_write\"".to_vec(),
+            json,
+            "the level should serialize as its attribute value",
+        );
+        assert_eq!(
+            AccessLevel::ReadWrite,
+            cosmwasm_std::from_json::<AccessLevel>(&json).unwrap(),
+            "the level should survive a serde round trip",
+        );
+        assert!(
+            cosmwasm_std::from_json::<AccessLevel>(b"\"admin\"").is_err(),
+            "an unrecognized level should not deserialize",
+        );
+    }
+}
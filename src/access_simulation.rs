@@ -0,0 +1,279 @@
+use crate::{
+    parse_gateway_events, AccessGrant, GrantKey, MockGateway, OsGatewayError, OsGatewayEvent,
+    ProcessingContext, ProcessingOutcome,
+};
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Event, Response};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// The signer attributed to every simulated event.  Responses do not record who signed the
+/// transaction, so the simulation registers this account as the value owner of every scope it
+/// encounters, and every event is honored.
+const SIMULATED_SIGNER: &str = "simulated_value_owner";
+
+/// A grant held at the end of a simulation, along with the response that created it.
+///
+/// # Parameters
+///
+/// * `grant` The grant, exactly as it was emitted.
+///
+/// * `response_index` The position, within the simulated responses, of the response that emitted
+///   the grant.  Later duplicates of the grant do not replace it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedGrant {
+    pub grant: AccessGrant,
+    pub response_index: usize,
+}
+
+/// The net access produced by a sequence of responses, as computed by
+/// [simulate_access_state](crate::simulate_access_state).
+///
+/// Responses do not record the block in which they were produced, so grant expirations are not
+/// evaluated, and every grant held counts as giving access.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessState {
+    grants: Vec<SimulatedGrant>,
+}
+impl AccessState {
+    /// Whether or not the account holds any grant of the scope.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `account_address` The bech32 address of the account.
+    pub fn has_access(&self, scope_address: &str, account_address: &str) -> bool {
+        self.grants_for_scope(scope_address)
+            .iter()
+            .any(|simulated| simulated.grant.target_account_address == account_address)
+    }
+
+    /// Every grant held, ordered by scope address, then by target account, and then by access
+    /// grant id.
+    pub fn grants(&self) -> &[SimulatedGrant] {
+        &self.grants
+    }
+
+    /// Every grant of the scope held, ordered by target account and then by access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    pub fn grants_for_scope(&self, scope_address: &str) -> Vec<&SimulatedGrant> {
+        self.grants
+            .iter()
+            .filter(|simulated| simulated.grant.scope_address == scope_address)
+            .collect()
+    }
+
+    /// Every account that holds a grant of the scope, in order and without repeats.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    pub fn accounts_with_access(&self, scope_address: &str) -> Vec<&str> {
+        let mut accounts = self
+            .grants_for_scope(scope_address)
+            .into_iter()
+            .map(|simulated| simulated.grant.target_account_address.as_str())
+            .collect::<Vec<&str>>();
+        accounts.dedup();
+        accounts
+    }
+
+    /// Whether or not no grants are held at all.
+    pub fn is_empty(&self) -> bool {
+        self.grants.is_empty()
+    }
+}
+
+/// The errors produced by [simulate_access_state](crate::simulate_access_state).
+///
+/// # Variants
+///
+/// * `InvalidEvent` Denotes that a response contained a gateway event that could not be parsed.
+///   The `response_index` field contains the position of the response, and the `error` field
+///   contains the reason the event was rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimulationError {
+    InvalidEvent {
+        response_index: usize,
+        error: OsGatewayError,
+    },
+}
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidEvent {
+                response_index,
+                error,
+            } => write!(
+                f,
+                "response [{response_index}] contained an invalid gateway event: {error}"
+            ),
+        }
+    }
+}
+impl std::error::Error for SimulationError {}
+
+/// Computes the net access produced by applying the gateway events of every response, in order,
+/// with the rules of a [MockGateway](crate::MockGateway).  Use this to assert the outcome of a
+/// multi-step workflow, such as one driven through cw-multi-test:
+///
+/// ```
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::{simulate_access_state, OsGatewayResponseExt};
+///
+/// let scope_address = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+/// let originator = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+/// let servicer = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+/// let responses: Vec<Response> = vec![
+///     Response::new().add_os_gateway_grant(scope_address, originator),
+///     Response::new().add_os_gateway_grant(scope_address, servicer),
+///     Response::new().add_os_gateway_revoke(scope_address, originator),
+/// ];
+/// let state = simulate_access_state(&responses).unwrap();
+/// assert_eq!(vec![servicer], state.accounts_with_access(scope_address));
+/// ```
+///
+/// Each response's flat attributes are read as the `wasm` event that the chain would record,
+/// followed by its custom events.  Every event is treated as signed by the scope's value owner, as
+/// responses do not record their signer.  Fails on the first gateway event that cannot be parsed.
+///
+/// # Parameters
+///
+/// * `responses` The responses, in the order in which they were produced.
+pub fn simulate_access_state<'a, T: 'a>(
+    responses: impl IntoIterator<Item = &'a Response<T>>,
+) -> Result<AccessState, SimulationError> {
+    let mut gateway = MockGateway::new();
+    let mut provenance = BTreeMap::<GrantKey, usize>::new();
+    for (response_index, response) in responses.into_iter().enumerate() {
+        let events =
+            std::iter::once(Event::new("wasm").add_attributes(response.attributes.clone()))
+                .chain(response.events.iter().cloned())
+                .collect::<Vec<Event>>();
+        for event in parse_gateway_events(&events) {
+            let event = event.map_err(|error| SimulationError::InvalidEvent {
+                response_index,
+                error,
+            })?;
+            gateway.set_value_owner(event.scope_address(), SIMULATED_SIGNER);
+            let key = match &event {
+                OsGatewayEvent::AccessGrant(grant) => Some(GrantKey::from(grant)),
+                _ => None,
+            };
+            if let ProcessingOutcome::Granted =
+                gateway.process(event, ProcessingContext::new(SIMULATED_SIGNER, mock_env()))
+            {
+                provenance.insert(key.expect("only grants are granted"), response_index);
+            }
+        }
+    }
+    Ok(AccessState {
+        grants: gateway
+            .grants()
+            .into_iter()
+            .map(|grant| SimulatedGrant {
+                response_index: provenance[&GrantKey::from(grant)],
+                grant: grant.clone(),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        simulate_access_state, AccessGrant, OsGatewayAttributeGenerator, OsGatewayError,
+        OsGatewayResponseExt, SimulatedGrant, SimulationError,
+    };
+    use cosmwasm_std::Response;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ORIGINATOR_ADDRESS: &str =
+        "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+    const SERVICER_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    #[test]
+    fn test_only_the_servicer_retains_access() {
+        let responses: Vec<Response> = vec![
+            Response::new()
+                .add_os_gateway_grant(SCOPE_ADDRESS, ORIGINATOR_ADDRESS)
+                .add_os_gateway_grant(OTHER_SCOPE_ADDRESS, ORIGINATOR_ADDRESS),
+            Response::new().add_os_gateway_event(
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, SERVICER_ADDRESS)
+                    .with_access_grant_id("servicing"),
+            ),
+            Response::new()
+                .add_os_gateway_revoke(SCOPE_ADDRESS, ORIGINATOR_ADDRESS)
+                .add_os_gateway_revoke(OTHER_SCOPE_ADDRESS, ORIGINATOR_ADDRESS),
+        ];
+        let state = simulate_access_state(&responses).unwrap();
+        assert_eq!(
+            vec![SERVICER_ADDRESS],
+            state.accounts_with_access(SCOPE_ADDRESS),
+            "only the servicer should retain access to the scope",
+        );
+        assert!(
+            !state.has_access(OTHER_SCOPE_ADDRESS, ORIGINATOR_ADDRESS),
+            "the originator should have lost access to the other scope",
+        );
+        assert_eq!(
+            &[SimulatedGrant {
+                grant: AccessGrant::new(SCOPE_ADDRESS, SERVICER_ADDRESS)
+                    .with_access_grant_id("servicing"),
+                response_index: 1,
+            }],
+            state.grants(),
+            "the grant should be attributed to the response that created it",
+        );
+    }
+
+    #[test]
+    fn test_custom_events_and_duplicates_are_applied() {
+        let responses: Vec<Response> = vec![
+            Response::new().add_event(
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, SERVICER_ADDRESS)
+                    .to_event(),
+            ),
+            Response::new().add_os_gateway_grant(SCOPE_ADDRESS, SERVICER_ADDRESS),
+        ];
+        let state = simulate_access_state(&responses).unwrap();
+        assert_eq!(
+            vec![0],
+            state
+                .grants_for_scope(SCOPE_ADDRESS)
+                .into_iter()
+                .map(|simulated| simulated.response_index)
+                .collect::<Vec<usize>>(),
+            "a duplicate grant should not replace the grant's provenance",
+        );
+        assert!(
+            simulate_access_state(&Vec::<Response>::new())
+                .unwrap()
+                .is_empty(),
+            "no responses should produce no access",
+        );
+    }
+
+    #[test]
+    fn test_invalid_events_fail_the_simulation() {
+        let responses: Vec<Response> = vec![
+            Response::new().add_os_gateway_grant(SCOPE_ADDRESS, SERVICER_ADDRESS),
+            Response::new().add_attribute("object_store_gateway_event_type", "access_grant"),
+        ];
+        let error = simulate_access_state(&responses).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                SimulationError::InvalidEvent {
+                    response_index: 1,
+                    error: OsGatewayError::MissingRequiredAttributes { .. },
+                },
+            ),
+            "the invalid event should be reported with its response, not {error:?}",
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use crate::OsGatewayError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+
+/// The human-readable part of every bech32 [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+/// address.
+pub(crate) const SCOPE_HRP: &str = "scope";
+
+/// The human-readable part of every bech32 [Provenance Blockchain Scope Specification](https://docs.provenance.io/modules/metadata-module#scope-specification)
+/// address.
+pub(crate) const SCOPE_SPEC_HRP: &str = "scopespec";
+
+/// The human-readable parts of [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+/// addresses on mainnet and testnet, respectively.
+const DEFAULT_ACCOUNT_HRPS: [&str; 2] = ["pb", "tp"];
+
+/// The separator between the human-readable part and the data part of a bech32 string.
+const BECH32_SEPARATOR: char = '1';
+
+/// Every character that may appear in the data part of a bech32 string.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The number of characters at the end of the data part of a bech32 string that hold its checksum.
+const BECH32_CHECKSUM_LENGTH: usize = 6;
+
+/// A validated bech32 address of a [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope).
+///
+/// Distinct address types allow the compiler to catch swapped scope and account arguments.  The
+/// address is verified to be a lowercase bech32 string with the `scope` human-readable part.  When
+/// the `bech32` feature is enabled, the address's checksum is verified as well.  When the `storage`
+/// feature is enabled, the address can be used directly as a [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus)
+/// map key or prefix.
+///
+/// ```
+/// use os_gateway_contract_attributes::ScopeAddress;
+///
+/// let scope_address = ScopeAddress::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zz")
+///     .expect("the scope address should be valid");
+/// assert_eq!("scope1qzn7jghj8puprmdcvunm3330jutsj803zz", scope_address.as_str());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeAddress(String);
+impl ScopeAddress {
+    /// Validates the provided value as a bech32 scope address.  Produces an [OsGatewayError::InvalidAddress](crate::OsGatewayError::InvalidAddress)
+    /// if the value is not a valid scope address.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope).
+    pub fn new<S: Into<String>>(scope_address: S) -> Result<Self, OsGatewayError> {
+        let scope_address = scope_address.into();
+        validate_bech32(&scope_address, &[SCOPE_HRP])?;
+        Ok(Self(scope_address))
+    }
+
+    /// Exposes the validated address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the value, producing the validated address.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+impl Display for ScopeAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for ScopeAddress {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl TryFrom<&str> for ScopeAddress {
+    type Error = OsGatewayError;
+
+    fn try_from(scope_address: &str) -> Result<Self, Self::Error> {
+        Self::new(scope_address)
+    }
+}
+impl TryFrom<String> for ScopeAddress {
+    type Error = OsGatewayError;
+
+    fn try_from(scope_address: String) -> Result<Self, Self::Error> {
+        Self::new(scope_address)
+    }
+}
+impl From<ScopeAddress> for String {
+    fn from(scope_address: ScopeAddress) -> Self {
+        scope_address.into_string()
+    }
+}
+/// Serializes the address as a plain string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ScopeAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+/// Deserializes the address from a plain string, rejecting values that are not valid scope
+/// addresses.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScopeAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A validated bech32 address of a [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts).
+///
+/// The address is verified to be a lowercase bech32 string with an allowed human-readable part,
+/// which defaults to the mainnet (`pb`) and testnet (`tp`) prefixes.  When the `bech32` feature is
+/// enabled, the address's checksum is verified as well.  When the `storage` feature is enabled, the
+/// address can be used directly as a [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus)
+/// map key or prefix.
+///
+/// ```
+/// use os_gateway_contract_attributes::AccountAddress;
+///
+/// let account_address = AccountAddress::new("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr")
+///     .expect("the account address should be valid");
+/// assert_eq!("tp", account_address.hrp());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AccountAddress(String);
+impl AccountAddress {
+    /// Validates the provided value as a bech32 account address with a mainnet (`pb`) or testnet
+    /// (`tp`) prefix.  Produces an [OsGatewayError::InvalidAddress](crate::OsGatewayError::InvalidAddress)
+    /// if the value is not a valid account address.
+    ///
+    /// # Parameters
+    ///
+    /// * `account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts).
+    pub fn new<S: Into<String>>(account_address: S) -> Result<Self, OsGatewayError> {
+        Self::new_with_hrps(account_address, &DEFAULT_ACCOUNT_HRPS)
+    }
+
+    /// Validates the provided value as a bech32 account address with one of the given prefixes.
+    /// Produces an [OsGatewayError::InvalidAddress](crate::OsGatewayError::InvalidAddress) if the
+    /// value is not a valid account address.
+    ///
+    /// # Parameters
+    ///
+    /// * `account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts).
+    /// * `allowed_hrps` Every human-readable part that the address may use as its prefix.
+    pub fn new_with_hrps<S: Into<String>>(
+        account_address: S,
+        allowed_hrps: &[&str],
+    ) -> Result<Self, OsGatewayError> {
+        let account_address = account_address.into();
+        validate_bech32(&account_address, allowed_hrps)?;
+        Ok(Self(account_address))
+    }
+
+    /// The human-readable part of the address, such as `pb` for mainnet accounts.
+    pub fn hrp(&self) -> &str {
+        self.0
+            .rsplit_once(BECH32_SEPARATOR)
+            .map(|(hrp, _)| hrp)
+            .unwrap_or_default()
+    }
+
+    /// Exposes the validated address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the value, producing the validated address.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+impl Display for AccountAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl AsRef<str> for AccountAddress {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl TryFrom<&str> for AccountAddress {
+    type Error = OsGatewayError;
+
+    fn try_from(account_address: &str) -> Result<Self, Self::Error> {
+        Self::new(account_address)
+    }
+}
+impl TryFrom<String> for AccountAddress {
+    type Error = OsGatewayError;
+
+    fn try_from(account_address: String) -> Result<Self, Self::Error> {
+        Self::new(account_address)
+    }
+}
+impl From<AccountAddress> for String {
+    fn from(account_address: AccountAddress) -> Self {
+        account_address.into_string()
+    }
+}
+/// Serializes the address as a plain string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AccountAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+/// Deserializes the address from a plain string, rejecting values that are not valid mainnet or
+/// testnet account addresses.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AccountAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "storage")]
+crate::storage_keys::string_storage_key!(ScopeAddress);
+#[cfg(feature = "storage")]
+crate::storage_keys::string_storage_key!(AccountAddress);
+
+/// Verifies that the address is a lowercase bech32 string with one of the allowed human-readable
+/// parts, producing that human-readable part.  The checksum is only verified when the `bech32`
+/// feature is enabled.
+pub(crate) fn validate_bech32<'a>(
+    address: &'a str,
+    allowed_hrps: &[&str],
+) -> Result<&'a str, OsGatewayError> {
+    let invalid = |reason: String| OsGatewayError::InvalidAddress {
+        address: address.to_string(),
+        reason,
+    };
+    let (hrp, data) = address
+        .rsplit_once(BECH32_SEPARATOR)
+        .ok_or_else(|| invalid("missing the bech32 separator".to_string()))?;
+    if !allowed_hrps.contains(&hrp) {
+        return Err(invalid(format!(
+            "expected a prefix of [{}] but found [{hrp}]",
+            allowed_hrps.join(", "),
+        )));
+    }
+    if data.len() <= BECH32_CHECKSUM_LENGTH {
+        return Err(invalid("the data part is too short".to_string()));
+    }
+    if let Some(character) = data.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+        return Err(invalid(format!("invalid bech32 character [{character}]")));
+    }
+    #[cfg(feature = "bech32")]
+    bech32::decode(address).map_err(|error| invalid(error.to_string()))?;
+    Ok(hrp)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccountAddress, OsGatewayError, ScopeAddress};
+
+    const VALID_SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const VALID_ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn assert_invalid(address: &str, expected_reason: &str) {
+        assert_eq!(
+            Err(OsGatewayError::InvalidAddress {
+                address: address.to_string(),
+                reason: expected_reason.to_string(),
+            }),
+            ScopeAddress::new(address),
+            "the address [{address}] should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_valid_scope_address() {
+        let scope_address =
+            ScopeAddress::try_from(VALID_SCOPE_ADDRESS).expect("the scope address should be valid");
+        assert_eq!(
+            VALID_SCOPE_ADDRESS,
+            scope_address.as_str(),
+            "the address should be retained verbatim",
+        );
+        assert_eq!(
+            VALID_SCOPE_ADDRESS,
+            scope_address.to_string(),
+            "the address should display as the underlying string",
+        );
+        assert_eq!(
+            VALID_SCOPE_ADDRESS,
+            String::from(scope_address),
+            "the address should convert back into the underlying string",
+        );
+    }
+
+    #[test]
+    fn test_invalid_scope_addresses() {
+        assert_invalid("scope", "missing the bech32 separator");
+        assert_invalid(
+            "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+            "expected a prefix of [scope] but found [tp]",
+        );
+        assert_invalid("scope1qqqqqq", "the data part is too short");
+        assert_invalid(
+            "scope1qzn7jghj8puprmdcvunm3330jutsj803zb",
+            "invalid bech32 character [b]",
+        );
+        assert_invalid(
+            "SCOPE1QZN7JGHJ8PUPRMDCVUNM3330JUTSJ803ZZ",
+            "expected a prefix of [scope] but found [SCOPE]",
+        );
+    }
+
+    #[test]
+    fn test_valid_account_address() {
+        let account_address = AccountAddress::try_from(VALID_ACCOUNT_ADDRESS)
+            .expect("the account address should be valid");
+        assert_eq!(
+            "tp",
+            account_address.hrp(),
+            "the human-readable part should be exposed",
+        );
+        assert_eq!(
+            VALID_ACCOUNT_ADDRESS,
+            account_address.to_string(),
+            "the address should display as the underlying string",
+        );
+    }
+
+    #[test]
+    fn test_account_address_hrps_are_configurable() {
+        assert_eq!(
+            Err(OsGatewayError::InvalidAddress {
+                address: VALID_ACCOUNT_ADDRESS.to_string(),
+                reason: "expected a prefix of [pb] but found [tp]".to_string(),
+            }),
+            AccountAddress::new_with_hrps(VALID_ACCOUNT_ADDRESS, &["pb"]),
+            "an address with a disallowed prefix should be rejected",
+        );
+        assert_eq!(
+            Err(OsGatewayError::InvalidAddress {
+                address: VALID_SCOPE_ADDRESS.to_string(),
+                reason: "expected a prefix of [pb, tp] but found [scope]".to_string(),
+            }),
+            AccountAddress::new(VALID_SCOPE_ADDRESS),
+            "a scope address should not be accepted as an account address",
+        );
+        assert!(
+            AccountAddress::new_with_hrps(VALID_ACCOUNT_ADDRESS, &["pb", "tp"]).is_ok(),
+            "an address with an allowed prefix should be accepted",
+        );
+    }
+
+    #[test]
+    fn test_account_addresses_are_ordered_by_value() {
+        let mut addresses = [
+            AccountAddress::new(VALID_ACCOUNT_ADDRESS).unwrap(),
+            AccountAddress::new("pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p").unwrap(),
+        ];
+        addresses.sort();
+        assert_eq!(
+            vec![
+                "pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p",
+                VALID_ACCOUNT_ADDRESS
+            ],
+            addresses
+                .iter()
+                .map(|address| address.as_str())
+                .collect::<Vec<&str>>(),
+            "addresses should be ordered by their string values",
+        );
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_invalid_scope_address_checksum() {
+        assert!(
+            matches!(
+                ScopeAddress::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zq"),
+                Err(OsGatewayError::InvalidAddress { .. }),
+            ),
+            "an address with an invalid checksum should be rejected",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates_scope_addresses() {
+        let scope_address = ScopeAddress::new(VALID_SCOPE_ADDRESS).unwrap();
+        let json = cosmwasm_std::to_json_binary(&scope_address).unwrap();
+        assert_eq!(
+            format!("\"{VALID_SCOPE_ADDRESS}\""),
+            String::from_utf8(json.to_vec()).unwrap(),
+            "the address should serialize as a plain string",
+        );
+        assert_eq!(
+            scope_address,
+            cosmwasm_std::from_json::<ScopeAddress>(&json).unwrap(),
+            "the address should deserialize from a plain string",
+        );
+        assert!(
+            cosmwasm_std::from_json::<ScopeAddress>(b"\"not_a_scope\"").is_err(),
+            "an invalid address should not deserialize",
+        );
+    }
+}
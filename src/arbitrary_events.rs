@@ -0,0 +1,228 @@
+use crate::access_grant_id::MAX_ACCESS_GRANT_ID_LENGTH;
+use crate::attribute_keys::ALL_KEYS;
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::{
+    AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayAttributeGenerator,
+    OsGatewayEvent, OS_GATEWAY_EVENT_TYPE_STRS, OS_GATEWAY_KEYS,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::collections::BTreeMap;
+
+/// Valid scope addresses, drawn from often so that fuzzed events are frequently valid.
+const SCOPE_ADDRESSES: [&str; 2] = [
+    "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+];
+
+/// Valid account addresses, drawn from often so that fuzzed events are frequently valid.
+const ACCOUNT_ADDRESSES: [&str; 3] = [
+    "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    "tp1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfxwkgt",
+    "pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p",
+];
+
+/// Produces one of the given valid addresses most of the time, and otherwise an empty or
+/// arbitrary string.
+fn arbitrary_address(u: &mut Unstructured, valid_addresses: &[&str]) -> Result<String> {
+    Ok(match u.int_in_range(0..=3)? {
+        0 | 1 => u.choose(valid_addresses)?.to_string(),
+        2 => String::new(),
+        _ => String::arbitrary(u)?,
+    })
+}
+
+/// Produces an absent, valid, maximum length, overlong, empty, or arbitrary access grant id.
+fn arbitrary_access_grant_id(u: &mut Unstructured) -> Result<Option<String>> {
+    Ok(match u.int_in_range(0..=5)? {
+        0 => None,
+        1 => Some(format!("grant-{}", u32::arbitrary(u)?)),
+        2 => Some("a".repeat(MAX_ACCESS_GRANT_ID_LENGTH)),
+        3 => Some("a".repeat(MAX_ACCESS_GRANT_ID_LENGTH + 1)),
+        4 => Some(String::new()),
+        _ => Some(String::arbitrary(u)?),
+    })
+}
+
+/// Produces up to two extra attributes, keyed by a prefixed key, a recognized key, or an arbitrary
+/// key.  Arbitrary keys never begin with an underscore, as cosmwasm reserves those keys and
+/// refuses to emit them.
+fn arbitrary_extra_attributes(u: &mut Unstructured) -> Result<BTreeMap<String, String>> {
+    let mut extra_attributes = BTreeMap::new();
+    for _ in 0..u.int_in_range(0..=2)? {
+        let key = match u.int_in_range(0..=2)? {
+            0 => format!("{OS_GATEWAY_KEY_PREFIX}{}", String::arbitrary(u)?),
+            1 => u.choose(&ALL_KEYS)?.to_string(),
+            _ => String::arbitrary(u)?.trim_start_matches('_').to_string(),
+        };
+        extra_attributes.insert(key, String::arbitrary(u)?);
+    }
+    Ok(extra_attributes)
+}
+
+impl<'a> Arbitrary<'a> for AccessLevel {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[Self::Read, Self::ReadWrite])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for GrantExpiration {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::Never,
+            1 => Self::AtSeconds(u64::arbitrary(u)?),
+            _ => Self::AtHeight(u64::arbitrary(u)?),
+        })
+    }
+}
+
+/// Produces a mix of valid and borderline grants: addresses are usually valid but may be empty or
+/// arbitrary unicode, access grant ids range from absent to overlong, and extra attributes may
+/// collide with recognized keys.
+impl<'a> Arbitrary<'a> for AccessGrant {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            scope_address: arbitrary_address(u, &SCOPE_ADDRESSES)?,
+            target_account_address: arbitrary_address(u, &ACCOUNT_ADDRESSES)?,
+            access_grant_id: arbitrary_access_grant_id(u)?,
+            expiration: Option::arbitrary(u)?,
+            access_level: Option::arbitrary(u)?,
+            extra_attributes: arbitrary_extra_attributes(u)?,
+        })
+    }
+}
+
+/// Produces a mix of valid and borderline revokes, in the same manner as grants.
+impl<'a> Arbitrary<'a> for AccessRevoke {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            scope_address: arbitrary_address(u, &SCOPE_ADDRESSES)?,
+            target_account_address: arbitrary_address(u, &ACCOUNT_ADDRESSES)?,
+            access_grant_id: arbitrary_access_grant_id(u)?,
+            extra_attributes: arbitrary_extra_attributes(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OsGatewayEvent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if bool::arbitrary(u)? {
+            Self::AccessGrant(AccessGrant::arbitrary(u)?)
+        } else {
+            Self::AccessRevoke(AccessRevoke::arbitrary(u)?)
+        })
+    }
+}
+
+/// Produces either the attributes of an arbitrary [OsGatewayEvent](crate::OsGatewayEvent), or
+/// attributes assembled directly, which may hold unrecognized event types and unparseable values
+/// that the typed events cannot represent.  The required attributes are always present.
+impl<'a> Arbitrary<'a> for OsGatewayAttributeGenerator {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if bool::arbitrary(u)? {
+            return Ok(OsGatewayEvent::arbitrary(u)?.into());
+        }
+        let event_type = if bool::arbitrary(u)? {
+            u.choose(&OS_GATEWAY_EVENT_TYPE_STRS)?.to_string()
+        } else {
+            String::arbitrary(u)?
+        };
+        let mut generator = Self::new()
+            .with_event_type(event_type)
+            .with_scope_address(arbitrary_address(u, &SCOPE_ADDRESSES)?)
+            .with_target_account_address(arbitrary_address(u, &ACCOUNT_ADDRESSES)?);
+        for key in [
+            OS_GATEWAY_KEYS.access_grant_id,
+            OS_GATEWAY_KEYS.expiration,
+            OS_GATEWAY_KEYS.access_level,
+            OS_GATEWAY_KEYS.payload_version,
+            OS_GATEWAY_KEYS.source_contract_version,
+        ] {
+            if bool::arbitrary(u)? {
+                generator.set_attribute(key, String::arbitrary(u)?);
+            }
+        }
+        for (key, value) in arbitrary_extra_attributes(u)? {
+            generator.set_attribute(key, value);
+        }
+        Ok(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute_keys::ALL_KEYS;
+    use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+    use crate::{
+        parse_gateway_event, OsGatewayAttributeGenerator, OsGatewayEvent, OS_GATEWAY_PAYLOAD_KEY,
+    };
+    use arbitrary::{Arbitrary, Unstructured};
+    use cosmwasm_std::Event;
+
+    /// Produces deterministic pseudo-random bytes from the seed.
+    fn bytes(seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..256)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    /// Whether the event is expected to survive being emitted and parsed unchanged: its values must
+    /// be valid, and its extra attributes must be prefixed keys that are not otherwise recognized.
+    fn round_trips(event: &OsGatewayEvent) -> bool {
+        let extra_attributes = match event {
+            OsGatewayEvent::AccessGrant(grant) => &grant.extra_attributes,
+            OsGatewayEvent::AccessRevoke(revoke) => &revoke.extra_attributes,
+        };
+        event.validate().is_ok()
+            && extra_attributes.keys().all(|key| {
+                key.starts_with(OS_GATEWAY_KEY_PREFIX)
+                    && !ALL_KEYS.contains(&key.as_str())
+                    && key != OS_GATEWAY_PAYLOAD_KEY
+            })
+    }
+
+    #[test]
+    fn test_arbitrary_events_parse_after_emit() {
+        let mut round_tripped = 0;
+        for seed in 0..2000 {
+            let bytes = bytes(seed);
+            let event = OsGatewayEvent::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            let parsed = parse_gateway_event(
+                &Event::new("wasm")
+                    .add_attributes(OsGatewayAttributeGenerator::from(event.clone())),
+            );
+            if round_trips(&event) {
+                assert_eq!(
+                    vec![Ok(event)],
+                    parsed,
+                    "a valid event should be unchanged after being emitted and parsed",
+                );
+                round_tripped += 1;
+            }
+        }
+        assert!(
+            round_tripped > 100,
+            "a meaningful share of arbitrary events should be valid, but only {round_tripped} were",
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_generators_hold_required_attributes() {
+        for seed in 0..2000 {
+            let bytes = bytes(seed);
+            let generator =
+                OsGatewayAttributeGenerator::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert_eq!(
+                Ok(()),
+                generator.verify_required_attributes(),
+                "an arbitrary generator should always hold the required attributes",
+            );
+            parse_gateway_event(&generator.to_event());
+        }
+    }
+}
@@ -1,21 +1,91 @@
 const ACCESS_GRANT_VALUE: &str = "access_grant";
 const ACCESS_REVOKE_VALUE: &str = "access_revoke";
 
+use crate::OsGatewayError;
+use alloc::string::ToString;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// A typed representation of every event type value recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+/// Each variant corresponds to the identically-named field in [OS_GATEWAY_EVENT_TYPES](crate::OS_GATEWAY_EVENT_TYPES),
+/// which is derived from this enum so that the two can never disagree.
+///
+/// # Variants
+///
+/// * `AccessGrant` See [OsGatewayEventTypes](crate::OsGatewayEventTypes) `access_grant`.
+///
+/// * `AccessRevoke` See [OsGatewayEventTypes](crate::OsGatewayEventTypes) `access_revoke`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum OsGatewayEventType {
+    AccessGrant,
+    AccessRevoke,
+}
+impl OsGatewayEventType {
+    /// Every variant, in declaration order.
+    pub const ALL: [OsGatewayEventType; 2] = [Self::AccessGrant, Self::AccessRevoke];
+
+    /// The exact value of the [event type attribute](crate::OsGatewayKeys) that denotes this event
+    /// type.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccessGrant => ACCESS_GRANT_VALUE,
+            Self::AccessRevoke => ACCESS_REVOKE_VALUE,
+        }
+    }
+}
+impl Display for OsGatewayEventType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+/// Parses an exact event type value into its typed event type, producing an
+/// [OsGatewayError::UnknownEventType](crate::OsGatewayError::UnknownEventType) if the value is not
+/// recognized.
+impl FromStr for OsGatewayEventType {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == s)
+            .ok_or_else(|| OsGatewayError::UnknownEventType {
+                event_type: s.to_string(),
+            })
+    }
+}
+/// Serializes the event type as its attribute value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayEventType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+/// Deserializes the event type from its attribute value, rejecting unrecognized event types.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayEventType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 /// A simple struct to contain all gateway expected event type values.
 ///
 /// # Parameters
 ///
 /// * `access_grant` The expected value for the [Event Type Key](crate::OS_GATEWAY_EVENT_TYPES) that denotes
-/// to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
-/// should be processed as an access grant, and that an entry will be made to allow the target address
-/// access to the underlying records contained in the target scope.
+///   to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
+///   should be processed as an access grant, and that an entry will be made to allow the target address
+///   access to the underlying records contained in the target scope.
 ///
 /// * `access_revoke` The expected value for the [Event Type Key](crate::OS_GATEWAY_EVENT_TYPES) that denotes
-/// to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
-/// should be processed as an access revocation, and that all entries for the given target address
-/// and scope address combination should be removed.  Note:  If an access grant id is provided, only
-/// a record with that id will be removed.  If no record exists with that id, then this event will
-/// take no action when interpreted by a gateway.
+///   to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
+///   should be processed as an access revocation, and that all entries for the given target address
+///   and scope address combination should be removed.  Note:  If an access grant id is provided, only
+///   a record with that id will be removed.  If no record exists with that id, then this event will
+///   take no action when interpreted by a gateway.
 pub struct OsGatewayEventTypes<'a> {
     pub access_grant: &'a str,
     pub access_revoke: &'a str,
@@ -27,17 +97,98 @@ pub struct OsGatewayEventTypes<'a> {
 /// # Values
 ///
 /// * `access_grant` The expected value for the [Event Type Key](crate::OS_GATEWAY_EVENT_TYPES) that denotes
-/// to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
-/// should be processed as an access grant, and that an entry will be made to allow the target address
-/// access to the underlying records contained in the target scope.
+///   to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
+///   should be processed as an access grant, and that an entry will be made to allow the target address
+///   access to the underlying records contained in the target scope.
 ///
 /// * `access_revoke` The expected value for the [Event Type Key](crate::OS_GATEWAY_EVENT_TYPES) that denotes
-/// to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
-/// should be processed as an access revocation, and that all entries for the given target address
-/// and scope address combination should be removed.  Note:  If an access grant id is provided, only
-/// a record with that id will be removed.  If no record exists with that id, then this event will
-/// take no action when interpreted by a gateway.
+///   to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) that the event
+///   should be processed as an access revocation, and that all entries for the given target address
+///   and scope address combination should be removed.  Note:  If an access grant id is provided, only
+///   a record with that id will be removed.  If no record exists with that id, then this event will
+///   take no action when interpreted by a gateway.
 pub const OS_GATEWAY_EVENT_TYPES: OsGatewayEventTypes<'static> = OsGatewayEventTypes {
-    access_grant: ACCESS_GRANT_VALUE,
-    access_revoke: ACCESS_REVOKE_VALUE,
+    access_grant: OsGatewayEventType::AccessGrant.as_str(),
+    access_revoke: OsGatewayEventType::AccessRevoke.as_str(),
 };
+
+/// The type of the custom [Cosmwasm Event](https://docs.rs/cosmwasm-std/latest/cosmwasm_std/struct.Event.html)
+/// produced by [to_event](crate::OsGatewayAttributeGenerator::to_event).  Note that the chain
+/// prefixes custom event types emitted by contracts with `wasm-`, so the event will be observed as
+/// `wasm-object_store_gateway` once the transaction is processed.
+pub const OS_GATEWAY_CUSTOM_EVENT_TYPE: &str = "object_store_gateway";
+
+/// Every event type value recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// as a fixed-size array, in the same order as the fields of [OsGatewayEventTypes](crate::OsGatewayEventTypes).
+/// The array's length and individual entries are usable in `const` contexts, such as array lengths
+/// and `match` patterns.  The length grows as the gateway recognizes new event types.
+pub const OS_GATEWAY_EVENT_TYPE_STRS: [&str; 2] = [
+    OsGatewayEventType::AccessGrant.as_str(),
+    OsGatewayEventType::AccessRevoke.as_str(),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OsGatewayError, OsGatewayEventType, OsGatewayEventTypes, OS_GATEWAY_EVENT_TYPES,
+        OS_GATEWAY_EVENT_TYPE_STRS,
+    };
+
+    #[test]
+    fn test_event_type_strs_match_struct_fields() {
+        // Exhaustively destructure the event types so that adding a field without updating this
+        // test, and therefore the array of event types, fails to compile
+        let OsGatewayEventTypes {
+            access_grant,
+            access_revoke,
+        } = OS_GATEWAY_EVENT_TYPES;
+        assert_eq!(
+            [access_grant, access_revoke],
+            OS_GATEWAY_EVENT_TYPE_STRS,
+            "the event type array should match the struct fields in declaration order",
+        );
+    }
+
+    #[test]
+    fn test_event_type_strs_are_const_usable() {
+        const ACCESS_REVOKE: &str = OS_GATEWAY_EVENT_TYPE_STRS[1];
+        let counts = [0; OS_GATEWAY_EVENT_TYPE_STRS.len()];
+        assert_eq!(
+            2,
+            counts.len(),
+            "the event type array length should be usable as an array length",
+        );
+        let is_revoke = |event_type: &str| matches!(event_type, ACCESS_REVOKE);
+        assert!(
+            is_revoke(OS_GATEWAY_EVENT_TYPES.access_revoke),
+            "the event type array entries should be usable as match patterns",
+        );
+        assert!(
+            !is_revoke(OS_GATEWAY_EVENT_TYPES.access_grant),
+            "other event types should not match the pattern",
+        );
+    }
+
+    #[test]
+    fn test_event_type_enum_round_trips() {
+        assert_eq!(
+            OS_GATEWAY_EVENT_TYPE_STRS,
+            OsGatewayEventType::ALL.map(|event_type| event_type.as_str()),
+            "the event type enum should cover every recognized event type, in order",
+        );
+        for event_type in OsGatewayEventType::ALL {
+            assert_eq!(
+                Ok(event_type),
+                event_type.to_string().parse::<OsGatewayEventType>(),
+                "the event type [{event_type}] should parse back into the same variant",
+            );
+        }
+        assert_eq!(
+            Err(OsGatewayError::UnknownEventType {
+                event_type: "access_request".to_string(),
+            }),
+            "access_request".parse::<OsGatewayEventType>(),
+            "an unrecognized event type should be rejected",
+        );
+    }
+}
@@ -1,6 +1,22 @@
-use crate::{OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS};
-use std::collections::BTreeMap;
-use std::vec::IntoIter;
+use crate::attribute_keys::{ALL_KEYS, REQUIRED_KEYS};
+use crate::fingerprint::{hash_fingerprint, FingerprintEncoder};
+#[cfg(feature = "serde")]
+use crate::EventEncoder;
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+use crate::OS_GATEWAY_CUSTOM_EVENT_TYPE;
+use crate::{
+    AccessLevel, AccountAddress, GrantBuilder, GrantExpiration, GrantTarget,
+    OsGatewayAttributeConflict, OsGatewayDefaults, OsGatewayError, OsGatewayEventBuilder,
+    OsGatewayKey, OsGatewayPayloadVersion, RevokeBuilder, ScopeAddress, OS_GATEWAY_KEYS,
+};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::{IntoIter, Vec};
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+use cosmwasm_std::{Addr, Attribute, Event, Response};
 
 /// Creates and tracks all attributes needed to properly interact with [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
 ///
@@ -9,7 +25,17 @@ use std::vec::IntoIter;
 ///
 /// __Note__: Repeatedly calling fluent functions will replace the previous value submitted via that
 /// function.
-#[derive(Clone, Debug)]
+///
+/// __Ordering__: All iteration and conversion functions produce attributes in a fixed, documented
+/// order: the [event type](crate::OsGatewayKeys), scope address, target account, and access grant
+/// id keys are always emitted first, in that order, followed by any other keys sorted
+/// alphabetically.  Optional metadata, such as the payload version and the source contract
+/// version, sorts among the other keys.  This allows consumers to short-circuit on the event type
+/// when scanning attributes linearly.
+///
+/// Generators are compared and hashed by their attribute contents alone, so two generators that
+/// will produce identical attributes are equal regardless of the order in which they were built.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct OsGatewayAttributeGenerator {
     attributes: BTreeMap<String, String>,
 }
@@ -21,27 +47,27 @@ impl OsGatewayAttributeGenerator {
     ///
     /// This event will be disregarded by the gateway unless the following criteria is met:
     /// * The [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-    /// that signs the wasm payload must be the value owner of the
-    /// [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope).
+    ///   that signs the wasm payload must be the value owner of the
+    ///   [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope).
     /// * A [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-    /// that is registered to an object store gateway instance must have been used as an additional
-    /// public key audience when the scope's records were stored in [Object Store](https://github.com/provenance-io/object-store).
+    ///   that is registered to an object store gateway instance must have been used as an additional
+    ///   public key audience when the scope's records were stored in [Object Store](https://github.com/provenance-io/object-store).
     ///
     /// # Parameters
     ///
     /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
-    /// to which this access grant refers.
+    ///   to which this access grant refers.
     /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-    /// to which this access grant refers.  The account will be able to retrieve all record data
-    /// for the referred scope upon successful processing of this event.
+    ///   to which this access grant refers.  The account will be able to retrieve all record data
+    ///   for the referred scope upon successful processing of this event.
     pub fn access_grant<S1: Into<String>, S2: Into<String>>(
         scope_address: S1,
         target_account_address: S2,
     ) -> Self {
-        Self::new()
-            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant)
-            .with_scope_address(scope_address)
-            .with_target_account_address(target_account_address)
+        OsGatewayEventBuilder::grant()
+            .scope(scope_address)
+            .target(target_account_address)
+            .build()
     }
 
     /// Generates the required values in the [Cosmwasm](https://github.com/CosmWasm/cosmwasm)
@@ -50,25 +76,232 @@ impl OsGatewayAttributeGenerator {
     ///
     /// This event will be disregarded by the gateway unless the following criteria is met:
     /// * The [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-    /// that signs the wasm payload must be the value owner of the
-    /// [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
-    /// OR the signer must be the same account as is used for [target_account_address](self::OsGatewayAttributeGenerator::access_revoke).
+    ///   that signs the wasm payload must be the value owner of the
+    ///   [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   OR the signer must be the same account as is used for [target_account_address](self::OsGatewayAttributeGenerator::access_revoke).
     ///
     /// # Parameters
     ///
     /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
-    /// to which this access revoke refers.
+    ///   to which this access revoke refers.
     /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-    /// to which this access revoke refers.  The account will no longer be able to retrieve records
-    /// for the given scope upon successful processing of this event.
+    ///   to which this access revoke refers.  The account will no longer be able to retrieve records
+    ///   for the given scope upon successful processing of this event.
     pub fn access_revoke<S1: Into<String>, S2: Into<String>>(
         scope_address: S1,
         target_account_address: S2,
     ) -> Self {
-        Self::new()
-            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_revoke)
-            .with_scope_address(scope_address)
-            .with_target_account_address(target_account_address)
+        OsGatewayEventBuilder::revoke()
+            .scope(scope_address)
+            .target(target_account_address)
+            .build()
+    }
+
+    /// Starts an access grant in the same manner as [access_grant](self::OsGatewayAttributeGenerator::access_grant),
+    /// but produces a [GrantBuilder](crate::GrantBuilder), which only exposes the options that the
+    /// gateway honors for grants, such as an expiration or access level.  The builder converts
+    /// back into a generator with [build](crate::GrantBuilder::build).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    pub fn grant<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> GrantBuilder {
+        GrantBuilder::new(scope_address, target_account_address)
+    }
+
+    /// Starts an access revoke in the same manner as [access_revoke](self::OsGatewayAttributeGenerator::access_revoke),
+    /// but produces a [RevokeBuilder](crate::RevokeBuilder), which only exposes the options that
+    /// the gateway honors for revokes.  The builder converts back into a generator with
+    /// [build](crate::RevokeBuilder::build).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    pub fn revoke<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> RevokeBuilder {
+        RevokeBuilder::new(scope_address, target_account_address)
+    }
+
+    /// Identical to [access_grant](self::OsGatewayAttributeGenerator::access_grant), but accepts
+    /// validated addresses so that the scope and target account arguments cannot be swapped:
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccountAddress, OsGatewayAttributeGenerator, ScopeAddress};
+    ///
+    /// let scope_address = ScopeAddress::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zz").unwrap();
+    /// let target_account_address = AccountAddress::new("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr").unwrap();
+    /// let generator = OsGatewayAttributeGenerator::access_grant_typed(&scope_address, &target_account_address);
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    pub fn access_grant_typed(
+        scope_address: &ScopeAddress,
+        target_account_address: &AccountAddress,
+    ) -> Self {
+        Self::access_grant(scope_address.as_str(), target_account_address.as_str())
+    }
+
+    /// Identical to [access_revoke](self::OsGatewayAttributeGenerator::access_revoke), but accepts
+    /// validated addresses so that the scope and target account arguments cannot be swapped.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    pub fn access_revoke_typed(
+        scope_address: &ScopeAddress,
+        target_account_address: &AccountAddress,
+    ) -> Self {
+        Self::access_revoke(scope_address.as_str(), target_account_address.as_str())
+    }
+
+    /// Identical to [access_grant](self::OsGatewayAttributeGenerator::access_grant), but accepts the
+    /// UUID of the scope, which is converted into its bech32 address with [scope_address_from_uuid](crate::scope_address_from_uuid).
+    /// Both hyphenated and unhyphenated UUIDs are accepted.  Produces an [OsGatewayError::InvalidScopeUuid](crate::OsGatewayError::InvalidScopeUuid)
+    /// if the value is not a UUID.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// let generator = OsGatewayAttributeGenerator::access_grant_for_scope_uuid(
+    ///     "91978ba2-5f35-459a-86a7-feca1b0512e0",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// )
+    /// .expect("the scope uuid should be valid");
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_uuid` The UUID of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    #[cfg(feature = "metadata-address")]
+    pub fn access_grant_for_scope_uuid<S1: AsRef<str>, S2: Into<String>>(
+        scope_uuid: S1,
+        target_account_address: S2,
+    ) -> Result<Self, OsGatewayError> {
+        let scope_address = crate::scope_address_from_uuid(scope_uuid.as_ref())?;
+        Ok(Self::access_grant(scope_address, target_account_address))
+    }
+
+    /// Identical to [access_revoke](self::OsGatewayAttributeGenerator::access_revoke), but accepts
+    /// the UUID of the scope, which is converted into its bech32 address with [scope_address_from_uuid](crate::scope_address_from_uuid).
+    /// Both hyphenated and unhyphenated UUIDs are accepted.  Produces an [OsGatewayError::InvalidScopeUuid](crate::OsGatewayError::InvalidScopeUuid)
+    /// if the value is not a UUID.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_uuid` The UUID of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    #[cfg(feature = "metadata-address")]
+    pub fn access_revoke_for_scope_uuid<S1: AsRef<str>, S2: Into<String>>(
+        scope_uuid: S1,
+        target_account_address: S2,
+    ) -> Result<Self, OsGatewayError> {
+        let scope_address = crate::scope_address_from_uuid(scope_uuid.as_ref())?;
+        Ok(Self::access_revoke(scope_address, target_account_address))
+    }
+
+    /// Identical to [access_grant](self::OsGatewayAttributeGenerator::access_grant), but also
+    /// includes every attribute in the provided [OsGatewayDefaults](crate::OsGatewayDefaults).
+    ///
+    /// # Parameters
+    ///
+    /// * `defaults` The profile of attributes to include with the access grant.
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    pub fn access_grant_with_defaults<S1: Into<String>, S2: Into<String>>(
+        defaults: &OsGatewayDefaults,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        defaults.apply(Self::access_grant(scope_address, target_account_address))
+    }
+
+    /// Identical to [access_revoke](self::OsGatewayAttributeGenerator::access_revoke), but also
+    /// includes every attribute in the provided [OsGatewayDefaults](crate::OsGatewayDefaults).
+    ///
+    /// # Parameters
+    ///
+    /// * `defaults` The profile of attributes to include with the access revoke.
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    pub fn access_revoke_with_defaults<S1: Into<String>, S2: Into<String>>(
+        defaults: &OsGatewayDefaults,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        defaults.apply(Self::access_revoke(scope_address, target_account_address))
+    }
+
+    /// Identical to [access_grant](self::OsGatewayAttributeGenerator::access_grant), but accepts the
+    /// target account as a borrowed [Addr](cosmwasm_std::Addr).  This allows values like
+    /// `info.sender` to be passed directly without cloning or converting them at the call site:
+    ///
+    /// ```
+    /// use cosmwasm_std::MessageInfo;
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// fn grant_to_sender(scope_address: String, info: &MessageInfo) -> OsGatewayAttributeGenerator {
+    ///     OsGatewayAttributeGenerator::access_grant_addr(scope_address, &info.sender)
+    /// }
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn access_grant_addr<S: Into<String>>(
+        scope_address: S,
+        target_account_address: &Addr,
+    ) -> Self {
+        Self::access_grant(scope_address, target_account_address.as_str())
+    }
+
+    /// Identical to [access_revoke](self::OsGatewayAttributeGenerator::access_revoke), but accepts
+    /// the target account as a borrowed [Addr](cosmwasm_std::Addr).  This allows values like
+    /// `info.sender` to be passed directly without cloning or converting them at the call site.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn access_revoke_addr<S: Into<String>>(
+        scope_address: S,
+        target_account_address: &Addr,
+    ) -> Self {
+        Self::access_revoke(scope_address, target_account_address.as_str())
     }
 
     /// Includes a custom access grant unique identifier in an access request event structure.
@@ -76,238 +309,1743 @@ impl OsGatewayAttributeGenerator {
     /// This value behaves differently based on the type of event in which it is included:
     ///
     /// * __Access Grants__:  This value will be used to establish a unique identifier in any
-    /// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) which processes
-    /// the event.  This allows the revocation process to directly target a record.
+    ///   [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) which processes
+    ///   the event.  This allows the revocation process to directly target a record.
     /// * __Access Revokes__: This value will be used to target a specific access grant to revoke.
-    /// If this value is omitted, the default behavior is to remove all grants specified for a
-    /// [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope) address
-    /// and grantee [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts) address
-    /// combination at once.
-    pub fn with_access_grant_id<S: Into<String>>(self, access_grant_id: S) -> Self {
-        self.insert_attribute(OS_GATEWAY_KEYS.access_grant_id, access_grant_id)
+    ///   If this value is omitted, the default behavior is to remove all grants specified for a
+    ///   [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope) address
+    ///   and grantee [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts) address
+    ///   combination at once.
+    pub fn with_access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.set_access_grant_id(access_grant_id);
+        self
     }
 
-    fn with_event_type<S: Into<String>>(self, event_type: S) -> Self {
-        self.insert_attribute(OS_GATEWAY_KEYS.event_type, event_type)
+    /// Includes a custom access grant unique identifier in the same manner as [with_access_grant_id](self::OsGatewayAttributeGenerator::with_access_grant_id)
+    /// when a value is provided, and does nothing when no value is provided.  A previously
+    /// included access grant id is retained when no value is provided.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The optional access grant id to include.
+    pub fn with_optional_access_grant_id<S: Into<String>>(
+        mut self,
+        access_grant_id: Option<S>,
+    ) -> Self {
+        self.set_optional_access_grant_id(access_grant_id);
+        self
     }
 
-    fn with_scope_address<S: Into<String>>(self, scope_address: S) -> Self {
-        self.insert_attribute(OS_GATEWAY_KEYS.scope_address, scope_address)
+    /// A non-consuming variant of [with_access_grant_id](self::OsGatewayAttributeGenerator::with_access_grant_id),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The access grant id to include.
+    pub fn set_access_grant_id<S: Into<String>>(&mut self, access_grant_id: S) {
+        self.set_attribute(OS_GATEWAY_KEYS.access_grant_id, access_grant_id);
     }
 
-    fn with_target_account_address<S: Into<String>>(self, target_account_address: S) -> Self {
-        self.insert_attribute(OS_GATEWAY_KEYS.target_account, target_account_address)
+    /// A non-consuming variant of [with_optional_access_grant_id](self::OsGatewayAttributeGenerator::with_optional_access_grant_id),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The optional access grant id to include.
+    pub fn set_optional_access_grant_id<S: Into<String>>(&mut self, access_grant_id: Option<S>) {
+        if let Some(access_grant_id) = access_grant_id {
+            self.set_access_grant_id(access_grant_id);
+        }
     }
 
-    fn new() -> Self {
-        Self {
-            attributes: BTreeMap::new(),
-        }
+    /// Includes the point at which an access grant stops being honored by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway),
+    /// replacing any previously included expiration.  The value is encoded as described by
+    /// [GrantExpiration](crate::GrantExpiration).  Expirations only apply to access grants.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the grant expires.
+    #[deprecated(
+        note = "expirations only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_expiration"
+    )]
+    pub fn with_expiration(mut self, expiration: GrantExpiration) -> Self {
+        self.insert_expiration(expiration);
+        self
     }
 
-    fn insert_attribute<S1: Into<String>, S2: Into<String>>(mut self, key: S1, value: S2) -> Self {
-        self.attributes.insert(key.into(), value.into());
+    /// Includes an expiration in the same manner as [with_expiration](self::OsGatewayAttributeGenerator::with_expiration)
+    /// when a value is provided, and does nothing when no value is provided.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The optional point at which the grant expires.
+    #[deprecated(
+        note = "expirations only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_optional_expiration"
+    )]
+    pub fn with_optional_expiration(mut self, expiration: Option<GrantExpiration>) -> Self {
+        if let Some(expiration) = expiration {
+            self.insert_expiration(expiration);
+        }
         self
     }
-}
-impl IntoIterator for OsGatewayAttributeGenerator {
-    type Item = (String, String);
 
-    type IntoIter = IntoIter<Self::Item>;
+    /// A non-consuming variant of [with_expiration](self::OsGatewayAttributeGenerator::with_expiration),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the grant expires.
+    #[deprecated(
+        note = "expirations only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_expiration"
+    )]
+    pub fn set_expiration(&mut self, expiration: GrantExpiration) {
+        self.insert_expiration(expiration);
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.attributes
-            .into_iter()
-            .collect::<Vec<(String, String)>>()
-            .into_iter()
+    /// Includes the level of access that an access grant gives its target account, replacing any
+    /// previously included level.  Access levels only apply to access grants.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access to grant.
+    #[deprecated(
+        note = "access levels only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_access_level"
+    )]
+    pub fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.insert_access_level(access_level);
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::attribute_generator::OsGatewayAttributeGenerator;
-    use crate::{OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS};
-    use cosmwasm_std::Response;
+    /// Includes an access level in the same manner as [with_access_level](self::OsGatewayAttributeGenerator::with_access_level)
+    /// when a value is provided, and does nothing when no value is provided.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The optional level of access to grant.
+    #[deprecated(
+        note = "access levels only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_optional_access_level"
+    )]
+    pub fn with_optional_access_level(mut self, access_level: Option<AccessLevel>) -> Self {
+        if let Some(access_level) = access_level {
+            self.insert_access_level(access_level);
+        }
+        self
+    }
 
-    const DEFAULT_SCOPE_ADDRESS: &str = "scope_address";
-    const DEFAULT_TARGET_ACCOUNT: &str = "target_account_address";
-    const DEFAULT_GRANT_ID: &str = "grant_id";
+    /// A non-consuming variant of [with_access_level](self::OsGatewayAttributeGenerator::with_access_level),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access to grant.
+    #[deprecated(
+        note = "access levels only apply to access grants; use OsGatewayAttributeGenerator::grant and GrantBuilder::with_access_level"
+    )]
+    pub fn set_access_level(&mut self, access_level: AccessLevel) {
+        self.insert_access_level(access_level);
+    }
 
-    impl OsGatewayAttributeGenerator {
-        pub fn test_access_grant() -> Self {
-            Self::access_grant(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
-        }
+    /// Includes the grant's expiration, for the builders and conversions that only produce grants.
+    pub(crate) fn insert_expiration(&mut self, expiration: GrantExpiration) {
+        self.set_attribute(OS_GATEWAY_KEYS.expiration, expiration.to_string());
+    }
 
-        pub fn test_access_revoke() -> Self {
-            Self::access_revoke(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
-        }
+    /// Includes the grant's access level, for the builders and conversions that only produce
+    /// grants.
+    pub(crate) fn insert_access_level(&mut self, access_level: AccessLevel) {
+        self.set_attribute(OS_GATEWAY_KEYS.access_level, access_level.as_str());
     }
 
-    #[test]
-    fn test_access_grant_contents() {
-        let mut access_grant = OsGatewayAttributeGenerator::test_access_grant();
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_grant,
-            &access_grant,
-            None,
-        );
-        access_grant = access_grant.with_access_grant_id(DEFAULT_GRANT_ID);
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_grant,
-            &access_grant,
-            Some(DEFAULT_GRANT_ID),
-        );
-        access_grant = access_grant.with_access_grant_id("grant_id_2");
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_grant,
-            &access_grant,
-            Some("grant_id_2"),
-        );
+    /// Stamps the attributes with [OsGatewayPayloadVersion::CURRENT](crate::OsGatewayPayloadVersion::CURRENT),
+    /// the layout produced by this release, so that consumers can detect events produced by an
+    /// incompatible release.  Any previously stamped version is replaced.
+    ///
+    /// Stamping is opt-in, rather than applied by every constructor, so that contracts that do not
+    /// ask for it keep emitting exactly the attributes they emitted before the payload version
+    /// existed.  An unstamped event should be treated as version `1`.
+    pub fn with_payload_version(mut self) -> Self {
+        self.set_payload_version();
+        self
     }
 
-    #[test]
-    fn test_access_revoke_contents() {
-        let mut access_revoke = OsGatewayAttributeGenerator::test_access_revoke();
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_revoke,
-            &access_revoke,
-            None,
-        );
-        access_revoke = access_revoke.with_access_grant_id(DEFAULT_GRANT_ID);
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_revoke,
-            &access_revoke,
-            Some(DEFAULT_GRANT_ID),
-        );
-        access_revoke = access_revoke.with_access_grant_id("grant_id_2");
-        assert_attribute_values_are_correct(
-            OS_GATEWAY_EVENT_TYPES.access_revoke,
-            &access_revoke,
-            Some("grant_id_2"),
+    /// A non-consuming variant of [with_payload_version](self::OsGatewayAttributeGenerator::with_payload_version),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    pub fn set_payload_version(&mut self) {
+        self.set_attribute(
+            OS_GATEWAY_KEYS.payload_version,
+            OsGatewayPayloadVersion::CURRENT.to_string(),
         );
     }
 
-    #[test]
-    fn test_output_attributes_are_deterministic() {
-        // Verify first that two identically-built generators produce the same output
-        let first_grant_attrs = OsGatewayAttributeGenerator::test_access_grant()
-            .with_access_grant_id("a")
-            .into_iter()
-            .collect::<Vec<(String, String)>>();
-        let second_grant_attrs = OsGatewayAttributeGenerator::test_access_grant()
-            .with_access_grant_id("a")
-            .into_iter()
-            .collect::<Vec<(String, String)>>();
-        assert_eq!(
-            first_grant_attrs,
-            second_grant_attrs,
-            "both grant attributes lists should be identical due to deterministic ordering of the BTreeMap",
-        );
-        assert_eq!(
-            4,
-            first_grant_attrs.len(),
-            "four attributes should be produced",
-        );
-        // Then sort keys from an unsorted state (based on their values in attribute_consts) and
-        // verify that the output produced in the sorted result matches exactly with the sorted
-        // key result.
-        let mut expected_keys = vec![
-            OS_GATEWAY_KEYS.scope_address,
-            OS_GATEWAY_KEYS.access_grant_id,
-            OS_GATEWAY_KEYS.event_type,
-            OS_GATEWAY_KEYS.target_account,
-        ];
-        expected_keys.sort();
-        for (index, key) in expected_keys.into_iter().enumerate() {
-            assert_eq!(
-                key,
-                first_grant_attrs[index].0,
-                "the key at position {} should be {key} - the result of the attribute sort was not deterministic",
-                index,
-            );
-        }
+    /// The payload version stamped on the attributes, or `None` if the attributes were produced
+    /// without one.  Produces an [OsGatewayError::InvalidPayloadVersion](crate::OsGatewayError::InvalidPayloadVersion)
+    /// if the stamped value cannot be parsed.
+    pub fn payload_version(&self) -> Result<Option<OsGatewayPayloadVersion>, OsGatewayError> {
+        self.get(OS_GATEWAY_KEYS.payload_version)
+            .map(str::parse)
+            .transpose()
     }
 
-    fn assert_attribute_values_are_correct(
-        expected_event_key: &str,
-        generator: &OsGatewayAttributeGenerator,
-        grant_id: Option<&str>,
-    ) {
-        let expected_attribute_count = 3 + if grant_id.is_some() { 1 } else { 0 };
-        // An arbitrary response with an arbitrary generic type because generics don't mean anything
-        // in a context where only attributes are being appended and not messages.
-        // The standard functionality will not require cloning, but this test does because we are
-        // operating on a reference to allow tests to subsequently modify the attributes generator
-        let response: Response<String> = Response::new().add_attributes(generator.clone());
-        assert_eq!(
-            expected_attribute_count,
-            generator.attributes.len(),
-            "expected the correct number of attributes to be held in the attribute generator",
-        );
-        assert_eq!(
-            expected_attribute_count,
-            response.attributes.len(),
-            "expected the correct number of attributes to be held in the cosmwasm response",
-        );
-        assert_eq!(
-            expected_event_key, generator.attributes[OS_GATEWAY_KEYS.event_type],
-            "the event type key should equate to the expected value in the attribute generator",
-        );
-        assert_eq!(
-            expected_event_key,
-            single_attribute_for_key(&response, OS_GATEWAY_KEYS.event_type),
-            "the event the key should equate to the expected value in the cosmwasm response",
-        );
-        assert_eq!(
-            DEFAULT_SCOPE_ADDRESS, generator.attributes[OS_GATEWAY_KEYS.scope_address],
-            "the scope address key should contain the default scope address value in the attribute generator",
-        );
-        assert_eq!(
-            DEFAULT_SCOPE_ADDRESS,
-            single_attribute_for_key(&response, OS_GATEWAY_KEYS.scope_address),
-            "the scope address key should contain the default scope address value in the cosmwasm response",
-        );
-        assert_eq!(
-            DEFAULT_TARGET_ACCOUNT, generator.attributes[OS_GATEWAY_KEYS.target_account],
-            "the target account key should contain the default target account address value in the attribute generator",
-        );
-        assert_eq!(
-            DEFAULT_TARGET_ACCOUNT,
-            single_attribute_for_key(&response, OS_GATEWAY_KEYS.target_account),
-            "the target account key should contain the default target account address value in the cosmwasm response",
+    /// Stamps the attributes with the name and version of the contract that produces them, as
+    /// `<contract name>/<version>`, such as `my-contract/1.4.2`, so that consumers can trace an
+    /// event to the release that emitted it.  Any previously stamped contract version is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract` The name of the contract, such as `my-contract`.
+    /// * `version` The version of the contract, such as `1.4.2`.
+    pub fn with_source_contract_version(mut self, contract: &str, version: &str) -> Self {
+        self.set_source_contract_version(contract, version);
+        self
+    }
+
+    /// A non-consuming variant of [with_source_contract_version](self::OsGatewayAttributeGenerator::with_source_contract_version),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `contract` The name of the contract, such as `my-contract`.
+    /// * `version` The version of the contract, such as `1.4.2`.
+    pub fn set_source_contract_version(&mut self, contract: &str, version: &str) {
+        self.set_attribute(
+            OS_GATEWAY_KEYS.source_contract_version,
+            format!("{contract}/{version}"),
         );
-        if let Some(grant_id) = grant_id {
-            assert_eq!(
-                grant_id, generator.attributes[OS_GATEWAY_KEYS.access_grant_id],
-                "the access grant id key should contain the provided access grant id value in the attribute generator",
-            );
-            assert_eq!(
-                grant_id,
-                single_attribute_for_key(&response, OS_GATEWAY_KEYS.access_grant_id),
-                "the access grant id key should contain the provided access grant id value in the cosmwasm response",
-            );
-        } else {
-            assert!(
-                !generator.attributes.contains_key(OS_GATEWAY_KEYS.access_grant_id),
-                "the access grant id key was not expected to be provided to the attribute generator",
-            );
-            assert!(
-                !response
-                    .attributes
-                    .iter()
-                    .any(|attr| attr.key == OS_GATEWAY_KEYS.access_grant_id),
-                "the access grant id key was not expected to be provided to the cosmwasm response",
-            )
-        }
     }
 
-    fn single_attribute_for_key<'a, T>(response: &'a Response<T>, key: &'a str) -> &'a str {
-        response
-            .attributes
-            .iter()
-            .find(|attr| attr.key.as_str() == key)
-            .unwrap()
-            .value
-            .as_str()
+    /// The contract name and version stamped on the attributes, as `<contract name>/<version>`,
+    /// or `None` if the attributes were produced without one.
+    pub fn source_contract_version(&self) -> Option<&str> {
+        self.get(OS_GATEWAY_KEYS.source_contract_version)
+    }
+
+    /// Sets who or what the attributes target, replacing the target account address and any
+    /// previously provided target kind.  Accounts are denoted by the target account address alone,
+    /// while markers and scope specifications are also denoted by the [target kind](crate::OsGatewayKeys)
+    /// attribute, which parsed events expose through [target](crate::OsGatewayEvent::target).
+    ///
+    /// # Parameters
+    ///
+    /// * `target` The account, marker, or scope specification to which the attributes refer.
+    pub fn with_target(mut self, target: GrantTarget) -> Self {
+        self.set_target(target);
+        self
+    }
+
+    /// A non-consuming variant of [with_target](self::OsGatewayAttributeGenerator::with_target),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant.
+    ///
+    /// # Parameters
+    ///
+    /// * `target` The account, marker, or scope specification to which the attributes refer.
+    pub fn set_target(&mut self, target: GrantTarget) {
+        match target.kind_attribute() {
+            Some(kind) => self.set_attribute(OS_GATEWAY_KEYS.target_kind, kind),
+            None => {
+                self.attributes.remove(OS_GATEWAY_KEYS.target_kind);
+            }
+        }
+        self.set_attribute(OS_GATEWAY_KEYS.target_account, target.address());
+    }
+
+    /// Reconstructs a generator from existing attribute key and value pairs, such as those read
+    /// from a previously emitted event.  The resulting generator emits the same attributes, in
+    /// this struct's documented order.  When a key is provided more than once, the last value
+    /// provided for it is used.
+    ///
+    /// Returns an [OsGatewayError::UnknownAttributeKeys](crate::OsGatewayError::UnknownAttributeKeys)
+    /// if any key is not recognized by the gateway, or an [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+    /// if the event type, scope address, or target account is missing.  Use
+    /// [from_attributes_lenient](self::OsGatewayAttributeGenerator::from_attributes_lenient) to
+    /// retain unrecognized keys instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `pairs` The attribute key and value pairs from which to build the generator.
+    pub fn from_attributes<I: IntoIterator<Item = (String, String)>>(
+        pairs: I,
+    ) -> Result<Self, OsGatewayError> {
+        let generator = Self::from_attributes_lenient(pairs)?;
+        let unknown_keys = generator
+            .attributes
+            .keys()
+            .filter(|key| !ALL_KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect::<Vec<String>>();
+        if unknown_keys.is_empty() {
+            Ok(generator)
+        } else {
+            Err(OsGatewayError::UnknownAttributeKeys { keys: unknown_keys })
+        }
+    }
+
+    /// Reconstructs a generator from existing attribute key and value pairs in the same manner as
+    /// [from_attributes](self::OsGatewayAttributeGenerator::from_attributes), except that keys
+    /// not recognized by the gateway are retained as extra attributes rather than rejected.  Extra
+    /// attributes are emitted in the documented order, sorted alphabetically among the optional
+    /// keys that the gateway recognizes.
+    ///
+    /// Returns an [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+    /// if the event type, scope address, or target account is missing.
+    ///
+    /// # Parameters
+    ///
+    /// * `pairs` The attribute key and value pairs from which to build the generator.
+    pub fn from_attributes_lenient<I: IntoIterator<Item = (String, String)>>(
+        pairs: I,
+    ) -> Result<Self, OsGatewayError> {
+        let generator = Self {
+            attributes: pairs.into_iter().collect(),
+        };
+        generator.verify_required_attributes()?;
+        Ok(generator)
+    }
+
+    /// Combines the attributes of another generator into this one.  Attributes present in only one
+    /// of the generators are retained, and attributes present in both with identical values are
+    /// kept as-is.
+    ///
+    /// Returns an [OsGatewayError::ConflictingAttributes](crate::OsGatewayError::ConflictingAttributes)
+    /// listing every key that is present in both generators with differing values.  Use
+    /// [merge_overriding](self::OsGatewayAttributeGenerator::merge_overriding) when the other
+    /// generator's values are intended to take precedence.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` The generator whose attributes will be combined into this one.
+    pub fn merge(self, other: OsGatewayAttributeGenerator) -> Result<Self, OsGatewayError> {
+        let conflicts = other
+            .attributes
+            .iter()
+            .filter_map(|(key, incoming_value)| {
+                self.attributes
+                    .get(key)
+                    .filter(|existing_value| *existing_value != incoming_value)
+                    .map(|existing_value| OsGatewayAttributeConflict {
+                        key: key.to_owned(),
+                        existing_value: existing_value.to_owned(),
+                        incoming_value: incoming_value.to_owned(),
+                    })
+            })
+            .collect::<Vec<OsGatewayAttributeConflict>>();
+        if conflicts.is_empty() {
+            Ok(self.merge_overriding(other))
+        } else {
+            Err(OsGatewayError::ConflictingAttributes { conflicts })
+        }
+    }
+
+    /// Combines the attributes of another generator into this one.  When a key is present in both
+    /// generators, the value from the other generator replaces the value in this one.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` The generator whose attributes will be combined into this one, taking precedence
+    ///   over any existing values.
+    pub fn merge_overriding(mut self, other: OsGatewayAttributeGenerator) -> Self {
+        self.attributes.extend(other.attributes);
+        self
+    }
+
+    /// The number of attributes that this generator will produce.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Whether or not this generator will produce no attributes at all.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Whether or not this generator holds a value for the given attribute key.  This is useful to
+    /// determine if an optional attribute, like [access_grant_id](crate::OsGatewayKeys), was set.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The attribute key to check, typically a value from [OS_GATEWAY_KEYS](crate::OS_GATEWAY_KEYS).
+    pub fn contains(&self, key: &str) -> bool {
+        self.attributes.contains_key(key)
+    }
+
+    /// Retrieves the value held for the given attribute key, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The attribute key to look up, typically a value from [OS_GATEWAY_KEYS](crate::OS_GATEWAY_KEYS).
+    pub fn get<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        self.attributes
+            .get(key.as_ref())
+            .map(|value| value.as_str())
+    }
+
+    /// Retrieves the value held for the given typed attribute key, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The typed attribute key to look up.
+    pub fn get_key(&self, key: OsGatewayKey) -> Option<&str> {
+        self.get(key.as_str())
+    }
+
+    /// Exposes read-only access to all attributes held in this generator, keyed by attribute key.
+    ///
+    /// The returned reference is immutable, so the generator's contents cannot be altered through
+    /// it.  Iterating the map produces keys in alphabetical order, which is stable across calls but
+    /// differs from the emission order used by this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter)
+    /// implementation.  Use [iter](self::OsGatewayAttributeGenerator::iter) for the emission order.
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+
+    /// Produces borrowed key and value pairs for all attributes held in this generator without
+    /// consuming it.
+    ///
+    /// The pairs borrow from the generator and cannot be used to alter its contents.  They are
+    /// ordered identically to the values produced by this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter)
+    /// implementation, and that ordering is stable across calls.
+    pub fn as_pairs(&self) -> Vec<(&str, &str)> {
+        self.iter().collect()
+    }
+
+    /// Iterates over borrowed key and value pairs for all attributes held in this generator without
+    /// consuming it or allocating any Strings.  The pairs are ordered identically to the values produced by
+    /// this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter) implementation.
+    ///
+    /// Because each pair converts into a [Cosmwasm Attribute](cosmwasm_std::Attribute), a borrowed
+    /// generator can also be used to extend existing attributes:
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+    /// use cosmwasm_std::Attribute;
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// let generator = OsGatewayAttributeGenerator::access_grant(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// );
+    /// let mut attributes = vec![Attribute::new("action", "grant")];
+    /// attributes.extend(generator.iter().map(Attribute::from));
+    /// # }
+    /// ```
+    pub fn iter(&self) -> OsGatewayAttributeIter<'_> {
+        let mut pairs = self
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        pairs.sort_by_key(|(key, _)| emission_order(key));
+        OsGatewayAttributeIter {
+            inner: pairs.into_iter(),
+        }
+    }
+
+    /// Produces a [Cosmwasm Attribute](cosmwasm_std::Attribute) for each value held in this
+    /// generator without consuming it.  The resulting attributes are ordered identically to the
+    /// values produced by this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter)
+    /// implementation.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn to_attributes(&self) -> Vec<Attribute> {
+        self.iter()
+            .map(|(key, value)| Attribute::new(key, value))
+            .collect()
+    }
+
+    /// Consumes this generator, producing a [Cosmwasm Attribute](cosmwasm_std::Attribute) for each
+    /// value held in it.  The resulting vector is allocated with exactly the required capacity, and
+    /// the attributes are ordered identically to the values produced by this struct's
+    /// [IntoIterator](self::OsGatewayAttributeGenerator::into_iter) implementation.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn into_attributes(self) -> Vec<Attribute> {
+        let mut attributes = Vec::with_capacity(self.attributes.len());
+        for (key, value) in self.attributes {
+            attributes.push(Attribute { key, value });
+        }
+        attributes
+            .sort_by(|first, second| emission_order(&first.key).cmp(&emission_order(&second.key)));
+        attributes
+    }
+
+    /// Produces a custom [Cosmwasm Event](cosmwasm_std::Event) of type [object_store_gateway](crate::OS_GATEWAY_CUSTOM_EVENT_TYPE)
+    /// containing all attributes held in this generator, in the documented emission order.
+    ///
+    /// This is an alternative to appending the attributes directly to a response, which places them
+    /// in the generic `wasm` event alongside every other attribute the contract emits.  Note that
+    /// the chain prefixes the event type with `wasm-` when processing the transaction.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn to_event(&self) -> Event {
+        Event::new(OS_GATEWAY_CUSTOM_EVENT_TYPE).add_attributes(self.to_attributes())
+    }
+
+    /// Appends all attributes held in this generator to the provided [Cosmwasm Response](cosmwasm_std::Response),
+    /// consuming the generator.  This is functionally identical to calling `add_attributes` on the
+    /// response directly, but reads more naturally when a response has already been constructed.
+    ///
+    /// # Parameters
+    ///
+    /// * `response` The response to which the attributes will be appended.  Any existing attributes,
+    ///   messages, and events on the response are retained.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn add_to_response<T>(self, response: Response<T>) -> Response<T> {
+        response.add_attributes(self)
+    }
+
+    /// Appends all attributes held in this generator to the provided [Cosmwasm Response](cosmwasm_std::Response)
+    /// without consuming the generator.  This allows the same generator to be attached to multiple
+    /// responses.
+    ///
+    /// # Parameters
+    ///
+    /// * `response` The response to which the attributes will be appended.  Any existing attributes,
+    ///   messages, and events on the response are retained.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn apply_to<T>(&self, response: Response<T>) -> Response<T> {
+        response.add_attributes(self.to_attributes())
+    }
+
+    /// Produces a stable, single-line textual fingerprint of the exact attributes held in this
+    /// generator, suitable for detecting changes to emitted attributes in review.
+    ///
+    /// The fingerprint is rendered as `v1;key=value;key=value`, where the leading version denotes
+    /// the fingerprint format and the pairs are sorted alphabetically by key, independently of
+    /// the emission order and of the order in which the generator was built.  Backslashes,
+    /// semicolons, equals signs, carriage returns, and newlines within keys and values are escaped
+    /// with a preceding backslash (with `\n` and `\r` standing in for the line breaks).
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// let fingerprint = OsGatewayAttributeGenerator::access_grant("scope", "target").fingerprint();
+    /// assert_eq!(
+    ///     "v1;object_store_gateway_event_type=access_grant;object_store_gateway_scope_address=scope;object_store_gateway_target_account_address=target",
+    ///     fingerprint,
+    /// );
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        let mut encoder = FingerprintEncoder::new();
+        self.encode(&mut encoder);
+        encoder.finish()
+    }
+
+    /// Produces a 32 byte hash of this generator's [fingerprint](self::OsGatewayAttributeGenerator::fingerprint).
+    /// The hash is lightweight, dependency-free, and suitable for use within wasm, but it is not
+    /// cryptographically secure and should only be used for change detection.
+    pub fn fingerprint_hash(&self) -> [u8; 32] {
+        hash_fingerprint(self.fingerprint().as_bytes())
+    }
+
+    pub(crate) fn with_event_type<S: Into<String>>(self, event_type: S) -> Self {
+        self.insert_attribute(OS_GATEWAY_KEYS.event_type, event_type)
+    }
+
+    pub(crate) fn with_scope_address<S: Into<String>>(self, scope_address: S) -> Self {
+        self.insert_attribute(OS_GATEWAY_KEYS.scope_address, scope_address)
+    }
+
+    pub(crate) fn with_target_account_address<S: Into<String>>(
+        self,
+        target_account_address: S,
+    ) -> Self {
+        self.insert_attribute(OS_GATEWAY_KEYS.target_account, target_account_address)
+    }
+
+    pub(crate) fn new() -> Self {
+        Self {
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn verify_required_attributes(&self) -> Result<(), OsGatewayError> {
+        let missing_keys = REQUIRED_KEYS
+            .into_iter()
+            .filter(|key| !self.contains(key))
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>();
+        if missing_keys.is_empty() {
+            Ok(())
+        } else {
+            Err(OsGatewayError::MissingRequiredAttributes { keys: missing_keys })
+        }
+    }
+
+    pub(crate) fn set_attribute<S1: Into<String>, S2: Into<String>>(&mut self, key: S1, value: S2) {
+        self.attributes.insert(key.into(), value.into());
+    }
+
+    fn insert_attribute<S1: Into<String>, S2: Into<String>>(mut self, key: S1, value: S2) -> Self {
+        self.set_attribute(key, value);
+        self
+    }
+}
+impl IntoIterator for OsGatewayAttributeGenerator {
+    type Item = (String, String);
+
+    type IntoIter = IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut pairs = self
+            .attributes
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        pairs.sort_by(|(first_key, _), (second_key, _)| {
+            emission_order(first_key).cmp(&emission_order(second_key))
+        });
+        pairs.into_iter()
+    }
+}
+/// The keys that are always emitted first, in this order.  All other keys, including the optional
+/// keys that the gateway recognizes, follow alphabetically.
+const LEADING_KEYS: [&str; 4] = [
+    OS_GATEWAY_KEYS.event_type,
+    OS_GATEWAY_KEYS.scope_address,
+    OS_GATEWAY_KEYS.target_account,
+    OS_GATEWAY_KEYS.access_grant_id,
+];
+
+fn emission_order(key: &str) -> (usize, &str) {
+    let position = LEADING_KEYS
+        .iter()
+        .position(|leading_key| *leading_key == key)
+        .unwrap_or(LEADING_KEYS.len());
+    (position, key)
+}
+
+impl<'a> IntoIterator for &'a OsGatewayAttributeGenerator {
+    type Item = (&'a str, &'a str);
+
+    type IntoIter = OsGatewayAttributeIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A borrowing iterator over the key and value pairs held in an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator),
+/// produced by [iter](crate::OsGatewayAttributeGenerator::iter).
+#[derive(Clone, Debug)]
+pub struct OsGatewayAttributeIter<'a> {
+    inner: IntoIter<(&'a str, &'a str)>,
+}
+impl<'a> Iterator for OsGatewayAttributeIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+impl ExactSizeIterator for OsGatewayAttributeIter<'_> {}
+
+/// Renders each attribute as a `key=value` pair, in the same order as this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter)
+/// implementation.  Pairs are separated by commas by default, and the alternate form (`{:#}`)
+/// renders one pair per line instead.  Values that contain commas, newlines, or double quotes are
+/// rendered as quoted and escaped strings.
+impl Display for OsGatewayAttributeGenerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let separator = if f.alternate() { "\n" } else { ", " };
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(separator)?;
+            }
+            if value.contains([',', '\n', '\r', '"']) {
+                write!(f, "{key}={value:?}")?;
+            } else {
+                write!(f, "{key}={value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders all attributes on a single line in the same order as this struct's [IntoIterator](self::OsGatewayAttributeGenerator::into_iter)
+/// implementation.  The alternate form (`{:#?}`) calls out the event type at the top and renders
+/// one `key: value` pair per line, grouped as required attributes first and optional attributes
+/// second.
+impl Debug for OsGatewayAttributeGenerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if !f.alternate() {
+            f.write_str("OsGatewayAttributeGenerator {")?;
+            for (index, (key, value)) in self.iter().enumerate() {
+                let separator = if index > 0 { ", " } else { " " };
+                write!(f, "{separator}{key}: {value:?}")?;
+            }
+            return f.write_str(if self.is_empty() { "}" } else { " }" });
+        }
+        match self.get(OS_GATEWAY_KEYS.event_type) {
+            Some(event_type) => write!(f, "OsGatewayAttributeGenerator({event_type:?}) {{")?,
+            None => f.write_str("OsGatewayAttributeGenerator(<no event type>) {")?,
+        }
+        for (group, is_required) in [("required", true), ("optional", false)] {
+            let mut pairs = self.iter().filter(|(key, _)| {
+                *key != OS_GATEWAY_KEYS.event_type && REQUIRED_KEYS.contains(key) == is_required
+            });
+            if let Some((key, value)) = pairs.next() {
+                write!(f, "\n    {group}:\n        {key}: {value:?},")?;
+            }
+            for (key, value) in pairs {
+                write!(f, "\n        {key}: {value:?},")?;
+            }
+        }
+        f.write_str("\n}")
+    }
+}
+/// Serializes the generator as a flat map of attribute keys to values, in the canonical order
+/// described by [EventEncoder](crate::EventEncoder), so that JSON, MessagePack, and every other
+/// serde format share the ordering of the crate's other encodings.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayAttributeGenerator {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut encoder = SerializeMapEncoder {
+            map: serializer.serialize_map(Some(self.attributes.len()))?,
+            error: None,
+        };
+        self.encode(&mut encoder);
+        match encoder.error {
+            Some(error) => Err(error),
+            None => serde::ser::SerializeMap::end(encoder.map),
+        }
+    }
+}
+/// Serializes every attribute as an entry of a serde map.  The first error produced by the
+/// serializer is retained, and every attribute that follows it is skipped.
+#[cfg(feature = "serde")]
+struct SerializeMapEncoder<M: serde::ser::SerializeMap> {
+    map: M,
+    error: Option<M::Error>,
+}
+#[cfg(feature = "serde")]
+impl<M: serde::ser::SerializeMap> EventEncoder for SerializeMapEncoder<M> {
+    fn field(&mut self, key: OsGatewayKey, value: &str) {
+        self.extra_attribute(key.as_str(), value);
+    }
+
+    fn extra_attribute(&mut self, key: &str, value: &str) {
+        if self.error.is_none() {
+            self.error = self.map.serialize_entry(key, value).err();
+        }
+    }
+}
+/// Deserializes the generator from a flat map of attribute keys to values, rejecting any key that
+/// is not recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayAttributeGenerator {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let attributes = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let unknown_keys = attributes
+            .keys()
+            .filter(|key| !ALL_KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect::<Vec<String>>();
+        if unknown_keys.is_empty() {
+            Ok(Self { attributes })
+        } else {
+            Err(serde::de::Error::custom(
+                OsGatewayError::UnknownAttributeKeys { keys: unknown_keys },
+            ))
+        }
+    }
+}
+/// Reconstructs a generator from a map of attribute keys to values, such as one persisted in
+/// contract state, with the same validation as [from_attributes](crate::OsGatewayAttributeGenerator::from_attributes).
+/// Use [from_attributes_lenient](crate::OsGatewayAttributeGenerator::from_attributes_lenient) to
+/// retain unrecognized keys instead of rejecting them.
+impl TryFrom<BTreeMap<String, String>> for OsGatewayAttributeGenerator {
+    type Error = OsGatewayError;
+
+    fn try_from(attributes: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        Self::from_attributes(attributes)
+    }
+}
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+impl From<OsGatewayAttributeGenerator> for Vec<Attribute> {
+    fn from(generator: OsGatewayAttributeGenerator) -> Self {
+        generator.into_attributes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute_generator::OsGatewayAttributeGenerator;
+    #[cfg(feature = "parsing")]
+    use crate::test_utils::{
+        assert_access_grant, assert_access_revoke, single_attribute_for_key, ExpectedGrant,
+        ExpectedRevoke,
+    };
+    use crate::{
+        AccountAddress, OsGatewayAttributeConflict, OsGatewayError, OsGatewayKey, ScopeAddress,
+        OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+    };
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use cosmwasm_std::{Addr, Attribute, Response};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    const DEFAULT_SCOPE_ADDRESS: &str = "scope_address";
+    const DEFAULT_TARGET_ACCOUNT: &str = "target_account_address";
+    const DEFAULT_GRANT_ID: &str = "grant_id";
+
+    impl OsGatewayAttributeGenerator {
+        pub fn test_access_grant() -> Self {
+            Self::access_grant(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+        }
+
+        pub fn test_access_revoke() -> Self {
+            Self::access_revoke(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+        }
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn test_access_grant_contents() {
+        let mut access_grant = OsGatewayAttributeGenerator::test_access_grant();
+        assert_eq!(
+            3,
+            access_grant.len(),
+            "the grant should hold only the required attributes"
+        );
+        assert_access_grant(
+            &Response::<()>::new().add_attributes(&access_grant),
+            ExpectedGrant::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT),
+        );
+        access_grant = access_grant.with_access_grant_id(DEFAULT_GRANT_ID);
+        assert_eq!(
+            4,
+            access_grant.len(),
+            "the grant should hold the access grant id"
+        );
+        assert_access_grant(
+            &Response::<()>::new().add_attributes(&access_grant),
+            ExpectedGrant::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+                .with_access_grant_id(DEFAULT_GRANT_ID),
+        );
+        access_grant = access_grant.with_access_grant_id("grant_id_2");
+        assert_eq!(
+            4,
+            access_grant.len(),
+            "the access grant id should be replaced"
+        );
+        assert_access_grant(
+            &Response::<()>::new().add_attributes(&access_grant),
+            ExpectedGrant::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+                .with_access_grant_id("grant_id_2"),
+        );
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn test_access_revoke_contents() {
+        let mut access_revoke = OsGatewayAttributeGenerator::test_access_revoke();
+        assert_eq!(
+            3,
+            access_revoke.len(),
+            "the revoke should hold only the required attributes"
+        );
+        assert_access_revoke(
+            &Response::<()>::new().add_attributes(&access_revoke),
+            ExpectedRevoke::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT),
+        );
+        access_revoke = access_revoke.with_access_grant_id(DEFAULT_GRANT_ID);
+        assert_eq!(
+            4,
+            access_revoke.len(),
+            "the revoke should hold the access grant id"
+        );
+        assert_access_revoke(
+            &Response::<()>::new().add_attributes(&access_revoke),
+            ExpectedRevoke::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+                .with_access_grant_id(DEFAULT_GRANT_ID),
+        );
+        access_revoke = access_revoke.with_access_grant_id("grant_id_2");
+        assert_eq!(
+            4,
+            access_revoke.len(),
+            "the access grant id should be replaced"
+        );
+        assert_access_revoke(
+            &Response::<()>::new().add_attributes(&access_revoke),
+            ExpectedRevoke::new(DEFAULT_SCOPE_ADDRESS, DEFAULT_TARGET_ACCOUNT)
+                .with_access_grant_id("grant_id_2"),
+        );
+    }
+
+    #[test]
+    fn test_optional_access_grant_id() {
+        let access_grant = OsGatewayAttributeGenerator::test_access_grant()
+            .with_optional_access_grant_id(None::<String>);
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_grant(),
+            access_grant,
+            "an absent access grant id should not be set",
+        );
+        let access_grant = access_grant.with_optional_access_grant_id(Some(DEFAULT_GRANT_ID));
+        assert_eq!(
+            Some(DEFAULT_GRANT_ID),
+            access_grant.get(OS_GATEWAY_KEYS.access_grant_id),
+            "a present access grant id should be set",
+        );
+        let access_grant = access_grant.with_optional_access_grant_id(Some("grant_id_2"));
+        assert_eq!(
+            Some("grant_id_2"),
+            access_grant.get(OS_GATEWAY_KEYS.access_grant_id),
+            "a present access grant id should replace the previous value",
+        );
+        let access_grant = access_grant.with_optional_access_grant_id(None::<&str>);
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id("grant_id_2"),
+            access_grant,
+            "an absent access grant id should retain the previous value",
+        );
+    }
+
+    #[test]
+    fn test_mutable_setters_match_consuming_setters() {
+        let consumed = OsGatewayAttributeGenerator::test_access_grant()
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .with_optional_access_grant_id(None::<String>);
+        let mut mutated = OsGatewayAttributeGenerator::test_access_grant();
+        mutated.set_access_grant_id(DEFAULT_GRANT_ID);
+        mutated.set_optional_access_grant_id(None::<String>);
+        assert_eq!(
+            consumed, mutated,
+            "both setter styles should produce identical generators",
+        );
+        let consumed = consumed.with_optional_access_grant_id(Some("grant_id_2"));
+        for grant_id in ["ignored", "grant_id_2"] {
+            mutated.set_optional_access_grant_id(Some(grant_id));
+        }
+        assert_eq!(
+            consumed, mutated,
+            "both setter styles should overwrite previous values identically",
+        );
+        assert!(
+            consumed.iter().eq(mutated.iter()),
+            "both setter styles should emit identical attributes",
+        );
+    }
+
+    #[test]
+    fn test_output_attributes_are_deterministic() {
+        // Verify first that two identically-built generators produce the same output
+        let first_grant_attrs = OsGatewayAttributeGenerator::test_access_grant()
+            .with_access_grant_id("a")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let second_grant_attrs = OsGatewayAttributeGenerator::test_access_grant()
+            .with_access_grant_id("a")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            first_grant_attrs,
+            second_grant_attrs,
+            "both grant attributes lists should be identical due to deterministic ordering of the BTreeMap",
+        );
+        assert_eq!(
+            4,
+            first_grant_attrs.len(),
+            "four attributes should be produced",
+        );
+        // Then verify that construction order has no impact on the output
+        let reordered_grant_attrs = OsGatewayAttributeGenerator::new()
+            .with_access_grant_id("a")
+            .with_target_account_address(DEFAULT_TARGET_ACCOUNT)
+            .with_scope_address(DEFAULT_SCOPE_ADDRESS)
+            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant)
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            first_grant_attrs, reordered_grant_attrs,
+            "the order in which attributes are provided should not impact the output order",
+        );
+    }
+
+    #[test]
+    fn test_output_attribute_order_is_pinned() {
+        let generator = OsGatewayAttributeGenerator::test_access_grant()
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .merge_overriding(
+                OsGatewayAttributeGenerator::new()
+                    .insert_attribute("object_store_gateway_zzz_future_key", "z")
+                    .insert_attribute(OS_GATEWAY_KEYS.expiration, "never")
+                    .insert_attribute(OS_GATEWAY_KEYS.access_level, "read")
+                    .insert_attribute("object_store_gateway_aaa_future_key", "a"),
+            )
+            .with_source_contract_version("my-contract", "1.4.2")
+            .with_payload_version();
+        let expected_keys = vec![
+            OS_GATEWAY_KEYS.event_type,
+            OS_GATEWAY_KEYS.scope_address,
+            OS_GATEWAY_KEYS.target_account,
+            OS_GATEWAY_KEYS.access_grant_id,
+            "object_store_gateway_aaa_future_key",
+            OS_GATEWAY_KEYS.access_level,
+            OS_GATEWAY_KEYS.expiration,
+            OS_GATEWAY_KEYS.payload_version,
+            OS_GATEWAY_KEYS.source_contract_version,
+            "object_store_gateway_zzz_future_key",
+        ];
+        assert_eq!(
+            expected_keys,
+            generator
+                .clone()
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<String>>(),
+            "owned iteration should emit the leading keys in order, followed by all other keys alphabetically",
+        );
+        assert_eq!(
+            expected_keys,
+            generator
+                .iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<&str>>(),
+            "borrowed iteration should emit the leading keys in order, followed by all other keys alphabetically",
+        );
+        #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+        assert_eq!(
+            expected_keys,
+            generator
+                .to_attributes()
+                .into_iter()
+                .map(|attribute| attribute.key)
+                .collect::<Vec<String>>(),
+            "attribute conversion should emit the leading keys in order, followed by all other keys alphabetically",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_attribute_conversions_are_identical() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let borrowed_attributes = generator.to_attributes();
+        let owned_attributes: Vec<Attribute> = generator.clone().into();
+        assert_eq!(
+            borrowed_attributes, owned_attributes,
+            "the borrowed and owned conversions should produce identical attribute lists",
+        );
+        let iterated_attributes = generator
+            .into_iter()
+            .map(|(key, value)| Attribute::new(key, value))
+            .collect::<Vec<Attribute>>();
+        assert_eq!(
+            iterated_attributes, owned_attributes,
+            "the attribute conversions should retain the ordering produced by into_iter",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_into_attributes_matches_into_iter() {
+        let generator = OsGatewayAttributeGenerator::test_access_revoke()
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .insert_attribute("object_store_gateway_a_custom_key", "custom");
+        let iterated_attributes = generator
+            .clone()
+            .into_iter()
+            .map(|(key, value)| Attribute::new(key, value))
+            .collect::<Vec<Attribute>>();
+        let attributes = generator.into_attributes();
+        assert_eq!(
+            attributes.len(),
+            attributes.capacity(),
+            "the attributes should be allocated with exactly the required capacity",
+        );
+        assert_eq!(
+            iterated_attributes, attributes,
+            "into_attributes should produce the same attributes in the same order as into_iter",
+        );
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn test_add_to_response_retains_existing_attributes() {
+        let generator = OsGatewayAttributeGenerator::test_access_revoke();
+        let base_response: Response<String> =
+            Response::new().add_attribute("existing_key", "existing_value");
+        let borrowed_response = generator.apply_to(base_response.clone());
+        let consumed_response = generator.clone().add_to_response(base_response);
+        assert_eq!(
+            borrowed_response, consumed_response,
+            "applying the generator by reference and by value should produce identical responses",
+        );
+        assert_eq!(
+            1 + generator.len(),
+            consumed_response.attributes.len(),
+            "the response should contain the pre-existing attribute and all generator attributes",
+        );
+        assert_eq!(
+            "existing_value",
+            single_attribute_for_key(&consumed_response, "existing_key"),
+            "the pre-existing attribute should be retained in the response",
+        );
+        assert_eq!(
+            OS_GATEWAY_EVENT_TYPES.access_revoke,
+            single_attribute_for_key(&consumed_response, OS_GATEWAY_KEYS.event_type),
+            "the generator's attributes should be appended to the response",
+        );
+    }
+
+    #[test]
+    fn test_from_attributes_reproduces_emitted_attributes() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let mut pairs = generator
+            .clone()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        pairs.reverse();
+        let reconstructed = OsGatewayAttributeGenerator::from_attributes(pairs)
+            .expect("all recognized and required keys were provided");
+        assert_eq!(
+            generator, reconstructed,
+            "the reconstructed generator should match the original",
+        );
+        assert!(
+            generator.iter().eq(reconstructed.iter()),
+            "the reconstructed generator should emit identical attributes",
+        );
+    }
+
+    #[test]
+    fn test_from_attributes_rejects_unknown_keys() {
+        let mut pairs = OsGatewayAttributeGenerator::test_access_revoke()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        pairs.push(("legacy_key".to_string(), "legacy_value".to_string()));
+        assert_eq!(
+            OsGatewayError::UnknownAttributeKeys {
+                keys: vec!["legacy_key".to_string()],
+            },
+            OsGatewayAttributeGenerator::from_attributes(pairs.clone()).unwrap_err(),
+            "the strict reconstruction should reject unknown keys",
+        );
+        let lenient = OsGatewayAttributeGenerator::from_attributes_lenient(pairs)
+            .expect("the lenient reconstruction should accept unknown keys");
+        assert_eq!(
+            Some(("legacy_key", "legacy_value")),
+            lenient.iter().last(),
+            "the unknown key should be retained and emitted after the leading keys",
+        );
+    }
+
+    #[test]
+    fn test_try_from_map() {
+        let generator = OsGatewayAttributeGenerator::test_access_revoke()
+            .with_access_grant_id(DEFAULT_GRANT_ID);
+        let reconstructed = OsGatewayAttributeGenerator::try_from(generator.as_map().clone())
+            .expect("a map produced by a generator should be accepted");
+        assert!(
+            generator.iter().eq(reconstructed.iter()),
+            "the reconstructed generator should emit identical attributes",
+        );
+        let mut unknown_map = generator.as_map().clone();
+        unknown_map.insert("unknown_b".to_string(), "b".to_string());
+        unknown_map.insert("unknown_a".to_string(), "a".to_string());
+        assert_eq!(
+            OsGatewayError::UnknownAttributeKeys {
+                keys: vec!["unknown_a".to_string(), "unknown_b".to_string()],
+            },
+            OsGatewayAttributeGenerator::try_from(unknown_map).unwrap_err(),
+            "every unknown key should be listed in the error",
+        );
+        let mut incomplete_map = generator.as_map().clone();
+        incomplete_map.remove(OS_GATEWAY_KEYS.target_account);
+        assert_eq!(
+            OsGatewayError::MissingRequiredAttributes {
+                keys: vec![OS_GATEWAY_KEYS.target_account.to_string()],
+            },
+            OsGatewayAttributeGenerator::try_from(incomplete_map).unwrap_err(),
+            "a map missing a required key should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_from_attributes_requires_all_required_keys() {
+        let pairs = vec![(
+            OS_GATEWAY_KEYS.scope_address.to_string(),
+            DEFAULT_SCOPE_ADDRESS.to_string(),
+        )];
+        assert_eq!(
+            OsGatewayError::MissingRequiredAttributes {
+                keys: vec![
+                    OS_GATEWAY_KEYS.event_type.to_string(),
+                    OS_GATEWAY_KEYS.target_account.to_string(),
+                ],
+            },
+            OsGatewayAttributeGenerator::from_attributes_lenient(pairs).unwrap_err(),
+            "the reconstruction should name every missing required key",
+        );
+    }
+
+    #[test]
+    fn test_merge_combines_distinct_and_identical_attributes() {
+        let decorator = OsGatewayAttributeGenerator::new()
+            .with_scope_address(DEFAULT_SCOPE_ADDRESS)
+            .with_access_grant_id(DEFAULT_GRANT_ID);
+        let merged = OsGatewayAttributeGenerator::test_access_grant()
+            .merge(decorator)
+            .expect("identical values should not produce a conflict");
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID),
+            merged,
+            "the merged generator should hold the distinct attributes of both generators",
+        );
+    }
+
+    #[test]
+    fn test_merge_reports_all_conflicts() {
+        let decorator =
+            OsGatewayAttributeGenerator::access_revoke("other_scope", DEFAULT_TARGET_ACCOUNT);
+        let error = OsGatewayAttributeGenerator::test_access_grant()
+            .merge(decorator)
+            .unwrap_err();
+        assert_eq!(
+            OsGatewayError::ConflictingAttributes {
+                conflicts: vec![
+                    OsGatewayAttributeConflict {
+                        key: OS_GATEWAY_KEYS.event_type.to_string(),
+                        existing_value: OS_GATEWAY_EVENT_TYPES.access_grant.to_string(),
+                        incoming_value: OS_GATEWAY_EVENT_TYPES.access_revoke.to_string(),
+                    },
+                    OsGatewayAttributeConflict {
+                        key: OS_GATEWAY_KEYS.scope_address.to_string(),
+                        existing_value: DEFAULT_SCOPE_ADDRESS.to_string(),
+                        incoming_value: "other_scope".to_string(),
+                    },
+                ],
+            },
+            error,
+            "the error should contain every conflicting key alongside both values",
+        );
+    }
+
+    #[test]
+    fn test_merge_overriding_prefers_other_values() {
+        let decorator = OsGatewayAttributeGenerator::new()
+            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_revoke)
+            .with_access_grant_id(DEFAULT_GRANT_ID);
+        let merged = OsGatewayAttributeGenerator::test_access_grant().merge_overriding(decorator);
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_revoke()
+                .with_access_grant_id(DEFAULT_GRANT_ID),
+            merged,
+            "the merged generator should hold the values of the other generator",
+        );
+    }
+
+    #[test]
+    fn test_borrowed_accessors_match_owned_iteration() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let owned_pairs = generator
+            .clone()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let borrowed_pairs = generator.as_pairs();
+        assert_eq!(
+            owned_pairs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            borrowed_pairs,
+            "the borrowed pairs should match the owned iteration exactly",
+        );
+        assert_eq!(
+            borrowed_pairs,
+            generator.as_pairs(),
+            "the borrowed pairs should be identical across calls",
+        );
+        let mut sorted_pairs = owned_pairs;
+        sorted_pairs.sort();
+        assert_eq!(
+            sorted_pairs,
+            generator
+                .as_map()
+                .iter()
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect::<Vec<(String, String)>>(),
+            "the borrowed map should contain every pair, ordered alphabetically by key",
+        );
+    }
+
+    #[cfg(feature = "metadata-address")]
+    #[test]
+    fn test_scope_uuid_constructors_match_string_constructors() {
+        let scope_address = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+        for scope_uuid in [
+            "91978ba2-5f35-459a-86a7-feca1b0512e0",
+            "91978ba25f35459a86a7feca1b0512e0",
+        ] {
+            assert_eq!(
+                Ok(OsGatewayAttributeGenerator::access_grant(
+                    scope_address,
+                    DEFAULT_TARGET_ACCOUNT,
+                )),
+                OsGatewayAttributeGenerator::access_grant_for_scope_uuid(
+                    scope_uuid,
+                    DEFAULT_TARGET_ACCOUNT,
+                ),
+                "the grant constructor should convert the uuid [{scope_uuid}] to its scope address",
+            );
+            assert_eq!(
+                Ok(OsGatewayAttributeGenerator::access_revoke(
+                    scope_address,
+                    DEFAULT_TARGET_ACCOUNT,
+                )),
+                OsGatewayAttributeGenerator::access_revoke_for_scope_uuid(
+                    scope_uuid,
+                    DEFAULT_TARGET_ACCOUNT,
+                ),
+                "the revoke constructor should convert the uuid [{scope_uuid}] to its scope address",
+            );
+        }
+        assert_eq!(
+            Err(OsGatewayError::InvalidScopeUuid {
+                uuid: "not-a-uuid".to_string(),
+                reason: "expected 32 hexadecimal digits, optionally hyphenated as 8-4-4-4-12"
+                    .to_string(),
+            }),
+            OsGatewayAttributeGenerator::access_grant_for_scope_uuid(
+                "not-a-uuid",
+                DEFAULT_TARGET_ACCOUNT,
+            ),
+            "an invalid uuid should produce a descriptive error",
+        );
+        assert_eq!(
+            Err(OsGatewayError::InvalidScopeUuid {
+                uuid: "91978ba2-5f35-459a-86a7-feca1b05-2e0".to_string(),
+                reason: "the uuid contains a non-hexadecimal character".to_string(),
+            }),
+            OsGatewayAttributeGenerator::access_revoke_for_scope_uuid(
+                "91978ba2-5f35-459a-86a7-feca1b05-2e0",
+                DEFAULT_TARGET_ACCOUNT,
+            ),
+            "a uuid with an extra hyphen should produce an error rather than a panic",
+        );
+    }
+
+    #[test]
+    fn test_typed_constructors_match_string_constructors() {
+        let scope_address = ScopeAddress::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zz").unwrap();
+        let target_account_address =
+            AccountAddress::new("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr").unwrap();
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_grant(
+                scope_address.as_str(),
+                target_account_address.as_str(),
+            ),
+            OsGatewayAttributeGenerator::access_grant_typed(
+                &scope_address,
+                &target_account_address
+            ),
+            "the typed grant constructor should match the string constructor",
+        );
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke(
+                scope_address.as_str(),
+                target_account_address.as_str(),
+            ),
+            OsGatewayAttributeGenerator::access_revoke_typed(
+                &scope_address,
+                &target_account_address
+            ),
+            "the typed revoke constructor should match the string constructor",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_addr_constructors_match_string_constructors() {
+        let target = Addr::unchecked(DEFAULT_TARGET_ACCOUNT);
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_grant(),
+            OsGatewayAttributeGenerator::access_grant_addr(DEFAULT_SCOPE_ADDRESS, &target),
+            "the addr grant constructor should produce the same attributes as the string constructor",
+        );
+        assert_eq!(
+            OsGatewayAttributeGenerator::test_access_revoke(),
+            OsGatewayAttributeGenerator::access_revoke_addr(DEFAULT_SCOPE_ADDRESS, &target),
+            "the addr revoke constructor should produce the same attributes as the string constructor",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_borrowed_iteration_extends_attributes() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let mut attributes = vec![Attribute::new("existing_key", "existing_value")];
+        attributes.extend(generator.iter().map(Attribute::from));
+        let mut expected_attributes = vec![Attribute::new("existing_key", "existing_value")];
+        expected_attributes.extend(generator.to_attributes());
+        assert_eq!(
+            expected_attributes, attributes,
+            "extending from the borrowed iterator should match extending from owned attributes",
+        );
+        let borrowed_response: Response<String> = Response::new().add_attributes(&generator);
+        let owned_response: Response<String> = Response::new().add_attributes(generator.clone());
+        assert_eq!(
+            owned_response, borrowed_response,
+            "a borrowed generator should be accepted directly by the response",
+        );
+        assert_eq!(
+            generator.len(),
+            generator.iter().len(),
+            "the borrowed iterator should report its exact size",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_to_event_contains_all_attributes() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let event = generator.to_event();
+        assert_eq!(
+            "object_store_gateway", event.ty,
+            "the custom event should use the gateway event type",
+        );
+        assert_eq!(
+            generator.to_attributes(),
+            event.attributes,
+            "the custom event should contain every attribute in emission order",
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_construction_order() {
+        let first = OsGatewayAttributeGenerator::test_access_grant()
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .insert_attribute("object_store_gateway_aaa_extra_key", "extra");
+        let second = OsGatewayAttributeGenerator::new()
+            .insert_attribute("object_store_gateway_aaa_extra_key", "extra")
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .with_target_account_address(DEFAULT_TARGET_ACCOUNT)
+            .with_scope_address(DEFAULT_SCOPE_ADDRESS)
+            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant);
+        assert_eq!(
+            first.fingerprint(),
+            second.fingerprint(),
+            "the fingerprint should not depend on the order of construction",
+        );
+        assert_eq!(
+            first.fingerprint_hash(),
+            second.fingerprint_hash(),
+            "the fingerprint hash should not depend on the order of construction",
+        );
+        assert_eq!(
+            format!(
+                "v1;object_store_gateway_aaa_extra_key=extra;{}={};{}={};{}={};{}={}",
+                OS_GATEWAY_KEYS.access_grant_id,
+                DEFAULT_GRANT_ID,
+                OS_GATEWAY_KEYS.event_type,
+                OS_GATEWAY_EVENT_TYPES.access_grant,
+                OS_GATEWAY_KEYS.scope_address,
+                DEFAULT_SCOPE_ADDRESS,
+                OS_GATEWAY_KEYS.target_account,
+                DEFAULT_TARGET_ACCOUNT,
+            ),
+            first.fingerprint(),
+            "the fingerprint should render the version followed by the pairs sorted by key",
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_changes_with_any_attribute() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let variants = [
+            OsGatewayAttributeGenerator::test_access_revoke()
+                .with_access_grant_id(DEFAULT_GRANT_ID),
+            OsGatewayAttributeGenerator::test_access_grant(),
+            generator.clone().with_access_grant_id("other_id"),
+            generator.clone().with_scope_address("other_scope"),
+            generator
+                .clone()
+                .with_target_account_address("other_target"),
+            generator
+                .clone()
+                .insert_attribute("object_store_gateway_extra_key", ""),
+        ];
+        for variant in variants {
+            assert_ne!(
+                generator.fingerprint(),
+                variant.fingerprint(),
+                "changing any attribute should change the fingerprint: {variant}",
+            );
+            assert_ne!(
+                generator.fingerprint_hash(),
+                variant.fingerprint_hash(),
+                "changing any attribute should change the fingerprint hash: {variant}",
+            );
+        }
+        assert_ne!(
+            OsGatewayAttributeGenerator::new()
+                .with_scope_address(format!("a;{}=b", OS_GATEWAY_KEYS.target_account))
+                .fingerprint(),
+            OsGatewayAttributeGenerator::new()
+                .with_scope_address("a")
+                .with_target_account_address("b")
+                .fingerprint(),
+            "escaping should prevent values from impersonating additional pairs",
+        );
+    }
+
+    #[test]
+    fn test_get_accessors() {
+        let generator = OsGatewayAttributeGenerator::test_access_grant();
+        assert_eq!(
+            Some(DEFAULT_SCOPE_ADDRESS),
+            generator.get(OS_GATEWAY_KEYS.scope_address),
+            "the raw accessor should produce the scope address",
+        );
+        assert_eq!(
+            Some(DEFAULT_TARGET_ACCOUNT),
+            generator.get(String::from(OS_GATEWAY_KEYS.target_account)),
+            "the raw accessor should accept owned keys",
+        );
+        assert_eq!(
+            Some(OS_GATEWAY_EVENT_TYPES.access_grant),
+            generator.get_key(OsGatewayKey::EventType),
+            "the typed accessor should produce the event type",
+        );
+        assert_eq!(
+            None,
+            generator.get_key(OsGatewayKey::AccessGrantId),
+            "the typed accessor should produce nothing for an unset key",
+        );
+        assert_eq!(
+            None,
+            generator.get("unknown_key"),
+            "the raw accessor should produce nothing for an unknown key",
+        );
+    }
+
+    #[test]
+    fn test_inspection_methods() {
+        let empty_generator = OsGatewayAttributeGenerator::new();
+        assert!(
+            empty_generator.is_empty(),
+            "a generator with no attributes should be empty",
+        );
+        assert_eq!(
+            0,
+            empty_generator.len(),
+            "a generator with no attributes should have no length",
+        );
+        let generator = OsGatewayAttributeGenerator::test_access_revoke();
+        assert!(
+            !generator.is_empty(),
+            "a generator with attributes should not be empty",
+        );
+        assert!(
+            generator.contains(OS_GATEWAY_KEYS.scope_address),
+            "the scope address should be contained in the generator",
+        );
+        assert!(
+            !generator.contains(OS_GATEWAY_KEYS.access_grant_id),
+            "the access grant id should not be contained in the generator before it is set",
+        );
+        let generator = generator.with_access_grant_id(DEFAULT_GRANT_ID);
+        assert!(
+            generator.contains(OS_GATEWAY_KEYS.access_grant_id),
+            "the access grant id should be contained in the generator after it is set",
+        );
+        assert_eq!(
+            4,
+            generator.len(),
+            "the generator should report every attribute it will produce",
+        );
+    }
+
+    #[test]
+    fn test_display_renders_ordered_pairs() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        assert_eq!(
+            format!(
+                "{}={}, {}={DEFAULT_SCOPE_ADDRESS}, {}={DEFAULT_TARGET_ACCOUNT}, {}={DEFAULT_GRANT_ID}",
+                OS_GATEWAY_KEYS.event_type,
+                OS_GATEWAY_EVENT_TYPES.access_grant,
+                OS_GATEWAY_KEYS.scope_address,
+                OS_GATEWAY_KEYS.target_account,
+                OS_GATEWAY_KEYS.access_grant_id,
+            ),
+            generator.to_string(),
+            "the compact display should render comma-separated pairs in iteration order",
+        );
+        assert_eq!(
+            format!(
+                "{}={}\n{}={DEFAULT_SCOPE_ADDRESS}\n{}={DEFAULT_TARGET_ACCOUNT}\n{}={DEFAULT_GRANT_ID}",
+                OS_GATEWAY_KEYS.event_type,
+                OS_GATEWAY_EVENT_TYPES.access_grant,
+                OS_GATEWAY_KEYS.scope_address,
+                OS_GATEWAY_KEYS.target_account,
+                OS_GATEWAY_KEYS.access_grant_id,
+            ),
+            format!("{generator:#}"),
+            "the alternate display should render one pair per line in iteration order",
+        );
+    }
+
+    #[test]
+    fn test_display_quotes_ambiguous_values() {
+        let generator = OsGatewayAttributeGenerator::new()
+            .with_access_grant_id("a,b")
+            .with_scope_address("line\nbreak");
+        assert_eq!(
+            format!(
+                "{}=\"line\\nbreak\", {}=\"a,b\"",
+                OS_GATEWAY_KEYS.scope_address, OS_GATEWAY_KEYS.access_grant_id,
+            ),
+            generator.to_string(),
+            "values containing commas or newlines should be quoted and escaped",
+        );
+    }
+
+    #[test]
+    fn test_debug_renders_compact_and_grouped_forms() {
+        let generator = OsGatewayAttributeGenerator::access_grant("scope", "target")
+            .with_access_grant_id("id")
+            .insert_attribute("object_store_gateway_extra_key", "extra");
+        assert_eq!(
+            "OsGatewayAttributeGenerator { \
+            object_store_gateway_event_type: \"access_grant\", \
+            object_store_gateway_scope_address: \"scope\", \
+            object_store_gateway_target_account_address: \"target\", \
+            object_store_gateway_access_grant_id: \"id\", \
+            object_store_gateway_extra_key: \"extra\" }",
+            format!("{generator:?}"),
+            "the default debug form should render all attributes on one line",
+        );
+        assert_eq!(
+            r#"OsGatewayAttributeGenerator("access_grant") {
+    required:
+        object_store_gateway_scope_address: "scope",
+        object_store_gateway_target_account_address: "target",
+    optional:
+        object_store_gateway_access_grant_id: "id",
+        object_store_gateway_extra_key: "extra",
+}"#,
+            format!("{generator:#?}"),
+            "the alternate debug form should call out the event type and group the attributes",
+        );
+    }
+
+    #[test]
+    fn test_debug_renders_incomplete_generators() {
+        assert_eq!(
+            "OsGatewayAttributeGenerator {}",
+            format!("{:?}", OsGatewayAttributeGenerator::new()),
+            "an empty generator should render empty braces",
+        );
+        assert_eq!(
+            r#"OsGatewayAttributeGenerator(<no event type>) {
+    required:
+        object_store_gateway_scope_address: "scope",
+}"#,
+            format!(
+                "{:#?}",
+                OsGatewayAttributeGenerator::new().with_scope_address("scope"),
+            ),
+            "a generator without an event type or optional attributes should omit them",
+        );
+    }
+
+    #[test]
+    fn test_equality_and_hashing_ignore_construction_order() {
+        let first =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let second = OsGatewayAttributeGenerator::new()
+            .with_access_grant_id(DEFAULT_GRANT_ID)
+            .with_target_account_address(DEFAULT_TARGET_ACCOUNT)
+            .with_scope_address(DEFAULT_SCOPE_ADDRESS)
+            .with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant);
+        assert_eq!(
+            first, second,
+            "generators with identical contents should be equal regardless of construction order",
+        );
+        assert_eq!(
+            hash_generator(&first),
+            hash_generator(&second),
+            "generators with identical contents should hash identically",
+        );
+        assert_ne!(
+            first,
+            OsGatewayAttributeGenerator::test_access_grant(),
+            "generators with differing contents should not be equal",
+        );
+        let deduplicated = [
+            first,
+            second,
+            OsGatewayAttributeGenerator::test_access_revoke(),
+        ]
+        .into_iter()
+        .collect::<HashSet<OsGatewayAttributeGenerator>>();
+        assert_eq!(
+            2,
+            deduplicated.len(),
+            "identical generators should be deduplicated by a hash set",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let generator =
+            OsGatewayAttributeGenerator::test_access_grant().with_access_grant_id(DEFAULT_GRANT_ID);
+        let binary = cosmwasm_std::to_json_binary(&generator)
+            .expect("the generator should serialize successfully");
+        let deserialized = cosmwasm_std::from_json::<OsGatewayAttributeGenerator>(&binary)
+            .expect("the generator should deserialize successfully");
+        assert_eq!(
+            generator, deserialized,
+            "the generator should be unchanged after a serialization round trip",
+        );
+        assert_eq!(
+            format!(
+                r#"{{"{}":"{DEFAULT_GRANT_ID}","{}":"{}","{}":"{DEFAULT_SCOPE_ADDRESS}","{}":"{DEFAULT_TARGET_ACCOUNT}"}}"#,
+                OS_GATEWAY_KEYS.access_grant_id,
+                OS_GATEWAY_KEYS.event_type,
+                OS_GATEWAY_EVENT_TYPES.access_grant,
+                OS_GATEWAY_KEYS.scope_address,
+                OS_GATEWAY_KEYS.target_account,
+            ),
+            String::from_utf8(binary.to_vec()).unwrap(),
+            "the generator should serialize as a flat string map",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_unknown_keys() {
+        let error = cosmwasm_std::from_json::<OsGatewayAttributeGenerator>(
+            format!(
+                r#"{{"{}":"{DEFAULT_SCOPE_ADDRESS}","unknown_key":"value"}}"#,
+                OS_GATEWAY_KEYS.scope_address,
+            )
+            .as_bytes(),
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("unknown_key"),
+            "the deserialization error should name the unknown key, but got: {error}",
+        );
+    }
+
+    fn hash_generator(generator: &OsGatewayAttributeGenerator) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        generator.hash(&mut hasher);
+        hasher.finish()
     }
 }
@@ -2,35 +2,211 @@ const EVENT_TYPE_KEY: &str = "object_store_gateway_event_type";
 const SCOPE_ADDRESS_KEY: &str = "object_store_gateway_scope_address";
 const TARGET_ACCOUNT_KEY: &str = "object_store_gateway_target_account_address";
 const ACCESS_GRANT_ID_KEY: &str = "object_store_gateway_access_grant_id";
+const EXPIRATION_KEY: &str = "object_store_gateway_expiration";
+const ACCESS_LEVEL_KEY: &str = "object_store_gateway_access_level";
+const PAYLOAD_VERSION_KEY: &str = "object_store_gateway_payload_version";
+const SOURCE_CONTRACT_VERSION_KEY: &str = "object_store_gateway_source_contract_version";
+const TARGET_KIND_KEY: &str = "object_store_gateway_target_kind";
+
+use crate::OsGatewayError;
+use alloc::string::ToString;
+use alloc::vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// The prefix shared by every attribute key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+/// Only the modules that match on the prefix, such as parsing, the compact payload, and the
+/// arbitrary events, which all build on cosmwasm-std or arbitrary, compile it.
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2", feature = "arbitrary"))]
+pub(crate) const OS_GATEWAY_KEY_PREFIX: &str = "object_store_gateway_";
+
+/// Every key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+pub(crate) const ALL_KEYS: [&str; 9] = [
+    OsGatewayKey::EventType.as_str(),
+    OsGatewayKey::ScopeAddress.as_str(),
+    OsGatewayKey::TargetAccount.as_str(),
+    OsGatewayKey::AccessGrantId.as_str(),
+    OsGatewayKey::Expiration.as_str(),
+    OsGatewayKey::AccessLevel.as_str(),
+    OsGatewayKey::PayloadVersion.as_str(),
+    OsGatewayKey::SourceContractVersion.as_str(),
+    OsGatewayKey::TargetKind.as_str(),
+];
+
+/// Every attribute key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway),
+/// in the same order as the fields of [OsGatewayKeys](crate::OsGatewayKeys).  This is useful for
+/// tooling that needs to filter raw attribute streams down to gateway-relevant attributes.
+pub const OS_GATEWAY_ALL_KEYS: &[&str] = &ALL_KEYS;
+
+/// Every attribute key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// as a fixed-size array, in the same order as the fields of [OsGatewayKeys](crate::OsGatewayKeys).
+/// Unlike [OS_GATEWAY_ALL_KEYS](crate::OS_GATEWAY_ALL_KEYS), the array's length and individual
+/// entries are usable in `const` contexts, such as array lengths and `match` patterns.  The length
+/// grows as the gateway recognizes new keys.
+pub const OS_GATEWAY_KEY_STRS: [&str; 9] = ALL_KEYS;
+
+/// Every key that must be present for [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// to process an event.
+pub(crate) const REQUIRED_KEYS: [&str; 3] = [
+    OsGatewayKey::EventType.as_str(),
+    OsGatewayKey::ScopeAddress.as_str(),
+    OsGatewayKey::TargetAccount.as_str(),
+];
+
+/// A typed representation of every attribute key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+/// Each variant corresponds to the identically-named field in [OS_GATEWAY_KEYS](crate::OS_GATEWAY_KEYS),
+/// which is derived from this enum so that the two can never disagree.
+///
+/// Variants are ordered in the same order that an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator)
+/// emits them.
+///
+/// # Variants
+///
+/// * `EventType` See [OsGatewayKeys](crate::OsGatewayKeys) `event_type`.
+///
+/// * `ScopeAddress` See [OsGatewayKeys](crate::OsGatewayKeys) `scope_address`.
+///
+/// * `TargetAccount` See [OsGatewayKeys](crate::OsGatewayKeys) `target_account`.
+///
+/// * `AccessGrantId` See [OsGatewayKeys](crate::OsGatewayKeys) `access_grant_id`.
+///
+/// * `Expiration` See [OsGatewayKeys](crate::OsGatewayKeys) `expiration`.
+///
+/// * `AccessLevel` See [OsGatewayKeys](crate::OsGatewayKeys) `access_level`.
+///
+/// * `PayloadVersion` See [OsGatewayKeys](crate::OsGatewayKeys) `payload_version`.
+///
+/// * `SourceContractVersion` See [OsGatewayKeys](crate::OsGatewayKeys) `source_contract_version`.
+///
+/// * `TargetKind` See [OsGatewayKeys](crate::OsGatewayKeys) `target_kind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OsGatewayKey {
+    EventType,
+    ScopeAddress,
+    TargetAccount,
+    AccessGrantId,
+    Expiration,
+    AccessLevel,
+    PayloadVersion,
+    SourceContractVersion,
+    TargetKind,
+}
+impl OsGatewayKey {
+    /// Every variant, in declaration order.
+    pub const ALL: [OsGatewayKey; 9] = [
+        Self::EventType,
+        Self::ScopeAddress,
+        Self::TargetAccount,
+        Self::AccessGrantId,
+        Self::Expiration,
+        Self::AccessLevel,
+        Self::PayloadVersion,
+        Self::SourceContractVersion,
+        Self::TargetKind,
+    ];
+
+    /// The exact attribute key string that [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+    /// expects for this key.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::EventType => EVENT_TYPE_KEY,
+            Self::ScopeAddress => SCOPE_ADDRESS_KEY,
+            Self::TargetAccount => TARGET_ACCOUNT_KEY,
+            Self::AccessGrantId => ACCESS_GRANT_ID_KEY,
+            Self::Expiration => EXPIRATION_KEY,
+            Self::AccessLevel => ACCESS_LEVEL_KEY,
+            Self::PayloadVersion => PAYLOAD_VERSION_KEY,
+            Self::SourceContractVersion => SOURCE_CONTRACT_VERSION_KEY,
+            Self::TargetKind => TARGET_KIND_KEY,
+        }
+    }
+
+    /// The name of the field in [OsGatewayKeys](crate::OsGatewayKeys) that holds this key, which
+    /// is a shorter name suitable for labeling the key, such as a column header.
+    pub const fn field_name(&self) -> &'static str {
+        match self {
+            Self::EventType => "event_type",
+            Self::ScopeAddress => "scope_address",
+            Self::TargetAccount => "target_account",
+            Self::AccessGrantId => "access_grant_id",
+            Self::Expiration => "expiration",
+            Self::AccessLevel => "access_level",
+            Self::PayloadVersion => "payload_version",
+            Self::SourceContractVersion => "source_contract_version",
+            Self::TargetKind => "target_kind",
+        }
+    }
+}
+impl Display for OsGatewayKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+/// Parses an exact attribute key string into its typed key, producing an
+/// [OsGatewayError::UnknownAttributeKeys](crate::OsGatewayError::UnknownAttributeKeys) if the
+/// string is not recognized.
+impl FromStr for OsGatewayKey {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|key| key.as_str() == s)
+            .ok_or_else(|| OsGatewayError::UnknownAttributeKeys {
+                keys: vec![s.to_string()],
+            })
+    }
+}
 
 /// A simple struct to contain all gateway key constants.
 ///
 /// # Parameters
 ///
 /// * `event_type` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which functionality to invoke upon digesting this event.
+///   which functionality to invoke upon digesting this event.
 ///
 /// * `scope_address` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#scope-data-structures)
-/// this event refers to.
+///   which [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#scope-data-structures)
+///   this event refers to.
 ///
 /// * `target_account` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-/// this event will take action upon.
+///   which [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   this event will take action upon.
 ///
 /// * `access_grant_id` If provided, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// that the access grant being referred to should be linked with this ID.
+///   that the access grant being referred to should be linked with this ID.
 ///
 /// __On a grant request__: The resulting grant will be created with this ID, or rejected if a grant
 /// with this ID already exists.
 ///
 /// __On a revoke request__: An existing grant with the specified scope and target account will be
 /// deleted if it exists.
+///
+/// * `expiration` If provided on a grant request, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+///   when the resulting grant stops being honored, encoded as described by [GrantExpiration](crate::GrantExpiration).
+///
+/// * `access_level` If provided on a grant request, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+///   the level of access that the resulting grant gives, encoded as described by [AccessLevel](crate::AccessLevel).
+///
+/// * `payload_version` If provided, this key denotes the layout of the event's attributes, so that
+///   consumers can detect events produced by an incompatible release.  See [OsGatewayPayloadVersion](crate::OsGatewayPayloadVersion).
+///
+/// * `source_contract_version` If provided, this key denotes the name and version of the contract
+///   that produced the event, as `<contract name>/<version>`, such as `my-contract/1.4.2`.
+///
+/// * `target_kind` If provided, this key denotes the kind of [GrantTarget](crate::GrantTarget)
+///   that the target account address identifies: `account`, `marker`, or `scope_spec`.  Events
+///   without it target an account.
 pub struct OsGatewayKeys<'a> {
     pub event_type: &'a str,
     pub scope_address: &'a str,
     pub target_account: &'a str,
     pub access_grant_id: &'a str,
+    pub expiration: &'a str,
+    pub access_level: &'a str,
+    pub payload_version: &'a str,
+    pub source_contract_version: &'a str,
+    pub target_kind: &'a str,
 }
 
 /// Contains all different attribute keys recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
@@ -39,27 +215,261 @@ pub struct OsGatewayKeys<'a> {
 /// # Values
 ///
 /// * `event_type` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which functionality to invoke upon digesting this event.
+///   which functionality to invoke upon digesting this event.
 ///
 /// * `scope_address` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#scope-data-structures)
-/// this event refers to.
+///   which [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#scope-data-structures)
+///   this event refers to.
 ///
 /// * `target_account` Denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// which [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
-/// this event will take action upon.
+///   which [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   this event will take action upon.
 ///
 /// * `access_grant_id` If provided, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
-/// that the access grant being referred to should be linked with this ID.
+///   that the access grant being referred to should be linked with this ID.
 ///
 /// __On a grant request__: The resulting grant will be created with this ID, or rejected if a grant
 /// with this ID already exists.
 ///
 /// __On a revoke request__: An existing grant with the specified scope and target account will be
 /// deleted if it exists.
+///
+/// * `expiration` If provided on a grant request, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+///   when the resulting grant stops being honored, encoded as described by [GrantExpiration](crate::GrantExpiration).
+///
+/// * `access_level` If provided on a grant request, this key denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+///   the level of access that the resulting grant gives, encoded as described by [AccessLevel](crate::AccessLevel).
+///
+/// * `payload_version` If provided, this key denotes the layout of the event's attributes, so that
+///   consumers can detect events produced by an incompatible release.  See [OsGatewayPayloadVersion](crate::OsGatewayPayloadVersion).
+///
+/// * `source_contract_version` If provided, this key denotes the name and version of the contract
+///   that produced the event, as `<contract name>/<version>`, such as `my-contract/1.4.2`.
+///
+/// * `target_kind` If provided, this key denotes the kind of [GrantTarget](crate::GrantTarget)
+///   that the target account address identifies: `account`, `marker`, or `scope_spec`.  Events
+///   without it target an account.
 pub const OS_GATEWAY_KEYS: OsGatewayKeys<'static> = OsGatewayKeys {
-    event_type: EVENT_TYPE_KEY,
-    scope_address: SCOPE_ADDRESS_KEY,
-    target_account: TARGET_ACCOUNT_KEY,
-    access_grant_id: ACCESS_GRANT_ID_KEY,
+    event_type: OsGatewayKey::EventType.as_str(),
+    scope_address: OsGatewayKey::ScopeAddress.as_str(),
+    target_account: OsGatewayKey::TargetAccount.as_str(),
+    access_grant_id: OsGatewayKey::AccessGrantId.as_str(),
+    expiration: OsGatewayKey::Expiration.as_str(),
+    access_level: OsGatewayKey::AccessLevel.as_str(),
+    payload_version: OsGatewayKey::PayloadVersion.as_str(),
+    source_contract_version: OsGatewayKey::SourceContractVersion.as_str(),
+    target_kind: OsGatewayKey::TargetKind.as_str(),
 };
+
+impl OsGatewayKeys<'_> {
+    /// Every attribute key recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+    /// Identical to [OS_GATEWAY_ALL_KEYS](crate::OS_GATEWAY_ALL_KEYS).
+    pub fn all(&self) -> &'static [&'static str] {
+        OS_GATEWAY_ALL_KEYS
+    }
+
+    /// Whether or not the given key is recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The attribute key to check.
+    pub fn contains(&self, key: &str) -> bool {
+        OS_GATEWAY_ALL_KEYS.contains(&key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OsGatewayError, OsGatewayKey, OsGatewayKeys, OS_GATEWAY_ALL_KEYS, OS_GATEWAY_KEYS,
+        OS_GATEWAY_KEY_STRS,
+    };
+
+    #[test]
+    fn test_all_keys_matches_struct_fields() {
+        // Exhaustively destructure the keys so that adding a field without updating this test, and
+        // therefore the array of all keys, fails to compile
+        let OsGatewayKeys {
+            event_type,
+            scope_address,
+            target_account,
+            access_grant_id,
+            expiration,
+            access_level,
+            payload_version,
+            source_contract_version,
+            target_kind,
+        } = OS_GATEWAY_KEYS;
+        let struct_keys = [
+            event_type,
+            scope_address,
+            target_account,
+            access_grant_id,
+            expiration,
+            access_level,
+            payload_version,
+            source_contract_version,
+            target_kind,
+        ];
+        assert_eq!(
+            struct_keys.len(),
+            OS_GATEWAY_ALL_KEYS.len(),
+            "the array of all keys should contain one entry per public field",
+        );
+        assert_eq!(
+            struct_keys.as_slice(),
+            OS_GATEWAY_KEYS.all(),
+            "the array of all keys should match the struct fields in declaration order",
+        );
+    }
+
+    #[test]
+    fn test_field_names_label_keys() {
+        for key in OsGatewayKey::ALL {
+            assert!(
+                key.as_str().contains(key.field_name()),
+                "the key [{key}] should contain its field name",
+            );
+            assert_eq!(
+                1,
+                OsGatewayKey::ALL
+                    .iter()
+                    .filter(|other| other.field_name() == key.field_name())
+                    .count(),
+                "the field name of [{key}] should be unique",
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_strs_match_struct_fields() {
+        let OsGatewayKeys {
+            event_type,
+            scope_address,
+            target_account,
+            access_grant_id,
+            expiration,
+            access_level,
+            payload_version,
+            source_contract_version,
+            target_kind,
+        } = OS_GATEWAY_KEYS;
+        assert_eq!(
+            [
+                event_type,
+                scope_address,
+                target_account,
+                access_grant_id,
+                expiration,
+                access_level,
+                payload_version,
+                source_contract_version,
+                target_kind,
+            ],
+            OS_GATEWAY_KEY_STRS,
+            "the key array should match the struct fields in declaration order",
+        );
+    }
+
+    #[test]
+    fn test_key_strs_are_const_usable() {
+        const KEY_COUNT: usize = OS_GATEWAY_KEY_STRS.len();
+        const SCOPE_ADDRESS: &str = OS_GATEWAY_KEY_STRS[1];
+        let flags = [false; KEY_COUNT];
+        assert_eq!(
+            OS_GATEWAY_ALL_KEYS.len(),
+            flags.len(),
+            "the key array length should be usable as an array length",
+        );
+        assert!(
+            matches!(OS_GATEWAY_KEYS.scope_address, SCOPE_ADDRESS),
+            "the key array entries should be usable as match patterns",
+        );
+    }
+
+    #[test]
+    fn test_contains_only_recognizes_gateway_keys() {
+        for key in OS_GATEWAY_KEYS.all() {
+            assert!(
+                OS_GATEWAY_KEYS.contains(key),
+                "the key [{key}] should be recognized",
+            );
+        }
+        assert!(
+            !OS_GATEWAY_KEYS.contains("object_store_gateway_unknown"),
+            "an unknown key should not be recognized",
+        );
+    }
+
+    #[test]
+    fn test_key_enum_round_trips_every_variant() {
+        for key in OsGatewayKey::ALL {
+            assert_eq!(
+                key,
+                key.as_str().parse::<OsGatewayKey>().unwrap(),
+                "the key [{key}] should parse back into the same variant",
+            );
+            assert_eq!(
+                key.as_str(),
+                key.to_string(),
+                "the key [{key}] should display as its attribute key string",
+            );
+        }
+        assert_eq!(
+            OS_GATEWAY_ALL_KEYS,
+            OsGatewayKey::ALL
+                .into_iter()
+                .map(|key| key.as_str())
+                .collect::<Vec<&str>>(),
+            "the key enum should cover every recognized key, in order",
+        );
+    }
+
+    #[test]
+    fn test_key_enum_matches_key_struct() {
+        assert_eq!(OS_GATEWAY_KEYS.event_type, OsGatewayKey::EventType.as_str());
+        assert_eq!(
+            OS_GATEWAY_KEYS.scope_address,
+            OsGatewayKey::ScopeAddress.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.target_account,
+            OsGatewayKey::TargetAccount.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.access_grant_id,
+            OsGatewayKey::AccessGrantId.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.expiration,
+            OsGatewayKey::Expiration.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.access_level,
+            OsGatewayKey::AccessLevel.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.payload_version,
+            OsGatewayKey::PayloadVersion.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.source_contract_version,
+            OsGatewayKey::SourceContractVersion.as_str(),
+        );
+        assert_eq!(
+            OS_GATEWAY_KEYS.target_kind,
+            OsGatewayKey::TargetKind.as_str(),
+        );
+    }
+
+    #[test]
+    fn test_key_enum_rejects_unknown_keys() {
+        assert_eq!(
+            OsGatewayError::UnknownAttributeKeys {
+                keys: vec!["event_type".to_string()],
+            },
+            "event_type".parse::<OsGatewayKey>().unwrap_err(),
+            "a key that is not an exact attribute key string should be rejected",
+        );
+    }
+}
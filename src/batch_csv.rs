@@ -0,0 +1,272 @@
+use crate::attribute_keys::ALL_KEYS;
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::{
+    GatewayEventBatch, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, OsGatewayKey,
+};
+use std::collections::BTreeSet;
+use std::io;
+
+/// The prefix of the header of each column that holds an extra attribute, followed by its key.
+const EXTRA_COLUMN_PREFIX: &str = "extra:";
+
+impl GatewayEventBatch {
+    /// Writes the batch as CSV, with a header row followed by one row per event, in order.
+    ///
+    /// The columns are the [field name](crate::OsGatewayKey::field_name) of every recognized
+    /// key, in declaration order: `event_type`, `scope_address`, `target_account`,
+    /// `access_grant_id`, `expiration`, `access_level`, `payload_version`,
+    /// `source_contract_version`, and `target_kind`.  These are followed by one column per extra
+    /// attribute held by any event, headed by `extra:` and its full attribute key, in alphabetical
+    /// order, so that no extra attribute can share a header with a recognized key.  Values are
+    /// written in their attribute encodings, and values that an event does not hold are left empty.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessGrant, GatewayEventBatch};
+    ///
+    /// let mut batch = GatewayEventBatch::new();
+    /// batch.push(AccessGrant::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// let mut csv = vec![];
+    /// batch.to_csv(&mut csv).expect("the batch should be written");
+    /// assert_eq!(batch, GatewayEventBatch::from_csv(csv.as_slice()).expect("the batch should be read"));
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `writer` The destination of the CSV.
+    pub fn to_csv<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let generators = self
+            .events()
+            .iter()
+            .cloned()
+            .map(OsGatewayAttributeGenerator::from)
+            .collect::<Vec<OsGatewayAttributeGenerator>>();
+        let extra_keys = generators
+            .iter()
+            .flat_map(|generator| generator.as_map().keys())
+            .map(String::as_str)
+            .filter(|key| !ALL_KEYS.contains(key))
+            .collect::<BTreeSet<&str>>();
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record(
+            OsGatewayKey::ALL
+                .iter()
+                .map(|key| key.field_name().to_string())
+                .chain(
+                    extra_keys
+                        .iter()
+                        .map(|key| format!("{EXTRA_COLUMN_PREFIX}{key}")),
+                ),
+        )?;
+        for generator in &generators {
+            csv.write_record(
+                ALL_KEYS
+                    .iter()
+                    .chain(extra_keys.iter())
+                    .map(|key| generator.get(key).unwrap_or_default()),
+            )?;
+        }
+        csv.flush()
+    }
+
+    /// Reads a batch from CSV in the layout written by [to_csv](self::GatewayEventBatch::to_csv).
+    ///
+    /// Columns may appear in any order and may be omitted, and rows may be shorter than the header.
+    /// A column is read as a recognized key when headed by its [field name](crate::OsGatewayKey::field_name)
+    /// or full attribute key, and as an extra attribute when headed by `extra:` and its key, or by
+    /// any other key prefixed with `object_store_gateway_`.  All other columns, such as reviewer
+    /// notes, are ignored, as are empty values.
+    ///
+    /// Produces an [OsGatewayError::InvalidCsv](crate::OsGatewayError::InvalidCsv) naming line 1
+    /// if more than one column holds the same attribute, or naming the line of the first row that
+    /// cannot be read or does not describe a complete event.
+    ///
+    /// # Parameters
+    ///
+    /// * `reader` The source of the CSV, which must begin with a header row.
+    pub fn from_csv<R: io::Read>(reader: R) -> Result<Self, OsGatewayError> {
+        let invalid = |line: u64, reason: String| OsGatewayError::InvalidCsv { line, reason };
+        let mut csv = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let column_keys = csv
+            .headers()
+            .map_err(|error| invalid(1, error.to_string()))?
+            .iter()
+            .map(column_key)
+            .collect::<Vec<Option<String>>>();
+        let mut seen_keys = BTreeSet::new();
+        if let Some(key) = column_keys
+            .iter()
+            .flatten()
+            .find(|key| !seen_keys.insert(key.as_str()))
+        {
+            return Err(invalid(
+                1,
+                format!("more than one column holds the attribute [{key}]"),
+            ));
+        }
+        let mut batch = Self::new();
+        for record in csv.records() {
+            let record = record.map_err(|error| {
+                let line = error.position().map_or(0, |position| position.line());
+                invalid(line, error.to_string())
+            })?;
+            let line = record.position().map_or(0, |position| position.line());
+            let attributes = column_keys
+                .iter()
+                .zip(record.iter())
+                .filter(|(_, value)| !value.is_empty())
+                .filter_map(|(key, value)| Some((key.clone()?, value.to_string())));
+            let event = OsGatewayAttributeGenerator::from_attributes_lenient(attributes)
+                .and_then(OsGatewayEvent::try_from)
+                .map_err(|error| invalid(line, error.to_string()))?;
+            batch.push(event);
+        }
+        Ok(batch)
+    }
+}
+
+/// The attribute key held in the column with the given header, or `None` if the column does not
+/// hold gateway attributes.
+fn column_key(header: &str) -> Option<String> {
+    if let Some(key) = header.strip_prefix(EXTRA_COLUMN_PREFIX) {
+        return Some(key.to_string());
+    }
+    OsGatewayKey::ALL
+        .into_iter()
+        .find(|key| key.field_name() == header)
+        .map(|key| key.as_str().to_string())
+        .or_else(|| {
+            header
+                .starts_with(OS_GATEWAY_KEY_PREFIX)
+                .then(|| header.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, GatewayEventBatch, GrantExpiration, OsGatewayError,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn write(batch: &GatewayEventBatch) -> String {
+        let mut csv = vec![];
+        batch.to_csv(&mut csv).expect("the batch should be written");
+        String::from_utf8(csv).unwrap()
+    }
+
+    #[test]
+    fn test_batches_round_trip() {
+        let mut batch = GatewayEventBatch::new();
+        batch.push(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::ReadWrite)
+                .with_extra_attribute("object_store_gateway_note", "shared, \"urgently\"\nby ops"),
+        );
+        batch.push(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+        let csv = write(&batch);
+        assert_eq!(
+            format!(
+                concat!(
+                    "event_type,scope_address,target_account,access_grant_id,expiration,access_level,payload_version,source_contract_version,target_kind,extra:object_store_gateway_note\n",
+                    "access_grant,{SCOPE_ADDRESS},{ACCOUNT_ADDRESS},my_unique_id,height:100,read_write,,,,\"shared, \"\"urgently\"\"\nby ops\"\n",
+                    "access_revoke,{SCOPE_ADDRESS},{ACCOUNT_ADDRESS},,,,,,,\n",
+                ),
+                SCOPE_ADDRESS = SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+            ),
+            csv,
+            "the batch should be written with the documented columns, quoting where needed",
+        );
+        assert_eq!(
+            Ok(batch),
+            GatewayEventBatch::from_csv(csv.as_bytes()),
+            "the batch should be unchanged after a CSV round trip",
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_missing_columns_are_tolerated() {
+        let csv = format!(
+            concat!(
+                "notes,target_account,object_store_gateway_future,scope_address,event_type\n",
+                "looks fine,{ACCOUNT_ADDRESS},value,{SCOPE_ADDRESS},access_grant\n",
+                "short row,{ACCOUNT_ADDRESS},,{SCOPE_ADDRESS},access_revoke\n",
+            ),
+            SCOPE_ADDRESS = SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+        );
+        let mut expected = GatewayEventBatch::new();
+        expected.push(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_extra_attribute("object_store_gateway_future", "value"),
+        );
+        expected.push(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+        assert_eq!(
+            Ok(expected),
+            GatewayEventBatch::from_csv(csv.as_bytes()),
+            "unrelated columns should be ignored and prefixed columns retained as extras",
+        );
+    }
+
+    #[test]
+    fn test_extra_attributes_round_trip_under_their_own_columns() {
+        let mut batch = GatewayEventBatch::new();
+        batch.push(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_extra_attribute("memo", "unprefixed")
+                .with_extra_attribute("scope_address", "not the scope"),
+        );
+        let csv = write(&batch);
+        assert!(
+            csv.starts_with(
+                "event_type,scope_address,target_account,access_grant_id,expiration,access_level,payload_version,source_contract_version,target_kind,extra:memo,extra:scope_address\n",
+            ),
+            "every extra attribute should be written under its own prefixed column, but found: {csv}",
+        );
+        assert_eq!(
+            Ok(batch),
+            GatewayEventBatch::from_csv(csv.as_bytes()),
+            "unprefixed extra attributes should survive a CSV round trip without replacing fields",
+        );
+    }
+
+    #[test]
+    fn test_header_collisions_are_rejected() {
+        for header in [
+            "event_type,scope_address,target_account,object_store_gateway_scope_address",
+            "event_type,scope_address,target_account,extra:object_store_gateway_target_account_address",
+            "event_type,scope_address,target_account,extra:memo,extra:memo",
+        ] {
+            let csv =
+                format!("{header}\naccess_grant,{SCOPE_ADDRESS},{ACCOUNT_ADDRESS},value,value\n");
+            assert!(
+                matches!(
+                    GatewayEventBatch::from_csv(csv.as_bytes()),
+                    Err(OsGatewayError::InvalidCsv { line: 1, .. }),
+                ),
+                "columns holding the same attribute should be rejected in the header [{header}]",
+            );
+        }
+    }
+
+    #[test]
+    fn test_incomplete_rows_are_reported_by_line() {
+        let csv = format!(
+            "event_type,scope_address,target_account\naccess_grant,{SCOPE_ADDRESS},{ACCOUNT_ADDRESS}\naccess_grant,{SCOPE_ADDRESS},\n",
+        );
+        assert!(
+            matches!(
+                GatewayEventBatch::from_csv(csv.as_bytes()),
+                Err(OsGatewayError::InvalidCsv { line: 3, .. }),
+            ),
+            "a row without every required attribute should be reported with its line",
+        );
+    }
+}
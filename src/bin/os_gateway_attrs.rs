@@ -0,0 +1,218 @@
+//! Generates, parses, and validates gateway attributes from the command line, such as
+//! `cargo run --features cli --bin os-gateway-attrs generate grant --scope <scope> --target <account>`.
+//! Built entirely on the library's public API, so its behavior always matches the library's.
+
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_std_2 as cosmwasm_std;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use cosmwasm_std::Event;
+use os_gateway_contract_attributes::{
+    parse_gateway_events, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "os-gateway-attrs",
+    about = "Generates, parses, and validates Object Store Gateway attributes"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the attributes that an event emits.
+    Generate {
+        #[command(subcommand)]
+        event: GenerateEvent,
+    },
+    /// Parses gateway events from attribute maps or emitted events, printing each typed event.
+    Parse(InputArgs),
+    /// Validates every attribute of attribute maps, printing each problem found.
+    Validate(InputArgs),
+}
+
+#[derive(Subcommand)]
+enum GenerateEvent {
+    /// An access grant.
+    Grant(GenerateArgs),
+    /// An access revoke.
+    Revoke(GenerateArgs),
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// The bech32 address of the scope.
+    #[arg(long)]
+    scope: String,
+    /// The bech32 address of the target account.
+    #[arg(long)]
+    target: String,
+    /// The unique identifier of the grant.
+    #[arg(long)]
+    grant_id: Option<String>,
+    /// The format in which to print the attributes.
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// A JSON map of attribute keys to values.
+    Json,
+    /// One `key=value` line per attribute, in emission order.
+    Kv,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// The JSON file to read, or `-` for standard input.  Accepts an attribute map, an array of
+    /// attribute maps, or an array of emitted events with `type` and `attributes`.
+    #[arg(long, default_value = "-")]
+    from_json: String,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Generate { event } => generate(event),
+        Command::Parse(input) => read_input(&input).and_then(parse),
+        Command::Validate(input) => read_input(&input).and_then(validate),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn generate(event: GenerateEvent) -> Result<bool, String> {
+    let (generator, args) = match event {
+        GenerateEvent::Grant(args) => (
+            OsGatewayAttributeGenerator::access_grant(&args.scope, &args.target),
+            args,
+        ),
+        GenerateEvent::Revoke(args) => (
+            OsGatewayAttributeGenerator::access_revoke(&args.scope, &args.target),
+            args,
+        ),
+    };
+    let generator = generator.with_optional_access_grant_id(args.grant_id);
+    match args.format {
+        Format::Json => println!("{}", to_json(&generator)?),
+        Format::Kv => {
+            for (key, value) in generator.iter() {
+                println!("{key}={value}");
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// The gateway-relevant contents of an input file.
+enum Input {
+    AttributeMaps(Vec<BTreeMap<String, String>>),
+    Events(Vec<Event>),
+}
+
+fn read_input(args: &InputArgs) -> Result<Input, String> {
+    let json = if args.from_json == "-" {
+        let mut json = String::new();
+        std::io::stdin()
+            .read_to_string(&mut json)
+            .map_err(|error| format!("could not read standard input: {error}"))?;
+        json
+    } else {
+        std::fs::read_to_string(&args.from_json)
+            .map_err(|error| format!("could not read [{}]: {error}", args.from_json))?
+    };
+    let value = serde_json::from_str::<Value>(&json)
+        .map_err(|error| format!("the input is not JSON: {error}"))?;
+    let values = match value {
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+    let is_event = |value: &Value| value.get("type").is_some() && value.get("attributes").is_some();
+    if !values.is_empty() && values.iter().all(is_event) {
+        serde_json::from_value(Value::Array(values))
+            .map(Input::Events)
+            .map_err(|error| format!("the input contains an invalid event: {error}"))
+    } else {
+        serde_json::from_value(Value::Array(values))
+            .map(Input::AttributeMaps)
+            .map_err(|error| {
+                format!("the input must contain maps of attribute keys to string values: {error}")
+            })
+    }
+}
+
+fn parse(input: Input) -> Result<bool, String> {
+    let results = match input {
+        Input::AttributeMaps(maps) => maps
+            .into_iter()
+            .map(|map| {
+                OsGatewayAttributeGenerator::from_attributes_lenient(map)
+                    .and_then(OsGatewayEvent::try_from)
+            })
+            .collect(),
+        Input::Events(events) => parse_gateway_events(&events),
+    };
+    let mut all_parsed = true;
+    for result in results {
+        match result {
+            Ok(OsGatewayEvent::AccessGrant(grant)) => println!("{}", to_json(&grant)?),
+            Ok(OsGatewayEvent::AccessRevoke(revoke)) => println!("{}", to_json(&revoke)?),
+            Ok(event) => println!("{}", to_json(&event)?),
+            Err(error) => {
+                all_parsed = false;
+                report(&error);
+            }
+        }
+    }
+    Ok(all_parsed)
+}
+
+fn validate(input: Input) -> Result<bool, String> {
+    let maps = match input {
+        Input::AttributeMaps(maps) => maps,
+        Input::Events(_) => {
+            return Err("validate accepts attribute maps, not emitted events".to_string());
+        }
+    };
+    let mut all_valid = true;
+    for (index, map) in maps.into_iter().enumerate() {
+        match OsGatewayAttributeGenerator::from_attributes(map)
+            .and_then(|generator| generator.validate())
+        {
+            Ok(()) => println!("[{index}] valid"),
+            Err(OsGatewayError::InvalidFields { errors }) => {
+                all_valid = false;
+                for error in errors {
+                    println!("[{index}] {error}");
+                }
+            }
+            Err(error) => {
+                all_valid = false;
+                println!("[{index}] {error}");
+            }
+        }
+    }
+    Ok(all_valid)
+}
+
+fn report(error: &OsGatewayError) {
+    eprintln!("{}: {error}", error.code());
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|error| format!("could not serialize the output: {error}"))
+}
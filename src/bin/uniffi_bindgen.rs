@@ -0,0 +1,6 @@
+//! Generates foreign language bindings for the `ffi` feature from the compiled library, such as
+//! `cargo run --features ffi-bindgen --bin uniffi-bindgen generate --library <library> --language kotlin --out-dir <dir>`.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
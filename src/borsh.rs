@@ -0,0 +1,349 @@
+use crate::{
+    AccessGrant, AccessGrantId, AccessLevel, AccessRevoke, AccountAddress, GrantExpiration,
+    OsGatewayError, OsGatewayEvent, OsGatewayPayloadVersion, ScopeAddress,
+};
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::collections::BTreeMap;
+
+// Every layout below is written field by field, rather than derived, so that reordering the
+// declarations of a struct or enum can never change the bytes of archived events.  Variant tags
+// are fixed numbers that must never be reused.
+
+const ACCESS_LEVEL_READ_TAG: u8 = 0;
+const ACCESS_LEVEL_READ_WRITE_TAG: u8 = 1;
+
+const EXPIRATION_NEVER_TAG: u8 = 0;
+const EXPIRATION_AT_SECONDS_TAG: u8 = 1;
+const EXPIRATION_AT_HEIGHT_TAG: u8 = 2;
+
+const EVENT_ACCESS_GRANT_TAG: u8 = 0;
+const EVENT_ACCESS_REVOKE_TAG: u8 = 1;
+
+/// Serializes the grant as its scope address, target account address, access grant id,
+/// expiration, access level, and extra attributes, in that order.
+impl BorshSerialize for AccessGrant {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.scope_address.serialize(writer)?;
+        self.target_account_address.serialize(writer)?;
+        self.access_grant_id.serialize(writer)?;
+        self.expiration.serialize(writer)?;
+        self.access_level.serialize(writer)?;
+        self.extra_attributes.serialize(writer)
+    }
+}
+/// Deserializes the grant from the layout written by its [BorshSerialize](borsh::BorshSerialize)
+/// implementation.
+impl BorshDeserialize for AccessGrant {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self {
+            scope_address: String::deserialize_reader(reader)?,
+            target_account_address: String::deserialize_reader(reader)?,
+            access_grant_id: Option::deserialize_reader(reader)?,
+            expiration: Option::deserialize_reader(reader)?,
+            access_level: Option::deserialize_reader(reader)?,
+            extra_attributes: BTreeMap::deserialize_reader(reader)?,
+        })
+    }
+}
+
+/// Serializes the revoke as its scope address, target account address, access grant id, and
+/// extra attributes, in that order.
+impl BorshSerialize for AccessRevoke {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.scope_address.serialize(writer)?;
+        self.target_account_address.serialize(writer)?;
+        self.access_grant_id.serialize(writer)?;
+        self.extra_attributes.serialize(writer)
+    }
+}
+/// Deserializes the revoke from the layout written by its [BorshSerialize](borsh::BorshSerialize)
+/// implementation.
+impl BorshDeserialize for AccessRevoke {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Self {
+            scope_address: String::deserialize_reader(reader)?,
+            target_account_address: String::deserialize_reader(reader)?,
+            access_grant_id: Option::deserialize_reader(reader)?,
+            extra_attributes: BTreeMap::deserialize_reader(reader)?,
+        })
+    }
+}
+
+/// Serializes the event as a one byte tag, `0` for a grant and `1` for a revoke, followed by the
+/// event itself.
+impl BorshSerialize for OsGatewayEvent {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            Self::AccessGrant(grant) => {
+                EVENT_ACCESS_GRANT_TAG.serialize(writer)?;
+                grant.serialize(writer)
+            }
+            Self::AccessRevoke(revoke) => {
+                EVENT_ACCESS_REVOKE_TAG.serialize(writer)?;
+                revoke.serialize(writer)
+            }
+        }
+    }
+}
+/// Deserializes the event from its tag and contents, rejecting unrecognized tags.
+impl BorshDeserialize for OsGatewayEvent {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            EVENT_ACCESS_GRANT_TAG => {
+                AccessGrant::deserialize_reader(reader).map(Self::AccessGrant)
+            }
+            EVENT_ACCESS_REVOKE_TAG => {
+                AccessRevoke::deserialize_reader(reader).map(Self::AccessRevoke)
+            }
+            tag => Err(unknown_tag("OsGatewayEvent", tag)),
+        }
+    }
+}
+
+/// Serializes the level as a one byte tag, `0` for read and `1` for read and write.
+impl BorshSerialize for AccessLevel {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            Self::Read => ACCESS_LEVEL_READ_TAG,
+            Self::ReadWrite => ACCESS_LEVEL_READ_WRITE_TAG,
+        }
+        .serialize(writer)
+    }
+}
+/// Deserializes the level from its tag, rejecting unrecognized tags.
+impl BorshDeserialize for AccessLevel {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            ACCESS_LEVEL_READ_TAG => Ok(Self::Read),
+            ACCESS_LEVEL_READ_WRITE_TAG => Ok(Self::ReadWrite),
+            tag => Err(unknown_tag("AccessLevel", tag)),
+        }
+    }
+}
+
+/// Serializes the expiration as a one byte tag, `0` for never, `1` for a block time, and `2` for a
+/// block height, followed by the contained value, if any.
+impl BorshSerialize for GrantExpiration {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        match self {
+            Self::Never => EXPIRATION_NEVER_TAG.serialize(writer),
+            Self::AtSeconds(seconds) => {
+                EXPIRATION_AT_SECONDS_TAG.serialize(writer)?;
+                seconds.serialize(writer)
+            }
+            Self::AtHeight(height) => {
+                EXPIRATION_AT_HEIGHT_TAG.serialize(writer)?;
+                height.serialize(writer)
+            }
+        }
+    }
+}
+/// Deserializes the expiration from its tag and contained value, rejecting unrecognized tags.
+impl BorshDeserialize for GrantExpiration {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        match u8::deserialize_reader(reader)? {
+            EXPIRATION_NEVER_TAG => Ok(Self::Never),
+            EXPIRATION_AT_SECONDS_TAG => u64::deserialize_reader(reader).map(Self::AtSeconds),
+            EXPIRATION_AT_HEIGHT_TAG => u64::deserialize_reader(reader).map(Self::AtHeight),
+            tag => Err(unknown_tag("GrantExpiration", tag)),
+        }
+    }
+}
+
+/// Serializes the version as its number.
+impl BorshSerialize for OsGatewayPayloadVersion {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.0.serialize(writer)
+    }
+}
+/// Deserializes the version from its number.
+impl BorshDeserialize for OsGatewayPayloadVersion {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        u32::deserialize_reader(reader).map(Self)
+    }
+}
+
+/// Implements borsh for a validated string newtype, which is written as a plain string and
+/// revalidated with its constructor when read.
+macro_rules! validated_string_borsh {
+    ($newtype:ident) => {
+        /// Serializes the value as a plain string.
+        impl BorshSerialize for $newtype {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                self.as_str().serialize(writer)
+            }
+        }
+        /// Deserializes the value from a plain string, rejecting values that do not pass
+        /// validation.
+        impl BorshDeserialize for $newtype {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                Self::new(String::deserialize_reader(reader)?).map_err(invalid_data)
+            }
+        }
+    };
+}
+validated_string_borsh!(AccessGrantId);
+validated_string_borsh!(AccountAddress);
+validated_string_borsh!(ScopeAddress);
+
+fn unknown_tag(type_name: &str, tag: u8) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("unrecognized {type_name} tag: {tag}"),
+    )
+}
+
+fn invalid_data(error: OsGatewayError) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessGrantId, AccessLevel, AccessRevoke, AccountAddress, GrantExpiration,
+        OsGatewayEvent, OsGatewayPayloadVersion, ScopeAddress,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn events() -> Vec<OsGatewayEvent> {
+        vec![
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::ReadWrite)
+                .with_extra_attribute("object_store_gateway_note", "shared")
+                .into(),
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_expiration(GrantExpiration::Never)
+                .into(),
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+        ]
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    #[test]
+    fn test_byte_layout_is_pinned() {
+        let grant = OsGatewayEvent::from(
+            AccessGrant::new("s", "t")
+                .with_access_grant_id("i")
+                .with_expiration(GrantExpiration::AtSeconds(1))
+                .with_access_level(AccessLevel::Read)
+                .with_extra_attribute("k", "v"),
+        );
+        assert_eq!(
+            concat!(
+                "00",                   // access grant tag
+                "0100000073",           // scope address
+                "0100000074",           // target account address
+                "010100000069",         // access grant id
+                "01010100000000000000", // expiration at seconds
+                "0100",                 // access level read
+                "01000000",             // one extra attribute
+                "010000006b",           // extra attribute key
+                "0100000076",           // extra attribute value
+            ),
+            hex(&borsh::to_vec(&grant).unwrap()),
+            "the grant layout should never change",
+        );
+        let revoke = OsGatewayEvent::from(AccessRevoke::new("s", "t"));
+        assert_eq!(
+            concat!(
+                "01",         // access revoke tag
+                "0100000073", // scope address
+                "0100000074", // target account address
+                "00",         // no access grant id
+                "00000000",   // no extra attributes
+            ),
+            hex(&borsh::to_vec(&revoke).unwrap()),
+            "the revoke layout should never change",
+        );
+        assert_eq!(
+            "0002c80000000000000001",
+            [
+                borsh::to_vec(&GrantExpiration::Never).unwrap(),
+                borsh::to_vec(&GrantExpiration::AtHeight(200)).unwrap(),
+                borsh::to_vec(&AccessLevel::ReadWrite).unwrap(),
+            ]
+            .iter()
+            .map(|bytes| hex(bytes))
+            .collect::<String>(),
+            "the expiration and access level layouts should never change",
+        );
+    }
+
+    #[test]
+    fn test_events_round_trip() {
+        for event in events() {
+            let bytes = borsh::to_vec(&event).unwrap();
+            assert_eq!(
+                event,
+                borsh::from_slice::<OsGatewayEvent>(&bytes).unwrap(),
+                "the event should survive a borsh round trip",
+            );
+        }
+        let bytes = borsh::to_vec(&events()).unwrap();
+        assert_eq!(
+            events(),
+            borsh::from_slice::<Vec<OsGatewayEvent>>(&bytes).unwrap(),
+            "an archive of events should survive a borsh round trip",
+        );
+    }
+
+    #[test]
+    fn test_newtypes_round_trip_and_revalidate() {
+        let scope_address = ScopeAddress::new(SCOPE_ADDRESS).unwrap();
+        let account_address = AccountAddress::new(ACCOUNT_ADDRESS).unwrap();
+        let access_grant_id = AccessGrantId::new("my_unique_id").unwrap();
+        assert_eq!(
+            borsh::to_vec(SCOPE_ADDRESS).unwrap(),
+            borsh::to_vec(&scope_address).unwrap(),
+            "a newtype should serialize as a plain string",
+        );
+        assert_eq!(
+            scope_address,
+            borsh::from_slice(&borsh::to_vec(&scope_address).unwrap()).unwrap(),
+            "the scope address should survive a borsh round trip",
+        );
+        assert_eq!(
+            account_address,
+            borsh::from_slice(&borsh::to_vec(&account_address).unwrap()).unwrap(),
+            "the account address should survive a borsh round trip",
+        );
+        assert_eq!(
+            access_grant_id,
+            borsh::from_slice(&borsh::to_vec(&access_grant_id).unwrap()).unwrap(),
+            "the access grant id should survive a borsh round trip",
+        );
+        assert_eq!(
+            OsGatewayPayloadVersion::CURRENT,
+            borsh::from_slice(&borsh::to_vec(&OsGatewayPayloadVersion::CURRENT).unwrap()).unwrap(),
+            "the payload version should survive a borsh round trip",
+        );
+        assert!(
+            borsh::from_slice::<ScopeAddress>(&borsh::to_vec(ACCOUNT_ADDRESS).unwrap()).is_err(),
+            "an invalid scope address should not deserialize",
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_tags_are_rejected() {
+        assert!(
+            borsh::from_slice::<OsGatewayEvent>(&[2]).is_err(),
+            "an unrecognized event tag should not deserialize",
+        );
+        assert!(
+            borsh::from_slice::<AccessLevel>(&[2]).is_err(),
+            "an unrecognized access level tag should not deserialize",
+        );
+        assert!(
+            borsh::from_slice::<GrantExpiration>(&[3]).is_err(),
+            "an unrecognized expiration tag should not deserialize",
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{EventEncoder, OsGatewayAttributeGenerator, OsGatewayEvent, OsGatewayKey};
+
+/// The first line of every canonical byte sequence, naming the canonicalization version.  This
+/// must be changed whenever the algorithm changes, so that signatures are never verified against
+/// bytes produced by a different algorithm.
+const CANONICAL_BYTES_VERSION: &str = "os-gateway-canonical:v1";
+
+/// The prefix reserved for attributes that carry detached signatures over an event's
+/// [canonical bytes](crate::OsGatewayAttributeGenerator::canonical_bytes).  Every attribute whose
+/// key begins with this prefix is excluded from the canonical bytes, so that a signature never
+/// covers itself.
+pub const OS_GATEWAY_SIGNATURE_KEY_PREFIX: &str = "object_store_gateway_signature";
+
+impl OsGatewayAttributeGenerator {
+    /// Produces the exact bytes that a client signs to authorize these attributes off chain, and
+    /// that the gateway reproduces to verify the signature.  The algorithm is fixed for each
+    /// canonicalization version, so the same attributes produce the same bytes on every platform
+    /// and in every release that uses the same version.
+    ///
+    /// The bytes are the UTF-8 encoding of the following text:
+    ///
+    /// 1. The version line `os-gateway-canonical:v1`, followed by a newline (`\n`).
+    /// 2. For every attribute, sorted by the bytes of its key, the escaped key followed by a
+    ///    newline, then the escaped value followed by a newline.  Attributes whose keys begin with
+    ///    [object_store_gateway_signature](crate::OS_GATEWAY_SIGNATURE_KEY_PREFIX) are skipped.
+    ///
+    /// Keys and values are escaped by replacing each backslash with `\\` and each newline with
+    /// `\n`, so that every line holds exactly one key or value.  No other characters are altered,
+    /// and no whitespace is trimmed.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// let generator = OsGatewayAttributeGenerator::access_revoke(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// );
+    /// assert_eq!(
+    ///     concat!(
+    ///         "os-gateway-canonical:v1\n",
+    ///         "object_store_gateway_event_type\naccess_revoke\n",
+    ///         "object_store_gateway_scope_address\nscope1qzn7jghj8puprmdcvunm3330jutsj803zz\n",
+    ///         "object_store_gateway_target_account_address\ntp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr\n",
+    ///     )
+    ///     .as_bytes(),
+    ///     generator.canonical_bytes(),
+    /// );
+    /// ```
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = CanonicalBytesEncoder {
+            text: format!("{CANONICAL_BYTES_VERSION}\n"),
+        };
+        self.encode(&mut encoder);
+        encoder.text.into_bytes()
+    }
+}
+
+impl OsGatewayEvent {
+    /// Produces the canonical bytes of the attributes that the event emits.  See
+    /// [OsGatewayAttributeGenerator::canonical_bytes](crate::OsGatewayAttributeGenerator::canonical_bytes).
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        OsGatewayAttributeGenerator::from(self.clone()).canonical_bytes()
+    }
+}
+
+/// Renders each attribute as an escaped key line and an escaped value line, skipping signatures.
+struct CanonicalBytesEncoder {
+    text: String,
+}
+impl EventEncoder for CanonicalBytesEncoder {
+    fn field(&mut self, key: OsGatewayKey, value: &str) {
+        push_escaped_line(&mut self.text, key.as_str());
+        push_escaped_line(&mut self.text, value);
+    }
+
+    fn extra_attribute(&mut self, key: &str, value: &str) {
+        if !key.starts_with(OS_GATEWAY_SIGNATURE_KEY_PREFIX) {
+            push_escaped_line(&mut self.text, key);
+            push_escaped_line(&mut self.text, value);
+        }
+    }
+}
+
+fn push_escaped_line(text: &mut String, value: &str) {
+    for character in value.chars() {
+        match character {
+            '\\' => text.push_str("\\\\"),
+            '\n' => text.push_str("\\n"),
+            character => text.push(character),
+        }
+    }
+    text.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrant, OsGatewayAttributeGenerator, OsGatewayEvent};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn canonical_text(generator: &OsGatewayAttributeGenerator) -> String {
+        String::from_utf8(generator.canonical_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_signature_attributes_are_excluded() {
+        let unsigned =
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("my_unique_id");
+        let signed = unsigned
+            .clone()
+            .with_extra_attribute("object_store_gateway_signature", "c2lnbmF0dXJl")
+            .with_extra_attribute("object_store_gateway_signature_key", "a2V5");
+        assert_eq!(
+            OsGatewayEvent::from(unsigned).canonical_bytes(),
+            OsGatewayEvent::from(signed).canonical_bytes(),
+            "adding signature attributes should not change the signed bytes",
+        );
+    }
+
+    #[test]
+    fn test_reserved_characters_are_escaped() {
+        let generator = OsGatewayAttributeGenerator::from(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_extra_attribute("object_store_gateway_note", "line\nbreak\\n"),
+        );
+        assert!(
+            canonical_text(&generator).contains("\nobject_store_gateway_note\nline\\nbreak\\\\n\n"),
+            "newlines and backslashes should be escaped so each value occupies one line",
+        );
+    }
+
+    #[test]
+    fn test_bytes_are_independent_of_insertion_order() {
+        let forward = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id");
+        let reversed = OsGatewayAttributeGenerator::from_attributes(
+            forward
+                .as_map()
+                .iter()
+                .rev()
+                .map(|(key, value)| (key.to_owned(), value.to_owned())),
+        )
+        .unwrap();
+        assert_eq!(
+            forward.canonical_bytes(),
+            reversed.canonical_bytes(),
+            "the order in which attributes were added should not change the bytes",
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! An asynchronous client for the REST API of [Object Store Gateway](https://github.com/provenance-io/object-store-gateway),
+//! for backends that must confirm that the gateway has processed a grant before reporting it to
+//! a user.
+//!
+//! The gateway processes events some time after the transaction that emitted them is committed,
+//! so [confirm_grant](self::GatewayClient::confirm_grant) polls with exponential backoff until the
+//! grant appears or the timeout elapses:
+//!
+//! ```no_run
+//! use os_gateway_contract_attributes::client::GatewayClient;
+//! use std::time::Duration;
+//!
+//! # async fn confirm() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = GatewayClient::new("https://gateway.example.com");
+//! let confirmation = client
+//!     .confirm_grant(
+//!         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!         Some("my_unique_id"),
+//!         Duration::from_secs(30),
+//!     )
+//!     .await?;
+//! println!("confirmed after {} attempts", confirmation.attempts);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::AccessGrant;
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// The path, relative to the gateway's base url, of the endpoint that lists the grants of a scope
+/// to a grantee.
+pub const GRANTS_PATH: &str = "/api/v1/grants";
+
+/// The delay before the first retry, when no other is configured.
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The longest delay between retries, when no other is configured.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// A grant confirmed by the gateway, produced by
+/// [confirm_grant](self::GatewayClient::confirm_grant).
+///
+/// # Parameters
+///
+/// * `grant` The grant, as recorded by the gateway.
+///
+/// * `granter_address` The bech32 address of the account that the gateway recorded as having
+///   given the grant, if it reported one.
+///
+/// * `attempts` The number of requests made before the grant was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrantConfirmation {
+    pub grant: AccessGrant,
+    pub granter_address: Option<String>,
+    pub attempts: u32,
+}
+
+/// The errors produced by a [GatewayClient](self::GatewayClient).
+///
+/// # Variants
+///
+/// * `Http` Denotes that a request could not be completed.  The contained error describes why.
+///
+/// * `UnexpectedStatus` Denotes that the gateway responded with a status that is neither success,
+///   not found, nor a server error.  The `status` field contains the status code, and the `body`
+///   field contains the body of the response.
+///
+/// * `InvalidResponse` Denotes that the gateway responded with a body that does not describe
+///   grants.  The `reason` field describes the problem.
+///
+/// * `Timeout` Denotes that the grant was not found before the timeout elapsed.  The `attempts`
+///   field contains the number of requests made.  If the gateway responded with a server error
+///   while polling, the `last_status` and `last_body` fields contain the status code and body of
+///   the last such response, so that an outage is not mistaken for a slow gateway.
+#[derive(Debug)]
+pub enum ClientError {
+    Http(reqwest::Error),
+    UnexpectedStatus {
+        status: u16,
+        body: String,
+    },
+    InvalidResponse {
+        reason: String,
+    },
+    Timeout {
+        attempts: u32,
+        last_status: Option<u16>,
+        last_body: Option<String>,
+    },
+}
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(error) => write!(f, "gateway request failed: {error}"),
+            Self::UnexpectedStatus { status, body } => {
+                write!(f, "gateway responded with status [{status}]: {body}")
+            }
+            Self::InvalidResponse { reason } => {
+                write!(f, "gateway responded with an invalid body: {reason}")
+            }
+            Self::Timeout {
+                attempts,
+                last_status,
+                last_body,
+            } => {
+                write!(
+                    f,
+                    "grant was not confirmed by the gateway after [{attempts}] attempts"
+                )?;
+                match last_status {
+                    Some(status) => write!(
+                        f,
+                        ", and its last server error was status [{status}]: {}",
+                        last_body.as_deref().unwrap_or_default(),
+                    ),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Http(error)
+    }
+}
+
+/// A client of a single gateway instance's REST API.
+#[derive(Clone, Debug)]
+pub struct GatewayClient {
+    http: reqwest::Client,
+    base_url: String,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+impl GatewayClient {
+    /// Creates a client of the gateway at the given base url, such as
+    /// `https://gateway.example.com`.
+    ///
+    /// # Parameters
+    ///
+    /// * `base_url` The url under which the gateway serves its REST API.
+    pub fn new<S: Into<String>>(base_url: S) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Sends requests with the given HTTP client, such as one configured with authentication
+    /// headers or a proxy.
+    ///
+    /// # Parameters
+    ///
+    /// * `http` The HTTP client.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sets the delays between polls.  Each delay doubles the previous one, up to the maximum.
+    ///
+    /// # Parameters
+    ///
+    /// * `initial_backoff` The delay before the first retry.
+    /// * `max_backoff` The longest delay between retries.
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff.max(initial_backoff);
+        self
+    }
+
+    /// Polls the gateway until it reports the grant of the scope to the target account, producing
+    /// a [ClientError::Timeout](self::ClientError::Timeout) if it does not before the timeout
+    /// elapses.  Not-found responses and server errors are retried, while any other failure ends
+    /// polling immediately.  The timeout reports the last server error received, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `target_account_address` The bech32 address of the account given access.
+    /// * `access_grant_id` The id of the grant to confirm.  Any grant of the scope to the account
+    ///   is confirmed when missing.
+    /// * `timeout` How long to poll before giving up.
+    pub async fn confirm_grant(
+        &self,
+        scope_address: &str,
+        target_account_address: &str,
+        access_grant_id: Option<&str>,
+        timeout: Duration,
+    ) -> Result<GrantConfirmation, ClientError> {
+        let deadline = Instant::now() + timeout;
+        let mut backoff = self.initial_backoff;
+        let mut attempts = 0;
+        let mut last_server_error = None;
+        loop {
+            attempts += 1;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let found = match self
+                .grants(scope_address, target_account_address, remaining)
+                .await
+            {
+                Ok(GrantsPoll::Grants(grants)) => grants.into_iter().find(|(grant, _)| {
+                    access_grant_id.is_none() || grant.access_grant_id.as_deref() == access_grant_id
+                }),
+                Ok(GrantsPoll::ServerError { status, body }) => {
+                    last_server_error = Some((status, body));
+                    None
+                }
+                Err(ClientError::Http(error)) if error.is_timeout() => None,
+                Err(error) => return Err(error),
+            };
+            if let Some((grant, granter_address)) = found {
+                return Ok(GrantConfirmation {
+                    grant,
+                    granter_address,
+                    attempts,
+                });
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let (last_status, last_body) = last_server_error.unzip();
+                return Err(ClientError::Timeout {
+                    attempts,
+                    last_status,
+                    last_body,
+                });
+            }
+            sleep(backoff.min(remaining)).await;
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+
+    /// Fetches the grants of the scope to the account, with the granter of each, treating
+    /// not-found responses as no grants.
+    async fn grants(
+        &self,
+        scope_address: &str,
+        target_account_address: &str,
+        timeout: Duration,
+    ) -> Result<GrantsPoll, ClientError> {
+        let response = self
+            .http
+            .get(format!("{}{GRANTS_PATH}", self.base_url))
+            .query(&[
+                ("scopeAddress", scope_address),
+                ("granteeAddress", target_account_address),
+            ])
+            .timeout(timeout)
+            .send()
+            .await?;
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND {
+            return Ok(GrantsPoll::Grants(vec![]));
+        }
+        if status.is_server_error() {
+            return Ok(GrantsPoll::ServerError {
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            });
+        }
+        if !status.is_success() {
+            return Err(ClientError::UnexpectedStatus {
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            });
+        }
+        parse_grants(&response.json::<Value>().await?).map(GrantsPoll::Grants)
+    }
+}
+
+/// The outcome of a single request for grants that does not end polling.
+enum GrantsPoll {
+    /// The grants of the scope to the account, with the granter of each.
+    Grants(Vec<(AccessGrant, Option<String>)>),
+    /// A server error, which is retried.
+    ServerError { status: u16, body: String },
+}
+
+/// Maps the body of a grants response, `{"grants": [{"scopeAddress", "granteeAddress", "grantId",
+/// "granterAddress"}]}`, into grants and their granters.  Unknown fields are ignored.
+fn parse_grants(body: &Value) -> Result<Vec<(AccessGrant, Option<String>)>, ClientError> {
+    let invalid = |reason: &str| ClientError::InvalidResponse {
+        reason: reason.to_string(),
+    };
+    body.get("grants")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("missing the [grants] array"))?
+        .iter()
+        .map(|grant| {
+            let field = |name: &str| grant.get(name).and_then(Value::as_str);
+            let scope_address = field("scopeAddress")
+                .ok_or_else(|| invalid("a grant is missing its [scopeAddress]"))?;
+            let grantee_address = field("granteeAddress")
+                .ok_or_else(|| invalid("a grant is missing its [granteeAddress]"))?;
+            Ok((
+                AccessGrant::new(scope_address, grantee_address)
+                    .with_optional_access_grant_id(field("grantId")),
+                field("granterAddress").map(str::to_owned),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{parse_grants, ClientError};
+    use crate::AccessGrant;
+    use serde_json::json;
+
+    #[test]
+    fn test_grants_are_mapped_into_typed_grants() {
+        assert_eq!(
+            vec![
+                (
+                    AccessGrant::new("scope", "grantee").with_access_grant_id("id"),
+                    Some("granter".to_string()),
+                ),
+                (AccessGrant::new("scope", "other_grantee"), None),
+            ],
+            parse_grants(&json!({
+                "grants": [
+                    {
+                        "scopeAddress": "scope",
+                        "granteeAddress": "grantee",
+                        "grantId": "id",
+                        "granterAddress": "granter",
+                        "created": "2024-01-01T00:00:00Z",
+                    },
+                    {"scopeAddress": "scope", "granteeAddress": "other_grantee", "grantId": null},
+                ],
+            }))
+            .unwrap(),
+            "every grant should be mapped, ignoring unknown fields",
+        );
+        assert!(
+            matches!(
+                parse_grants(&json!({"grants": [{"scopeAddress": "scope"}]})),
+                Err(ClientError::InvalidResponse { .. }),
+            ),
+            "a grant without a grantee should be rejected",
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::{EventEncoder, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayKey};
+use cosmwasm_std::{Attribute, Binary};
+use std::collections::BTreeMap;
+
+/// The key of the single attribute produced by [to_compact_attribute](crate::OsGatewayAttributeGenerator::to_compact_attribute),
+/// which carries an entire gateway event as its value.
+///
+/// The value is the base64 encoding of a JSON object that maps each attribute key, with its
+/// shared `object_store_gateway_` prefix removed, to its value, such as
+/// `{"event_type":"access_grant","scope_address":"...","target_account_address":"..."}`.  The
+/// object's keys are sorted and the JSON contains no whitespace, so equal events always produce
+/// identical bytes.  The payload is recognized by [parse_gateway_events](crate::parse_gateway_events)
+/// alongside the flat attribute form.
+pub const OS_GATEWAY_PAYLOAD_KEY: &str = "object_store_gateway_payload";
+
+impl OsGatewayAttributeGenerator {
+    /// Produces a single [Cosmwasm Attribute](cosmwasm_std::Attribute) keyed by [object_store_gateway_payload](crate::OS_GATEWAY_PAYLOAD_KEY)
+    /// that carries every attribute held in this generator, rather than one attribute per value.
+    /// This avoids repeating the long attribute keys for contracts that emit many events.
+    ///
+    /// Only attributes prefixed with `object_store_gateway_` are carried, as no others are
+    /// recognized when the event is parsed.
+    ///
+    /// ```
+    /// # #[cfg(feature = "parsing")] {
+    /// use cosmwasm_std::Event;
+    /// use os_gateway_contract_attributes::{parse_gateway_event, AccessGrant, OsGatewayAttributeGenerator, OsGatewayEvent};
+    ///
+    /// let generator = OsGatewayAttributeGenerator::access_grant(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// );
+    /// let event = Event::new("wasm").add_attributes([generator.to_compact_attribute()]);
+    /// assert_eq!(
+    ///     vec![Ok(OsGatewayEvent::AccessGrant(AccessGrant::new(
+    ///         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    ///     )))],
+    ///     parse_gateway_event(&event),
+    /// );
+    /// # }
+    /// ```
+    pub fn to_compact_attribute(&self) -> Attribute {
+        let mut encoder = CompactPayloadEncoder::default();
+        self.encode(&mut encoder);
+        // Serializing a map of strings cannot fail
+        let json = cosmwasm_std::to_json_vec(&encoder.shortened)
+            .expect("a map of strings should always serialize");
+        Attribute::new(OS_GATEWAY_PAYLOAD_KEY, Binary::from(json).to_base64())
+    }
+
+    /// Reconstructs a generator from the value of a [compact payload](crate::OS_GATEWAY_PAYLOAD_KEY)
+    /// attribute, retaining unrecognized keys as extra attributes in the same manner as
+    /// [from_attributes_lenient](self::OsGatewayAttributeGenerator::from_attributes_lenient).
+    ///
+    /// Returns an [OsGatewayError::InvalidCompactPayload](crate::OsGatewayError::InvalidCompactPayload)
+    /// if the value is not base64 encoded JSON mapping keys to strings, or an
+    /// [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+    /// if the event type, scope address, or target account is missing.
+    ///
+    /// # Parameters
+    ///
+    /// * `payload` The value of the compact payload attribute.
+    pub fn from_compact_payload(payload: &str) -> Result<Self, OsGatewayError> {
+        let invalid = |reason: String| OsGatewayError::InvalidCompactPayload {
+            payload: payload.to_string(),
+            reason,
+        };
+        let json = Binary::from_base64(payload)
+            .map_err(|error| invalid(format!("expected base64: {error}")))?;
+        let shortened = cosmwasm_std::from_json::<BTreeMap<String, String>>(&json)
+            .map_err(|error| invalid(format!("expected a JSON object of strings: {error}")))?;
+        Self::from_attributes_lenient(
+            shortened
+                .into_iter()
+                .map(|(short_key, value)| (format!("{OS_GATEWAY_KEY_PREFIX}{short_key}"), value)),
+        )
+    }
+}
+
+/// Collects every attribute prefixed with `object_store_gateway_` under its shortened key.
+#[derive(Default)]
+struct CompactPayloadEncoder {
+    shortened: BTreeMap<String, String>,
+}
+impl EventEncoder for CompactPayloadEncoder {
+    fn field(&mut self, key: OsGatewayKey, value: &str) {
+        self.extra_attribute(key.as_str(), value);
+    }
+
+    fn extra_attribute(&mut self, key: &str, value: &str) {
+        if let Some(short_key) = key.strip_prefix(OS_GATEWAY_KEY_PREFIX) {
+            self.shortened
+                .insert(short_key.to_string(), value.to_string());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use crate::{
+        parse_gateway_event, parse_gateway_event_ref, AccessGrant, AccessLevel, AccessRevoke,
+        GrantExpiration, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+        OS_GATEWAY_PAYLOAD_KEY,
+    };
+    use cosmwasm_std::{Binary, Event};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    #[test]
+    fn test_payload_is_canonical() {
+        let generator = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id");
+        let attribute = generator.to_compact_attribute();
+        assert_eq!(
+            OS_GATEWAY_PAYLOAD_KEY, attribute.key,
+            "the payload should be keyed by the payload key",
+        );
+        assert_eq!(
+            format!(
+                concat!(
+                    r#"{{"access_grant_id":"my_unique_id","event_type":"access_grant","#,
+                    r#""scope_address":"{SCOPE_ADDRESS}","target_account_address":"{ACCOUNT_ADDRESS}"}}"#,
+                ),
+                SCOPE_ADDRESS = SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+            ),
+            String::from_utf8(Binary::from_base64(&attribute.value).unwrap().to_vec()).unwrap(),
+            "the payload should be sorted JSON without whitespace or key prefixes",
+        );
+        let reordered = OsGatewayAttributeGenerator::from_attributes(
+            generator
+                .as_map()
+                .iter()
+                .rev()
+                .map(|(key, value)| (key.to_owned(), value.to_owned())),
+        )
+        .unwrap();
+        assert_eq!(
+            attribute,
+            reordered.to_compact_attribute(),
+            "the payload should not depend on the order in which the generator was built",
+        );
+    }
+
+    #[test]
+    fn test_payload_round_trips_through_parser() {
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::ReadWrite)
+            .with_extra_attribute("object_store_gateway_custom", "value");
+        let revoke = AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let event = Event::new("wasm")
+            .add_attribute("action", "share")
+            .add_attributes([
+                OsGatewayAttributeGenerator::from(grant.clone()).to_compact_attribute()
+            ])
+            .add_attributes(OsGatewayAttributeGenerator::from(revoke.clone()))
+            .add_attributes([
+                OsGatewayAttributeGenerator::from(grant.clone()).to_compact_attribute()
+            ]);
+        assert_eq!(
+            vec![
+                Ok(OsGatewayEvent::AccessGrant(grant.clone())),
+                Ok(OsGatewayEvent::AccessRevoke(revoke)),
+                Ok(OsGatewayEvent::AccessGrant(grant)),
+            ],
+            parse_gateway_event(&event),
+            "compact payloads should be parsed alongside flat attributes, in order",
+        );
+    }
+
+    #[test]
+    fn test_malformed_payloads_are_reported() {
+        for payload in ["not base64!", "WzFd", "eyJldmVudF90eXBlIjoxfQ=="] {
+            let event = Event::new("wasm").add_attribute(OS_GATEWAY_PAYLOAD_KEY, payload);
+            assert!(
+                matches!(
+                    parse_gateway_event(&event).as_slice(),
+                    [Err(OsGatewayError::InvalidCompactPayload { .. })],
+                ),
+                "the payload [{payload}] should be reported as invalid",
+            );
+        }
+        let incomplete = Binary::from(br#"{"event_type":"access_grant"}"#.as_slice()).to_base64();
+        assert!(
+            matches!(
+                OsGatewayAttributeGenerator::from_compact_payload(&incomplete),
+                Err(OsGatewayError::MissingRequiredAttributes { .. }),
+            ),
+            "a payload without every required attribute should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_borrowed_parser_reports_payloads() {
+        let event = Event::new("wasm").add_attributes([OsGatewayAttributeGenerator::access_grant(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+        )
+        .to_compact_attribute()]);
+        assert!(
+            matches!(
+                parse_gateway_event_ref(&event).as_slice(),
+                [Err(OsGatewayError::InvalidCompactPayload { .. })],
+            ),
+            "a compact payload cannot be borrowed and should be reported",
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{AccessGrant, AccessRevoke, OsGatewayError, OsGatewayEvent};
+use std::collections::BTreeMap;
+
+/// The kind that begins the compact string of an access grant.
+const GRANT_KIND: &str = "grant";
+
+/// The kind that begins the compact string of an access revoke.
+const REVOKE_KIND: &str = "revoke";
+
+/// The name of the option that holds a grant's expiration.
+const EXPIRATION_OPTION: &str = "expiration";
+
+/// The name of the option that holds a grant's access level.
+const ACCESS_LEVEL_OPTION: &str = "access_level";
+
+/// The marker that begins the option of an extra attribute.
+const EXTRA_MARKER: char = '+';
+
+impl OsGatewayEvent {
+    /// Renders the event as a single line of text, suitable for command line tools and logs.
+    /// This is a distinct format from the attributes that the event is emitted as, and is parsed
+    /// back into an identical event by [from_compact_string](self::OsGatewayEvent::from_compact_string).
+    ///
+    /// The grammar of the format is:
+    ///
+    /// ```text
+    /// line    = kind " " scope ":" target [ "@" id ] *( " " option )
+    /// kind    = "grant" / "revoke"
+    /// option  = "expiration=" value / "access_level=" value / "+" key "=" value
+    /// ```
+    ///
+    /// The `expiration` and `access_level` options are only written for grants, in their attribute
+    /// encodings.  Each extra attribute is written as an option marked by `+`, sorted by key.
+    /// Within every component, the characters `\`, space, `:`, `@`, and `=` are escaped by a
+    /// preceding `\`, and line breaks are written as `\n` and `\r`, so a line never contains an
+    /// unescaped delimiter or break.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessGrant, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(
+    ///     AccessGrant::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zz", "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr")
+    ///         .with_access_grant_id("my:grant"),
+    /// );
+    /// let line = event.to_compact_string();
+    /// assert_eq!(
+    ///     r"grant scope1qzn7jghj8puprmdcvunm3330jutsj803zz:tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr@my\:grant",
+    ///     line,
+    /// );
+    /// assert_eq!(Ok(event), OsGatewayEvent::from_compact_string(&line));
+    /// ```
+    pub fn to_compact_string(&self) -> String {
+        let (kind, options, extra_attributes) = match self {
+            Self::AccessGrant(grant) => {
+                let options = [
+                    (
+                        EXPIRATION_OPTION,
+                        grant.expiration.map(|expiration| expiration.to_string()),
+                    ),
+                    (
+                        ACCESS_LEVEL_OPTION,
+                        grant
+                            .access_level
+                            .map(|access_level| access_level.as_str().to_string()),
+                    ),
+                ];
+                (GRANT_KIND, options.to_vec(), &grant.extra_attributes)
+            }
+            Self::AccessRevoke(revoke) => (REVOKE_KIND, vec![], &revoke.extra_attributes),
+        };
+        let mut line = format!(
+            "{kind} {}:{}",
+            escape(self.scope_address()),
+            escape(self.target_account()),
+        );
+        if let Some(access_grant_id) = self.access_grant_id() {
+            line.push('@');
+            line.push_str(&escape(access_grant_id));
+        }
+        for (name, value) in options {
+            if let Some(value) = value {
+                line.push_str(&format!(" {name}={}", escape(&value)));
+            }
+        }
+        for (key, value) in extra_attributes {
+            line.push_str(&format!(" {EXTRA_MARKER}{}={}", escape(key), escape(value)));
+        }
+        line
+    }
+
+    /// Parses a line rendered by [to_compact_string](self::OsGatewayEvent::to_compact_string),
+    /// accepting options in any order.  Produces an [OsGatewayError::InvalidCompactString](crate::OsGatewayError::InvalidCompactString)
+    /// if the line does not follow the grammar, repeats an option, or holds an expiration or
+    /// access level that cannot be parsed.
+    ///
+    /// # Parameters
+    ///
+    /// * `line` The line to parse.
+    pub fn from_compact_string(line: &str) -> Result<Self, OsGatewayError> {
+        let invalid = |reason: String| OsGatewayError::InvalidCompactString {
+            value: line.to_string(),
+            reason,
+        };
+        let tokens = split_unescaped(line, ' ');
+        let (kind, addresses, options) = match tokens.as_slice() {
+            [kind, addresses, options @ ..] => (*kind, *addresses, options),
+            _ => {
+                return Err(invalid(
+                    "expected a kind followed by a scope and target".to_string(),
+                ))
+            }
+        };
+        let (addresses, access_grant_id) = match split_unescaped(addresses, '@').as_slice() {
+            [addresses] => (*addresses, None),
+            [addresses, access_grant_id] => (*addresses, Some(unescape(access_grant_id)?)),
+            _ => return Err(invalid("expected at most one [@]".to_string())),
+        };
+        let (scope_address, target_account_address) =
+            match split_unescaped(addresses, ':').as_slice() {
+                [scope_address, target_account_address] => {
+                    (unescape(scope_address)?, unescape(target_account_address)?)
+                }
+                _ => {
+                    return Err(invalid(
+                        "expected a scope and target separated by [:]".to_string(),
+                    ))
+                }
+            };
+        let mut named_options = BTreeMap::new();
+        let mut extra_attributes = BTreeMap::new();
+        for option in options {
+            let (name, value) = match split_unescaped(option, '=').as_slice() {
+                [name, value] => (*name, unescape(value)?),
+                _ => {
+                    return Err(invalid(format!(
+                        "expected the option [{option}] to be a name and value separated by [=]",
+                    )))
+                }
+            };
+            let repeated = match name.strip_prefix(EXTRA_MARKER) {
+                Some(key) => extra_attributes.insert(unescape(key)?, value).is_some(),
+                None => named_options.insert(name, value).is_some(),
+            };
+            if repeated {
+                return Err(invalid(format!("the option [{name}] is repeated")));
+            }
+        }
+        match kind {
+            GRANT_KIND => {
+                let expiration = named_options
+                    .remove(EXPIRATION_OPTION)
+                    .map(|expiration| expiration.parse())
+                    .transpose()
+                    .map_err(|error: OsGatewayError| invalid(error.to_string()))?;
+                let access_level = named_options
+                    .remove(ACCESS_LEVEL_OPTION)
+                    .map(|access_level| access_level.parse())
+                    .transpose()
+                    .map_err(|error: OsGatewayError| invalid(error.to_string()))?;
+                reject_unknown_options(&named_options).map_err(invalid)?;
+                Ok(Self::AccessGrant(AccessGrant {
+                    scope_address,
+                    target_account_address,
+                    access_grant_id,
+                    expiration,
+                    access_level,
+                    extra_attributes,
+                }))
+            }
+            REVOKE_KIND => {
+                reject_unknown_options(&named_options).map_err(invalid)?;
+                Ok(Self::AccessRevoke(AccessRevoke {
+                    scope_address,
+                    target_account_address,
+                    access_grant_id,
+                    extra_attributes,
+                }))
+            }
+            kind => Err(invalid(format!(
+                "expected the kind [{GRANT_KIND}] or [{REVOKE_KIND}] but found [{kind}]",
+            ))),
+        }
+    }
+}
+
+fn reject_unknown_options(options: &BTreeMap<&str, String>) -> Result<(), String> {
+    match options.keys().next() {
+        Some(name) => Err(format!("the option [{name}] is not recognized")),
+        None => Ok(()),
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\\' | ' ' | ':' | '@' | '=' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Splits the value at every occurrence of the delimiter that is not escaped, leaving the pieces
+/// escaped.
+fn split_unescaped(value: &str, delimiter: char) -> Vec<&str> {
+    let mut pieces = vec![];
+    let mut start = 0;
+    let mut escaping = false;
+    for (index, character) in value.char_indices() {
+        if escaping {
+            escaping = false;
+        } else if character == '\\' {
+            escaping = true;
+        } else if character == delimiter {
+            pieces.push(&value[start..index]);
+            start = index + character.len_utf8();
+        }
+    }
+    pieces.push(&value[start..]);
+    pieces
+}
+
+fn unescape(value: &str) -> Result<String, OsGatewayError> {
+    let invalid = |reason: &str| OsGatewayError::InvalidCompactString {
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+    let mut unescaped = String::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => match characters.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(escaped @ ('\\' | ' ' | ':' | '@' | '=')) => unescaped.push(escaped),
+                Some(_) => return Err(invalid("contains an unrecognized escape sequence")),
+                None => return Err(invalid("ends with an incomplete escape sequence")),
+            },
+            ' ' | ':' | '@' | '=' | '\n' | '\r' => {
+                return Err(invalid("contains an unescaped delimiter"))
+            }
+            character => unescaped.push(character),
+        }
+    }
+    Ok(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError, OsGatewayEvent,
+    };
+
+    /// Characters drawn from when generating values, weighted towards those that must be escaped.
+    const CHARACTERS: [char; 14] = [
+        'a', 'Z', '1', '_', '\\', ' ', ':', '@', '=', '+', '\n', '\r', 'é', 'n',
+    ];
+
+    /// Produces a deterministic pseudo-random event from the seed, with every component drawn from
+    /// a pool of characters that includes every delimiter and escape.
+    fn generated_event(seed: u64) -> OsGatewayEvent {
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % bound) as usize
+        };
+        let value = |next: &mut dyn FnMut(u64) -> usize| {
+            (0..next(6))
+                .map(|_| CHARACTERS[next(CHARACTERS.len() as u64)])
+                .collect::<String>()
+        };
+        let scope_address = value(&mut next);
+        let target_account_address = value(&mut next);
+        let access_grant_id = (next(2) == 0).then(|| value(&mut next));
+        let extra_attributes = (0..next(3))
+            .map(|_| (value(&mut next), value(&mut next)))
+            .collect();
+        if next(2) == 0 {
+            OsGatewayEvent::AccessRevoke(AccessRevoke {
+                scope_address,
+                target_account_address,
+                access_grant_id,
+                extra_attributes,
+            })
+        } else {
+            let expirations = [
+                None,
+                Some(GrantExpiration::Never),
+                Some(GrantExpiration::AtHeight(next(1000) as u64)),
+            ];
+            let access_levels = [None, Some(AccessLevel::Read), Some(AccessLevel::ReadWrite)];
+            OsGatewayEvent::AccessGrant(AccessGrant {
+                scope_address,
+                target_account_address,
+                access_grant_id,
+                expiration: expirations[next(3)],
+                access_level: access_levels[next(3)],
+                extra_attributes,
+            })
+        }
+    }
+
+    #[test]
+    fn test_grammar_is_rendered() {
+        let grant = OsGatewayEvent::from(
+            AccessGrant::new("scope", "target")
+                .with_access_grant_id("id@1")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::Read)
+                .with_extra_attribute("note", "a b"),
+        );
+        assert_eq!(
+            r"grant scope:target@id\@1 expiration=height\:100 access_level=read +note=a\ b",
+            grant.to_compact_string(),
+            "every component of a grant should be rendered and escaped",
+        );
+        assert_eq!(
+            "revoke scope:target",
+            OsGatewayEvent::from(AccessRevoke::new("scope", "target")).to_compact_string(),
+            "a revoke without options should render only its addresses",
+        );
+    }
+
+    #[test]
+    fn test_generated_events_round_trip() {
+        for seed in 0..1000 {
+            let event = generated_event(seed);
+            let line = event.to_compact_string();
+            assert!(
+                !line.contains('\n') && !line.contains('\r'),
+                "the line [{line}] should not contain line breaks",
+            );
+            assert_eq!(
+                Ok(event),
+                OsGatewayEvent::from_compact_string(&line),
+                "the line [{line}] should parse back into the same event",
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_lines_are_rejected() {
+        for line in [
+            "",
+            "grant",
+            "grant scope",
+            "audit scope:target",
+            "grant scope:target:other",
+            "grant scope:target@a@b",
+            "grant scope:target expiration",
+            "grant scope:target expiration=soon",
+            "grant scope:target access_level=admin",
+            "grant scope:target expiration=never expiration=never",
+            "revoke scope:target expiration=never",
+            "grant scope:target colour=blue",
+            r"grant scope:target@\x",
+            r"grant scope:target@id\",
+        ] {
+            assert!(
+                matches!(
+                    OsGatewayEvent::from_compact_string(line),
+                    Err(OsGatewayError::InvalidCompactString { .. }),
+                ),
+                "the line [{line}] should be rejected",
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::OsGatewayEvent;
+use sha2::{Digest, Sha256};
+
+/// The domain tag that begins every digested serialization.  This must be changed whenever the
+/// serialization changes, so that stored digests are never compared against a different format.
+const DIGEST_DOMAIN: &str = "os-gateway-event-digest:v1";
+
+/// Accumulates the canonical serialization of an event directly into the hasher.
+struct DigestWriter(Sha256);
+impl DigestWriter {
+    /// Writes the value's length in bytes as a big-endian u64, followed by its bytes.
+    fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.0.update(value.as_bytes());
+    }
+
+    /// Writes a byte of `0` for an absent value, or a byte of `1` followed by the value.
+    fn write_optional_str(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.0.update([1]);
+                self.write_str(value);
+            }
+            None => self.0.update([0]),
+        }
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.0.update((len as u64).to_be_bytes());
+    }
+}
+
+impl OsGatewayEvent {
+    /// Produces a SHA-256 digest of the event's content, suitable as an idempotency key when the
+    /// same event may be delivered more than once.  Equal events always produce equal digests,
+    /// regardless of whether they were built, converted, or parsed, and any change to any field
+    /// produces a different digest.
+    ///
+    /// The digest is computed over the following serialization, in which every string is written
+    /// as its length in bytes as a big-endian u64 followed by its UTF-8 bytes, so that, for
+    /// example, the values `"ab", "c"` never serialize identically to `"a", "bc"`:
+    ///
+    /// 1. The domain tag `os-gateway-event-digest:v1`.
+    /// 2. The [event type](crate::OsGatewayEventType::as_str), scope address, and target account
+    ///    address.
+    /// 3. The access grant id, expiration, and access level, each written as a byte of `0` when
+    ///    absent, or a byte of `1` followed by its attribute encoding when present.  Revokes always
+    ///    write all three, with the expiration and access level absent.
+    /// 4. The number of extra attributes as a big-endian u64, followed by each key and value,
+    ///    sorted by key.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessGrant, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(AccessGrant::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// assert_eq!(event.content_digest(), event.clone().content_digest());
+    /// ```
+    pub fn content_digest(&self) -> [u8; 32] {
+        let (expiration, access_level, extra_attributes) = match self {
+            Self::AccessGrant(grant) => (
+                grant.expiration.map(|expiration| expiration.to_string()),
+                grant.access_level.map(|access_level| access_level.as_str()),
+                &grant.extra_attributes,
+            ),
+            Self::AccessRevoke(revoke) => (None, None, &revoke.extra_attributes),
+        };
+        let mut writer = DigestWriter(Sha256::new());
+        writer.write_str(DIGEST_DOMAIN);
+        writer.write_str(self.typed_event_type().as_str());
+        writer.write_str(self.scope_address());
+        writer.write_str(self.target_account());
+        writer.write_optional_str(self.access_grant_id());
+        writer.write_optional_str(expiration.as_deref());
+        writer.write_optional_str(access_level);
+        writer.write_len(extra_attributes.len());
+        for (key, value) in extra_attributes {
+            writer.write_str(key);
+            writer.write_str(value);
+        }
+        writer.0.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_gateway_event, AccessGrant, AccessLevel, AccessRevoke, GrantExpiration,
+        OsGatewayAttributeGenerator, OsGatewayEvent,
+    };
+    use cosmwasm_std::Event;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn hex_digest<E: Into<OsGatewayEvent>>(event: E) -> String {
+        event
+            .into()
+            .content_digest()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_digests_are_pinned() {
+        let vectors: [(OsGatewayEvent, &str); 4] = [
+            (
+                AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+                "dc612b2e7e540110499358a857dfb8a9b0468e12783ca69db25b87a8fba2f2f7",
+            ),
+            (
+                AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_access_grant_id("my_unique_id")
+                    .with_expiration(GrantExpiration::AtHeight(100))
+                    .with_access_level(AccessLevel::ReadWrite)
+                    .with_extra_attribute("object_store_gateway_note", "shared")
+                    .into(),
+                "11a87292f0aeb91624bcb6a143e3f8e5582f5046849cf105bd59295d042dc547",
+            ),
+            (
+                AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+                "f4542d3ad73cf690cd6ee2f330c0ea25fdd5deb6f153394ab0a38992d1886c30",
+            ),
+            (
+                AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_access_grant_id("my_unique_id")
+                    .into(),
+                "9ba10c5ceb5f06cde3ca02f8f0d4c096733b8e1426694e9cf61dc819e24b7e3d",
+            ),
+        ];
+        for (event, digest) in vectors {
+            assert_eq!(
+                digest,
+                hex_digest(event.clone()),
+                "the digest of {event:?} should never change without a new domain tag",
+            );
+        }
+    }
+
+    #[test]
+    fn test_values_are_length_prefixed() {
+        assert_ne!(
+            hex_digest(AccessGrant::new("ab", "c")),
+            hex_digest(AccessGrant::new("a", "bc")),
+            "moving a character between adjacent values should change the digest",
+        );
+        assert_ne!(
+            hex_digest(AccessGrant::new("a", "b").with_extra_attribute("k", "")),
+            hex_digest(AccessGrant::new("a", "b").with_extra_attribute("", "k")),
+            "moving a character between a key and its value should change the digest",
+        );
+        assert_ne!(
+            hex_digest(AccessGrant::new("a", "b")),
+            hex_digest(AccessGrant::new("a", "b").with_access_grant_id("")),
+            "an empty access grant id should be distinguished from an absent one",
+        );
+    }
+
+    #[test]
+    fn test_digest_is_independent_of_construction() {
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_extra_attribute("object_store_gateway_b", "2")
+            .with_extra_attribute("object_store_gateway_a", "1");
+        let reordered = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_extra_attribute("object_store_gateway_a", "1")
+            .with_extra_attribute("object_store_gateway_b", "2")
+            .with_access_grant_id("my_unique_id");
+        let parsed = parse_gateway_event(
+            &Event::new("wasm").add_attributes(OsGatewayAttributeGenerator::from(grant.clone())),
+        )
+        .remove(0)
+        .unwrap();
+        assert_eq!(
+            hex_digest(grant.clone()),
+            hex_digest(reordered),
+            "the order in which fields are set should not affect the digest",
+        );
+        assert_eq!(
+            hex_digest(grant),
+            hex_digest(parsed),
+            "a parsed event should have the same digest as the event that was emitted",
+        );
+    }
+
+    #[test]
+    fn test_event_types_are_distinguished() {
+        assert_ne!(
+            hex_digest(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+            hex_digest(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+            "a grant and revoke of the same target should have different digests",
+        );
+    }
+}
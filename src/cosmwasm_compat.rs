@@ -0,0 +1,43 @@
+//! Constructors for the few cosmwasm-std types whose fields differ between 1.x and 2.x.  Every
+//! other type is used through the `cosmwasm_std` name, which the crate root binds to whichever
+//! version the `cosmwasm_1` or `cosmwasm_2` feature selects.
+
+use cosmwasm_std::{Event, Reply, SubMsgResponse, SubMsgResult};
+
+/// Creates the reply to a submessage, leaving every field added by 2.x empty.
+///
+/// # Parameters
+///
+/// * `id` The id of the submessage.
+///
+/// * `result` The result of the submessage.
+pub(crate) fn reply(id: u64, result: SubMsgResult) -> Reply {
+    #[cfg(feature = "cosmwasm_1")]
+    let reply = Reply { id, result };
+    #[cfg(not(feature = "cosmwasm_1"))]
+    let reply = Reply {
+        id,
+        payload: Default::default(),
+        gas_used: 0,
+        result,
+    };
+    reply
+}
+
+/// Creates the response of a successful submessage that emitted the given events, without data.
+///
+/// # Parameters
+///
+/// * `events` The events emitted by the submessage.
+pub(crate) fn sub_msg_response(events: Vec<Event>) -> SubMsgResponse {
+    #[cfg(feature = "cosmwasm_1")]
+    let response = SubMsgResponse { events, data: None };
+    #[cfg(not(feature = "cosmwasm_1"))]
+    #[allow(deprecated)]
+    let response = SubMsgResponse {
+        events,
+        data: None,
+        msg_responses: vec![],
+    };
+    response
+}
@@ -0,0 +1,141 @@
+use crate::{OsGatewayAttributeGenerator, OsGatewayError};
+use cosmwasm_std::Storage;
+
+impl OsGatewayAttributeGenerator {
+    /// Stamps the attributes with the name and version of the contract, as stored in the
+    /// [cw2](https://docs.rs/cw2) `contract_info` item by `cw2::set_contract_version`, so that the
+    /// stamp always matches the release that the contract reports.  Produces an
+    /// [OsGatewayError::MissingContractVersion](crate::OsGatewayError::MissingContractVersion) if
+    /// the contract has not set its version, or if the stored version cannot be read or is
+    /// incomplete.  See [with_source_contract_version](self::OsGatewayAttributeGenerator::with_source_contract_version).
+    ///
+    /// ```
+    /// use cosmwasm_std::testing::MockStorage;
+    /// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+    ///
+    /// let mut storage = MockStorage::new();
+    /// cw2::set_contract_version(&mut storage, "my-contract", "1.4.2").unwrap();
+    /// let generator = OsGatewayAttributeGenerator::access_grant(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// )
+    /// .with_contract_version_from(&storage)
+    /// .unwrap();
+    /// assert_eq!(Some("my-contract/1.4.2"), generator.source_contract_version());
+    /// ```
+    ///
+    /// # Parameters
+    ///
+    /// * `store` The storage of the contract producing the attributes.
+    pub fn with_contract_version_from(
+        mut self,
+        store: &dyn Storage,
+    ) -> Result<Self, OsGatewayError> {
+        self.set_contract_version_from(store)?;
+        Ok(self)
+    }
+
+    /// A non-consuming variant of [with_contract_version_from](self::OsGatewayAttributeGenerator::with_contract_version_from),
+    /// useful when building a generator across loops or helper functions.  Behaves identically to
+    /// the consuming variant, and leaves the attributes unchanged when it fails.
+    ///
+    /// # Parameters
+    ///
+    /// * `store` The storage of the contract producing the attributes.
+    pub fn set_contract_version_from(&mut self, store: &dyn Storage) -> Result<(), OsGatewayError> {
+        let missing = |reason: String| OsGatewayError::MissingContractVersion { reason };
+        let version = cw2::CONTRACT
+            .may_load(store)
+            .map_err(|error| {
+                missing(format!(
+                    "the stored cw2 contract version is invalid: {error}"
+                ))
+            })?
+            .ok_or_else(|| {
+                missing("the contract has not set its cw2 contract version".to_string())
+            })?;
+        if version.contract.is_empty() || version.version.is_empty() {
+            return Err(missing(format!(
+                "the stored cw2 contract version [{}/{}] is incomplete",
+                version.contract, version.version,
+            )));
+        }
+        self.set_source_contract_version(&version.contract, &version.version);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OsGatewayAttributeGenerator, OsGatewayError};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Storage;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn generator() -> OsGatewayAttributeGenerator {
+        OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+    }
+
+    #[test]
+    fn test_stored_contract_version_is_stamped() {
+        let mut storage = MockStorage::new();
+        cw2::set_contract_version(&mut storage, "crates.io:my-contract", "1.4.2").unwrap();
+        let stamped = generator().with_contract_version_from(&storage).unwrap();
+        assert_eq!(
+            Some("crates.io:my-contract/1.4.2"),
+            stamped.source_contract_version(),
+            "the stored contract name and version should be stamped",
+        );
+        assert_eq!(
+            Ok(()),
+            stamped.validate(),
+            "the stamped contract version should pass validation",
+        );
+        cw2::set_contract_version(&mut storage, "crates.io:my-contract", "1.5.0").unwrap();
+        let mut restamped = stamped;
+        restamped.set_contract_version_from(&storage).unwrap();
+        assert_eq!(
+            Some("crates.io:my-contract/1.5.0"),
+            restamped.source_contract_version(),
+            "a migrated contract version should replace the previous stamp",
+        );
+    }
+
+    #[test]
+    fn test_missing_contract_version_is_reported() {
+        let storage = MockStorage::new();
+        let mut unstamped = generator();
+        assert_eq!(
+            Err(OsGatewayError::MissingContractVersion {
+                reason: "the contract has not set its cw2 contract version".to_string(),
+            }),
+            unstamped.set_contract_version_from(&storage),
+            "a contract without a cw2 version should be reported",
+        );
+        assert_eq!(
+            generator(),
+            unstamped,
+            "the attributes should be unchanged when the version is missing",
+        );
+    }
+
+    #[test]
+    fn test_unreadable_contract_versions_are_reported() {
+        let mut corrupt = MockStorage::new();
+        corrupt.set(cw2::CONTRACT.as_slice(), b"not json");
+        let mut incomplete = MockStorage::new();
+        cw2::set_contract_version(&mut incomplete, "my-contract", "").unwrap();
+        for (storage, case) in [
+            (corrupt, "a corrupt version"),
+            (incomplete, "an incomplete version"),
+        ] {
+            let result = generator().with_contract_version_from(&storage);
+            assert!(
+                matches!(result, Err(OsGatewayError::MissingContractVersion { .. })),
+                "{case} should be reported, but produced: {result:?}",
+            );
+        }
+    }
+}
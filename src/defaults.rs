@@ -0,0 +1,181 @@
+use crate::OsGatewayAttributeGenerator;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+/// A reusable profile of attributes that a contract includes with every [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// event it emits, such as a tag identifying the team or contract that produced the event.  The
+/// profile is built once per contract, either at the call site or during instantiation, where it
+/// can be stored when the `serde` feature is enabled.
+///
+/// Defaults never replace a value that a generator already holds, so explicitly provided values
+/// always take precedence over the profile:
+///
+/// ```
+/// use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayDefaults};
+///
+/// let defaults = OsGatewayDefaults::new().with_attribute("team", "records");
+/// let generator = OsGatewayAttributeGenerator::access_grant_with_defaults(
+///     &defaults,
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// assert_eq!(Some("records"), generator.get("team"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OsGatewayDefaults {
+    attributes: BTreeMap<String, String>,
+}
+impl OsGatewayDefaults {
+    /// Creates an empty profile that does not alter any generator it is applied to.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an attribute to the profile.  Replaces the value previously provided for the same key.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The key of the attribute to include with every event.
+    /// * `value` The value of the attribute to include with every event, unless the generator
+    ///   already holds a value for the key.
+    pub fn with_attribute<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds every attribute in this profile to the provided generator, skipping any key for which
+    /// the generator already holds a value.
+    ///
+    /// # Parameters
+    ///
+    /// * `generator` The generator to which the default attributes will be added.
+    pub fn apply(&self, mut generator: OsGatewayAttributeGenerator) -> OsGatewayAttributeGenerator {
+        for (key, value) in &self.attributes {
+            if !generator.contains(key) {
+                generator.set_attribute(key, value);
+            }
+        }
+        generator
+    }
+
+    /// Exposes read-only access to all attributes held in this profile.
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+}
+
+/// Serializes the profile as a flat map of attribute keys to values.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayDefaults {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.attributes.serialize(serializer)
+    }
+}
+/// Deserializes the profile from a flat map of attribute keys to values.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayDefaults {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            attributes: BTreeMap::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OsGatewayAttributeGenerator, OsGatewayDefaults, OS_GATEWAY_KEYS};
+
+    #[test]
+    fn test_apply_adds_missing_attributes() {
+        let defaults = OsGatewayDefaults::new()
+            .with_attribute("team", "records")
+            .with_attribute("payload_version", "2");
+        let generator =
+            defaults.apply(OsGatewayAttributeGenerator::access_grant("scope", "target"));
+        assert_eq!(
+            Some("records"),
+            generator.get("team"),
+            "the team default should be added to the generator",
+        );
+        assert_eq!(
+            Some("2"),
+            generator.get("payload_version"),
+            "the payload version default should be added to the generator",
+        );
+        assert_eq!(
+            5,
+            generator.len(),
+            "the generator should hold its own attributes and every default",
+        );
+    }
+
+    #[test]
+    fn test_apply_never_overrides_explicit_values() {
+        let defaults = OsGatewayDefaults::new()
+            .with_attribute(OS_GATEWAY_KEYS.access_grant_id, "default_id")
+            .with_attribute(OS_GATEWAY_KEYS.scope_address, "default_scope");
+        let generator = defaults.apply(
+            OsGatewayAttributeGenerator::access_grant("scope", "target")
+                .with_access_grant_id("explicit_id"),
+        );
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_grant("scope", "target")
+                .with_access_grant_id("explicit_id"),
+            generator,
+            "defaults should never replace values that the generator already holds",
+        );
+        let generator = defaults.apply(OsGatewayAttributeGenerator::access_revoke(
+            "scope", "target",
+        ));
+        assert_eq!(
+            Some("default_id"),
+            generator.get(OS_GATEWAY_KEYS.access_grant_id),
+            "defaults should fill in optional values that were not provided",
+        );
+    }
+
+    #[test]
+    fn test_constructors_with_defaults_match_apply() {
+        let defaults = OsGatewayDefaults::new().with_attribute("team", "records");
+        assert_eq!(
+            defaults.apply(OsGatewayAttributeGenerator::access_grant("scope", "target")),
+            OsGatewayAttributeGenerator::access_grant_with_defaults(&defaults, "scope", "target"),
+            "the grant constructor should apply the defaults",
+        );
+        assert_eq!(
+            defaults.apply(OsGatewayAttributeGenerator::access_revoke(
+                "scope", "target"
+            )),
+            OsGatewayAttributeGenerator::access_revoke_with_defaults(&defaults, "scope", "target"),
+            "the revoke constructor should apply the defaults",
+        );
+    }
+
+    #[test]
+    fn test_empty_defaults_do_not_alter_generators() {
+        let generator =
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id");
+        assert_eq!(
+            generator.clone(),
+            OsGatewayDefaults::new().apply(generator),
+            "an empty profile should not alter the generator",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let defaults = OsGatewayDefaults::new().with_attribute("team", "records");
+        let json = cosmwasm_std::to_json_binary(&defaults).expect("the defaults should serialize");
+        assert_eq!(
+            defaults,
+            cosmwasm_std::from_json::<OsGatewayDefaults>(&json)
+                .expect("the defaults should deserialize"),
+            "the defaults should survive a serde round trip",
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{OsGatewayError, OsGatewayEvent};
+use cosmwasm_std::{Binary, HexBinary};
+
+/// The largest JSON encoding, in bytes, of an event that can be [transported](crate::OsGatewayEvent::to_url_safe_base64)
+/// as URL-safe base64 or hex.  Larger values are rejected before they are decoded, so that an
+/// oversized value cannot force a large allocation.
+pub const OS_GATEWAY_MAX_ENCODED_EVENT_BYTES: usize = 8 * 1024;
+
+/// The name of the URL-safe base64 encoding, as reported in errors.
+const URL_SAFE_BASE64: &str = "url-safe base64";
+
+/// The name of the hex encoding, as reported in errors.
+const HEX: &str = "hex";
+
+impl OsGatewayEvent {
+    /// Encodes the event's JSON as unpadded URL-safe base64, using the `-` and `_` alphabet, so
+    /// that a single event can be carried in a URL query parameter or an HTTP header.  The JSON is
+    /// the flat map of attribute keys to values produced by [OsGatewayJson](crate::OsGatewayJson),
+    /// so the same event always produces the same value.  Produces an [OsGatewayError::InvalidEncodedEvent](crate::OsGatewayError::InvalidEncodedEvent)
+    /// if the JSON exceeds [OS_GATEWAY_MAX_ENCODED_EVENT_BYTES](crate::OS_GATEWAY_MAX_ENCODED_EVENT_BYTES).
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessRevoke, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(AccessRevoke::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// let encoded = event.to_url_safe_base64().expect("the event should encode");
+    /// assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    /// assert_eq!(Ok(event), OsGatewayEvent::from_url_safe_base64(&encoded));
+    /// ```
+    pub fn to_url_safe_base64(&self) -> Result<String, OsGatewayError> {
+        Ok(Binary::from(encode_json(self, URL_SAFE_BASE64)?)
+            .to_base64()
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_"))
+    }
+
+    /// Decodes an event from the value produced by [to_url_safe_base64](self::OsGatewayEvent::to_url_safe_base64).
+    /// Decoding is strict: padding, the standard `+` and `/` alphabet, whitespace, non-canonical
+    /// trailing bits, and anything following the event's JSON are all rejected.  Produces an
+    /// [OsGatewayError::InvalidEncodedEvent](crate::OsGatewayError::InvalidEncodedEvent) if the
+    /// value is malformed, would decode to more than [OS_GATEWAY_MAX_ENCODED_EVENT_BYTES](crate::OS_GATEWAY_MAX_ENCODED_EVENT_BYTES),
+    /// or does not hold a complete event of a recognized type.
+    ///
+    /// # Parameters
+    ///
+    /// * `encoded` The unpadded URL-safe base64 to decode.
+    pub fn from_url_safe_base64(encoded: &str) -> Result<Self, OsGatewayError> {
+        let invalid = |reason: String| invalid(URL_SAFE_BASE64, reason);
+        // Unpadded base64 holds 4 characters for every 3 bytes, rounded up
+        if encoded.len() > (OS_GATEWAY_MAX_ENCODED_EVENT_BYTES * 4).div_ceil(3) {
+            return Err(too_large(URL_SAFE_BASE64));
+        }
+        if let Some(character) = encoded
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+        {
+            return Err(invalid(format!("invalid character [{character}]")));
+        }
+        let mut standard = encoded.replace('-', "+").replace('_', "/");
+        standard.push_str(&"=".repeat((4 - standard.len() % 4) % 4));
+        let json = Binary::from_base64(&standard).map_err(|error| invalid(error.to_string()))?;
+        decode_json(&json, URL_SAFE_BASE64)
+    }
+
+    /// Encodes the event's JSON as lowercase hex, for transports that cannot carry base64.  The
+    /// JSON is identical to that encoded by [to_url_safe_base64](self::OsGatewayEvent::to_url_safe_base64),
+    /// and is subject to the same size limit.
+    pub fn to_hex(&self) -> Result<String, OsGatewayError> {
+        Ok(HexBinary::from(encode_json(self, HEX)?).to_hex())
+    }
+
+    /// Decodes an event from the value produced by [to_hex](self::OsGatewayEvent::to_hex), which may
+    /// use either case.  Produces an [OsGatewayError::InvalidEncodedEvent](crate::OsGatewayError::InvalidEncodedEvent)
+    /// if the value is not hex, would decode to more than [OS_GATEWAY_MAX_ENCODED_EVENT_BYTES](crate::OS_GATEWAY_MAX_ENCODED_EVENT_BYTES),
+    /// or does not hold a complete event of a recognized type followed by nothing else.
+    ///
+    /// # Parameters
+    ///
+    /// * `encoded` The hex to decode.
+    pub fn from_hex(encoded: &str) -> Result<Self, OsGatewayError> {
+        if encoded.len() > OS_GATEWAY_MAX_ENCODED_EVENT_BYTES * 2 {
+            return Err(too_large(HEX));
+        }
+        let json = HexBinary::from_hex(encoded).map_err(|error| invalid(HEX, error.to_string()))?;
+        decode_json(&json, HEX)
+    }
+}
+
+fn encode_json(event: &OsGatewayEvent, encoding: &str) -> Result<Vec<u8>, OsGatewayError> {
+    let json =
+        cosmwasm_std::to_json_vec(event).map_err(|error| invalid(encoding, error.to_string()))?;
+    if json.len() > OS_GATEWAY_MAX_ENCODED_EVENT_BYTES {
+        return Err(too_large(encoding));
+    }
+    Ok(json)
+}
+
+fn decode_json(json: &[u8], encoding: &str) -> Result<OsGatewayEvent, OsGatewayError> {
+    cosmwasm_std::from_json(json).map_err(|error| invalid(encoding, error.to_string()))
+}
+
+fn too_large(encoding: &str) -> OsGatewayError {
+    invalid(
+        encoding,
+        format!("the event exceeds {OS_GATEWAY_MAX_ENCODED_EVENT_BYTES} bytes"),
+    )
+}
+
+fn invalid(encoding: &str, reason: String) -> OsGatewayError {
+    OsGatewayError::InvalidEncodedEvent {
+        encoding: encoding.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError, OsGatewayEvent,
+        OS_GATEWAY_MAX_ENCODED_EVENT_BYTES,
+    };
+    use cosmwasm_std::{Binary, HexBinary};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn events() -> Vec<OsGatewayEvent> {
+        vec![
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::ReadWrite)
+                .with_extra_attribute("object_store_gateway_note", "shared?/+")
+                .into(),
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+        ]
+    }
+
+    fn revoke_json() -> Vec<u8> {
+        cosmwasm_std::to_json_vec(&events()[1]).unwrap()
+    }
+
+    fn assert_rejected(result: Result<OsGatewayEvent, OsGatewayError>, encoding: &str, case: &str) {
+        assert!(
+            matches!(
+                &result,
+                Err(OsGatewayError::InvalidEncodedEvent { encoding: actual, .. }) if actual == encoding,
+            ),
+            "{case} should be rejected, but produced: {result:?}",
+        );
+    }
+
+    #[test]
+    fn test_events_round_trip() {
+        for event in events() {
+            let base64 = event.to_url_safe_base64().unwrap();
+            assert!(
+                base64
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+                "the encoding [{base64}] should only use url-safe characters",
+            );
+            assert_eq!(
+                Ok(event.clone()),
+                OsGatewayEvent::from_url_safe_base64(&base64),
+                "the event should survive a url-safe base64 round trip",
+            );
+            assert_eq!(
+                Ok(event.clone()),
+                OsGatewayEvent::from_hex(&event.to_hex().unwrap()),
+                "the event should survive a hex round trip",
+            );
+            assert_eq!(
+                Ok(event.clone()),
+                OsGatewayEvent::from_hex(&event.to_hex().unwrap().to_uppercase()),
+                "uppercase hex should be accepted",
+            );
+        }
+    }
+
+    #[test]
+    fn test_encodings_are_of_the_json() {
+        let event = events().remove(1);
+        assert_eq!(
+            HexBinary::from(revoke_json()).to_hex(),
+            event.to_hex().unwrap(),
+            "the hex should encode the event's json",
+        );
+        assert_eq!(
+            Binary::from(revoke_json())
+                .to_base64()
+                .trim_end_matches('=')
+                .replace('+', "-")
+                .replace('/', "_"),
+            event.to_url_safe_base64().unwrap(),
+            "the base64 should encode the event's json",
+        );
+    }
+
+    #[test]
+    fn test_malformed_base64_is_rejected() {
+        let encoded = events()[1].to_url_safe_base64().unwrap();
+        let standard = Binary::from(revoke_json()).to_base64();
+        for (value, case) in [
+            (format!("{encoded}="), "padding"),
+            (standard.clone(), "the standard alphabet"),
+            (format!("{encoded} "), "whitespace"),
+            (format!("{encoded}A"), "an incomplete trailing group"),
+            ("QR".to_string(), "non-canonical trailing bits"),
+            (
+                Binary::from([revoke_json(), b"garbage".to_vec()].concat())
+                    .to_base64()
+                    .trim_end_matches('=')
+                    .replace('+', "-")
+                    .replace('/', "_"),
+                "json followed by garbage",
+            ),
+            (
+                Binary::from(b"{\"object_store_gateway_event_type\":\"access_revoke\"}".to_vec())
+                    .to_base64()
+                    .trim_end_matches('=')
+                    .to_string(),
+                "an incomplete event",
+            ),
+        ] {
+            assert_rejected(
+                OsGatewayEvent::from_url_safe_base64(&value),
+                "url-safe base64",
+                case,
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_hex_is_rejected() {
+        let encoded = events()[1].to_hex().unwrap();
+        for (value, case) in [
+            (format!("{encoded}0"), "an odd number of digits"),
+            (format!("{encoded}zz"), "a non-hex digit"),
+            (format!("0x{encoded}"), "a prefix"),
+            (
+                HexBinary::from([revoke_json(), b" x".to_vec()].concat()).to_hex(),
+                "json followed by garbage",
+            ),
+        ] {
+            assert_rejected(OsGatewayEvent::from_hex(&value), "hex", case);
+        }
+    }
+
+    #[test]
+    fn test_oversized_events_are_rejected() {
+        let event = OsGatewayEvent::from(
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_extra_attribute(
+                "object_store_gateway_note",
+                "x".repeat(OS_GATEWAY_MAX_ENCODED_EVENT_BYTES),
+            ),
+        );
+        assert_eq!(
+            Err(OsGatewayError::InvalidEncodedEvent {
+                encoding: "hex".to_string(),
+                reason: format!("the event exceeds {OS_GATEWAY_MAX_ENCODED_EVENT_BYTES} bytes"),
+            }),
+            event.to_hex(),
+            "an oversized event should not be encoded",
+        );
+        assert_rejected(
+            OsGatewayEvent::from_url_safe_base64(
+                &"A".repeat(OS_GATEWAY_MAX_ENCODED_EVENT_BYTES * 2),
+            ),
+            "url-safe base64",
+            "an oversized value",
+        );
+        assert_rejected(
+            OsGatewayEvent::from_hex(&"0".repeat(OS_GATEWAY_MAX_ENCODED_EVENT_BYTES * 2 + 2)),
+            "hex",
+            "an oversized value",
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use crate::FieldError;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// All errors that can be produced when assembling attributes for [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+///
+/// # Variants
+///
+/// * `MissingRequiredAttributes` Denotes that an attempt was made to produce an event without all
+///   of the attributes that the gateway requires.  The `keys` field contains the attribute key of
+///   every missing value.
+///
+/// * `ConflictingAttributes` Denotes that two sets of attributes could not be merged because at
+///   least one key was present in both with differing values.  The `conflicts` field contains
+///   every conflicting key alongside both of its values.
+///
+/// * `UnknownAttributeKeys` Denotes that a set of attributes contained keys that are not recognized
+///   by the gateway.  The `keys` field contains every unrecognized key.
+///
+/// * `DuplicateTargetAccounts` Denotes that a batch of access grants targeted the same account more
+///   than once.  The `target_accounts` field contains every duplicated account address.
+///
+/// * `DuplicateAccessGrantIds` Denotes that a batch of access grants used the same access grant id
+///   more than once.  The `access_grant_ids` field contains every duplicated id.
+///
+/// * `UnexpectedEventType` Denotes that a set of attributes could not be converted into a typed
+///   event because its event type denotes a different kind of event.  The `expected` field contains
+///   the event type required by the conversion, and the `actual` field contains the event type that
+///   was found.
+///
+/// * `UnknownEventType` Denotes that a set of attributes could not be converted into a typed event
+///   because its event type is not recognized.  The `event_type` field contains the unrecognized
+///   event type.
+///
+/// * `InvalidAddress` Denotes that a value could not be used as a bech32 address.  The `address`
+///   field contains the rejected value, and the `reason` field describes why it was rejected.
+///
+/// * `InvalidAccessGrantId` Denotes that a value could not be used as an access grant id.  The
+///   `access_grant_id` field contains the rejected value, and the `reason` field describes why it
+///   was rejected.
+///
+/// * `InvalidScopeUuid` Denotes that a value could not be used as the UUID of a scope.  The `uuid`
+///   field contains the rejected value, and the `reason` field describes why it was rejected.
+///
+/// * `InvalidExpiration` Denotes that a value could not be used as a grant expiration.  The
+///   `expiration` field contains the rejected value, and the `reason` field describes why it was
+///   rejected.
+///
+/// * `MissingRequiredFields` Denotes that a typed event could not be built because required fields
+///   were never provided.  The `fields` field contains the name of every missing field.
+///
+/// * `InvalidPayloadVersion` Denotes that a value could not be used as a payload version.  The
+///   `version` field contains the rejected value, and the `reason` field describes why it was
+///   rejected.
+///
+/// * `InvalidFields` Denotes that one or more attributes failed validation.  The `errors` field
+///   contains a [FieldError](crate::FieldError) for every problem found, so that all of them can be
+///   reported at once.
+///
+/// * `UnknownAccessLevel` Denotes that a value is not a recognized access level.  The
+///   `access_level` field contains the unrecognized value.
+///
+/// * `InvalidCompactPayload` Denotes that a [compact payload](crate::OS_GATEWAY_PAYLOAD_KEY) could
+///   not be decoded.  The `payload` field contains the rejected value, and the `reason` field
+///   describes why it was rejected.
+///
+/// * `InvalidCsv` Denotes that a batch of events could not be read from CSV.  The `line` field
+///   contains the line of the CSV at which reading failed, and the `reason` field describes why.
+///
+/// * `InvalidCompactString` Denotes that a line could not be parsed as the [compact string](crate::OsGatewayEvent::to_compact_string)
+///   of an event.  The `value` field contains the rejected line or component, and the `reason`
+///   field describes why it was rejected.
+///
+/// * `InvalidEncodedEvent` Denotes that an event could not be [encoded](crate::OsGatewayEvent::to_url_safe_base64)
+///   for transport, or decoded from its encoding.  The `encoding` field names the encoding, and the
+///   `reason` field describes why it failed.
+///
+/// * `MissingContractVersion` Denotes that the name and version of a contract could not be read
+///   from the [cw2](https://docs.rs/cw2) item in its storage.  The `reason` field describes why, such
+///   as the contract never having set its version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OsGatewayError {
+    MissingRequiredAttributes {
+        keys: Vec<String>,
+    },
+    ConflictingAttributes {
+        conflicts: Vec<OsGatewayAttributeConflict>,
+    },
+    UnknownAttributeKeys {
+        keys: Vec<String>,
+    },
+    DuplicateTargetAccounts {
+        target_accounts: Vec<String>,
+    },
+    DuplicateAccessGrantIds {
+        access_grant_ids: Vec<String>,
+    },
+    UnexpectedEventType {
+        expected: String,
+        actual: String,
+    },
+    UnknownEventType {
+        event_type: String,
+    },
+    InvalidAddress {
+        address: String,
+        reason: String,
+    },
+    InvalidAccessGrantId {
+        access_grant_id: String,
+        reason: String,
+    },
+    InvalidScopeUuid {
+        uuid: String,
+        reason: String,
+    },
+    InvalidExpiration {
+        expiration: String,
+        reason: String,
+    },
+    MissingRequiredFields {
+        fields: Vec<String>,
+    },
+    InvalidPayloadVersion {
+        version: String,
+        reason: String,
+    },
+    InvalidFields {
+        errors: Vec<FieldError>,
+    },
+    UnknownAccessLevel {
+        access_level: String,
+    },
+    InvalidCompactPayload {
+        payload: String,
+        reason: String,
+    },
+    InvalidCsv {
+        line: u64,
+        reason: String,
+    },
+    InvalidCompactString {
+        value: String,
+        reason: String,
+    },
+    InvalidEncodedEvent {
+        encoding: String,
+        reason: String,
+    },
+    MissingContractVersion {
+        reason: String,
+    },
+}
+impl OsGatewayError {
+    /// The stable snake_case code of the error's variant, such as `missing_required_attributes`,
+    /// suitable for handling errors by machine and grouping them in structured logs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingRequiredAttributes { .. } => "missing_required_attributes",
+            Self::ConflictingAttributes { .. } => "conflicting_attributes",
+            Self::UnknownAttributeKeys { .. } => "unknown_attribute_keys",
+            Self::DuplicateTargetAccounts { .. } => "duplicate_target_accounts",
+            Self::DuplicateAccessGrantIds { .. } => "duplicate_access_grant_ids",
+            Self::UnexpectedEventType { .. } => "unexpected_event_type",
+            Self::UnknownEventType { .. } => "unknown_event_type",
+            Self::InvalidAddress { .. } => "invalid_address",
+            Self::InvalidAccessGrantId { .. } => "invalid_access_grant_id",
+            Self::InvalidScopeUuid { .. } => "invalid_scope_uuid",
+            Self::InvalidExpiration { .. } => "invalid_expiration",
+            Self::MissingRequiredFields { .. } => "missing_required_fields",
+            Self::InvalidPayloadVersion { .. } => "invalid_payload_version",
+            Self::InvalidFields { .. } => "invalid_fields",
+            Self::UnknownAccessLevel { .. } => "unknown_access_level",
+            Self::InvalidCompactPayload { .. } => "invalid_compact_payload",
+            Self::InvalidCsv { .. } => "invalid_csv",
+            Self::InvalidCompactString { .. } => "invalid_compact_string",
+            Self::InvalidEncodedEvent { .. } => "invalid_encoded_event",
+            Self::MissingContractVersion { .. } => "missing_contract_version",
+        }
+    }
+}
+impl Display for OsGatewayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingRequiredAttributes { keys } => write!(
+                f,
+                "missing required object store gateway attributes: [{}]",
+                keys.join(", "),
+            ),
+            Self::ConflictingAttributes { conflicts } => write!(
+                f,
+                "conflicting object store gateway attributes: [{}]",
+                conflicts
+                    .iter()
+                    .map(|conflict| conflict.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            Self::UnknownAttributeKeys { keys } => write!(
+                f,
+                "unknown object store gateway attribute keys: [{}]",
+                keys.join(", "),
+            ),
+            Self::DuplicateTargetAccounts { target_accounts } => write!(
+                f,
+                "duplicate object store gateway target accounts: [{}]",
+                target_accounts.join(", "),
+            ),
+            Self::DuplicateAccessGrantIds { access_grant_ids } => write!(
+                f,
+                "duplicate object store gateway access grant ids: [{}]",
+                access_grant_ids.join(", "),
+            ),
+            Self::UnexpectedEventType { expected, actual } => write!(
+                f,
+                "unexpected object store gateway event type: expected [{expected}] but found [{actual}]",
+            ),
+            Self::UnknownEventType { event_type } => write!(
+                f,
+                "unknown object store gateway event type: [{event_type}]",
+            ),
+            Self::InvalidAddress { address, reason } => {
+                write!(f, "invalid bech32 address [{address}]: {reason}")
+            }
+            Self::InvalidAccessGrantId {
+                access_grant_id,
+                reason,
+            } => write!(f, "invalid access grant id [{access_grant_id}]: {reason}"),
+            Self::InvalidScopeUuid { uuid, reason } => {
+                write!(f, "invalid scope uuid [{uuid}]: {reason}")
+            }
+            Self::InvalidExpiration { expiration, reason } => {
+                write!(f, "invalid grant expiration [{expiration}]: {reason}")
+            }
+            Self::MissingRequiredFields { fields } => write!(
+                f,
+                "missing required object store gateway event fields: [{}]",
+                fields.join(", "),
+            ),
+            Self::InvalidPayloadVersion { version, reason } => {
+                write!(f, "invalid payload version [{version}]: {reason}")
+            }
+            Self::InvalidFields { errors } => write!(
+                f,
+                "invalid object store gateway attributes: {}",
+                errors
+                    .iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            ),
+            Self::UnknownAccessLevel { access_level } => {
+                write!(f, "unknown access level [{access_level}]")
+            }
+            Self::InvalidCompactPayload { payload, reason } => {
+                write!(f, "invalid compact payload [{payload}]: {reason}")
+            }
+            Self::InvalidCsv { line, reason } => {
+                write!(f, "invalid csv at line {line}: {reason}")
+            }
+            Self::InvalidCompactString { value, reason } => {
+                write!(f, "invalid compact string [{value}]: {reason}")
+            }
+            Self::InvalidEncodedEvent { encoding, reason } => {
+                write!(f, "invalid {encoding} encoded event: {reason}")
+            }
+            Self::MissingContractVersion { reason } => {
+                write!(f, "missing contract version: {reason}")
+            }
+        }
+    }
+}
+impl core::error::Error for OsGatewayError {}
+
+/// Describes a single attribute key that held a different value in each of two merged sets of
+/// attributes.
+///
+/// # Parameters
+///
+/// * `key` The attribute key present in both sets of attributes.
+///
+/// * `existing_value` The value held for the key by the set of attributes being merged into.
+///
+/// * `incoming_value` The value held for the key by the set of attributes being merged in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OsGatewayAttributeConflict {
+    pub key: String,
+    pub existing_value: String,
+    pub incoming_value: String,
+}
+impl Display for OsGatewayAttributeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: [{}] vs [{}]",
+            self.key, self.existing_value, self.incoming_value,
+        )
+    }
+}
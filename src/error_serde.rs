@@ -0,0 +1,521 @@
+use crate::{FieldError, OsGatewayAttributeConflict, OsGatewayError};
+use serde::de::{Error, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+
+/// The field that holds the [code](crate::OsGatewayError::code) of a serialized error, which
+/// denotes its variant.
+const CODE_FIELD: &str = "code";
+
+/// The field that holds the human-readable description of a serialized error.  It is derived from
+/// the other fields, so it is ignored when deserializing.
+const MESSAGE_FIELD: &str = "message";
+
+/// Serializes the error as a flat structure tagged by its [code](crate::OsGatewayError::code), with
+/// each of the variant's fields under its own name and a human-readable `message` last:
+///
+/// ```
+/// use os_gateway_contract_attributes::{OsGatewayError, OsGatewayJson};
+///
+/// let error = OsGatewayError::UnknownEventType { event_type: "bogus".to_string() };
+/// assert_eq!(
+///     r#"{"code":"unknown_event_type","event_type":"bogus","message":"unknown object store gateway event type: [bogus]"}"#,
+///     String::from_utf8(error.to_json_binary().unwrap().to_vec()).unwrap(),
+/// );
+/// ```
+///
+/// Codes and field names are stable, so a client can branch on the code without parsing messages.
+impl Serialize for OsGatewayError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry(CODE_FIELD, self.code())?;
+        match self {
+            Self::MissingRequiredAttributes { keys } | Self::UnknownAttributeKeys { keys } => {
+                map.serialize_entry("keys", keys)?;
+            }
+            Self::ConflictingAttributes { conflicts } => {
+                map.serialize_entry("conflicts", conflicts)?;
+            }
+            Self::DuplicateTargetAccounts { target_accounts } => {
+                map.serialize_entry("target_accounts", target_accounts)?;
+            }
+            Self::DuplicateAccessGrantIds { access_grant_ids } => {
+                map.serialize_entry("access_grant_ids", access_grant_ids)?;
+            }
+            Self::UnexpectedEventType { expected, actual } => {
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            }
+            Self::UnknownEventType { event_type } => {
+                map.serialize_entry("event_type", event_type)?;
+            }
+            Self::InvalidAddress { address, reason } => {
+                map.serialize_entry("address", address)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidAccessGrantId {
+                access_grant_id,
+                reason,
+            } => {
+                map.serialize_entry("access_grant_id", access_grant_id)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidScopeUuid { uuid, reason } => {
+                map.serialize_entry("uuid", uuid)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidExpiration { expiration, reason } => {
+                map.serialize_entry("expiration", expiration)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::MissingRequiredFields { fields } => {
+                map.serialize_entry("fields", fields)?;
+            }
+            Self::InvalidPayloadVersion { version, reason } => {
+                map.serialize_entry("version", version)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidFields { errors } => {
+                map.serialize_entry("errors", errors)?;
+            }
+            Self::UnknownAccessLevel { access_level } => {
+                map.serialize_entry("access_level", access_level)?;
+            }
+            Self::InvalidCompactPayload { payload, reason } => {
+                map.serialize_entry("payload", payload)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidCsv { line, reason } => {
+                map.serialize_entry("line", line)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidCompactString { value, reason } => {
+                map.serialize_entry("value", value)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::InvalidEncodedEvent { encoding, reason } => {
+                map.serialize_entry("encoding", encoding)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            Self::MissingContractVersion { reason } => {
+                map.serialize_entry("reason", reason)?;
+            }
+        }
+        map.serialize_entry(MESSAGE_FIELD, &self.to_string())?;
+        map.end()
+    }
+}
+/// Deserializes the error from the structure produced by its serialization, selecting the variant
+/// by its code.  The `message` field and any unrecognized fields are ignored, so errors serialized
+/// by a newer release that adds fields can still be read, but an unrecognized code is rejected.
+impl<'de> Deserialize<'de> for OsGatewayError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ErrorVisitor)
+    }
+}
+
+/// Every field that any variant of a serialized error may hold.
+#[derive(Default)]
+struct ErrorFields {
+    code: Option<String>,
+    keys: Option<Vec<String>>,
+    conflicts: Option<Vec<OsGatewayAttributeConflict>>,
+    target_accounts: Option<Vec<String>>,
+    access_grant_ids: Option<Vec<String>>,
+    expected: Option<String>,
+    actual: Option<String>,
+    event_type: Option<String>,
+    address: Option<String>,
+    access_grant_id: Option<String>,
+    uuid: Option<String>,
+    expiration: Option<String>,
+    fields: Option<Vec<String>>,
+    version: Option<String>,
+    errors: Option<Vec<FieldError>>,
+    access_level: Option<String>,
+    payload: Option<String>,
+    line: Option<u64>,
+    value: Option<String>,
+    encoding: Option<String>,
+    reason: Option<String>,
+}
+
+/// Takes a field from the collected fields, failing if the variant requires it but it was absent.
+fn required<T, E: Error>(value: Option<T>, field: &'static str) -> Result<T, E> {
+    value.ok_or_else(|| E::missing_field(field))
+}
+
+struct ErrorVisitor;
+impl<'de> Visitor<'de> for ErrorVisitor {
+    type Value = OsGatewayError;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an object store gateway error")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = ErrorFields::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                CODE_FIELD => fields.code = Some(map.next_value()?),
+                "keys" => fields.keys = Some(map.next_value()?),
+                "conflicts" => fields.conflicts = Some(map.next_value()?),
+                "target_accounts" => fields.target_accounts = Some(map.next_value()?),
+                "access_grant_ids" => fields.access_grant_ids = Some(map.next_value()?),
+                "expected" => fields.expected = Some(map.next_value()?),
+                "actual" => fields.actual = Some(map.next_value()?),
+                "event_type" => fields.event_type = Some(map.next_value()?),
+                "address" => fields.address = Some(map.next_value()?),
+                "access_grant_id" => fields.access_grant_id = Some(map.next_value()?),
+                "uuid" => fields.uuid = Some(map.next_value()?),
+                "expiration" => fields.expiration = Some(map.next_value()?),
+                "fields" => fields.fields = Some(map.next_value()?),
+                "version" => fields.version = Some(map.next_value()?),
+                "errors" => fields.errors = Some(map.next_value()?),
+                "access_level" => fields.access_level = Some(map.next_value()?),
+                "payload" => fields.payload = Some(map.next_value()?),
+                "line" => fields.line = Some(map.next_value()?),
+                "value" => fields.value = Some(map.next_value()?),
+                "encoding" => fields.encoding = Some(map.next_value()?),
+                "reason" => fields.reason = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let code = required(fields.code, CODE_FIELD)?;
+        let reason = || required(fields.reason.clone(), "reason");
+        Ok(match code.as_str() {
+            "missing_required_attributes" => OsGatewayError::MissingRequiredAttributes {
+                keys: required(fields.keys, "keys")?,
+            },
+            "conflicting_attributes" => OsGatewayError::ConflictingAttributes {
+                conflicts: required(fields.conflicts, "conflicts")?,
+            },
+            "unknown_attribute_keys" => OsGatewayError::UnknownAttributeKeys {
+                keys: required(fields.keys, "keys")?,
+            },
+            "duplicate_target_accounts" => OsGatewayError::DuplicateTargetAccounts {
+                target_accounts: required(fields.target_accounts, "target_accounts")?,
+            },
+            "duplicate_access_grant_ids" => OsGatewayError::DuplicateAccessGrantIds {
+                access_grant_ids: required(fields.access_grant_ids, "access_grant_ids")?,
+            },
+            "unexpected_event_type" => OsGatewayError::UnexpectedEventType {
+                expected: required(fields.expected, "expected")?,
+                actual: required(fields.actual, "actual")?,
+            },
+            "unknown_event_type" => OsGatewayError::UnknownEventType {
+                event_type: required(fields.event_type, "event_type")?,
+            },
+            "invalid_address" => OsGatewayError::InvalidAddress {
+                address: required(fields.address, "address")?,
+                reason: reason()?,
+            },
+            "invalid_access_grant_id" => OsGatewayError::InvalidAccessGrantId {
+                access_grant_id: required(fields.access_grant_id, "access_grant_id")?,
+                reason: reason()?,
+            },
+            "invalid_scope_uuid" => OsGatewayError::InvalidScopeUuid {
+                uuid: required(fields.uuid, "uuid")?,
+                reason: reason()?,
+            },
+            "invalid_expiration" => OsGatewayError::InvalidExpiration {
+                expiration: required(fields.expiration, "expiration")?,
+                reason: reason()?,
+            },
+            "missing_required_fields" => OsGatewayError::MissingRequiredFields {
+                fields: required(fields.fields, "fields")?,
+            },
+            "invalid_payload_version" => OsGatewayError::InvalidPayloadVersion {
+                version: required(fields.version, "version")?,
+                reason: reason()?,
+            },
+            "invalid_fields" => OsGatewayError::InvalidFields {
+                errors: required(fields.errors, "errors")?,
+            },
+            "unknown_access_level" => OsGatewayError::UnknownAccessLevel {
+                access_level: required(fields.access_level, "access_level")?,
+            },
+            "invalid_compact_payload" => OsGatewayError::InvalidCompactPayload {
+                payload: required(fields.payload, "payload")?,
+                reason: reason()?,
+            },
+            "invalid_csv" => OsGatewayError::InvalidCsv {
+                line: required(fields.line, "line")?,
+                reason: reason()?,
+            },
+            "invalid_compact_string" => OsGatewayError::InvalidCompactString {
+                value: required(fields.value, "value")?,
+                reason: reason()?,
+            },
+            "invalid_encoded_event" => OsGatewayError::InvalidEncodedEvent {
+                encoding: required(fields.encoding, "encoding")?,
+                reason: reason()?,
+            },
+            "missing_contract_version" => {
+                OsGatewayError::MissingContractVersion { reason: reason()? }
+            }
+            _ => {
+                return Err(A::Error::custom(format!(
+                    "unknown object store gateway error code [{code}]"
+                )))
+            }
+        })
+    }
+}
+
+/// Serializes the conflict as a structure of its key and both of its values.
+impl Serialize for OsGatewayAttributeConflict {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OsGatewayAttributeConflict", 3)?;
+        state.serialize_field("key", &self.key)?;
+        state.serialize_field("existing_value", &self.existing_value)?;
+        state.serialize_field("incoming_value", &self.incoming_value)?;
+        state.end()
+    }
+}
+/// Deserializes the conflict from the structure produced by its serialization.
+impl<'de> Deserialize<'de> for OsGatewayAttributeConflict {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields =
+            <std::collections::BTreeMap<String, String> as Deserialize>::deserialize(deserializer)?;
+        let mut take = |field: &'static str| {
+            fields
+                .remove(field)
+                .ok_or_else(|| D::Error::missing_field(field))
+        };
+        Ok(Self {
+            key: take("key")?,
+            existing_value: take("existing_value")?,
+            incoming_value: take("incoming_value")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        FieldError, FieldErrorKind, OsGatewayAttributeConflict, OsGatewayError, OsGatewayJson,
+        OsGatewayKey,
+    };
+    use cosmwasm_std::Binary;
+
+    /// Verifies that the error serializes to exactly the expected JSON, and back into itself.
+    fn assert_snapshot(error: OsGatewayError, expected: &str) {
+        let binary = error.to_json_binary().expect("the error should serialize");
+        assert_eq!(
+            expected,
+            String::from_utf8(binary.to_vec()).unwrap(),
+            "the error [{error:?}] should serialize to its pinned JSON",
+        );
+        assert_eq!(
+            error,
+            OsGatewayError::from_json(&binary).expect("the error should deserialize"),
+            "the error should survive a JSON round trip",
+        );
+    }
+
+    fn reason_snapshot(code: &str, field: &str, message: &str) -> String {
+        format!(r#"{{"code":"{code}","{field}":"value","reason":"why","message":"{message}"}}"#)
+    }
+
+    #[test]
+    fn test_list_errors_are_pinned() {
+        assert_snapshot(
+            OsGatewayError::MissingRequiredAttributes {
+                keys: vec!["object_store_gateway_event_type".to_string()],
+            },
+            concat!(
+                r#"{"code":"missing_required_attributes","keys":["object_store_gateway_event_type"],"#,
+                r#""message":"missing required object store gateway attributes: [object_store_gateway_event_type]"}"#,
+            ),
+        );
+        assert_snapshot(
+            OsGatewayError::UnknownAttributeKeys {
+                keys: vec!["a".to_string(), "b".to_string()],
+            },
+            r#"{"code":"unknown_attribute_keys","keys":["a","b"],"message":"unknown object store gateway attribute keys: [a, b]"}"#,
+        );
+        assert_snapshot(
+            OsGatewayError::DuplicateTargetAccounts {
+                target_accounts: vec!["tp1".to_string()],
+            },
+            r#"{"code":"duplicate_target_accounts","target_accounts":["tp1"],"message":"duplicate object store gateway target accounts: [tp1]"}"#,
+        );
+        assert_snapshot(
+            OsGatewayError::DuplicateAccessGrantIds {
+                access_grant_ids: vec!["id".to_string()],
+            },
+            r#"{"code":"duplicate_access_grant_ids","access_grant_ids":["id"],"message":"duplicate object store gateway access grant ids: [id]"}"#,
+        );
+        assert_snapshot(
+            OsGatewayError::MissingRequiredFields {
+                fields: vec!["scope_address".to_string()],
+            },
+            r#"{"code":"missing_required_fields","fields":["scope_address"],"message":"missing required object store gateway event fields: [scope_address]"}"#,
+        );
+    }
+
+    #[test]
+    fn test_structured_errors_are_pinned() {
+        assert_snapshot(
+            OsGatewayError::ConflictingAttributes {
+                conflicts: vec![OsGatewayAttributeConflict {
+                    key: "k".to_string(),
+                    existing_value: "a".to_string(),
+                    incoming_value: "b".to_string(),
+                }],
+            },
+            concat!(
+                r#"{"code":"conflicting_attributes","conflicts":[{"key":"k","existing_value":"a","incoming_value":"b"}],"#,
+                r#""message":"conflicting object store gateway attributes: [k: [a] vs [b]]"}"#,
+            ),
+        );
+        assert_snapshot(
+            OsGatewayError::InvalidFields {
+                errors: vec![FieldError::new(
+                    OsGatewayKey::AccessLevel,
+                    FieldErrorKind::UnknownAccessLevel,
+                    "admin",
+                )],
+            },
+            concat!(
+                r#"{"code":"invalid_fields","errors":[{"key":"object_store_gateway_access_level","kind":"unknown_access_level","value_preview":"admin"}],"#,
+                r#""message":"invalid object store gateway attributes: [object_store_gateway_access_level] is not a recognized access level: [admin]"}"#,
+            ),
+        );
+        assert_snapshot(
+            OsGatewayError::UnexpectedEventType {
+                expected: "access_grant".to_string(),
+                actual: "access_revoke".to_string(),
+            },
+            concat!(
+                r#"{"code":"unexpected_event_type","expected":"access_grant","actual":"access_revoke","#,
+                r#""message":"unexpected object store gateway event type: expected [access_grant] but found [access_revoke]"}"#,
+            ),
+        );
+        assert_snapshot(
+            OsGatewayError::InvalidCsv {
+                line: 3,
+                reason: "why".to_string(),
+            },
+            r#"{"code":"invalid_csv","line":3,"reason":"why","message":"invalid csv at line 3: why"}"#,
+        );
+        assert_snapshot(
+            OsGatewayError::MissingContractVersion {
+                reason: "why".to_string(),
+            },
+            r#"{"code":"missing_contract_version","reason":"why","message":"missing contract version: why"}"#,
+        );
+    }
+
+    #[test]
+    fn test_single_value_errors_are_pinned() {
+        assert_snapshot(
+            OsGatewayError::UnknownEventType {
+                event_type: "value".to_string(),
+            },
+            r#"{"code":"unknown_event_type","event_type":"value","message":"unknown object store gateway event type: [value]"}"#,
+        );
+        assert_snapshot(
+            OsGatewayError::UnknownAccessLevel {
+                access_level: "value".to_string(),
+            },
+            r#"{"code":"unknown_access_level","access_level":"value","message":"unknown access level [value]"}"#,
+        );
+        let value = || "value".to_string();
+        let reason = || "why".to_string();
+        for (error, field) in [
+            (
+                OsGatewayError::InvalidAddress {
+                    address: value(),
+                    reason: reason(),
+                },
+                "address",
+            ),
+            (
+                OsGatewayError::InvalidAccessGrantId {
+                    access_grant_id: value(),
+                    reason: reason(),
+                },
+                "access_grant_id",
+            ),
+            (
+                OsGatewayError::InvalidScopeUuid {
+                    uuid: value(),
+                    reason: reason(),
+                },
+                "uuid",
+            ),
+            (
+                OsGatewayError::InvalidExpiration {
+                    expiration: value(),
+                    reason: reason(),
+                },
+                "expiration",
+            ),
+            (
+                OsGatewayError::InvalidPayloadVersion {
+                    version: value(),
+                    reason: reason(),
+                },
+                "version",
+            ),
+            (
+                OsGatewayError::InvalidCompactPayload {
+                    payload: value(),
+                    reason: reason(),
+                },
+                "payload",
+            ),
+            (
+                OsGatewayError::InvalidCompactString {
+                    value: value(),
+                    reason: reason(),
+                },
+                "value",
+            ),
+            (
+                OsGatewayError::InvalidEncodedEvent {
+                    encoding: value(),
+                    reason: reason(),
+                },
+                "encoding",
+            ),
+        ] {
+            let expected = reason_snapshot(error.code(), field, &error.to_string());
+            assert_snapshot(error, &expected);
+        }
+    }
+
+    #[test]
+    fn test_deserialization_is_tolerant_but_requires_known_codes() {
+        assert_eq!(
+            OsGatewayError::UnknownEventType {
+                event_type: "bogus".to_string(),
+            },
+            OsGatewayError::from_json(&Binary::from(
+                br#"{"message":"stale","added_later":[1,2],"event_type":"bogus","code":"unknown_event_type"}"#
+                    .as_slice(),
+            ))
+            .unwrap(),
+            "fields may appear in any order, and the message and unknown fields should be ignored",
+        );
+        let error = OsGatewayError::from_json(&Binary::from(br#"{"code":"bogus"}"#.as_slice()))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("unknown object store gateway error code [bogus]"),
+            "an unknown code should be rejected, but produced: {error}",
+        );
+        assert!(
+            OsGatewayError::from_json(&Binary::from(
+                br#"{"code":"invalid_address","address":"value"}"#.as_slice(),
+            ))
+            .is_err(),
+            "a missing field should be rejected",
+        );
+    }
+}
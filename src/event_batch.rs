@@ -0,0 +1,312 @@
+use crate::{AccessGrant, AccessRevoke, OsGatewayError, OsGatewayEvent};
+use std::collections::BTreeSet;
+
+/// An ordered collection of gateway events, such as every event emitted within a block, that can
+/// be reduced to the access grants that survive it.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrant, AccessRevoke, GatewayEventBatch};
+///
+/// let scope_address = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+/// let mut batch = GatewayEventBatch::new();
+/// batch.push(AccessGrant::new(scope_address, "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr"));
+/// batch.push(AccessGrant::new(scope_address, "tp1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfxwkgt"));
+/// batch.push(AccessRevoke::new(scope_address, "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr"));
+/// assert_eq!(
+///     vec![AccessGrant::new(scope_address, "tp1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfxwkgt")],
+///     batch.net_grants(),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GatewayEventBatch(Vec<OsGatewayEvent>);
+impl GatewayEventBatch {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an event to the end of the batch.
+    ///
+    /// # Parameters
+    ///
+    /// * `event` The event to append, or any typed event that converts into one.
+    pub fn push<E: Into<OsGatewayEvent>>(&mut self, event: E) {
+        self.0.push(event.into());
+    }
+
+    /// Appends every provided event to the end of the batch, in order.
+    ///
+    /// # Parameters
+    ///
+    /// * `events` The events to append.
+    pub fn extend<I: IntoIterator<Item = OsGatewayEvent>>(&mut self, events: I) {
+        self.0.extend(events);
+    }
+
+    /// The events in the batch, in the order they were added.
+    pub fn events(&self) -> &[OsGatewayEvent] {
+        &self.0
+    }
+
+    /// Consumes the batch, producing its events in the order they were added.
+    pub fn into_events(self) -> Vec<OsGatewayEvent> {
+        self.0
+    }
+
+    /// The number of events in the batch.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether or not the batch contains no events.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Verifies that every event holds valid attribute values, as described by
+    /// [OsGatewayEvent::validate](crate::OsGatewayEvent::validate), producing the error of the
+    /// first invalid event.  Then verifies that no access grant id is granted more than once
+    /// within the same scope, which [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+    /// would reject, producing an [OsGatewayError::DuplicateAccessGrantIds](crate::OsGatewayError::DuplicateAccessGrantIds)
+    /// containing every duplicated id.
+    pub fn validate(&self) -> Result<(), OsGatewayError> {
+        for event in &self.0 {
+            event.validate()?;
+        }
+        let mut seen = BTreeSet::new();
+        let mut duplicates = BTreeSet::new();
+        for grant in self.grants() {
+            if let Some(access_grant_id) = &grant.access_grant_id {
+                if !seen.insert((grant.scope_address.as_str(), access_grant_id.as_str())) {
+                    duplicates.insert(access_grant_id.to_owned());
+                }
+            }
+        }
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(OsGatewayError::DuplicateAccessGrantIds {
+                access_grant_ids: duplicates.into_iter().collect(),
+            })
+        }
+    }
+
+    /// Applies every event in order and produces the access grants that remain, in the order they
+    /// were granted.
+    ///
+    /// * A grant is added, unless a remaining grant in the same scope already uses its access
+    ///   grant id, in which case it is skipped in the same way that the gateway rejects it.
+    /// * A revoke with an access grant id removes the remaining grant with that id for the same
+    ///   scope and target account.
+    /// * A revoke without an access grant id removes every remaining grant for the same scope and
+    ///   target account.
+    ///
+    /// Netting is idempotent: a batch built from the produced grants nets to the same grants.
+    pub fn net_grants(&self) -> Vec<AccessGrant> {
+        let mut grants: Vec<AccessGrant> = Vec::new();
+        for event in &self.0 {
+            match event {
+                OsGatewayEvent::AccessGrant(grant) => {
+                    let id_in_use = grant.access_grant_id.is_some()
+                        && grants.iter().any(|existing| {
+                            existing.scope_address == grant.scope_address
+                                && existing.access_grant_id == grant.access_grant_id
+                        });
+                    if !id_in_use {
+                        grants.push(grant.to_owned());
+                    }
+                }
+                OsGatewayEvent::AccessRevoke(revoke) => {
+                    grants.retain(|grant| !revokes(revoke, grant));
+                }
+            }
+        }
+        grants
+    }
+
+    fn grants(&self) -> impl Iterator<Item = &AccessGrant> {
+        self.0.iter().filter_map(|event| match event {
+            OsGatewayEvent::AccessGrant(grant) => Some(grant),
+            OsGatewayEvent::AccessRevoke(_) => None,
+        })
+    }
+}
+impl From<Vec<OsGatewayEvent>> for GatewayEventBatch {
+    fn from(events: Vec<OsGatewayEvent>) -> Self {
+        Self(events)
+    }
+}
+impl FromIterator<OsGatewayEvent> for GatewayEventBatch {
+    fn from_iter<I: IntoIterator<Item = OsGatewayEvent>>(events: I) -> Self {
+        Self(events.into_iter().collect())
+    }
+}
+impl IntoIterator for GatewayEventBatch {
+    type Item = OsGatewayEvent;
+
+    type IntoIter = std::vec::IntoIter<OsGatewayEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Whether or not the revoke removes the grant.
+fn revokes(revoke: &AccessRevoke, grant: &AccessGrant) -> bool {
+    revoke.scope_address == grant.scope_address
+        && revoke.target_account_address == grant.target_account_address
+        && (revoke.access_grant_id.is_none() || revoke.access_grant_id == grant.access_grant_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrant, AccessRevoke, GatewayEventBatch, OsGatewayError, OsGatewayEvent};
+
+    const SCOPE_ADDRESSES: [&str; 2] = [
+        "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+        "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+    ];
+    const ACCOUNT_ADDRESSES: [&str; 2] = [
+        "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+        "tp1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqfxwkgt",
+    ];
+
+    fn grant(scope: usize, target: usize, id: Option<&str>) -> AccessGrant {
+        AccessGrant::new(SCOPE_ADDRESSES[scope], ACCOUNT_ADDRESSES[target])
+            .with_optional_access_grant_id(id)
+    }
+
+    fn revoke(scope: usize, target: usize, id: Option<&str>) -> AccessRevoke {
+        AccessRevoke::new(SCOPE_ADDRESSES[scope], ACCOUNT_ADDRESSES[target])
+            .with_optional_access_grant_id(id)
+    }
+
+    /// Produces a deterministic pseudo-random batch from the seed, drawing from a small pool of
+    /// scopes, targets, and ids so that revokes and id collisions are frequent.
+    fn generated_batch(seed: u64) -> GatewayEventBatch {
+        let mut state = seed;
+        let mut next = |bound: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % bound) as usize
+        };
+        let ids = [None, Some("first"), Some("second")];
+        let length = next(12);
+        (0..length)
+            .map(|_| {
+                let (scope, target, id) = (next(2), next(2), ids[next(3)]);
+                if next(3) == 0 {
+                    revoke(scope, target, id).into()
+                } else {
+                    grant(scope, target, id).into()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_revokes_apply_by_id_or_by_target() {
+        let mut batch = GatewayEventBatch::new();
+        batch.extend([
+            grant(0, 0, Some("first")).into(),
+            grant(0, 0, Some("second")).into(),
+            grant(0, 1, None).into(),
+            grant(1, 0, None).into(),
+        ]);
+        batch.push(revoke(0, 0, Some("first")));
+        assert_eq!(
+            vec![
+                grant(0, 0, Some("second")),
+                grant(0, 1, None),
+                grant(1, 0, None)
+            ],
+            batch.net_grants(),
+            "a revoke with an id should only remove the grant with that id",
+        );
+        batch.push(revoke(0, 0, None));
+        assert_eq!(
+            vec![grant(0, 1, None), grant(1, 0, None)],
+            batch.net_grants(),
+            "a revoke without an id should remove every grant for the scope and target",
+        );
+    }
+
+    #[test]
+    fn test_events_apply_in_order() {
+        let batch = GatewayEventBatch::from(vec![
+            OsGatewayEvent::from(revoke(0, 0, None)),
+            OsGatewayEvent::from(grant(0, 0, None)),
+        ]);
+        assert_eq!(
+            vec![grant(0, 0, None)],
+            batch.net_grants(),
+            "a revoke should not affect grants that follow it",
+        );
+    }
+
+    #[test]
+    fn test_grants_reusing_an_id_are_skipped() {
+        let batch = [
+            grant(0, 0, Some("first")),
+            grant(0, 1, Some("first")),
+            grant(1, 1, Some("first")),
+        ]
+        .into_iter()
+        .map(OsGatewayEvent::from)
+        .collect::<GatewayEventBatch>();
+        assert_eq!(
+            vec![grant(0, 0, Some("first")), grant(1, 1, Some("first"))],
+            batch.net_grants(),
+            "a grant reusing an id within a scope should be skipped",
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_duplicate_ids_per_scope() {
+        let mut batch = GatewayEventBatch::new();
+        batch.push(grant(0, 0, Some("first")));
+        batch.push(grant(1, 0, Some("first")));
+        assert_eq!(
+            Ok(()),
+            batch.validate(),
+            "the same id in different scopes should be accepted",
+        );
+        batch.push(grant(0, 1, Some("first")));
+        assert_eq!(
+            Err(OsGatewayError::DuplicateAccessGrantIds {
+                access_grant_ids: vec!["first".to_string()],
+            }),
+            batch.validate(),
+            "the same id within a scope should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_events() {
+        let mut batch = GatewayEventBatch::new();
+        batch.push(AccessGrant::new(SCOPE_ADDRESSES[0], "target"));
+        assert!(
+            matches!(batch.validate(), Err(OsGatewayError::InvalidFields { .. })),
+            "an event with invalid attributes should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_netting_is_idempotent() {
+        for seed in 0..500 {
+            let batch = generated_batch(seed);
+            let netted = batch.net_grants();
+            let renetted = netted
+                .iter()
+                .cloned()
+                .map(OsGatewayEvent::from)
+                .collect::<GatewayEventBatch>()
+                .net_grants();
+            assert_eq!(
+                netted, renetted,
+                "netting an already-netted batch should change nothing: {batch:?}",
+            );
+        }
+    }
+}
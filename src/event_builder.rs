@@ -0,0 +1,313 @@
+use crate::{
+    OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEventType, OS_GATEWAY_EVENT_TYPES,
+    OS_GATEWAY_KEYS,
+};
+use alloc::string::String;
+use core::marker::PhantomData;
+
+/// A marker type denoting that a required value has not yet been provided to an
+/// [OsGatewayEventBuilder](crate::OsGatewayEventBuilder).
+#[derive(Clone, Copy, Debug)]
+pub struct Missing;
+
+/// A marker type denoting that a required value has been provided to an
+/// [OsGatewayEventBuilder](crate::OsGatewayEventBuilder).
+#[derive(Clone, Copy, Debug)]
+pub struct Present;
+
+/// A marker type denoting that an [OsGatewayEventBuilder](crate::OsGatewayEventBuilder) tracks its
+/// values at runtime via setters, and that required values are verified when the builder is built.
+#[derive(Clone, Copy, Debug)]
+pub struct Unchecked;
+
+/// A typestate builder for [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) that
+/// proves at compile time that all required values have been provided.
+///
+/// The `Scope` and `Target` type parameters track whether or not the scope address and target
+/// account address have been provided.  The [build](self::OsGatewayEventBuilder::build) function only
+/// exists when both are [Present](crate::Present), so omitting either value is a compile error
+/// rather than a malformed event.
+///
+/// ```
+/// use os_gateway_contract_attributes::OsGatewayEventBuilder;
+///
+/// let generator = OsGatewayEventBuilder::grant()
+///     .scope("scope1qzn7jghj8puprmdcvunm3330jutsj803zz")
+///     .target("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr")
+///     .access_grant_id("my_unique_id")
+///     .build();
+/// ```
+///
+/// For values that arrive in an arbitrary order or may be absent, [default](self::OsGatewayEventBuilder::default)
+/// produces an [Unchecked](crate::Unchecked) builder with `&mut self` setters, which instead
+/// verifies its required values when [built](self::OsGatewayEventBuilder::build):
+///
+/// ```
+/// use os_gateway_contract_attributes::{OsGatewayEventBuilder, OsGatewayEventType};
+///
+/// let mut builder = OsGatewayEventBuilder::default();
+/// builder.set_event_type(OsGatewayEventType::AccessRevoke);
+/// builder.set_target_account("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr");
+/// builder.set_scope_address("scope1qzn7jghj8puprmdcvunm3330jutsj803zz");
+/// let generator = builder.build().expect("all required values were provided");
+/// ```
+///
+/// ```compile_fail
+/// use os_gateway_contract_attributes::OsGatewayEventBuilder;
+///
+/// // The target account was never provided, so build is unavailable
+/// let generator = OsGatewayEventBuilder::grant()
+///     .scope("scope1qzn7jghj8puprmdcvunm3330jutsj803zz")
+///     .build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct OsGatewayEventBuilder<Scope = Missing, Target = Missing> {
+    generator: OsGatewayAttributeGenerator,
+    state: PhantomData<(Scope, Target)>,
+}
+impl OsGatewayEventBuilder<Missing, Missing> {
+    /// Starts a builder for an event that denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+    /// that access needs to be granted to an account.  See [access_grant](crate::OsGatewayAttributeGenerator::access_grant)
+    /// for the criteria the gateway uses to accept this event.
+    pub fn grant() -> Self {
+        Self::with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant)
+    }
+
+    /// Starts a builder for an event that denotes to [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+    /// that access needs to be revoked from an account.  See [access_revoke](crate::OsGatewayAttributeGenerator::access_revoke)
+    /// for the criteria the gateway uses to accept this event.
+    pub fn revoke() -> Self {
+        Self::with_event_type(OS_GATEWAY_EVENT_TYPES.access_revoke)
+    }
+
+    fn with_event_type(event_type: &str) -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::new().with_event_type(event_type),
+            state: PhantomData,
+        }
+    }
+}
+impl<Target> OsGatewayEventBuilder<Missing, Target> {
+    /// Provides the bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which the event refers.
+    pub fn scope<S: Into<String>>(
+        self,
+        scope_address: S,
+    ) -> OsGatewayEventBuilder<Present, Target> {
+        OsGatewayEventBuilder {
+            generator: self.generator.with_scope_address(scope_address),
+            state: PhantomData,
+        }
+    }
+}
+impl<Scope> OsGatewayEventBuilder<Scope, Missing> {
+    /// Provides the bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// to which the event refers.
+    pub fn target<S: Into<String>>(
+        self,
+        target_account_address: S,
+    ) -> OsGatewayEventBuilder<Scope, Present> {
+        OsGatewayEventBuilder {
+            generator: self
+                .generator
+                .with_target_account_address(target_account_address),
+            state: PhantomData,
+        }
+    }
+}
+impl<Scope, Target> OsGatewayEventBuilder<Scope, Target> {
+    /// Includes a custom access grant unique identifier in the event.  See
+    /// [with_access_grant_id](crate::OsGatewayAttributeGenerator::with_access_grant_id) for how the
+    /// gateway interprets this value.
+    pub fn access_grant_id<S: Into<String>>(self, access_grant_id: S) -> Self {
+        Self {
+            generator: self.generator.with_access_grant_id(access_grant_id),
+            state: PhantomData,
+        }
+    }
+}
+impl OsGatewayEventBuilder<Present, Present> {
+    /// Produces the [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) containing all
+    /// provided values.  Only available once both the scope address and target account address
+    /// have been provided.
+    pub fn build(self) -> OsGatewayAttributeGenerator {
+        self.generator
+    }
+}
+impl Default for OsGatewayEventBuilder<Unchecked, Unchecked> {
+    fn default() -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::new(),
+            state: PhantomData,
+        }
+    }
+}
+impl OsGatewayEventBuilder<Unchecked, Unchecked> {
+    /// Sets the event type.  Takes an [OsGatewayEventType](crate::OsGatewayEventType) rather than
+    /// a string, so that an unrecognized event type cannot be built.  A value received as a string
+    /// can be converted with [from_str](core::str::FromStr::from_str), which produces an
+    /// [OsGatewayError::UnknownEventType](crate::OsGatewayError::UnknownEventType) for an
+    /// unrecognized value.  Replaces any previously set value.
+    pub fn set_event_type(&mut self, event_type: OsGatewayEventType) {
+        self.generator
+            .set_attribute(OS_GATEWAY_KEYS.event_type, event_type.as_str());
+    }
+
+    /// Sets the bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which the event refers.  Replaces any previously set value.
+    pub fn set_scope_address<S: Into<String>>(&mut self, scope_address: S) {
+        self.generator
+            .set_attribute(OS_GATEWAY_KEYS.scope_address, scope_address);
+    }
+
+    /// Sets the bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// to which the event refers.  Replaces any previously set value.
+    pub fn set_target_account<S: Into<String>>(&mut self, target_account_address: S) {
+        self.generator
+            .set_attribute(OS_GATEWAY_KEYS.target_account, target_account_address);
+    }
+
+    /// Sets a custom access grant unique identifier.  Replaces any previously set value.
+    pub fn set_access_grant_id<S: Into<String>>(&mut self, access_grant_id: S) {
+        self.generator
+            .set_attribute(OS_GATEWAY_KEYS.access_grant_id, access_grant_id);
+    }
+
+    /// Produces the [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) containing all
+    /// set values, or an [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+    /// naming every required attribute key that was never set.
+    pub fn build(self) -> Result<OsGatewayAttributeGenerator, OsGatewayError> {
+        self.generator.verify_required_attributes()?;
+        Ok(self.generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEventBuilder, OsGatewayEventType,
+        OS_GATEWAY_KEYS,
+    };
+
+    #[test]
+    fn test_builder_matches_fluent_constructors() {
+        let built_grant = OsGatewayEventBuilder::grant()
+            .scope("scope")
+            .target("target")
+            .access_grant_id("grant_id")
+            .build()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let fluent_grant = OsGatewayAttributeGenerator::access_grant("scope", "target")
+            .with_access_grant_id("grant_id")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            fluent_grant, built_grant,
+            "the builder should produce the same grant attributes as the fluent constructor",
+        );
+        let built_revoke = OsGatewayEventBuilder::revoke()
+            .scope("scope")
+            .target("target")
+            .build()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let fluent_revoke = OsGatewayAttributeGenerator::access_revoke("scope", "target")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            fluent_revoke, built_revoke,
+            "the builder should produce the same revoke attributes as the fluent constructor",
+        );
+    }
+
+    #[test]
+    fn test_builder_required_values_can_be_provided_in_any_order() {
+        let scope_first = OsGatewayEventBuilder::grant()
+            .scope("scope")
+            .target("target")
+            .build()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let target_first = OsGatewayEventBuilder::grant()
+            .target("target")
+            .scope("scope")
+            .build()
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            scope_first, target_first,
+            "the order in which required values are provided should not impact the output",
+        );
+    }
+
+    #[test]
+    fn test_setter_builder_matches_fluent_constructors() {
+        let mut builder = OsGatewayEventBuilder::default();
+        builder.set_access_grant_id("grant_id");
+        builder.set_target_account("target");
+        builder.set_scope_address("scope");
+        builder.set_event_type(OsGatewayEventType::AccessGrant);
+        let built_grant = builder
+            .build()
+            .expect("all required values were provided")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let fluent_grant = OsGatewayAttributeGenerator::access_grant("scope", "target")
+            .with_access_grant_id("grant_id")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            fluent_grant, built_grant,
+            "the setter builder should produce the same attributes as the fluent constructor",
+        );
+    }
+
+    #[test]
+    fn test_setter_builder_names_missing_values() {
+        let mut builder = OsGatewayEventBuilder::default();
+        builder.set_scope_address("scope");
+        builder.set_access_grant_id("grant_id");
+        assert_eq!(
+            OsGatewayError::MissingRequiredAttributes {
+                keys: vec![
+                    OS_GATEWAY_KEYS.event_type.to_string(),
+                    OS_GATEWAY_KEYS.target_account.to_string(),
+                ],
+            },
+            builder.build().unwrap_err(),
+            "the error should name exactly the event type and target account keys",
+        );
+        assert_eq!(
+            OsGatewayError::MissingRequiredAttributes {
+                keys: vec![
+                    OS_GATEWAY_KEYS.event_type.to_string(),
+                    OS_GATEWAY_KEYS.scope_address.to_string(),
+                    OS_GATEWAY_KEYS.target_account.to_string(),
+                ],
+            },
+            OsGatewayEventBuilder::default().build().unwrap_err(),
+            "an empty builder should report all required keys as missing",
+        );
+    }
+
+    #[test]
+    fn test_setter_builder_event_type_parsed_from_string() {
+        let mut builder = OsGatewayEventBuilder::default();
+        builder.set_event_type("access_revoke".parse::<OsGatewayEventType>().unwrap());
+        builder.set_scope_address("scope");
+        builder.set_target_account("target");
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke("scope", "target"),
+            builder.build().expect("all required values were provided"),
+            "a parsed event type should produce the same attributes as the fluent constructor",
+        );
+        assert_eq!(
+            Err(OsGatewayError::UnknownEventType {
+                event_type: "access_granted".to_string(),
+            }),
+            "access_granted".parse::<OsGatewayEventType>(),
+            "an unrecognized event type should be rejected before it reaches the builder",
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{OsGatewayEvent, OsGatewayEventType};
+
+/// A broad classification of gateway events, for consumers that group events by their effect
+/// rather than by their exact event type, such as analytics over every event a contract emits.
+/// Every [OsGatewayEventType](crate::OsGatewayEventType) belongs to exactly one category, and new
+/// event types are classified as they are added.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessRevoke, EventCategory, OsGatewayEvent};
+///
+/// let event = OsGatewayEvent::from(AccessRevoke::new(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// ));
+/// assert_eq!(EventCategory::RevokeMutation, event.category());
+/// assert!(event.category().is_mutation());
+/// ```
+///
+/// # Variants
+///
+/// * `GrantMutation` The event gives an account access to the records of a scope.
+///
+/// * `RevokeMutation` The event removes an account's access to the records of a scope.
+///
+/// * `Informational` The event changes no access, and is only observed.  No event type recognized
+///   by this release falls into this category.
+///
+/// * `Administrative` The event changes the configuration of the gateway itself rather than the
+///   access to any scope.  No event type recognized by this release falls into this category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum EventCategory {
+    GrantMutation,
+    RevokeMutation,
+    Informational,
+    Administrative,
+}
+impl EventCategory {
+    /// Whether or not events in this category change the access to a scope.
+    pub fn is_mutation(&self) -> bool {
+        matches!(self, Self::GrantMutation | Self::RevokeMutation)
+    }
+}
+
+impl OsGatewayEventType {
+    /// The category to which events of this type belong.
+    pub fn category(&self) -> EventCategory {
+        match self {
+            Self::AccessGrant => EventCategory::GrantMutation,
+            Self::AccessRevoke => EventCategory::RevokeMutation,
+        }
+    }
+}
+
+impl OsGatewayEvent {
+    /// The category to which this event belongs.  See [EventCategory](crate::EventCategory).
+    pub fn category(&self) -> EventCategory {
+        self.typed_event_type().category()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrant, AccessRevoke, EventCategory, OsGatewayEvent, OsGatewayEventType};
+
+    #[test]
+    fn test_every_event_type_is_classified() {
+        for event_type in OsGatewayEventType::ALL {
+            // Exhaustively match the event types so that adding a variant without deciding its
+            // category here fails to compile
+            let expected = match event_type {
+                OsGatewayEventType::AccessGrant => EventCategory::GrantMutation,
+                OsGatewayEventType::AccessRevoke => EventCategory::RevokeMutation,
+            };
+            assert_eq!(
+                expected,
+                event_type.category(),
+                "the event type [{event_type}] should be classified in the expected category",
+            );
+        }
+    }
+
+    #[test]
+    fn test_events_share_their_event_type_category() {
+        let events = [
+            OsGatewayEvent::from(AccessGrant::new("scope", "target")),
+            OsGatewayEvent::from(AccessRevoke::new("scope", "target")),
+        ];
+        for event in events {
+            assert_eq!(
+                event.typed_event_type().category(),
+                event.category(),
+                "the event [{event:?}] should share the category of its event type",
+            );
+            assert!(
+                event.category().is_mutation(),
+                "the event [{event:?}] should be classified as a mutation",
+            );
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{OsGatewayAttributeGenerator, OsGatewayEvent, OsGatewayKey};
+
+/// Receives every attribute of an event in the crate's canonical order, so that a wire format can
+/// be added without re-deriving the ordering rules.  The [canonical bytes](crate::OsGatewayAttributeGenerator::canonical_bytes),
+/// the [compact attribute](crate::OsGatewayAttributeGenerator::to_compact_attribute), the
+/// [fingerprint](crate::OsGatewayAttributeGenerator::fingerprint), and the serde serialization
+/// behind the JSON and MessagePack encodings are all produced by encoders.
+///
+/// The canonical order visits each attribute exactly once, sorted by the bytes of its full key.
+/// Attributes with a key recognized by the gateway are passed to [field](self::EventEncoder::field),
+/// and every other attribute is passed to [extra_attribute](self::EventEncoder::extra_attribute),
+/// interleaved in the same order.
+///
+/// For example, an encoder that renders tab-separated lines:
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessRevoke, EventEncoder, OsGatewayEvent, OsGatewayKey};
+///
+/// #[derive(Default)]
+/// struct TsvEncoder(String);
+/// impl EventEncoder for TsvEncoder {
+///     fn field(&mut self, key: OsGatewayKey, value: &str) {
+///         self.0.push_str(&format!("{}\t{value}\n", key.field_name()));
+///     }
+///
+///     fn extra_attribute(&mut self, key: &str, value: &str) {
+///         self.0.push_str(&format!("{key}\t{value}\n"));
+///     }
+/// }
+///
+/// let event = OsGatewayEvent::from(
+///     AccessRevoke::new(
+///         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     )
+///     .with_extra_attribute("object_store_gateway_note", "shared"),
+/// );
+/// let mut encoder = TsvEncoder::default();
+/// event.encode(&mut encoder);
+/// assert_eq!(
+///     concat!(
+///         "event_type\taccess_revoke\n",
+///         "object_store_gateway_note\tshared\n",
+///         "scope_address\tscope1qzn7jghj8puprmdcvunm3330jutsj803zz\n",
+///         "target_account\ttp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr\n",
+///     ),
+///     encoder.0,
+/// );
+/// ```
+pub trait EventEncoder {
+    /// Receives an attribute whose key is recognized by the gateway.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The typed key of the attribute.
+    /// * `value` The value of the attribute, exactly as emitted.
+    fn field(&mut self, key: OsGatewayKey, value: &str);
+
+    /// Receives an attribute whose key is not recognized by the gateway, such as an extra attribute
+    /// of a typed event.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The value of the attribute, exactly as emitted.
+    fn extra_attribute(&mut self, key: &str, value: &str);
+}
+
+impl OsGatewayAttributeGenerator {
+    /// Passes every attribute held in this generator to the encoder, in the canonical order
+    /// described by [EventEncoder](crate::EventEncoder).
+    ///
+    /// # Parameters
+    ///
+    /// * `encoder` The encoder that receives the attributes.
+    pub fn encode<E: EventEncoder + ?Sized>(&self, encoder: &mut E) {
+        // The attributes are held in a BTreeMap, which orders Strings by their bytes
+        for (key, value) in self.as_map() {
+            match key.parse::<OsGatewayKey>() {
+                Ok(key) => encoder.field(key, value),
+                Err(_) => encoder.extra_attribute(key, value),
+            }
+        }
+    }
+}
+
+impl OsGatewayEvent {
+    /// Passes every attribute that the event emits to the encoder, in the canonical order
+    /// described by [EventEncoder](crate::EventEncoder).
+    ///
+    /// # Parameters
+    ///
+    /// * `encoder` The encoder that receives the attributes.
+    pub fn encode<E: EventEncoder + ?Sized>(&self, encoder: &mut E) {
+        OsGatewayAttributeGenerator::from(self.clone()).encode(encoder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, EventEncoder, GrantExpiration, OsGatewayAttributeGenerator,
+        OsGatewayEvent, OsGatewayKey,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    /// Records every call, distinguishing recognized fields from extra attributes.
+    #[derive(Default)]
+    struct RecordingEncoder(Vec<(Option<OsGatewayKey>, String, String)>);
+    impl EventEncoder for RecordingEncoder {
+        fn field(&mut self, key: OsGatewayKey, value: &str) {
+            self.0
+                .push((Some(key), key.as_str().to_string(), value.to_string()));
+        }
+
+        fn extra_attribute(&mut self, key: &str, value: &str) {
+            self.0.push((None, key.to_string(), value.to_string()));
+        }
+    }
+
+    /// Renders tab-separated lines of short field names and values, as a downstream format would.
+    #[derive(Default)]
+    struct TsvEncoder(String);
+    impl EventEncoder for TsvEncoder {
+        fn field(&mut self, key: OsGatewayKey, value: &str) {
+            self.0.push_str(&format!("{}\t{value}\n", key.field_name()));
+        }
+
+        fn extra_attribute(&mut self, key: &str, value: &str) {
+            self.0.push_str(&format!("{key}\t{value}\n"));
+        }
+    }
+
+    fn event() -> OsGatewayEvent {
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::Read)
+            .with_extra_attribute("object_store_gateway_note", "shared")
+            .into()
+    }
+
+    #[test]
+    fn test_attributes_are_visited_in_canonical_order() {
+        let mut encoder = RecordingEncoder::default();
+        event().encode(&mut encoder);
+        let generator = OsGatewayAttributeGenerator::from(event());
+        assert_eq!(
+            generator
+                .as_map()
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<(String, String)>>(),
+            encoder
+                .0
+                .iter()
+                .map(|(_, key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<(String, String)>>(),
+            "every attribute should be visited once, sorted by key",
+        );
+        assert_eq!(
+            vec!["object_store_gateway_note"],
+            encoder
+                .0
+                .iter()
+                .filter(|(key, _, _)| key.is_none())
+                .map(|(_, key, _)| key.as_str())
+                .collect::<Vec<&str>>(),
+            "only unrecognized keys should be visited as extra attributes",
+        );
+    }
+
+    #[test]
+    fn test_downstream_formats_can_be_added() {
+        let mut encoder = TsvEncoder::default();
+        event().encode(&mut encoder);
+        assert_eq!(
+            format!(
+                concat!(
+                    "access_grant_id\tmy_unique_id\n",
+                    "access_level\tread\n",
+                    "event_type\taccess_grant\n",
+                    "expiration\theight:100\n",
+                    "object_store_gateway_note\tshared\n",
+                    "scope_address\t{}\n",
+                    "target_account\t{}\n",
+                ),
+                SCOPE_ADDRESS, ACCOUNT_ADDRESS,
+            ),
+            encoder.0,
+            "the encoder should render the event in the canonical order",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialization_follows_canonical_order() {
+        let mut encoder = RecordingEncoder::default();
+        event().encode(&mut encoder);
+        let expected = encoder
+            .0
+            .iter()
+            .map(|(_, key, value)| format!("{key:?}:{value:?}"))
+            .collect::<Vec<String>>()
+            .join(",");
+        assert_eq!(
+            format!("{{{expected}}}"),
+            serde_json::to_string(&event()).unwrap(),
+            "the JSON encoding should hold every attribute in the canonical order",
+        );
+    }
+
+    #[test]
+    fn test_encoders_can_be_used_as_trait_objects() {
+        let mut encoder = TsvEncoder::default();
+        let dynamic: &mut dyn EventEncoder = &mut encoder;
+        OsGatewayAttributeGenerator::from(event()).encode(dynamic);
+        let mut expected = TsvEncoder::default();
+        event().encode(&mut expected);
+        assert_eq!(
+            expected.0, encoder.0,
+            "a dynamically dispatched encoder should receive the same attributes",
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{parse_gateway_event, OsGatewayError, OsGatewayEvent};
+use cosmwasm_std::Event;
+
+/// Extends the [Cosmwasm Event](cosmwasm_std::Event) with functions that inspect it for
+/// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) events, mirroring
+/// [OsGatewayResponseExt](crate::OsGatewayResponseExt) for code that reads events, such as reply
+/// handlers and indexers.  Both emission forms are recognized, exactly as by [parse_gateway_event](crate::parse_gateway_event):
+/// flat attributes within a `wasm` event, and custom events of type `object_store_gateway` or
+/// `wasm-object_store_gateway`.
+///
+/// ```
+/// use cosmwasm_std::Event;
+/// use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayEventExt};
+///
+/// let event = OsGatewayAttributeGenerator::access_grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .to_event();
+/// if let Some(Ok(gateway_event)) = event.os_gateway_event() {
+///     assert_eq!("scope1qzn7jghj8puprmdcvunm3330jutsj803zz", gateway_event.scope_address());
+/// }
+/// assert!(!Event::new("transfer").is_os_gateway_event());
+/// ```
+pub trait OsGatewayEventExt {
+    /// Parses the first gateway event contained in this event, producing `None` if it contains no
+    /// gateway events at all.  Use [parse_gateway_event](crate::parse_gateway_event) to parse
+    /// every gateway event when a single event may contain several.
+    fn os_gateway_event(&self) -> Option<Result<OsGatewayEvent, OsGatewayError>>;
+
+    /// Whether or not this event contains a gateway event, including one that cannot be parsed.
+    fn is_os_gateway_event(&self) -> bool;
+}
+impl OsGatewayEventExt for Event {
+    fn os_gateway_event(&self) -> Option<Result<OsGatewayEvent, OsGatewayError>> {
+        parse_gateway_event(self).into_iter().next()
+    }
+
+    fn is_os_gateway_event(&self) -> bool {
+        self.os_gateway_event().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessRevoke, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+        OsGatewayEventExt,
+    };
+    use cosmwasm_std::Event;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn revoke() -> OsGatewayAttributeGenerator {
+        OsGatewayAttributeGenerator::access_revoke(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+    }
+
+    #[test]
+    fn test_every_emission_form_is_recognized() {
+        let expected = OsGatewayEvent::from(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+        for event in [
+            Event::new("wasm")
+                .add_attribute("action", "revoke")
+                .add_attributes(revoke()),
+            revoke().to_event(),
+            Event::new("wasm-object_store_gateway").add_attributes(revoke()),
+        ] {
+            assert!(
+                event.is_os_gateway_event(),
+                "[{}] should be recognized as a gateway event",
+                event.ty,
+            );
+            assert_eq!(
+                Some(Ok(expected.clone())),
+                event.os_gateway_event(),
+                "[{}] should produce its gateway event",
+                event.ty,
+            );
+        }
+    }
+
+    #[test]
+    fn test_unrelated_events_are_not_recognized() {
+        for event in [
+            Event::new("transfer").add_attributes(revoke()),
+            Event::new("wasm").add_attribute("action", "revoke"),
+        ] {
+            assert!(
+                !event.is_os_gateway_event(),
+                "[{}] should not be recognized as a gateway event",
+                event.ty,
+            );
+            assert_eq!(
+                None,
+                event.os_gateway_event(),
+                "[{}] should produce no gateway event",
+                event.ty,
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_gateway_events_are_recognized_with_their_error() {
+        let event =
+            Event::new("wasm").add_attribute("object_store_gateway_event_type", "access_revoke");
+        assert!(
+            event.is_os_gateway_event(),
+            "an incomplete gateway event should still be recognized",
+        );
+        assert!(
+            matches!(
+                event.os_gateway_event(),
+                Some(Err(OsGatewayError::MissingRequiredAttributes { .. })),
+            ),
+            "an incomplete gateway event should produce its error",
+        );
+    }
+
+    #[test]
+    fn test_only_the_first_gateway_event_is_produced() {
+        let event = Event::new("wasm")
+            .add_attributes(OsGatewayAttributeGenerator::access_grant(
+                SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS,
+            ))
+            .add_attributes(revoke());
+        assert!(
+            matches!(
+                event.os_gateway_event(),
+                Some(Ok(OsGatewayEvent::AccessGrant(_))),
+            ),
+            "the first gateway event in the event should be produced",
+        );
+    }
+}
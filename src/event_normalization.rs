@@ -0,0 +1,195 @@
+use cosmwasm_std::{Attribute, Event};
+
+/// The type of the event under which the chain places all flat attributes appended to a contract's
+/// [Cosmwasm Response](cosmwasm_std::Response).
+const WASM_EVENT_TYPE: &str = "wasm";
+
+/// The key under which the chain records the address of the contract that emitted a `wasm` event.
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+/// The type under which legacy versions of wasmd placed a contract's flat attributes, before the
+/// `wasm` event was introduced.
+const LEGACY_EVENT_TYPE: &str = "from_contract";
+
+/// The key under which legacy versions of wasmd recorded the address of the contract that emitted
+/// a `from_contract` event.
+const LEGACY_CONTRACT_ADDRESS_KEY: &str = "contract_address";
+
+/// Flattens the known nesting patterns of contract attributes into plain `wasm` events, each
+/// holding the attributes of a single contract execution and keyed by its `_contract_address`.
+/// Every other event is produced unchanged, in its original position.
+///
+/// The recognized patterns are:
+///
+/// * __Merged log events__: The per-message logs of a transaction merge every event of the same
+///   type, so a single `wasm` event carries the attributes of every contract executed by the
+///   message.  This is always the case for the contracts executed by an authz `MsgExec`, which
+///   wraps every nested `MsgExecuteContract` in one message.  Each `_contract_address` attribute
+///   begins a new event.
+/// * __Legacy events__: Older versions of wasmd placed a contract's attributes in a
+///   `from_contract` event, keyed by `contract_address`.  These are converted to `wasm` events
+///   keyed by `_contract_address`, and split like merged log events.
+///
+/// [parse_gateway_events](crate::parse_gateway_events) and [parse_gateway_events_ref](crate::parse_gateway_events_ref)
+/// apply this normalization to every event that they parse, so normalizing beforehand is only
+/// needed when handing events to other tools.
+///
+/// # Parameters
+///
+/// * `events` The events to normalize, typically from a transaction result or log.
+pub fn normalize_wasm_events(events: &[Event]) -> Vec<Event> {
+    events
+        .iter()
+        .flat_map(|event| normalize_wasm_event(event).unwrap_or_else(|| vec![event.to_owned()]))
+        .collect()
+}
+
+/// Normalizes a single event as described by [normalize_wasm_events](crate::normalize_wasm_events),
+/// producing `None` if the event is already in its normal form.
+pub(crate) fn normalize_wasm_event(event: &Event) -> Option<Vec<Event>> {
+    let groups = contract_attribute_groups(event)?;
+    if event.ty == WASM_EVENT_TYPE && groups.len() <= 1 {
+        return None;
+    }
+    Some(
+        groups
+            .into_iter()
+            .map(|attributes| {
+                let mut normalized = Event::new(WASM_EVENT_TYPE);
+                // Constructed directly, as chain-added keys like _contract_address are reserved
+                normalized.attributes = attributes
+                    .iter()
+                    .map(|attribute| Attribute {
+                        key: if attribute.key == LEGACY_CONTRACT_ADDRESS_KEY
+                            && event.ty == LEGACY_EVENT_TYPE
+                        {
+                            CONTRACT_ADDRESS_KEY.to_string()
+                        } else {
+                            attribute.key.to_owned()
+                        },
+                        value: attribute.value.to_owned(),
+                    })
+                    .collect();
+                normalized
+            })
+            .collect(),
+    )
+}
+
+/// Splits the attributes of a `wasm` or legacy `from_contract` event into those of each contract
+/// execution, without copying them.  Each contract address attribute after the first begins a new
+/// group, so attributes ahead of the first address stay with the first contract.  Produces `None`
+/// for events of every other type.
+pub(crate) fn contract_attribute_groups(event: &Event) -> Option<Vec<&[Attribute]>> {
+    let contract_address_key = match event.ty.as_str() {
+        WASM_EVENT_TYPE => CONTRACT_ADDRESS_KEY,
+        LEGACY_EVENT_TYPE => LEGACY_CONTRACT_ADDRESS_KEY,
+        _ => return None,
+    };
+    let mut groups = vec![];
+    let mut group_start = 0;
+    let mut has_contract_address = false;
+    for (index, attribute) in event.attributes.iter().enumerate() {
+        if attribute.key == contract_address_key {
+            if has_contract_address {
+                groups.push(&event.attributes[group_start..index]);
+                group_start = index;
+            }
+            has_contract_address = true;
+        }
+    }
+    groups.push(&event.attributes[group_start..]);
+    Some(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        normalize_wasm_events, parse_gateway_events, parse_gateway_events_ref, AccessGrant,
+        OsGatewayAttributeGenerator, OsGatewayEvent,
+    };
+    use cosmwasm_std::{Attribute, Event};
+
+    fn contract_address(value: &str) -> Attribute {
+        Attribute {
+            key: "_contract_address".to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merged_events_are_split_by_contract() {
+        let merged = Event::new("wasm")
+            .add_attributes([contract_address("first")])
+            .add_attribute("action", "share")
+            .add_attributes([contract_address("second")])
+            .add_attribute("action", "share")
+            .add_attribute("authz_msg_index", "0");
+        assert_eq!(
+            vec![
+                Event::new("transfer").add_attribute("amount", "1nhash"),
+                Event::new("wasm")
+                    .add_attributes([contract_address("first")])
+                    .add_attribute("action", "share"),
+                Event::new("wasm")
+                    .add_attributes([contract_address("second")])
+                    .add_attribute("action", "share")
+                    .add_attribute("authz_msg_index", "0"),
+            ],
+            normalize_wasm_events(&[
+                Event::new("transfer").add_attribute("amount", "1nhash"),
+                merged,
+            ]),
+            "the merged event should be split at each contract address",
+        );
+    }
+
+    #[test]
+    fn test_legacy_events_are_converted() {
+        let legacy = Event::new("from_contract")
+            .add_attribute("contract_address", "contract")
+            .add_attributes(OsGatewayAttributeGenerator::access_grant("scope", "target"));
+        assert_eq!(
+            vec![Event::new("wasm")
+                .add_attributes([contract_address("contract")])
+                .add_attributes(OsGatewayAttributeGenerator::access_grant("scope", "target"))],
+            normalize_wasm_events(std::slice::from_ref(&legacy)),
+            "the legacy event should become a wasm event",
+        );
+        assert_eq!(
+            vec![Ok(OsGatewayEvent::AccessGrant(AccessGrant::new(
+                "scope", "target"
+            )))],
+            parse_gateway_events(std::slice::from_ref(&legacy)),
+            "the parser should recognize the legacy event",
+        );
+        assert_eq!(
+            vec![Ok(OsGatewayEvent::AccessGrant(AccessGrant::new(
+                "scope", "target"
+            )))],
+            parse_gateway_events_ref(&[legacy])
+                .into_iter()
+                .map(|result| result.map(|event| event.to_owned()))
+                .collect::<Vec<_>>(),
+            "the borrowed parser should recognize the legacy event",
+        );
+    }
+
+    #[test]
+    fn test_normal_events_are_unchanged() {
+        let events = vec![
+            Event::new("wasm")
+                .add_attributes([contract_address("contract")])
+                .add_attribute("action", "share"),
+            Event::new("wasm").add_attribute("action", "unattributed"),
+            Event::new("wasm-object_store_gateway")
+                .add_attributes([contract_address("contract")])
+                .add_attributes([contract_address("other")]),
+        ];
+        assert_eq!(
+            events,
+            normalize_wasm_events(&events),
+            "events without nesting should not be changed",
+        );
+    }
+}
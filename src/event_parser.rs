@@ -0,0 +1,466 @@
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::event_normalization::{contract_attribute_groups, normalize_wasm_event};
+use crate::{
+    OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, OsGatewayEventRef,
+    OS_GATEWAY_CUSTOM_EVENT_TYPE, OS_GATEWAY_KEYS, OS_GATEWAY_PAYLOAD_KEY,
+};
+use cosmwasm_std::{Attribute, Event, Reply, SubMsgResult};
+
+/// The type of the event under which the chain places all flat attributes appended to a contract's
+/// [Cosmwasm Response](cosmwasm_std::Response).
+const WASM_EVENT_TYPE: &str = "wasm";
+
+/// The prefix that the chain adds to the type of every custom event emitted by a contract.
+const WASM_EVENT_TYPE_PREFIX: &str = "wasm-";
+
+/// Parses every gateway event contained in the given [Cosmwasm Events](cosmwasm_std::Event) into an
+/// [OsGatewayEvent](crate::OsGatewayEvent), in the order in which they were emitted.
+///
+/// Both emission forms are recognized:
+///
+/// * __Flat attributes__: Attributes appended directly to a response, which the chain places in an
+///   event of type `wasm` alongside every other attribute the contract emits.
+/// * __Custom events__: Events produced by [to_event](crate::OsGatewayAttributeGenerator::to_event),
+///   of type `object_store_gateway` or `wasm-object_store_gateway`.
+///
+/// Within either form, each [compact payload](crate::OS_GATEWAY_PAYLOAD_KEY) attribute is decoded
+/// as a complete gateway event of its own, producing an [OsGatewayError::InvalidCompactPayload](crate::OsGatewayError::InvalidCompactPayload)
+/// in its place if it cannot be decoded.
+///
+/// Events are first [normalized](crate::normalize_wasm_events), so contract attributes are also
+/// recognized in merged log events, such as those of an authz `MsgExec`, and in the legacy
+/// `from_contract` events of older versions of wasmd.  All events of other types are skipped, as
+/// are attributes within a `wasm` event that are not prefixed with `object_store_gateway_`.  When a
+/// single event contains multiple gateway events, a new gateway event begins at each event type
+/// attribute, or at any attribute key that repeats within the current gateway event, and keys that
+/// an alphabetically sorted emitter places ahead of the event type are kept with the gateway event
+/// that follows them.  Unrecognized `object_store_gateway_` attributes are retained as the extra
+/// attributes of the typed event, but each gateway event must contain the event type, scope
+/// address, and target account, or an
+/// [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+/// is produced in its place.  Gateway events with an unrecognized event type produce an
+/// [OsGatewayError::UnknownEventType](crate::OsGatewayError::UnknownEventType).
+///
+/// # Parameters
+///
+/// * `events` The events from which to parse gateway events, typically from a transaction result
+///   or submessage reply.
+pub fn parse_gateway_events(events: &[Event]) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    events.iter().flat_map(parse_gateway_event).collect()
+}
+
+/// Parses every gateway event contained in a single [Cosmwasm Event](cosmwasm_std::Event) into an
+/// [OsGatewayEvent](crate::OsGatewayEvent), in the order in which they were emitted.  See
+/// [parse_gateway_events](crate::parse_gateway_events) for the recognized forms.  Produces no
+/// results for events that are not gateway-relevant.
+///
+/// # Parameters
+///
+/// * `event` The event from which to parse gateway events.
+pub fn parse_gateway_event(event: &Event) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    if let Some(events) = normalize_wasm_event(event) {
+        return events.iter().flat_map(parse_normalized_event).collect();
+    }
+    parse_normalized_event(event)
+}
+
+fn parse_normalized_event(event: &Event) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    if !is_gateway_event_type(&event.ty) {
+        return vec![];
+    }
+    split_gateway_attributes(&event.attributes)
+        .into_iter()
+        .map(|segment| {
+            #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+            let span = crate::instrumentation::parse_span().entered();
+            let generator = match segment {
+                Segment::Attributes(attributes) => {
+                    OsGatewayAttributeGenerator::from_attributes_lenient(
+                        attributes
+                            .into_iter()
+                            .map(|(key, value)| (key.to_owned(), value.to_owned())),
+                    )
+                }
+                Segment::CompactPayload(payload) => {
+                    OsGatewayAttributeGenerator::from_compact_payload(payload)
+                }
+            };
+            #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+            if let Ok(generator) = &generator {
+                crate::instrumentation::record_parsed(&span, generator);
+            }
+            let result = generator.and_then(OsGatewayEvent::try_from);
+            #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+            crate::instrumentation::record_outcome(&result);
+            result
+        })
+        .collect()
+}
+
+/// Parses every gateway event emitted by a submessage, from the [Reply](cosmwasm_std::Reply) that
+/// a contract receives once the submessage completes, in the order in which they were emitted.
+/// Events are recognized exactly as by [parse_gateway_events](crate::parse_gateway_events).  A
+/// failed submessage emits no events, so produces no results.
+///
+/// # Parameters
+///
+/// * `reply` The reply received by the contract's `reply` entry point.
+pub fn parse_gateway_events_from_reply(
+    reply: &Reply,
+) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    match &reply.result {
+        SubMsgResult::Ok(response) => parse_gateway_events(&response.events),
+        SubMsgResult::Err(_) => vec![],
+    }
+}
+
+/// Parses every gateway event contained in the given [Cosmwasm Events](cosmwasm_std::Event) into a
+/// borrowed [OsGatewayEventRef](crate::OsGatewayEventRef), without copying any attribute values.
+/// Events are recognized, split, and validated exactly as by
+/// [parse_gateway_events](crate::parse_gateway_events), except that
+/// [compact payloads](crate::OS_GATEWAY_PAYLOAD_KEY) must be decoded into owned values, so each
+/// produces an [OsGatewayError::InvalidCompactPayload](crate::OsGatewayError::InvalidCompactPayload)
+/// in its place.  Events are [normalized](crate::normalize_wasm_events) in place, by splitting
+/// their attributes at each contract address, so the results always match those of the owned
+/// parser.
+///
+/// # Parameters
+///
+/// * `events` The events from which to parse gateway events, which must outlive the results.
+pub fn parse_gateway_events_ref(
+    events: &[Event],
+) -> Vec<Result<OsGatewayEventRef<'_>, OsGatewayError>> {
+    events.iter().flat_map(parse_gateway_event_ref).collect()
+}
+
+/// Parses every gateway event contained in a single [Cosmwasm Event](cosmwasm_std::Event) into a
+/// borrowed [OsGatewayEventRef](crate::OsGatewayEventRef), without copying any attribute values.
+/// See [parse_gateway_events_ref](crate::parse_gateway_events_ref).
+///
+/// # Parameters
+///
+/// * `event` The event from which to parse gateway events, which must outlive the results.
+pub fn parse_gateway_event_ref(
+    event: &Event,
+) -> Vec<Result<OsGatewayEventRef<'_>, OsGatewayError>> {
+    let groups = match contract_attribute_groups(event) {
+        Some(groups) => groups,
+        None if is_gateway_event_type(&event.ty) => vec![event.attributes.as_slice()],
+        None => return vec![],
+    };
+    groups
+        .into_iter()
+        .flat_map(split_gateway_attributes)
+        .map(|segment| match segment {
+            Segment::Attributes(attributes) => OsGatewayEventRef::from_pairs(attributes),
+            Segment::CompactPayload(payload) => Err(OsGatewayError::InvalidCompactPayload {
+                payload: payload.to_string(),
+                reason: "compact payloads can only be parsed into owned events".to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// Produces the event type under which the chain will emit a custom event that a contract created
+/// with the given type, by adding the `wasm-` prefix.
+///
+/// # Parameters
+///
+/// * `base` The unprefixed event type, as provided to [Event::new](cosmwasm_std::Event::new).
+pub fn wasm_event_type(base: &str) -> String {
+    format!("{WASM_EVENT_TYPE_PREFIX}{base}")
+}
+
+/// Removes the `wasm-` prefix that the chain adds to custom contract event types, producing the
+/// type that the contract originally provided.  Produces `None` if the type is not prefixed.
+///
+/// # Parameters
+///
+/// * `ty` The event type, as observed on chain.
+pub fn strip_wasm_prefix(ty: &str) -> Option<&str> {
+    ty.strip_prefix(WASM_EVENT_TYPE_PREFIX)
+}
+
+fn is_gateway_event_type(ty: &str) -> bool {
+    ty == WASM_EVENT_TYPE || strip_wasm_prefix(ty).unwrap_or(ty) == OS_GATEWAY_CUSTOM_EVENT_TYPE
+}
+
+/// The attributes that describe a single gateway event within an emitted event.
+enum Segment<'a> {
+    /// Flat attribute key and value pairs.
+    Attributes(Vec<(&'a str, &'a str)>),
+    /// The value of a single compact payload attribute.
+    CompactPayload(&'a str),
+}
+
+/// Splits the gateway attributes of an event into the attributes of each gateway event.  A new
+/// gateway event begins at each event type attribute, unless the current one has yet to receive
+/// its event type, and at any key that repeats within the current gateway event.  Emitters that
+/// sort keys alphabetically, such as the first releases of this crate, place keys like the access
+/// grant id ahead of the event type, so once a gateway event has shown that order, any such key
+/// arriving after its event type begins the next gateway event instead.
+fn split_gateway_attributes(attributes: &[Attribute]) -> Vec<Segment<'_>> {
+    let mut segments: Vec<Segment> = vec![];
+    for attribute in attributes
+        .iter()
+        .filter(|attribute| attribute.key.starts_with(OS_GATEWAY_KEY_PREFIX))
+    {
+        if attribute.key == OS_GATEWAY_PAYLOAD_KEY {
+            segments.push(Segment::CompactPayload(&attribute.value));
+            continue;
+        }
+        let starts_new_segment = match segments.last() {
+            Some(Segment::Attributes(segment)) => {
+                let event_type_position = segment
+                    .iter()
+                    .position(|(key, _)| *key == OS_GATEWAY_KEYS.event_type);
+                let sorted_keys = event_type_position.is_some_and(|position| position > 0);
+                segment.iter().any(|(key, _)| *key == attribute.key)
+                    || (attribute.key == OS_GATEWAY_KEYS.event_type
+                        && event_type_position.is_some())
+                    || (sorted_keys && attribute.key.as_str() < OS_GATEWAY_KEYS.event_type)
+            }
+            Some(Segment::CompactPayload(_)) | None => true,
+        };
+        if starts_new_segment {
+            segments.push(Segment::Attributes(vec![]));
+        }
+        if let Some(Segment::Attributes(segment)) = segments.last_mut() {
+            segment.push((&attribute.key, &attribute.value));
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosmwasm_compat::{reply, sub_msg_response};
+    use crate::{
+        parse_gateway_event, parse_gateway_event_ref, parse_gateway_events,
+        parse_gateway_events_from_reply, strip_wasm_prefix, wasm_event_type, AccessGrant,
+        AccessLevel, AccessRevoke, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+        OS_GATEWAY_CUSTOM_EVENT_TYPE, OS_GATEWAY_KEYS,
+    };
+    use cosmwasm_std::{Event, SubMsgResult};
+
+    #[test]
+    fn test_parse_flat_attributes_from_wasm_event() {
+        let grant =
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id");
+        let revoke = OsGatewayAttributeGenerator::access_revoke("scope", "other_target");
+        let event = Event::new("wasm")
+            .add_attribute("_contract_address", "contract")
+            .add_attribute("action", "share")
+            .add_attributes(grant)
+            .add_attributes(revoke);
+        assert_eq!(
+            vec![
+                Ok(OsGatewayEvent::AccessGrant(
+                    AccessGrant::new("scope", "target").with_access_grant_id("id"),
+                )),
+                Ok(OsGatewayEvent::AccessRevoke(AccessRevoke::new(
+                    "scope",
+                    "other_target",
+                ))),
+            ],
+            parse_gateway_event(&event),
+            "every gateway event should be parsed from the flat attributes, in order",
+        );
+    }
+
+    #[test]
+    fn test_parse_flat_attributes_in_alphabetical_order() {
+        // The first releases of this crate emitted keys alphabetically, which places the access
+        // grant id and access level ahead of the event type
+        let sorted = |generator: OsGatewayAttributeGenerator| {
+            let mut attributes = generator.into_iter().collect::<Vec<(String, String)>>();
+            attributes.sort();
+            attributes
+        };
+        let event = Event::new("wasm")
+            .add_attributes(sorted(
+                OsGatewayAttributeGenerator::access_grant("scope", "first_target")
+                    .with_access_grant_id("first_id"),
+            ))
+            .add_attributes(sorted(
+                OsGatewayAttributeGenerator::access_grant("scope", "second_target")
+                    .with_access_grant_id("second_id"),
+            ));
+        assert_eq!(
+            vec![
+                Ok(OsGatewayEvent::AccessGrant(
+                    AccessGrant::new("scope", "first_target").with_access_grant_id("first_id"),
+                )),
+                Ok(OsGatewayEvent::AccessGrant(
+                    AccessGrant::new("scope", "second_target").with_access_grant_id("second_id"),
+                )),
+            ],
+            parse_gateway_event(&event),
+            "each grant should keep its own access grant id",
+        );
+        let event = Event::new("wasm")
+            .add_attributes(sorted(
+                OsGatewayAttributeGenerator::grant("scope", "first_target")
+                    .with_access_level(AccessLevel::Read)
+                    .build(),
+            ))
+            .add_attributes(sorted(
+                OsGatewayAttributeGenerator::access_grant("scope", "second_target")
+                    .with_access_grant_id("second_id"),
+            ));
+        assert_eq!(
+            vec![
+                Ok(OsGatewayEvent::AccessGrant(AccessGrant {
+                    access_level: Some(AccessLevel::Read),
+                    ..AccessGrant::new("scope", "first_target")
+                })),
+                Ok(OsGatewayEvent::AccessGrant(
+                    AccessGrant::new("scope", "second_target").with_access_grant_id("second_id"),
+                )),
+            ],
+            parse_gateway_event(&event),
+            "an access grant id following a sorted grant should begin the next grant",
+        );
+        assert_eq!(
+            parse_gateway_event(&event),
+            parse_gateway_event_ref(&event)
+                .into_iter()
+                .map(|result| result.map(|event| event.to_owned()))
+                .collect::<Vec<_>>(),
+            "the borrowed parser should split the attributes identically",
+        );
+    }
+
+    #[test]
+    fn test_parse_custom_events() {
+        let grant = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        let unprefixed_event = grant.to_event();
+        let prefixed_event = Event::new("wasm-object_store_gateway")
+            .add_attribute("_contract_address", "contract")
+            .add_attributes(grant);
+        let expected = OsGatewayEvent::AccessGrant(AccessGrant::new("scope", "target"));
+        assert_eq!(
+            vec![Ok(expected.clone()), Ok(expected)],
+            parse_gateway_events(&[unprefixed_event, prefixed_event]),
+            "the custom event should be parsed with and without the wasm prefix",
+        );
+    }
+
+    #[test]
+    fn test_wasm_prefix_helpers() {
+        assert_eq!(
+            "wasm-object_store_gateway",
+            wasm_event_type(OS_GATEWAY_CUSTOM_EVENT_TYPE),
+            "the wasm prefix should be added to the base type",
+        );
+        assert_eq!(
+            Some(OS_GATEWAY_CUSTOM_EVENT_TYPE),
+            strip_wasm_prefix(&wasm_event_type(OS_GATEWAY_CUSTOM_EVENT_TYPE)),
+            "stripping the prefix should produce the original base type",
+        );
+        assert_eq!(
+            None,
+            strip_wasm_prefix(OS_GATEWAY_CUSTOM_EVENT_TYPE),
+            "stripping an unprefixed type should produce nothing",
+        );
+        assert_eq!(
+            Some(""),
+            strip_wasm_prefix("wasm-"),
+            "stripping a bare prefix should produce an empty type",
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_unrelated_wasm_events() {
+        let grant = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        let events = vec![
+            Event::new(wasm_event_type("other_event")).add_attributes(grant.clone()),
+            Event::new("wasm-object_store_gateway_v2").add_attributes(grant.clone()),
+            Event::new(wasm_event_type(OS_GATEWAY_CUSTOM_EVENT_TYPE)).add_attributes(grant.clone()),
+            Event::new(OS_GATEWAY_CUSTOM_EVENT_TYPE).add_attributes(grant),
+        ];
+        let expected = OsGatewayEvent::AccessGrant(AccessGrant::new("scope", "target"));
+        assert_eq!(
+            vec![Ok(expected.clone()), Ok(expected)],
+            parse_gateway_events(&events),
+            "only the prefixed and unprefixed gateway custom events should be parsed",
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_unrelated_events() {
+        let events = vec![
+            Event::new("message").add_attribute("action", "execute"),
+            Event::new("wasm").add_attribute("action", "no_gateway_attributes"),
+            Event::new("transfer")
+                .add_attributes(OsGatewayAttributeGenerator::access_grant("scope", "target")),
+        ];
+        assert!(
+            parse_gateway_events(&events).is_empty(),
+            "events without gateway attributes, or of unrelated types, should be skipped",
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_incomplete_events() {
+        let event = Event::new("wasm").add_attribute(OS_GATEWAY_KEYS.scope_address, "scope");
+        assert_eq!(
+            vec![Err(OsGatewayError::MissingRequiredAttributes {
+                keys: vec![
+                    OS_GATEWAY_KEYS.event_type.to_string(),
+                    OS_GATEWAY_KEYS.target_account.to_string(),
+                ],
+            })],
+            parse_gateway_event(&event),
+            "an incomplete gateway event should produce an error naming the missing keys",
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_unknown_event_types() {
+        let event = Event::new("wasm").add_attributes(
+            OsGatewayAttributeGenerator::access_grant("scope", "target")
+                .with_event_type("access_audit"),
+        );
+        assert_eq!(
+            vec![Err(OsGatewayError::UnknownEventType {
+                event_type: "access_audit".to_string(),
+            })],
+            parse_gateway_event(&event),
+            "a gateway event with an unrecognized event type should produce an error",
+        );
+    }
+
+    #[test]
+    fn test_parse_fixture_corpus() {
+        for case in crate::fixtures::all() {
+            assert_eq!(
+                vec![case.expected.clone()],
+                parse_gateway_event(&case.to_event()),
+                "[{}] should parse to its expected result",
+                case.description,
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_events_from_replies() {
+        let grant = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        assert_eq!(
+            vec![Ok(OsGatewayEvent::try_from(&grant).unwrap())],
+            parse_gateway_events_from_reply(&reply(
+                1,
+                SubMsgResult::Ok(sub_msg_response(vec![
+                    Event::new("execute"),
+                    Event::new("wasm").add_attributes(grant),
+                ]))
+            )),
+            "the events of a successful submessage should be parsed",
+        );
+        assert!(
+            parse_gateway_events_from_reply(&reply(1, SubMsgResult::Err("failed".to_string())))
+                .is_empty(),
+            "a failed submessage should produce no events",
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use crate::attribute_keys::REQUIRED_KEYS;
+use crate::keys_manifest::{event_type_purpose, key_event_types, key_purpose};
+use crate::schema::{source_contract_version_schema, target_kind_schema};
+use crate::{
+    AccessGrantId, AccessLevel, AccountAddress, GrantExpiration, OsGatewayEventType, OsGatewayKey,
+    OsGatewayPayloadVersion, ScopeAddress, OS_GATEWAY_CUSTOM_EVENT_TYPE,
+};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use serde_json::{json, Value};
+
+/// The key under which [gateway_events_schema](crate::gateway_events_schema) is merged into the
+/// API document that `cosmwasm-schema` generates for a contract.  Prefixed with `x-` so that
+/// tooling that does not recognize it ignores it.
+pub const OS_GATEWAY_EVENTS_SCHEMA_KEY: &str = "x-events";
+
+/// Produces a structured description of the events that a contract emits for [Object Store Gateway](https://github.com/provenance-io/object-store-gateway),
+/// for merging into the contract's generated API document under [OS_GATEWAY_EVENTS_SCHEMA_KEY](crate::OS_GATEWAY_EVENTS_SCHEMA_KEY).
+/// The description is generated from the same constants as [keys_manifest](crate::keys_manifest),
+/// so the two can never drift apart.
+///
+/// The document has the following fields:
+///
+/// * `payload_version` The [payload version](crate::OsGatewayPayloadVersion) of the attribute
+///   layout described.
+/// * `emitted_as` The types of the events that carry the attributes: `wasm` when appended to a
+///   response's attributes, or `wasm-object_store_gateway` when emitted as a custom event.
+/// * `events` Every event type, as an object containing its exact `event_type` value, its
+///   `description`, and its `attributes`.  Each attribute contains its exact `key`, its field
+///   `name` in [OsGatewayKeys](crate::OsGatewayKeys), its `description`, whether or not it is
+///   `required`, and a JSON `schema` of the format of its value.
+///
+/// ```
+/// use os_gateway_contract_attributes::{gateway_events_schema, OS_GATEWAY_EVENTS_SCHEMA_KEY};
+///
+/// let mut api = serde_json::json!({ "contract_name": "my-contract" });
+/// api[OS_GATEWAY_EVENTS_SCHEMA_KEY] = gateway_events_schema();
+/// assert_eq!("access_grant", api["x-events"]["events"][0]["event_type"]);
+/// ```
+///
+/// A contract's `schema` binary merges the document into the file written by `write_api!`:
+///
+/// ```ignore
+/// use cosmwasm_schema::write_api;
+/// use os_gateway_contract_attributes::{gateway_events_schema, OS_GATEWAY_EVENTS_SCHEMA_KEY};
+///
+/// fn main() {
+///     write_api! {
+///         instantiate: InstantiateMsg,
+///         execute: ExecuteMsg,
+///         query: QueryMsg,
+///     }
+///     let path = format!("schema/{}.json", env!("CARGO_PKG_NAME"));
+///     let mut api: serde_json::Value =
+///         serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+///     api[OS_GATEWAY_EVENTS_SCHEMA_KEY] = gateway_events_schema();
+///     std::fs::write(&path, serde_json::to_vec_pretty(&api).unwrap()).unwrap();
+/// }
+/// ```
+pub fn gateway_events_schema() -> Value {
+    // Inline every subschema so that each attribute's schema stands alone, without definitions
+    let mut gen = SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    let events = OsGatewayEventType::ALL
+        .into_iter()
+        .map(|event_type| {
+            let attributes = OsGatewayKey::ALL
+                .into_iter()
+                .filter(|key| key_event_types(*key).contains(&event_type))
+                .map(|key| {
+                    json!({
+                        "key": key.as_str(),
+                        "name": key.field_name(),
+                        "description": key_purpose(key),
+                        "required": REQUIRED_KEYS.contains(&key.as_str()),
+                        "schema": attribute_schema(&mut gen, key, event_type),
+                    })
+                })
+                .collect::<Vec<Value>>();
+            json!({
+                "event_type": event_type.as_str(),
+                "description": event_type_purpose(event_type),
+                "attributes": attributes,
+            })
+        })
+        .collect::<Vec<Value>>();
+    json!({
+        "payload_version": OsGatewayPayloadVersion::CURRENT,
+        "emitted_as": ["wasm", format!("wasm-{OS_GATEWAY_CUSTOM_EVENT_TYPE}")],
+        "events": events,
+    })
+}
+
+/// Produces the schema of the key's value within an event of the given type.  The event type
+/// attribute is constrained to the exact value of that type.
+fn attribute_schema(
+    gen: &mut SchemaGenerator,
+    key: OsGatewayKey,
+    event_type: OsGatewayEventType,
+) -> Value {
+    let schema = match key {
+        OsGatewayKey::EventType => Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            const_value: Some(Value::from(event_type.as_str())),
+            ..Default::default()
+        }),
+        OsGatewayKey::ScopeAddress => gen.subschema_for::<ScopeAddress>(),
+        OsGatewayKey::TargetAccount => gen.subschema_for::<AccountAddress>(),
+        OsGatewayKey::AccessGrantId => gen.subschema_for::<AccessGrantId>(),
+        OsGatewayKey::Expiration => gen.subschema_for::<GrantExpiration>(),
+        OsGatewayKey::AccessLevel => gen.subschema_for::<AccessLevel>(),
+        OsGatewayKey::PayloadVersion => gen.subschema_for::<OsGatewayPayloadVersion>(),
+        OsGatewayKey::SourceContractVersion => source_contract_version_schema(),
+        OsGatewayKey::TargetKind => target_kind_schema(),
+    };
+    // Serializing a schema cannot fail, as it contains only strings, numbers, and objects
+    serde_json::to_value(schema).expect("the attribute schema should always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gateway_events_schema, keys_manifest, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS};
+    use serde_json::Value;
+
+    #[test]
+    fn test_events_schema_matches_keys_manifest() {
+        let schema = gateway_events_schema();
+        let manifest = serde_json::from_str::<Value>(&keys_manifest()).unwrap();
+        assert_eq!(
+            manifest["payload_version"], schema["payload_version"],
+            "the payload versions should match",
+        );
+        let events = schema["events"].as_array().unwrap();
+        let event_types = manifest["event_types"].as_array().unwrap();
+        assert_eq!(
+            event_types.len(),
+            events.len(),
+            "every event type in the manifest should be described",
+        );
+        for (event, event_type) in events.iter().zip(event_types) {
+            assert_eq!(
+                event_type["value"], event["event_type"],
+                "the event types should be described in manifest order",
+            );
+            assert_eq!(
+                event_type["purpose"], event["description"],
+                "the event type [{}] should share the manifest's purpose",
+                event["event_type"],
+            );
+            let expected_keys = manifest["keys"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|key| {
+                    key["event_types"]
+                        .as_array()
+                        .unwrap()
+                        .contains(&event["event_type"])
+                })
+                .collect::<Vec<&Value>>();
+            let attributes = event["attributes"].as_array().unwrap();
+            assert_eq!(
+                expected_keys.len(),
+                attributes.len(),
+                "the event type [{}] should describe exactly the keys that apply to it",
+                event["event_type"],
+            );
+            for (key, attribute) in expected_keys.into_iter().zip(attributes) {
+                for (manifest_field, schema_field) in [
+                    ("key", "key"),
+                    ("name", "name"),
+                    ("purpose", "description"),
+                    ("required", "required"),
+                ] {
+                    assert_eq!(
+                        key[manifest_field], attribute[schema_field],
+                        "the attribute [{}] should share the manifest's [{manifest_field}]",
+                        key["key"],
+                    );
+                }
+                assert!(
+                    attribute["schema"].is_object(),
+                    "the attribute [{}] should describe the format of its value",
+                    key["key"],
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_attribute_schemas_describe_value_formats() {
+        let schema = gateway_events_schema();
+        let attribute = |event_index: usize, key: &str| -> Value {
+            schema["events"][event_index]["attributes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|attribute| attribute["key"] == key)
+                .unwrap_or_else(|| panic!("expected the attribute [{key}]"))["schema"]
+                .clone()
+        };
+        assert_eq!(
+            OS_GATEWAY_EVENT_TYPES.access_revoke,
+            attribute(1, OS_GATEWAY_KEYS.event_type)["const"],
+            "the event type attribute should be constrained to the event's own type",
+        );
+        assert_eq!(
+            "^[A-Za-z0-9._:-]+$",
+            attribute(0, OS_GATEWAY_KEYS.access_grant_id)["pattern"],
+            "the access grant id should carry its pattern inline",
+        );
+        assert!(
+            attribute(0, OS_GATEWAY_KEYS.access_level)["enum"]
+                .as_array()
+                .is_some_and(|values| values.contains(&Value::from("read_write"))),
+            "the access level should list its values inline",
+        );
+        assert_eq!(
+            vec!["wasm", "wasm-object_store_gateway"],
+            schema["emitted_as"].as_array().unwrap().to_vec(),
+            "both of the event types that carry the attributes should be listed",
+        );
+        assert!(
+            !schema.to_string().contains("$ref"),
+            "every subschema should be inlined",
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use crate::{
+    AccessLevel, GrantExpiration, OsGatewayAttributeGenerator, OsGatewayEvent, OsGatewayEventType,
+    OS_GATEWAY_KEYS,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// The value that an [ExpectedGatewayEvent](crate::ExpectedGatewayEvent) expects an attribute to
+/// hold.
+///
+/// # Variants
+///
+/// * `Value` The attribute must hold exactly this value.
+///
+/// * `Any` The attribute must be present, but may hold any value.
+///
+/// * `Absent` The attribute must not be present.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ExpectedValue {
+    Value(String),
+    Any,
+    Absent,
+}
+impl ExpectedValue {
+    pub(crate) fn accepts(&self, actual: Option<&str>) -> bool {
+        match (self, actual) {
+            (Self::Value(expected), Some(actual)) => expected == actual,
+            (Self::Any, Some(_)) => true,
+            (Self::Absent, None) => true,
+            _ => false,
+        }
+    }
+}
+impl Display for ExpectedValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value(value) => f.write_str(value),
+            Self::Any => f.write_str("<any value>"),
+            Self::Absent => f.write_str("<absent>"),
+        }
+    }
+}
+
+/// A single attribute of an event that did not hold its expected value.
+///
+/// # Parameters
+///
+/// * `key` The full key of the attribute.
+///
+/// * `expected` The value that was expected.
+///
+/// * `actual` The value that the event held, or `None` if the event did not hold the attribute.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldMismatch {
+    pub key: String,
+    pub expected: ExpectedValue,
+    pub actual: Option<String>,
+}
+impl Display for FieldMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "- {}: {}", self.key, self.expected)?;
+        match &self.actual {
+            Some(actual) => write!(f, "+ {}: {actual}", self.key),
+            None => write!(f, "+ {}: {}", self.key, ExpectedValue::Absent),
+        }
+    }
+}
+
+/// The outcome of comparing an event against an [ExpectedGatewayEvent](crate::ExpectedGatewayEvent).
+///
+/// # Variants
+///
+/// * `Matched` Every expected attribute held its expected value.
+///
+/// * `Mismatched` At least one attribute did not hold its expected value.  Every such attribute is
+///   included, sorted by key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MatchResult {
+    Matched,
+    Mismatched(Vec<FieldMismatch>),
+}
+impl MatchResult {
+    /// Whether or not the event matched.
+    pub fn is_match(&self) -> bool {
+        matches!(self, Self::Matched)
+    }
+}
+
+/// Produced by [ExpectedGatewayEvent::assert_in](crate::ExpectedGatewayEvent::assert_in) when no
+/// event matched.  Its [Display] form is a readable diff between the expectation and every event
+/// that was searched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnmatchedExpectation {
+    event_type: OsGatewayEventType,
+    mismatches: Vec<Vec<FieldMismatch>>,
+}
+impl UnmatchedExpectation {
+    /// The mismatched attributes of every searched event, in the order the events were provided.
+    pub fn mismatches(&self) -> &[Vec<FieldMismatch>] {
+        &self.mismatches
+    }
+}
+impl Display for UnmatchedExpectation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.mismatches.is_empty() {
+            return write!(
+                f,
+                "expected an {} event, but no events were found",
+                self.event_type
+            );
+        }
+        write!(
+            f,
+            "expected an {} event, but none of the {} events matched",
+            self.event_type,
+            self.mismatches.len(),
+        )?;
+        for (index, mismatches) in self.mismatches.iter().enumerate() {
+            write!(f, "\nevent {index}:")?;
+            for mismatch in mismatches {
+                for line in mismatch.to_string().lines() {
+                    write!(f, "\n  {line}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for UnmatchedExpectation {}
+
+/// Describes a gateway event that off-chain code expects a transaction to contain, for verifying
+/// the events of a broadcast execute message against the crate's definitions rather than by
+/// comparing strings.
+///
+/// Expectations are exact by default: every attribute that was not expected must be absent.  A
+/// [partial](self::ExpectedGatewayEvent::partial) expectation instead ignores every attribute that
+/// was not expected, and any single attribute can accept any value, such as an access grant id
+/// generated by the contract.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrant, ExpectedGatewayEvent, OsGatewayEvent};
+///
+/// let scope_address = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+/// let target_account_address = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+/// let events = vec![OsGatewayEvent::from(
+///     AccessGrant::new(scope_address, target_account_address).with_access_grant_id("generated-1"),
+/// )];
+/// ExpectedGatewayEvent::access_grant(scope_address, target_account_address)
+///     .with_any_access_grant_id()
+///     .assert_in(&events)
+///     .expect("the grant should have been emitted");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedGatewayEvent {
+    event_type: OsGatewayEventType,
+    attributes: BTreeMap<String, ExpectedValue>,
+    partial: bool,
+}
+impl ExpectedGatewayEvent {
+    fn new<S1: Into<String>, S2: Into<String>>(
+        event_type: OsGatewayEventType,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            event_type,
+            attributes: BTreeMap::new(),
+            partial: false,
+        }
+        .with_value(OS_GATEWAY_KEYS.event_type, event_type.as_str())
+        .with_value(OS_GATEWAY_KEYS.scope_address, scope_address)
+        .with_value(OS_GATEWAY_KEYS.target_account, target_account_address)
+    }
+
+    /// Expects an access grant, in the same manner as [OsGatewayAttributeGenerator::access_grant](crate::OsGatewayAttributeGenerator::access_grant).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope that the grant must refer to.
+    /// * `target_account_address` The bech32 address of the account that the grant must refer to.
+    pub fn access_grant<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self::new(
+            OsGatewayEventType::AccessGrant,
+            scope_address,
+            target_account_address,
+        )
+    }
+
+    /// Expects an access revoke, in the same manner as [OsGatewayAttributeGenerator::access_revoke](crate::OsGatewayAttributeGenerator::access_revoke).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope that the revoke must refer to.
+    /// * `target_account_address` The bech32 address of the account that the revoke must refer to.
+    pub fn access_revoke<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self::new(
+            OsGatewayEventType::AccessRevoke,
+            scope_address,
+            target_account_address,
+        )
+    }
+
+    /// Expects the event to hold exactly this access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The expected access grant id.
+    pub fn with_access_grant_id<S: Into<String>>(self, access_grant_id: S) -> Self {
+        self.with_value(OS_GATEWAY_KEYS.access_grant_id, access_grant_id)
+    }
+
+    /// Expects the event to hold an access grant id, without regard to its value.
+    pub fn with_any_access_grant_id(self) -> Self {
+        self.with_expected_value(OS_GATEWAY_KEYS.access_grant_id, ExpectedValue::Any)
+    }
+
+    /// Expects the event to hold exactly this expiration.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The expected expiration.
+    pub fn with_expiration(self, expiration: GrantExpiration) -> Self {
+        self.with_value(OS_GATEWAY_KEYS.expiration, expiration.to_string())
+    }
+
+    /// Expects the event to hold an expiration, without regard to its value.
+    pub fn with_any_expiration(self) -> Self {
+        self.with_expected_value(OS_GATEWAY_KEYS.expiration, ExpectedValue::Any)
+    }
+
+    /// Expects the event to hold exactly this access level.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The expected access level.
+    pub fn with_access_level(self, access_level: AccessLevel) -> Self {
+        self.with_value(OS_GATEWAY_KEYS.access_level, access_level.as_str())
+    }
+
+    /// Expects the event to hold exactly this value for an attribute that is not otherwise
+    /// modeled.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The expected value of the attribute.
+    pub fn with_extra_attribute<S1: Into<String>, S2: Into<String>>(
+        self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        self.with_value(key, value)
+    }
+
+    /// Sets the expectation for any attribute, including those that are modeled, replacing any
+    /// previous expectation for the same key.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `expected` The value that the attribute is expected to hold.
+    pub fn with_expected_value<S: Into<String>>(mut self, key: S, expected: ExpectedValue) -> Self {
+        self.attributes.insert(key.into(), expected);
+        self
+    }
+
+    /// Ignores every attribute that has no expectation, rather than requiring it to be absent.
+    pub fn partial(mut self) -> Self {
+        self.partial = true;
+        self
+    }
+
+    /// Compares the event against this expectation, producing every attribute that does not hold
+    /// its expected value.
+    ///
+    /// # Parameters
+    ///
+    /// * `event` The event to compare.
+    pub fn matches(&self, event: &OsGatewayEvent) -> MatchResult {
+        let generator = OsGatewayAttributeGenerator::from(event.clone());
+        let mut keys = self
+            .attributes
+            .keys()
+            .map(String::as_str)
+            .collect::<BTreeSet<&str>>();
+        if !self.partial {
+            keys.extend(generator.as_map().keys().map(String::as_str));
+        }
+        let mismatches = keys
+            .into_iter()
+            .filter_map(|key| {
+                let expected = self
+                    .attributes
+                    .get(key)
+                    .cloned()
+                    .unwrap_or(ExpectedValue::Absent);
+                let actual = generator.get(key);
+                (!expected.accepts(actual)).then(|| FieldMismatch {
+                    key: key.to_string(),
+                    expected,
+                    actual: actual.map(str::to_string),
+                })
+            })
+            .collect::<Vec<FieldMismatch>>();
+        if mismatches.is_empty() {
+            MatchResult::Matched
+        } else {
+            MatchResult::Mismatched(mismatches)
+        }
+    }
+
+    /// Finds the first event that matches this expectation, or produces an
+    /// [UnmatchedExpectation](crate::UnmatchedExpectation) that renders a diff against every event
+    /// when none match.
+    ///
+    /// # Parameters
+    ///
+    /// * `events` The events to search, such as those parsed from a transaction's events.
+    pub fn assert_in<'a>(
+        &self,
+        events: &'a [OsGatewayEvent],
+    ) -> Result<&'a OsGatewayEvent, UnmatchedExpectation> {
+        let mut mismatches = Vec::with_capacity(events.len());
+        for event in events {
+            match self.matches(event) {
+                MatchResult::Matched => return Ok(event),
+                MatchResult::Mismatched(event_mismatches) => mismatches.push(event_mismatches),
+            }
+        }
+        Err(UnmatchedExpectation {
+            event_type: self.event_type,
+            mismatches,
+        })
+    }
+
+    fn with_value<S1: Into<String>, S2: Into<String>>(self, key: S1, value: S2) -> Self {
+        self.with_expected_value(key, ExpectedValue::Value(value.into()))
+    }
+}
+/// Expects exactly the given event.
+impl From<OsGatewayEvent> for ExpectedGatewayEvent {
+    fn from(event: OsGatewayEvent) -> Self {
+        let event_type = event.typed_event_type();
+        Self {
+            event_type,
+            attributes: OsGatewayAttributeGenerator::from(event)
+                .as_map()
+                .iter()
+                .map(|(key, value)| (key.to_owned(), ExpectedValue::Value(value.to_owned())))
+                .collect(),
+            partial: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, ExpectedGatewayEvent, ExpectedValue, FieldMismatch,
+        GrantExpiration, MatchResult, OsGatewayEvent, OS_GATEWAY_KEYS,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn grant() -> OsGatewayEvent {
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("generated-1")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .into()
+    }
+
+    #[test]
+    fn test_exact_expectations_match_identical_events() {
+        assert_eq!(
+            MatchResult::Matched,
+            ExpectedGatewayEvent::from(grant()).matches(&grant()),
+            "an event should match an expectation built from itself",
+        );
+        assert_eq!(
+            MatchResult::Matched,
+            ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("generated-1")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .matches(&grant()),
+            "an event should match an expectation built from the same inputs",
+        );
+    }
+
+    #[test]
+    fn test_exact_expectations_reject_unexpected_attributes() {
+        assert_eq!(
+            MatchResult::Mismatched(vec![
+                FieldMismatch {
+                    key: OS_GATEWAY_KEYS.access_grant_id.to_string(),
+                    expected: ExpectedValue::Absent,
+                    actual: Some("generated-1".to_string()),
+                },
+                FieldMismatch {
+                    key: OS_GATEWAY_KEYS.expiration.to_string(),
+                    expected: ExpectedValue::Absent,
+                    actual: Some("height:100".to_string()),
+                },
+            ]),
+            ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS).matches(&grant()),
+            "every unexpected attribute should be reported",
+        );
+    }
+
+    #[test]
+    fn test_partial_expectations_ignore_unexpected_attributes() {
+        let expected = ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_any_access_grant_id()
+            .partial();
+        assert!(
+            expected.matches(&grant()).is_match(),
+            "a partial expectation should ignore the unexpected expiration",
+        );
+        assert_eq!(
+            MatchResult::Mismatched(vec![FieldMismatch {
+                key: OS_GATEWAY_KEYS.access_grant_id.to_string(),
+                expected: ExpectedValue::Any,
+                actual: None,
+            }]),
+            expected.matches(&AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()),
+            "a wildcard should still require the attribute to be present",
+        );
+        assert!(
+            !expected
+                .matches(&AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into())
+                .is_match(),
+            "a partial expectation should still require the event type",
+        );
+    }
+
+    #[test]
+    fn test_assert_in_finds_first_match() {
+        let events = vec![
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+            grant(),
+        ];
+        assert_eq!(
+            Ok(&events[1]),
+            ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_any_access_grant_id()
+                .with_any_expiration()
+                .assert_in(&events),
+            "the matching grant should be found after the revoke",
+        );
+    }
+
+    #[test]
+    fn test_assert_in_renders_diff() {
+        let events = vec![grant()];
+        let failure = ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("generated-2")
+            .with_access_level(AccessLevel::Read)
+            .partial()
+            .assert_in(&events)
+            .unwrap_err();
+        assert_eq!(
+            concat!(
+                "expected an access_grant event, but none of the 1 events matched\n",
+                "event 0:\n",
+                "  - object_store_gateway_access_grant_id: generated-2\n",
+                "  + object_store_gateway_access_grant_id: generated-1\n",
+                "  - object_store_gateway_access_level: read\n",
+                "  + object_store_gateway_access_level: <absent>",
+            ),
+            failure.to_string(),
+            "the failure should render a diff of every mismatched attribute",
+        );
+        assert_eq!(
+            "expected an access_grant event, but no events were found",
+            ExpectedGatewayEvent::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .assert_in(&[])
+                .unwrap_err()
+                .to_string(),
+            "searching no events should say so",
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Bindings for services written in other languages, generated by [UniFFI](https://github.com/mozilla/uniffi-rs),
+//! so that the gateway can parse and validate attributes with the same logic that this crate uses
+//! to produce them, rather than re-implementing it.
+//!
+//! Kotlin bindings are generated from the compiled library with the `uniffi-bindgen` binary, built
+//! with the `ffi-bindgen` feature:
+//!
+//! ```shell
+//! cargo rustc --lib --release --features ffi --crate-type cdylib
+//! cargo run --features ffi-bindgen --bin uniffi-bindgen -- generate \
+//!     --library target/release/libos_gateway_contract_attributes.so --language kotlin --out-dir kotlin
+//! ```
+//!
+//! The expected behavior of every binding is pinned by the conformance vectors in
+//! `tests/conformance/vectors.json`, which both this crate and its consumers test against.
+
+use crate::{OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, OS_GATEWAY_KEYS};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// A single attribute key and value, as emitted by a contract.
+///
+/// # Parameters
+///
+/// * `key` The attribute's key.
+///
+/// * `value` The attribute's value.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct GatewayAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// A typed gateway event, mirroring [OsGatewayEvent](crate::OsGatewayEvent) with optional values
+/// in their attribute encodings.
+///
+/// # Variants
+///
+/// * `AccessGrant` Mirrors an [AccessGrant](crate::AccessGrant).
+///
+/// * `AccessRevoke` Mirrors an [AccessRevoke](crate::AccessRevoke).
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Enum)]
+pub enum GatewayEvent {
+    AccessGrant {
+        scope_address: String,
+        target_account_address: String,
+        access_grant_id: Option<String>,
+        expiration: Option<String>,
+        access_level: Option<String>,
+        extra_attributes: HashMap<String, String>,
+    },
+    AccessRevoke {
+        scope_address: String,
+        target_account_address: String,
+        access_grant_id: Option<String>,
+        extra_attributes: HashMap<String, String>,
+    },
+}
+impl From<OsGatewayEvent> for GatewayEvent {
+    fn from(event: OsGatewayEvent) -> Self {
+        match event {
+            OsGatewayEvent::AccessGrant(grant) => Self::AccessGrant {
+                scope_address: grant.scope_address,
+                target_account_address: grant.target_account_address,
+                access_grant_id: grant.access_grant_id,
+                expiration: grant.expiration.map(|expiration| expiration.to_string()),
+                access_level: grant
+                    .access_level
+                    .map(|access_level| access_level.as_str().to_string()),
+                extra_attributes: grant.extra_attributes.into_iter().collect(),
+            },
+            OsGatewayEvent::AccessRevoke(revoke) => Self::AccessRevoke {
+                scope_address: revoke.scope_address,
+                target_account_address: revoke.target_account_address,
+                access_grant_id: revoke.access_grant_id,
+                extra_attributes: revoke.extra_attributes.into_iter().collect(),
+            },
+        }
+    }
+}
+
+/// A single problem with a single attribute, mirroring a [FieldError](crate::FieldError).
+///
+/// # Parameters
+///
+/// * `key` The attribute key with the problem.
+///
+/// * `code` The stable [code](crate::FieldErrorKind::code) of the kind of problem.
+///
+/// * `value_preview` The rejected value, truncated to a bounded length.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Record)]
+pub struct GatewayFieldError {
+    pub key: String,
+    pub code: String,
+    pub value_preview: String,
+}
+
+/// The errors produced by the bindings.
+///
+/// # Variants
+///
+/// * `InvalidFields` Denotes that one or more attributes failed validation.  The `errors` field
+///   contains every problem found.
+///
+/// * `Rejected` Denotes any other [OsGatewayError](crate::OsGatewayError).  The `code` field
+///   contains the error's [code](crate::OsGatewayError::code), such as `missing_required_attributes`,
+///   and the `message` field contains its description.
+#[derive(Clone, Debug, PartialEq, Eq, uniffi::Error)]
+pub enum GatewayError {
+    InvalidFields { errors: Vec<GatewayFieldError> },
+    Rejected { code: String, message: String },
+}
+impl Display for GatewayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFields { errors } => write!(
+                f,
+                "invalid object store gateway attributes: {}",
+                errors
+                    .iter()
+                    .map(|error| format!("[{}] {}", error.key, error.code))
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            ),
+            Self::Rejected { message, .. } => f.write_str(message),
+        }
+    }
+}
+impl std::error::Error for GatewayError {}
+impl From<OsGatewayError> for GatewayError {
+    fn from(error: OsGatewayError) -> Self {
+        match error {
+            OsGatewayError::InvalidFields { errors } => Self::InvalidFields {
+                errors: errors
+                    .into_iter()
+                    .map(|error| GatewayFieldError {
+                        key: error.key.as_str().to_string(),
+                        code: error.kind.code().to_string(),
+                        value_preview: error.value_preview,
+                    })
+                    .collect(),
+            },
+            error => Self::Rejected {
+                code: error.code().to_string(),
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+/// Parses the attributes of a single gateway event into a typed event, exactly as
+/// [parse_gateway_events](crate::parse_gateway_events) parses each event it finds.  Attributes
+/// that the typed event does not model are retained as its extra attributes, and values are not
+/// validated beyond what is needed to type them; see [validate_grant](self::validate_grant).
+///
+/// # Parameters
+///
+/// * `attributes` The attributes of the event, in any order.
+#[uniffi::export]
+pub fn parse_event(attributes: Vec<GatewayAttribute>) -> Result<GatewayEvent, GatewayError> {
+    OsGatewayAttributeGenerator::from_attributes_lenient(
+        attributes
+            .into_iter()
+            .map(|attribute| (attribute.key, attribute.value)),
+    )
+    .and_then(OsGatewayEvent::try_from)
+    .map(GatewayEvent::from)
+    .map_err(GatewayError::from)
+}
+
+/// Validates the values of an access grant, exactly as [validate](crate::OsGatewayAttributeGenerator::validate)
+/// validates the attributes that a contract emits, producing a [GatewayError::InvalidFields](self::GatewayError::InvalidFields)
+/// that lists every problem found.  Optional values are provided in their attribute encodings, and
+/// are validated as given rather than parsed first, so that malformed values are reported as
+/// field errors.
+///
+/// # Parameters
+///
+/// * `scope_address` The bech32 address of the scope to which access is granted.
+/// * `target_account_address` The bech32 address of the account to which access is granted.
+/// * `access_grant_id` The unique identifier of the grant, if any.
+/// * `expiration` The expiration of the grant, if any.
+/// * `access_level` The access level of the grant, if any.
+#[uniffi::export]
+pub fn validate_grant(
+    scope_address: String,
+    target_account_address: String,
+    access_grant_id: Option<String>,
+    expiration: Option<String>,
+    access_level: Option<String>,
+) -> Result<(), GatewayError> {
+    let mut generator =
+        OsGatewayAttributeGenerator::access_grant(scope_address, target_account_address);
+    for (key, value) in [
+        (OS_GATEWAY_KEYS.access_grant_id, access_grant_id),
+        (OS_GATEWAY_KEYS.expiration, expiration),
+        (OS_GATEWAY_KEYS.access_level, access_level),
+    ] {
+        if let Some(value) = value {
+            generator.set_attribute(key, value);
+        }
+    }
+    generator.validate().map_err(GatewayError::from)
+}
@@ -0,0 +1,458 @@
+use crate::attribute_keys::REQUIRED_KEYS;
+use crate::grant_target::{is_target_address, is_target_kind, SCOPE_SPEC_KIND};
+use crate::source_contract_version::split_source_contract_version;
+use crate::{
+    AccessGrantId, AccessLevel, GrantExpiration, OsGatewayAttributeGenerator, OsGatewayError,
+    OsGatewayEvent, OsGatewayKey, OsGatewayPayloadVersion, ScopeAddress,
+    OS_GATEWAY_EVENT_TYPE_STRS,
+};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// The number of characters of a rejected value that are retained in a [FieldError](crate::FieldError),
+/// which keeps log lines bounded when a value is unexpectedly large.
+const VALUE_PREVIEW_LENGTH: usize = 32;
+
+/// Appended to a value preview when the value was truncated.
+const TRUNCATION_MARKER: &str = "...";
+
+/// A single problem with a single attribute, collected alongside every other problem found in the
+/// same set of attributes so that all of them can be reported at once.
+///
+/// # Parameters
+///
+/// * `key` The attribute with the problem.
+///
+/// * `kind` The kind of problem.
+///
+/// * `value_preview` The rejected value, truncated to a bounded length.  Empty when the attribute
+///   is missing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct FieldError {
+    pub key: OsGatewayKey,
+    pub kind: FieldErrorKind,
+    pub value_preview: String,
+}
+impl FieldError {
+    /// Creates a field error, truncating the value into its preview.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The attribute with the problem.
+    /// * `kind` The kind of problem.
+    /// * `value` The full rejected value.
+    pub fn new(key: OsGatewayKey, kind: FieldErrorKind, value: &str) -> Self {
+        let mut value_preview = value.chars().take(VALUE_PREVIEW_LENGTH).collect::<String>();
+        if value_preview.len() < value.len() {
+            value_preview.push_str(TRUNCATION_MARKER);
+        }
+        Self {
+            key,
+            kind,
+            value_preview,
+        }
+    }
+}
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            FieldErrorKind::Absent => write!(f, "[{}] {}", self.key, self.kind),
+            _ => write!(f, "[{}] {}: [{}]", self.key, self.kind, self.value_preview),
+        }
+    }
+}
+/// Serializes the error as a structure with the key, the kind's code, and the value preview.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("FieldError", 3)?;
+        state.serialize_field("key", self.key.as_str())?;
+        state.serialize_field("kind", self.kind.code())?;
+        state.serialize_field("value_preview", &self.value_preview)?;
+        state.end()
+    }
+}
+/// Deserializes the error from the structure produced by its serialization.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldError {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut fields =
+            <alloc::collections::BTreeMap<String, String> as serde::Deserialize>::deserialize(
+                deserializer,
+            )?;
+        let mut take = |field: &'static str| {
+            fields
+                .remove(field)
+                .ok_or_else(|| D::Error::missing_field(field))
+        };
+        let key = take("key")?.parse().map_err(D::Error::custom)?;
+        let kind = take("kind")?;
+        let kind = FieldErrorKind::from_code(&kind)
+            .ok_or_else(|| D::Error::custom(format!("unknown field error kind [{kind}]")))?;
+        let value_preview = take("value_preview")?;
+        Ok(Self {
+            key,
+            kind,
+            value_preview,
+        })
+    }
+}
+
+/// The kinds of problems that a single attribute can have.
+///
+/// Each kind has a stable snake_case code, used in its serialized form, and a human-readable
+/// [Display] phrase.
+///
+/// # Variants
+///
+/// * `Absent` The attribute is required but missing.
+///
+/// * `Empty` The attribute is present with an empty value.
+///
+/// * `UnknownEventType` The event type is not recognized by the gateway.
+///
+/// * `InvalidScopeAddress` The value is not a valid [ScopeAddress](crate::ScopeAddress).
+///
+/// * `InvalidAccountAddress` The value is not a valid [AccountAddress](crate::AccountAddress).
+///
+/// * `InvalidAccessGrantId` The value is not a valid [AccessGrantId](crate::AccessGrantId).
+///
+/// * `InvalidExpiration` The value is not a valid [GrantExpiration](crate::GrantExpiration).
+///
+/// * `InvalidPayloadVersion` The value is not a valid [OsGatewayPayloadVersion](crate::OsGatewayPayloadVersion).
+///
+/// * `UnknownAccessLevel` The value is not a recognized [AccessLevel](crate::AccessLevel).
+///
+/// * `InvalidSourceContractVersion` The value is not a contract name and version separated by a
+///   `/`.  See [with_source_contract_version](crate::OsGatewayAttributeGenerator::with_source_contract_version).
+///
+/// * `UnknownTargetKind` The value is not a recognized kind of [GrantTarget](crate::GrantTarget).
+///
+/// * `InvalidScopeSpecAddress` The value is not a valid address for a scope specification target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FieldErrorKind {
+    Absent,
+    Empty,
+    UnknownEventType,
+    InvalidScopeAddress,
+    InvalidAccountAddress,
+    InvalidAccessGrantId,
+    InvalidExpiration,
+    InvalidPayloadVersion,
+    UnknownAccessLevel,
+    InvalidSourceContractVersion,
+    UnknownTargetKind,
+    InvalidScopeSpecAddress,
+}
+impl FieldErrorKind {
+    const ALL: [FieldErrorKind; 12] = [
+        Self::Absent,
+        Self::Empty,
+        Self::UnknownEventType,
+        Self::InvalidScopeAddress,
+        Self::InvalidAccountAddress,
+        Self::InvalidAccessGrantId,
+        Self::InvalidExpiration,
+        Self::InvalidPayloadVersion,
+        Self::UnknownAccessLevel,
+        Self::InvalidSourceContractVersion,
+        Self::UnknownTargetKind,
+        Self::InvalidScopeSpecAddress,
+    ];
+
+    /// The stable snake_case code of the kind, suitable for grouping errors in structured logs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Absent => "absent",
+            Self::Empty => "empty",
+            Self::UnknownEventType => "unknown_event_type",
+            Self::InvalidScopeAddress => "invalid_scope_address",
+            Self::InvalidAccountAddress => "invalid_account_address",
+            Self::InvalidAccessGrantId => "invalid_access_grant_id",
+            Self::InvalidExpiration => "invalid_expiration",
+            Self::InvalidPayloadVersion => "invalid_payload_version",
+            Self::UnknownAccessLevel => "unknown_access_level",
+            Self::InvalidSourceContractVersion => "invalid_source_contract_version",
+            Self::UnknownTargetKind => "unknown_target_kind",
+            Self::InvalidScopeSpecAddress => "invalid_scope_spec_address",
+        }
+    }
+
+    /// The kind with the given stable code, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `code` A code produced by [code](self::FieldErrorKind::code).
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+}
+impl Display for FieldErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Absent => "is missing",
+            Self::Empty => "is empty",
+            Self::UnknownEventType => "is not a recognized event type",
+            Self::InvalidScopeAddress => "is not a valid scope address",
+            Self::InvalidAccountAddress => "is not a valid account address",
+            Self::InvalidAccessGrantId => "is not a valid access grant id",
+            Self::InvalidExpiration => "is not a valid grant expiration",
+            Self::InvalidPayloadVersion => "is not a valid payload version",
+            Self::UnknownAccessLevel => "is not a recognized access level",
+            Self::InvalidSourceContractVersion => "is not a valid source contract version",
+            Self::UnknownTargetKind => "is not a recognized target kind",
+            Self::InvalidScopeSpecAddress => "is not a valid scope specification address",
+        })
+    }
+}
+
+impl OsGatewayAttributeGenerator {
+    /// Validates the value of every recognized attribute, rather than only checking that the
+    /// required attributes are present.  Every problem is collected, so a single call reports, for
+    /// example, both an invalid scope address and an invalid access grant id.  Produces an
+    /// [OsGatewayError::InvalidFields](crate::OsGatewayError::InvalidFields) containing a
+    /// [FieldError](crate::FieldError) for each problem found.
+    pub fn validate(&self) -> Result<(), OsGatewayError> {
+        #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+        let _span = crate::instrumentation::validate_span(self).entered();
+        let mut errors = Vec::new();
+        let target_kind = self.get_key(OsGatewayKey::TargetKind);
+        for key in OsGatewayKey::ALL {
+            let value = match self.get_key(key) {
+                Some(value) => value,
+                None => {
+                    if REQUIRED_KEYS.contains(&key.as_str()) {
+                        errors.push(FieldError::new(key, FieldErrorKind::Absent, ""));
+                    }
+                    continue;
+                }
+            };
+            if value.is_empty() {
+                errors.push(FieldError::new(key, FieldErrorKind::Empty, value));
+                continue;
+            }
+            if let Some(kind) = value_error(key, value, target_kind) {
+                errors.push(FieldError::new(key, kind, value));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(OsGatewayError::InvalidFields { errors })
+        }
+    }
+}
+
+impl OsGatewayEvent {
+    /// Validates the value of every attribute that the event emits.  See
+    /// [OsGatewayAttributeGenerator::validate](crate::OsGatewayAttributeGenerator::validate).
+    pub fn validate(&self) -> Result<(), OsGatewayError> {
+        OsGatewayAttributeGenerator::from(self.clone()).validate()
+    }
+}
+
+/// Determines the problem with a non-empty value for the given key, if any.  The target account is
+/// validated as an address of the kind given by the target kind attribute, if any.
+fn value_error(
+    key: OsGatewayKey,
+    value: &str,
+    target_kind: Option<&str>,
+) -> Option<FieldErrorKind> {
+    let (valid, kind) = match key {
+        OsGatewayKey::EventType => (
+            OS_GATEWAY_EVENT_TYPE_STRS.contains(&value),
+            FieldErrorKind::UnknownEventType,
+        ),
+        OsGatewayKey::ScopeAddress => (
+            ScopeAddress::new(value).is_ok(),
+            FieldErrorKind::InvalidScopeAddress,
+        ),
+        OsGatewayKey::TargetAccount => (
+            is_target_address(target_kind, value),
+            if target_kind == Some(SCOPE_SPEC_KIND) {
+                FieldErrorKind::InvalidScopeSpecAddress
+            } else {
+                FieldErrorKind::InvalidAccountAddress
+            },
+        ),
+        OsGatewayKey::AccessGrantId => (
+            AccessGrantId::new(value).is_ok(),
+            FieldErrorKind::InvalidAccessGrantId,
+        ),
+        OsGatewayKey::Expiration => (
+            value.parse::<GrantExpiration>().is_ok(),
+            FieldErrorKind::InvalidExpiration,
+        ),
+        OsGatewayKey::AccessLevel => (
+            value.parse::<AccessLevel>().is_ok(),
+            FieldErrorKind::UnknownAccessLevel,
+        ),
+        OsGatewayKey::PayloadVersion => (
+            value.parse::<OsGatewayPayloadVersion>().is_ok(),
+            FieldErrorKind::InvalidPayloadVersion,
+        ),
+        OsGatewayKey::SourceContractVersion => (
+            split_source_contract_version(value).is_some(),
+            FieldErrorKind::InvalidSourceContractVersion,
+        ),
+        OsGatewayKey::TargetKind => (is_target_kind(value), FieldErrorKind::UnknownTargetKind),
+    };
+    (!valid).then_some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessRevoke, FieldError, FieldErrorKind, OsGatewayAttributeGenerator, OsGatewayError,
+        OsGatewayEvent, OsGatewayKey, OS_GATEWAY_KEYS,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    #[test]
+    fn test_valid_attributes_pass_validation() {
+        let generator = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_payload_version();
+        assert_eq!(
+            Ok(()),
+            generator.validate(),
+            "valid attributes should pass validation",
+        );
+    }
+
+    #[test]
+    fn test_validation_collects_every_problem() {
+        let mut generator = OsGatewayAttributeGenerator::new();
+        generator.set_attribute(OS_GATEWAY_KEYS.event_type, "access_request");
+        generator.set_attribute(OS_GATEWAY_KEYS.scope_address, ACCOUNT_ADDRESS);
+        generator.set_attribute(OS_GATEWAY_KEYS.access_grant_id, "");
+        generator.set_attribute(OS_GATEWAY_KEYS.expiration, "soon");
+        assert_eq!(
+            Err(OsGatewayError::InvalidFields {
+                errors: vec![
+                    FieldError::new(
+                        OsGatewayKey::EventType,
+                        FieldErrorKind::UnknownEventType,
+                        "access_request",
+                    ),
+                    FieldError::new(
+                        OsGatewayKey::ScopeAddress,
+                        FieldErrorKind::InvalidScopeAddress,
+                        ACCOUNT_ADDRESS,
+                    ),
+                    FieldError::new(OsGatewayKey::TargetAccount, FieldErrorKind::Absent, ""),
+                    FieldError::new(OsGatewayKey::AccessGrantId, FieldErrorKind::Empty, ""),
+                    FieldError::new(
+                        OsGatewayKey::Expiration,
+                        FieldErrorKind::InvalidExpiration,
+                        "soon",
+                    ),
+                ],
+            }),
+            generator.validate(),
+            "every problem should be reported, in key order",
+        );
+    }
+
+    #[test]
+    fn test_typed_event_validation() {
+        let event = OsGatewayEvent::from(AccessRevoke::new(SCOPE_ADDRESS, "target"));
+        assert_eq!(
+            Err(OsGatewayError::InvalidFields {
+                errors: vec![FieldError::new(
+                    OsGatewayKey::TargetAccount,
+                    FieldErrorKind::InvalidAccountAddress,
+                    "target",
+                )],
+            }),
+            event.validate(),
+            "the event's attributes should be validated",
+        );
+    }
+
+    #[test]
+    fn test_value_preview_is_truncated() {
+        let value = "a".repeat(100);
+        let error = FieldError::new(
+            OsGatewayKey::AccessGrantId,
+            FieldErrorKind::InvalidAccessGrantId,
+            &value,
+        );
+        assert_eq!(
+            format!("{}...", "a".repeat(32)),
+            error.value_preview,
+            "long values should be truncated in the preview",
+        );
+        assert_eq!(
+            "short",
+            FieldError::new(OsGatewayKey::AccessGrantId, FieldErrorKind::Empty, "short")
+                .value_preview,
+            "short values should be previewed in full",
+        );
+    }
+
+    #[test]
+    fn test_display_reads_well_in_logs() {
+        assert_eq!(
+            "[object_store_gateway_target_account_address] is missing",
+            FieldError::new(OsGatewayKey::TargetAccount, FieldErrorKind::Absent, "").to_string(),
+            "a missing attribute should not render a value",
+        );
+        assert_eq!(
+            "[object_store_gateway_expiration] is not a valid grant expiration: [soon]",
+            FieldError::new(
+                OsGatewayKey::Expiration,
+                FieldErrorKind::InvalidExpiration,
+                "soon",
+            )
+            .to_string(),
+            "an invalid attribute should render its value preview",
+        );
+    }
+
+    #[test]
+    fn test_kind_codes_round_trip() {
+        for kind in FieldErrorKind::ALL {
+            assert_eq!(
+                Some(kind),
+                FieldErrorKind::from_code(kind.code()),
+                "the code of [{kind:?}] should resolve back into the same kind",
+            );
+        }
+        assert_eq!(
+            None,
+            FieldErrorKind::from_code("bad"),
+            "an unknown code should not resolve",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_produces_structured_data() {
+        let error = FieldError::new(
+            OsGatewayKey::ScopeAddress,
+            FieldErrorKind::InvalidScopeAddress,
+            "scope",
+        );
+        let json = cosmwasm_std::to_json_string(&error).unwrap();
+        assert_eq!(
+            r#"{"key":"object_store_gateway_scope_address","kind":"invalid_scope_address","value_preview":"scope"}"#,
+            json,
+            "the error should serialize as a structure",
+        );
+        assert_eq!(
+            error,
+            cosmwasm_std::from_json::<FieldError>(&json).unwrap(),
+            "the error should survive a serde round trip",
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{EventEncoder, OsGatewayKey};
+use alloc::string::{String, ToString};
+
+/// The version prefix of every fingerprint.  This must be incremented whenever the fingerprint
+/// format changes, so that stored fingerprints are never compared against a different format.
+pub(crate) const FINGERPRINT_VERSION: &str = "v1";
+
+/// The offset bases of each 64-bit lane in the fingerprint hash.  The first is the standard FNV-1a
+/// offset basis, and the remaining lanes are seeded with distinct values so that each lane
+/// produces independent output.
+const LANE_OFFSET_BASES: [u64; 4] = [
+    0xcbf29ce484222325,
+    0x84222325cbf29ce4,
+    0x9e3779b97f4a7c15,
+    0x243f6a8885a308d3,
+];
+
+/// The standard 64-bit FNV-1a prime.
+const FNV_PRIME: u64 = 0x00000100000001b3;
+
+/// Renders key and value pairs in the canonical fingerprint format: `v1;key=value;key=value`.
+/// Backslashes, semicolons, equals signs, carriage returns, and newlines within keys and values
+/// are escaped with a backslash so that the rendered string is unambiguous and fits on one line.
+/// As an [EventEncoder](crate::EventEncoder), every attribute is rendered in the canonical order.
+pub(crate) struct FingerprintEncoder {
+    fingerprint: String,
+}
+impl FingerprintEncoder {
+    pub(crate) fn new() -> Self {
+        Self {
+            fingerprint: FINGERPRINT_VERSION.to_string(),
+        }
+    }
+
+    /// Appends a pair to the fingerprint.  Pairs must be provided sorted by key.
+    pub(crate) fn push_pair(&mut self, key: &str, value: &str) {
+        self.fingerprint.push(';');
+        push_escaped(&mut self.fingerprint, key);
+        self.fingerprint.push('=');
+        push_escaped(&mut self.fingerprint, value);
+    }
+
+    pub(crate) fn finish(self) -> String {
+        self.fingerprint
+    }
+}
+impl EventEncoder for FingerprintEncoder {
+    fn field(&mut self, key: OsGatewayKey, value: &str) {
+        self.push_pair(key.as_str(), value);
+    }
+
+    fn extra_attribute(&mut self, key: &str, value: &str) {
+        self.push_pair(key, value);
+    }
+}
+
+/// Produces a 256-bit hash of the given bytes from four independently seeded FNV-1a lanes, each
+/// finalized with an avalanche mix.  This is a lightweight, dependency-free, and deterministic
+/// hash suitable for change detection within wasm.  It is not cryptographically secure.
+pub(crate) fn hash_fingerprint(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    for (lane, offset_basis) in LANE_OFFSET_BASES.iter().enumerate() {
+        let mut state = *offset_basis;
+        for byte in bytes {
+            state ^= u64::from(*byte);
+            state = state.wrapping_mul(FNV_PRIME);
+        }
+        state ^= bytes.len() as u64;
+        hash[lane * 8..(lane + 1) * 8].copy_from_slice(&mix(state).to_be_bytes());
+    }
+    hash
+}
+
+fn push_escaped(fingerprint: &mut String, value: &str) {
+    for character in value.chars() {
+        match character {
+            '\\' => fingerprint.push_str("\\\\"),
+            ';' => fingerprint.push_str("\\;"),
+            '=' => fingerprint.push_str("\\="),
+            '\n' => fingerprint.push_str("\\n"),
+            '\r' => fingerprint.push_str("\\r"),
+            _ => fingerprint.push(character),
+        }
+    }
+}
+
+/// The splitmix64 finalizer, which ensures that every input bit affects every output bit.
+fn mix(mut state: u64) -> u64 {
+    state = (state ^ (state >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94d049bb133111eb);
+    state ^ (state >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fingerprint::{hash_fingerprint, FingerprintEncoder};
+
+    fn render_fingerprint(pairs: &[(&str, &str)]) -> String {
+        let mut encoder = FingerprintEncoder::new();
+        for (key, value) in pairs {
+            encoder.push_pair(key, value);
+        }
+        encoder.finish()
+    }
+
+    #[test]
+    fn test_render_escapes_reserved_characters() {
+        assert_eq!(
+            "v1;a\\=b=c\\;d;e=f\\\\g\\nh\\ri",
+            render_fingerprint(&[("a=b", "c;d"), ("e", "f\\g\nh\ri")]),
+            "reserved characters should be escaped with a backslash",
+        );
+        assert_eq!(
+            "v1",
+            render_fingerprint(&[]),
+            "an empty set of pairs should render only the version",
+        );
+    }
+
+    #[test]
+    fn test_hash_is_pinned() {
+        assert_eq!(
+            hash_fingerprint(b"v1"),
+            hash_fingerprint(b"v1"),
+            "the hash should be deterministic",
+        );
+        assert_ne!(
+            hash_fingerprint(b"v1;a=b"),
+            hash_fingerprint(b"v1;a=c"),
+            "a single changed byte should change the hash",
+        );
+        assert_eq!(
+            "1ed6eda983cd654b74b71f004d6a7d932ce9937984a7d7bf18437569ade8b3fe",
+            hash_fingerprint(b"v1;a=b")
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>(),
+            "the hash algorithm should never change without a new fingerprint version",
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! A curated corpus of gateway attribute sets, shaped like the `wasm` events that the chain records
+//! for contracts emitting gateway attributes, paired with the result that parsing each one must
+//! produce.  Downstream parsers, including those written in other languages, can run the same
+//! conformance checks by exporting the corpus as JSON when the `serde` feature is enabled.
+//!
+//! ```
+//! use os_gateway_contract_attributes::fixtures;
+//! use os_gateway_contract_attributes::parse_gateway_event;
+//!
+//! for case in fixtures::all() {
+//!     assert_eq!(
+//!         vec![case.expected.clone()],
+//!         parse_gateway_event(&case.to_event()),
+//!         "{}",
+//!         case.description,
+//!     );
+//! }
+//! ```
+
+use crate::{
+    AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError, OsGatewayEvent,
+    OS_GATEWAY_KEYS,
+};
+use cosmwasm_std::{Attribute, Event};
+
+/// The address of the contract that emitted every fixture, which the chain records as the first
+/// attribute of each `wasm` event.
+const CONTRACT_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+/// A single case of the fixture corpus.
+///
+/// # Parameters
+///
+/// * `description` A short explanation of what the case covers.
+///
+/// * `attributes` The raw attributes of the `wasm` event, in the order in which the chain recorded
+///   them, including attributes added by the chain itself.
+///
+/// * `expected` The result that parsing the attributes must produce.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureCase {
+    pub description: String,
+    pub attributes: Vec<Attribute>,
+    pub expected: Result<OsGatewayEvent, OsGatewayError>,
+}
+impl FixtureCase {
+    /// Produces the `wasm` [Cosmwasm Event](cosmwasm_std::Event) that holds the case's attributes,
+    /// ready to be passed to [parse_gateway_event](crate::parse_gateway_event).
+    pub fn to_event(&self) -> Event {
+        Event::new("wasm").add_attributes(self.attributes.clone())
+    }
+}
+/// Serializes the case as an object holding its description, its attributes in the layout of
+/// Cosmwasm Attributes, and its expected result as either `{"event": ...}` or `{"error": ...}`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FixtureCase {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeStruct};
+
+        struct Expected<'a>(&'a Result<OsGatewayEvent, OsGatewayError>);
+        impl serde::Serialize for Expected<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut expected = serializer.serialize_map(Some(1))?;
+                match self.0 {
+                    Ok(event) => expected.serialize_entry("event", event)?,
+                    Err(error) => expected.serialize_entry("error", error)?,
+                }
+                expected.end()
+            }
+        }
+
+        let mut case = serializer.serialize_struct("FixtureCase", 3)?;
+        case.serialize_field("description", &self.description)?;
+        case.serialize_field("attributes", &self.attributes)?;
+        case.serialize_field("expected", &Expected(&self.expected))?;
+        case.end()
+    }
+}
+
+/// Produces every case of the fixture corpus, in a stable order.
+pub fn all() -> Vec<FixtureCase> {
+    let keys = &OS_GATEWAY_KEYS;
+    vec![
+        case(
+            "a grant with only the required attributes",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+            ],
+            Ok(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()),
+        ),
+        case(
+            "a grant with an access grant id",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.access_grant_id, "4ad6e3d4-7a3c-4a6b-9d1e-2f0c5b8e1a77"),
+            ],
+            Ok(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("4ad6e3d4-7a3c-4a6b-9d1e-2f0c5b8e1a77")
+                .into()),
+        ),
+        case(
+            "a grant with an id, a block height expiration, and read access",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, OTHER_SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.access_grant_id, "loan-review-17"),
+                (keys.expiration, "height:15120000"),
+                (keys.access_level, "read"),
+            ],
+            Ok(AccessGrant::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("loan-review-17")
+                .with_expiration(GrantExpiration::AtHeight(15120000))
+                .with_access_level(AccessLevel::Read)
+                .into()),
+        ),
+        case(
+            "a grant with a block time expiration and read and write access",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.expiration, "1735689600"),
+                (keys.access_level, "read_write"),
+            ],
+            Ok(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_expiration(GrantExpiration::AtSeconds(1735689600))
+                .with_access_level(AccessLevel::ReadWrite)
+                .into()),
+        ),
+        case(
+            "a grant with an attribute from a newer release",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                ("object_store_gateway_note", "shared with servicer"),
+            ],
+            Ok(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_extra_attribute("object_store_gateway_note", "shared with servicer")
+                .into()),
+        ),
+        case(
+            "a revoke with only the required attributes",
+            &[
+                (keys.event_type, "access_revoke"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+            ],
+            Ok(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()),
+        ),
+        case(
+            "a revoke limited to a single grant",
+            &[
+                (keys.event_type, "access_revoke"),
+                (keys.scope_address, OTHER_SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.access_grant_id, "loan-review-17"),
+            ],
+            Ok(AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("loan-review-17")
+                .into()),
+        ),
+        case(
+            "a revoke missing its target account",
+            &[
+                (keys.event_type, "access_revoke"),
+                (keys.scope_address, SCOPE_ADDRESS),
+            ],
+            Err(OsGatewayError::MissingRequiredAttributes {
+                keys: vec![keys.target_account.to_string()],
+            }),
+        ),
+        case(
+            "a grant missing its scope address and target account",
+            &[(keys.event_type, "access_grant")],
+            Err(OsGatewayError::MissingRequiredAttributes {
+                keys: vec![
+                    keys.scope_address.to_string(),
+                    keys.target_account.to_string(),
+                ],
+            }),
+        ),
+        case(
+            "an event type the gateway does not recognize",
+            &[
+                (keys.event_type, "access_transfer"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+            ],
+            Err(OsGatewayError::UnknownEventType {
+                event_type: "access_transfer".to_string(),
+            }),
+        ),
+        case(
+            "a grant with an expiration written as a date",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.expiration, "2025-01-01"),
+            ],
+            Err("2025-01-01".parse::<GrantExpiration>().unwrap_err()),
+        ),
+        case(
+            "a grant with an access level the gateway does not recognize",
+            &[
+                (keys.event_type, "access_grant"),
+                (keys.scope_address, SCOPE_ADDRESS),
+                (keys.target_account, ACCOUNT_ADDRESS),
+                (keys.access_level, "admin"),
+            ],
+            Err("admin".parse::<AccessLevel>().unwrap_err()),
+        ),
+    ]
+}
+
+/// Builds a case whose attributes follow the contract address that the chain records first.
+fn case(
+    description: &str,
+    attributes: &[(&str, &str)],
+    expected: Result<OsGatewayEvent, OsGatewayError>,
+) -> FixtureCase {
+    FixtureCase {
+        description: description.to_string(),
+        attributes: [("_contract_address", CONTRACT_ADDRESS)]
+            .iter()
+            .chain(attributes)
+            .map(|(key, value)| Attribute {
+                // Constructed directly, as chain-added keys like _contract_address are reserved
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect(),
+        expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_descriptions_are_unique() {
+        let cases = fixtures::all();
+        assert_eq!(
+            cases.len(),
+            cases
+                .iter()
+                .map(|case| case.description.as_str())
+                .collect::<HashSet<&str>>()
+                .len(),
+            "every case should be identifiable by its description",
+        );
+    }
+
+    #[test]
+    fn test_corpus_covers_grants_revokes_and_rejections() {
+        let cases = fixtures::all();
+        assert!(
+            cases
+                .iter()
+                .any(|case| matches!(case.expected, Ok(crate::OsGatewayEvent::AccessGrant(_)))),
+            "the corpus should contain grants",
+        );
+        assert!(
+            cases
+                .iter()
+                .any(|case| matches!(case.expected, Ok(crate::OsGatewayEvent::AccessRevoke(_)))),
+            "the corpus should contain revokes",
+        );
+        assert!(
+            cases.iter().any(|case| case.expected.is_err()),
+            "the corpus should contain rejected attribute sets",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_cases_export_as_json() {
+        let json = cosmwasm_std::to_json_string(&fixtures::all()[7]).unwrap();
+        assert_eq!(
+            concat!(
+                r#"{"description":"a revoke missing its target account","attributes":["#,
+                r#"{"key":"_contract_address","value":"tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9"},"#,
+                r#"{"key":"object_store_gateway_event_type","value":"access_revoke"},"#,
+                r#"{"key":"object_store_gateway_scope_address","value":"scope1qzn7jghj8puprmdcvunm3330jutsj803zz"}],"#,
+                r#""expected":{"error":{"code":"missing_required_attributes","#,
+                r#""keys":["object_store_gateway_target_account_address"],"#,
+                r#""message":"#,
+            ),
+            &json[..json.find(r#""message":"#).unwrap() + 10],
+            "a case should export its attributes and expected error",
+        );
+        assert!(
+            cosmwasm_std::to_json_string(&fixtures::all()[0])
+                .unwrap()
+                .contains(r#""expected":{"event":{"#),
+            "a case should export its expected event",
+        );
+    }
+}
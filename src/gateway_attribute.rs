@@ -0,0 +1,216 @@
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+use crate::OsGatewayError;
+use crate::{OsGatewayAttributeGenerator, OsGatewayKey};
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+use cosmwasm_std::Attribute;
+use std::fmt::{Display, Formatter};
+
+/// A single attribute recognized by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway),
+/// pairing a typed key with its value.  This sits between raw [Cosmwasm Attributes](cosmwasm_std::Attribute)
+/// and complete events, for code that filters or rewrites attributes individually.
+///
+/// ```
+/// # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+/// use cosmwasm_std::Attribute;
+/// use os_gateway_contract_attributes::{OsGatewayAttribute, OsGatewayKey};
+///
+/// let attribute = Attribute::new(
+///     "object_store_gateway_scope_address",
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+/// );
+/// let typed = OsGatewayAttribute::try_from(&attribute).expect("the key should be recognized");
+/// assert_eq!(OsGatewayKey::ScopeAddress, typed.key);
+/// assert_eq!(attribute, Attribute::from(typed));
+/// # }
+/// ```
+///
+/// With the `serde` feature enabled, the attribute serializes in the same layout as a Cosmwasm
+/// Attribute, with its key written as the exact attribute key string.
+///
+/// # Parameters
+///
+/// * `key` The typed key of the attribute.
+///
+/// * `value` The value of the attribute, exactly as emitted.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OsGatewayAttribute {
+    pub key: OsGatewayKey,
+    pub value: String,
+}
+impl OsGatewayAttribute {
+    /// Creates an attribute from its key and value.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The typed key of the attribute.
+    /// * `value` The value of the attribute.
+    pub fn new<S: Into<String>>(key: OsGatewayKey, value: S) -> Self {
+        Self {
+            key,
+            value: value.into(),
+        }
+    }
+}
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+impl From<OsGatewayAttribute> for Attribute {
+    fn from(attribute: OsGatewayAttribute) -> Self {
+        Attribute::new(attribute.key.as_str(), attribute.value)
+    }
+}
+/// Produces an [OsGatewayError::UnknownAttributeKeys](crate::OsGatewayError::UnknownAttributeKeys)
+/// if the attribute's key is not recognized by the gateway.
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+impl TryFrom<&Attribute> for OsGatewayAttribute {
+    type Error = OsGatewayError;
+
+    fn try_from(attribute: &Attribute) -> Result<Self, Self::Error> {
+        Ok(Self::new(attribute.key.parse()?, &attribute.value))
+    }
+}
+/// Renders the attribute as `key=value`, using the exact attribute key string.
+impl Display for OsGatewayAttribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+/// Serializes the attribute as an object holding its exact attribute key string and its value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayAttribute {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut attribute = serializer.serialize_struct("OsGatewayAttribute", 2)?;
+        attribute.serialize_field("key", self.key.as_str())?;
+        attribute.serialize_field("value", &self.value)?;
+        attribute.end()
+    }
+}
+/// Deserializes the attribute from the layout of a Cosmwasm Attribute, rejecting keys that are not
+/// recognized by the gateway.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayAttribute {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(&Attribute::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl OsGatewayAttributeGenerator {
+    /// Produces a typed [OsGatewayAttribute](crate::OsGatewayAttribute) for each attribute held in
+    /// this generator with a recognized key, in the documented emission order.  Extra attributes,
+    /// whose keys the gateway does not recognize, are omitted.
+    pub fn attributes_typed(&self) -> Vec<OsGatewayAttribute> {
+        self.iter()
+            .filter_map(|(key, value)| {
+                key.parse()
+                    .ok()
+                    .map(|key| OsGatewayAttribute::new(key, value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use crate::OsGatewayError;
+    use crate::{AccessLevel, OsGatewayAttribute, OsGatewayAttributeGenerator, OsGatewayKey};
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use cosmwasm_std::Attribute;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_attributes_convert_to_and_from_cosmwasm() {
+        for key in OsGatewayKey::ALL {
+            let attribute = OsGatewayAttribute::new(key, "value");
+            let cosmwasm = Attribute::from(attribute.clone());
+            assert_eq!(
+                key.as_str(),
+                cosmwasm.key,
+                "the exact attribute key string should be emitted for [{key}]",
+            );
+            assert_eq!(
+                Ok(attribute),
+                OsGatewayAttribute::try_from(&cosmwasm),
+                "the attribute for [{key}] should convert back",
+            );
+        }
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_unrecognized_keys_are_rejected() {
+        assert_eq!(
+            Err(OsGatewayError::UnknownAttributeKeys {
+                keys: vec!["action".to_string()],
+            }),
+            OsGatewayAttribute::try_from(&Attribute::new("action", "grant")),
+            "a key the gateway does not recognize should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_display_renders_key_and_value() {
+        assert_eq!(
+            format!("object_store_gateway_scope_address={SCOPE_ADDRESS}"),
+            OsGatewayAttribute::new(OsGatewayKey::ScopeAddress, SCOPE_ADDRESS).to_string(),
+            "the attribute should render its exact key and value",
+        );
+    }
+
+    #[test]
+    fn test_generator_produces_typed_attributes_in_emission_order() {
+        let generator = OsGatewayAttributeGenerator::grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_level(AccessLevel::Read)
+            .with_access_grant_id("my_unique_id")
+            .build();
+        let attributes = generator.attributes_typed();
+        assert_eq!(
+            generator.iter().collect::<Vec<(&str, &str)>>(),
+            attributes
+                .iter()
+                .map(|attribute| (attribute.key.as_str(), attribute.value.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            "the typed attributes should match the emitted attributes in order",
+        );
+        let with_extra = OsGatewayAttributeGenerator::from_attributes_lenient(
+            generator
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .chain([(
+                    "object_store_gateway_note".to_string(),
+                    "shared".to_string(),
+                )]),
+        )
+        .unwrap();
+        assert_eq!(
+            attributes,
+            with_extra.attributes_typed(),
+            "extra attributes should be omitted",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_matches_cosmwasm_layout() {
+        let attribute = OsGatewayAttribute::new(OsGatewayKey::AccessLevel, "read");
+        let json = cosmwasm_std::to_json_vec(&attribute).unwrap();
+        assert_eq!(
+            cosmwasm_std::to_json_vec(&Attribute::from(attribute.clone())).unwrap(),
+            json,
+            "the attribute should serialize as a cosmwasm attribute",
+        );
+        assert_eq!(
+            attribute,
+            cosmwasm_std::from_json::<OsGatewayAttribute>(&json).unwrap(),
+            "the attribute should survive a serde round trip",
+        );
+        assert!(
+            cosmwasm_std::from_json::<OsGatewayAttribute>(br#"{"key":"action","value":"grant"}"#)
+                .is_err(),
+            "an unrecognized key should not deserialize",
+        );
+    }
+}
@@ -0,0 +1,1360 @@
+use crate::attribute_keys::REQUIRED_KEYS;
+use crate::{
+    AccessLevel, AccountAddress, GrantExpiration, GrantTarget, OsGatewayAttributeGenerator,
+    OsGatewayError, OsGatewayEventType, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+};
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A typed representation of an access grant event, which instructs [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// to grant an account access to the records of a scope.
+///
+/// # Parameters
+///
+/// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+///   to which this access grant refers.
+/// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   to which this access grant refers, or of the marker or scope specification when a target kind
+///   is retained in the extra attributes.  See [target](self::AccessGrant::target).
+/// * `access_grant_id` An optional unique identifier for the grant, which enables referral to the
+///   grant after the event is processed.
+/// * `expiration` An optional point at which the grant stops being honored.
+/// * `access_level` An optional level of access given by the grant.
+/// * `extra_attributes` Any attributes that accompany the grant but are not modeled by this type,
+///   keyed by their full attribute key.  These are retained so that conversions to and from an
+///   [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) are lossless.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AccessGrant {
+    pub scope_address: String,
+    pub target_account_address: String,
+    pub access_grant_id: Option<String>,
+    pub expiration: Option<GrantExpiration>,
+    pub access_level: Option<AccessLevel>,
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// A typed representation of an access revoke event, which instructs [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// to revoke an account's access to the records of a scope.
+///
+/// # Parameters
+///
+/// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+///   to which this access revoke refers.
+/// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   to which this access revoke refers, or of the marker or scope specification when a target
+///   kind is retained in the extra attributes.  See [target](self::AccessRevoke::target).
+/// * `access_grant_id` An optional identifier that limits the revoke to the grant created with the
+///   same id.
+/// * `extra_attributes` Any attributes that accompany the revoke but are not modeled by this type,
+///   keyed by their full attribute key.  These are retained so that conversions to and from an
+///   [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) are lossless.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AccessRevoke {
+    pub scope_address: String,
+    pub target_account_address: String,
+    pub access_grant_id: Option<String>,
+    pub extra_attributes: BTreeMap<String, String>,
+}
+
+/// A typed representation of every event consumed by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+/// New variants will be added as the gateway recognizes new event types, so matches on this enum
+/// must include a wildcard arm.
+///
+/// ```
+/// # #[cfg(feature = "parsing")] {
+/// use os_gateway_contract_attributes::{parse_gateway_events, OsGatewayAttributeGenerator, OsGatewayEvent};
+///
+/// let events = [OsGatewayAttributeGenerator::access_grant("scope", "target").to_event()];
+/// for event in parse_gateway_events(&events) {
+///     match event.expect("the event should be complete") {
+///         OsGatewayEvent::AccessGrant(grant) => assert_eq!("target", grant.target_account_address),
+///         OsGatewayEvent::AccessRevoke(revoke) => panic!("unexpected revoke: {revoke:?}"),
+///         other => panic!("unexpected event: {other:?}"),
+///     }
+/// }
+/// # }
+/// ```
+///
+/// # Variants
+///
+/// * `AccessGrant` An event that grants an account access to the records of a scope.
+///
+/// * `AccessRevoke` An event that revokes an account's access to the records of a scope.
+///
+/// Events are ordered by scope address, then target account address, then access grant id, with
+/// events that lack an id first, then event type.  See [sort_events](crate::sort_events).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OsGatewayEvent {
+    AccessGrant(AccessGrant),
+    AccessRevoke(AccessRevoke),
+}
+impl OsGatewayEvent {
+    /// The value of the [event type attribute](crate::OsGatewayKeys) that denotes this kind of event.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::AccessGrant(_) => OS_GATEWAY_EVENT_TYPES.access_grant,
+            Self::AccessRevoke(_) => OS_GATEWAY_EVENT_TYPES.access_revoke,
+        }
+    }
+
+    /// The typed [OsGatewayEventType](crate::OsGatewayEventType) that denotes this kind of event.
+    pub fn typed_event_type(&self) -> OsGatewayEventType {
+        match self {
+            Self::AccessGrant(_) => OsGatewayEventType::AccessGrant,
+            Self::AccessRevoke(_) => OsGatewayEventType::AccessRevoke,
+        }
+    }
+
+    /// The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which this event refers.
+    pub fn scope_address(&self) -> &str {
+        match self {
+            Self::AccessGrant(grant) => &grant.scope_address,
+            Self::AccessRevoke(revoke) => &revoke.scope_address,
+        }
+    }
+
+    /// The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// upon which this event takes action.
+    pub fn target_account(&self) -> &str {
+        match self {
+            Self::AccessGrant(grant) => &grant.target_account_address,
+            Self::AccessRevoke(revoke) => &revoke.target_account_address,
+        }
+    }
+
+    /// The access grant id included with this event, if any.
+    pub fn access_grant_id(&self) -> Option<&str> {
+        match self {
+            Self::AccessGrant(grant) => grant.access_grant_id.as_deref(),
+            Self::AccessRevoke(revoke) => revoke.access_grant_id.as_deref(),
+        }
+    }
+
+    /// The target of this event.  See [GrantTarget](crate::GrantTarget).
+    pub fn target(&self) -> GrantTarget {
+        match self {
+            Self::AccessGrant(grant) => grant.target(),
+            Self::AccessRevoke(revoke) => revoke.target(),
+        }
+    }
+
+    /// Validates the target account as a mainnet or testnet [AccountAddress](crate::AccountAddress).
+    pub fn target_account_parsed(&self) -> Result<AccountAddress, OsGatewayError> {
+        AccountAddress::new(self.target_account())
+    }
+}
+
+impl AccessGrant {
+    /// Creates an access grant without an access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            scope_address: scope_address.into(),
+            target_account_address: target_account_address.into(),
+            access_grant_id: None,
+            expiration: None,
+            access_level: None,
+            extra_attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the access grant id, replacing any previously provided value.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier with which the grant will be created.
+    pub fn with_access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.access_grant_id = Some(access_grant_id.into());
+        self
+    }
+
+    /// Sets the access grant id only if one is provided, otherwise leaving any previously provided
+    /// value in place.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier with which the grant will be created, if any.
+    pub fn with_optional_access_grant_id<S: Into<String>>(
+        self,
+        access_grant_id: Option<S>,
+    ) -> Self {
+        match access_grant_id {
+            Some(access_grant_id) => self.with_access_grant_id(access_grant_id),
+            None => self,
+        }
+    }
+
+    /// Sets the point at which the grant stops being honored, replacing any previously provided
+    /// value.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the grant expires.
+    pub fn with_expiration(mut self, expiration: GrantExpiration) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Sets the level of access given by the grant, replacing any previously provided value.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access to grant.
+    pub fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = Some(access_level);
+        self
+    }
+
+    /// Adds an attribute that is not modeled by this type, replacing any previously provided value
+    /// for the same key.  Keys modeled by this type, such as the scope address or expiration, are
+    /// ignored, as their values belong in the grant's own fields.  This keeps conversions into an
+    /// [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) and back lossless.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The value of the attribute.
+    pub fn with_extra_attribute<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        insert_extra_attribute(&mut self.extra_attributes, &GRANT_KEYS, key, value);
+        self
+    }
+
+    /// The target of this grant, built from the target account address and the target kind
+    /// retained in the extra attributes.  Without a target kind, this is a
+    /// [GrantTarget::Account](crate::GrantTarget::Account).
+    pub fn target(&self) -> GrantTarget {
+        GrantTarget::from_kind(
+            self.extra_attributes
+                .get(OS_GATEWAY_KEYS.target_kind)
+                .map(String::as_str),
+            &self.target_account_address,
+        )
+    }
+
+    /// Sets the target of the grant, replacing the target account address and any previously
+    /// provided target kind.  Targets other than accounts are denoted by the target kind
+    /// attribute, which is retained in the extra attributes.
+    ///
+    /// # Parameters
+    ///
+    /// * `target` The account, marker, or scope specification to which the grant refers.
+    pub fn with_target(mut self, target: GrantTarget) -> Self {
+        set_target(
+            &mut self.target_account_address,
+            &mut self.extra_attributes,
+            target,
+        );
+        self
+    }
+
+    /// Validates the target account as a mainnet or testnet [AccountAddress](crate::AccountAddress).
+    pub fn target_account_parsed(&self) -> Result<AccountAddress, OsGatewayError> {
+        AccountAddress::new(self.target_account_address.as_str())
+    }
+}
+
+impl AccessRevoke {
+    /// Creates an access revoke without an access grant id, which revokes all access that the
+    /// target account has been granted to the scope.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            scope_address: scope_address.into(),
+            target_account_address: target_account_address.into(),
+            access_grant_id: None,
+            extra_attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Limits the revoke to the grant created with the given id, replacing any previously provided
+    /// value.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant to revoke.
+    pub fn with_access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.access_grant_id = Some(access_grant_id.into());
+        self
+    }
+
+    /// Limits the revoke to the grant created with the given id only if one is provided, otherwise
+    /// leaving any previously provided value in place.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant to revoke, if any.
+    pub fn with_optional_access_grant_id<S: Into<String>>(
+        self,
+        access_grant_id: Option<S>,
+    ) -> Self {
+        match access_grant_id {
+            Some(access_grant_id) => self.with_access_grant_id(access_grant_id),
+            None => self,
+        }
+    }
+
+    /// Adds an attribute that is not modeled by this type, replacing any previously provided value
+    /// for the same key.  Keys modeled by this type, such as the scope address, are ignored, as
+    /// their values belong in the revoke's own fields.  This keeps conversions into an
+    /// [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) and back lossless.
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The value of the attribute.
+    pub fn with_extra_attribute<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        insert_extra_attribute(&mut self.extra_attributes, &REVOKE_KEYS, key, value);
+        self
+    }
+
+    /// The target of this revoke, built from the target account address and the target kind
+    /// retained in the extra attributes.  Without a target kind, this is a
+    /// [GrantTarget::Account](crate::GrantTarget::Account).
+    pub fn target(&self) -> GrantTarget {
+        GrantTarget::from_kind(
+            self.extra_attributes
+                .get(OS_GATEWAY_KEYS.target_kind)
+                .map(String::as_str),
+            &self.target_account_address,
+        )
+    }
+
+    /// Sets the target of the revoke, replacing the target account address and any previously
+    /// provided target kind.  Targets other than accounts are denoted by the target kind
+    /// attribute, which is retained in the extra attributes.
+    ///
+    /// # Parameters
+    ///
+    /// * `target` The account, marker, or scope specification to which the revoke refers.
+    pub fn with_target(mut self, target: GrantTarget) -> Self {
+        set_target(
+            &mut self.target_account_address,
+            &mut self.extra_attributes,
+            target,
+        );
+        self
+    }
+
+    /// Validates the target account as a mainnet or testnet [AccountAddress](crate::AccountAddress).
+    pub fn target_account_parsed(&self) -> Result<AccountAddress, OsGatewayError> {
+        AccountAddress::new(self.target_account_address.as_str())
+    }
+}
+
+/// Orders grants by scope address, then target account address, then access grant id, with grants
+/// that lack an id first.  Any remaining ties are broken by expiration, then by access level, and
+/// then by extra attributes, so that only equal grants compare as equal.
+impl Ord for AccessGrant {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            &self.scope_address,
+            &self.target_account_address,
+            &self.access_grant_id,
+            &self.expiration,
+            &self.access_level,
+            &self.extra_attributes,
+        )
+            .cmp(&(
+                &other.scope_address,
+                &other.target_account_address,
+                &other.access_grant_id,
+                &other.expiration,
+                &other.access_level,
+                &other.extra_attributes,
+            ))
+    }
+}
+impl PartialOrd for AccessGrant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders revokes by scope address, then target account address, then access grant id, with
+/// revokes that lack an id first.  Any remaining ties are broken by extra attributes, so that only
+/// equal revokes compare as equal.
+impl Ord for AccessRevoke {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            &self.scope_address,
+            &self.target_account_address,
+            &self.access_grant_id,
+            &self.extra_attributes,
+        )
+            .cmp(&(
+                &other.scope_address,
+                &other.target_account_address,
+                &other.access_grant_id,
+                &other.extra_attributes,
+            ))
+    }
+}
+impl PartialOrd for AccessRevoke {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders events by scope address, then target account address, then access grant id, with events
+/// that lack an id first, then event type.  Any remaining ties are broken by the ordering of the
+/// events themselves, so that only equal events compare as equal.
+impl Ord for OsGatewayEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (
+            self.scope_address(),
+            self.target_account(),
+            self.access_grant_id(),
+            self.event_type(),
+        )
+            .cmp(&(
+                other.scope_address(),
+                other.target_account(),
+                other.access_grant_id(),
+                other.event_type(),
+            ))
+            .then_with(|| match (self, other) {
+                (Self::AccessGrant(grant), Self::AccessGrant(other_grant)) => {
+                    grant.cmp(other_grant)
+                }
+                (Self::AccessRevoke(revoke), Self::AccessRevoke(other_revoke)) => {
+                    revoke.cmp(other_revoke)
+                }
+                // Events of differing types are already ordered by their event types
+                _ => Ordering::Equal,
+            })
+    }
+}
+impl PartialOrd for OsGatewayEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Sorts events into the order documented on [OsGatewayEvent](crate::OsGatewayEvent): by scope
+/// address, then target account address, then access grant id, then event type.  This is useful
+/// when comparing parsed events against another source of grants.
+///
+/// # Parameters
+///
+/// * `events` The events to sort in place.
+pub fn sort_events(events: &mut [OsGatewayEvent]) {
+    events.sort();
+}
+
+/// Removes every event that exactly matches an earlier event, leaving the first occurrence of each
+/// event in its original position.
+///
+/// # Parameters
+///
+/// * `events` The events from which to remove duplicates in place.
+pub fn dedup_events(events: &mut Vec<OsGatewayEvent>) {
+    let mut seen = BTreeSet::new();
+    events.retain(|event| seen.insert(event.clone()));
+}
+
+/// Converts a grant into the attributes that describe it.  This conversion cannot fail: every
+/// modeled field maps onto its attribute, and extra attributes are included as-is, except for any
+/// that hold a key the grant models.
+impl From<AccessGrant> for OsGatewayAttributeGenerator {
+    fn from(grant: AccessGrant) -> Self {
+        with_extra_attributes(
+            OsGatewayAttributeGenerator::grant(grant.scope_address, grant.target_account_address)
+                .with_optional_access_grant_id(grant.access_grant_id)
+                .with_optional_expiration(grant.expiration)
+                .with_optional_access_level(grant.access_level)
+                .build(),
+            grant.extra_attributes,
+            &GRANT_KEYS,
+        )
+    }
+}
+
+/// Converts a revoke into the attributes that describe it.  This conversion cannot fail: every
+/// modeled field maps onto its attribute, and extra attributes are included as-is, except for any
+/// that hold a key the revoke models.
+impl From<AccessRevoke> for OsGatewayAttributeGenerator {
+    fn from(revoke: AccessRevoke) -> Self {
+        with_extra_attributes(
+            OsGatewayAttributeGenerator::access_revoke(
+                revoke.scope_address,
+                revoke.target_account_address,
+            )
+            .with_optional_access_grant_id(revoke.access_grant_id),
+            revoke.extra_attributes,
+            &REVOKE_KEYS,
+        )
+    }
+}
+
+impl From<AccessGrant> for OsGatewayEvent {
+    fn from(grant: AccessGrant) -> Self {
+        Self::AccessGrant(grant)
+    }
+}
+
+impl From<AccessRevoke> for OsGatewayEvent {
+    fn from(revoke: AccessRevoke) -> Self {
+        Self::AccessRevoke(revoke)
+    }
+}
+
+/// Converts an event into the attributes that describe it.  This conversion cannot fail, and is the
+/// inverse of the conversion from an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator).
+impl From<OsGatewayEvent> for OsGatewayAttributeGenerator {
+    fn from(event: OsGatewayEvent) -> Self {
+        match event {
+            OsGatewayEvent::AccessGrant(grant) => grant.into(),
+            OsGatewayEvent::AccessRevoke(revoke) => revoke.into(),
+        }
+    }
+}
+
+/// Converts a set of attributes into the typed event denoted by its event type.  Attributes that the
+/// typed event does not model are retained as its extra attributes, so converting the event back
+/// into a generator reproduces the original attributes.
+///
+/// This direction is fallible because a generator may hold any set of attributes.  Produces an
+/// [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+/// if any required attribute is absent, an [OsGatewayError::UnknownEventType](crate::OsGatewayError::UnknownEventType)
+/// if the event type is not recognized, or an [OsGatewayError::InvalidExpiration](crate::OsGatewayError::InvalidExpiration)
+/// if a grant's expiration attribute cannot be parsed.
+impl TryFrom<&OsGatewayAttributeGenerator> for OsGatewayEvent {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: &OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        generator.verify_required_attributes()?;
+        match generator.get(OS_GATEWAY_KEYS.event_type) {
+            Some(event_type) if event_type == OS_GATEWAY_EVENT_TYPES.access_grant => {
+                AccessGrant::try_from(generator).map(Self::AccessGrant)
+            }
+            Some(event_type) if event_type == OS_GATEWAY_EVENT_TYPES.access_revoke => {
+                AccessRevoke::try_from(generator).map(Self::AccessRevoke)
+            }
+            event_type => Err(OsGatewayError::UnknownEventType {
+                event_type: event_type.unwrap_or_default().to_string(),
+            }),
+        }
+    }
+}
+impl TryFrom<OsGatewayAttributeGenerator> for OsGatewayEvent {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        Self::try_from(&generator)
+    }
+}
+
+/// Converts the attributes of an access grant into its typed form.  Attributes that the typed form
+/// does not model are retained as its extra attributes.
+///
+/// This direction is fallible because a generator may hold any set of attributes.  Produces an
+/// [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+/// if any required attribute is absent, an [OsGatewayError::UnexpectedEventType](crate::OsGatewayError::UnexpectedEventType)
+/// if the attributes describe a different kind of event, an [OsGatewayError::InvalidExpiration](crate::OsGatewayError::InvalidExpiration)
+/// if the expiration attribute cannot be parsed, or an [OsGatewayError::UnknownAccessLevel](crate::OsGatewayError::UnknownAccessLevel)
+/// if the access level attribute is not recognized.
+impl TryFrom<&OsGatewayAttributeGenerator> for AccessGrant {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: &OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        let (scope_address, target_account_address) =
+            typed_fields(generator, OS_GATEWAY_EVENT_TYPES.access_grant)?;
+        let expiration = generator
+            .get(OS_GATEWAY_KEYS.expiration)
+            .map(str::parse::<GrantExpiration>)
+            .transpose()?;
+        let access_level = generator
+            .get(OS_GATEWAY_KEYS.access_level)
+            .map(str::parse::<AccessLevel>)
+            .transpose()?;
+        Ok(Self {
+            expiration,
+            access_level,
+            extra_attributes: extra_attributes(generator, &GRANT_KEYS),
+            ..Self::new(scope_address, target_account_address)
+                .with_optional_access_grant_id(generator.get(OS_GATEWAY_KEYS.access_grant_id))
+        })
+    }
+}
+impl TryFrom<OsGatewayAttributeGenerator> for AccessGrant {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        Self::try_from(&generator)
+    }
+}
+
+/// Converts the attributes of an access revoke into its typed form.  Attributes that the typed form
+/// does not model are retained as its extra attributes.
+///
+/// This direction is fallible because a generator may hold any set of attributes.  Produces an
+/// [OsGatewayError::MissingRequiredAttributes](crate::OsGatewayError::MissingRequiredAttributes)
+/// if any required attribute is absent, or an [OsGatewayError::UnexpectedEventType](crate::OsGatewayError::UnexpectedEventType)
+/// if the attributes describe a different kind of event.
+impl TryFrom<&OsGatewayAttributeGenerator> for AccessRevoke {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: &OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        let (scope_address, target_account_address) =
+            typed_fields(generator, OS_GATEWAY_EVENT_TYPES.access_revoke)?;
+        Ok(Self {
+            extra_attributes: extra_attributes(generator, &REVOKE_KEYS),
+            ..Self::new(scope_address, target_account_address)
+                .with_optional_access_grant_id(generator.get(OS_GATEWAY_KEYS.access_grant_id))
+        })
+    }
+}
+impl TryFrom<OsGatewayAttributeGenerator> for AccessRevoke {
+    type Error = OsGatewayError;
+
+    fn try_from(generator: OsGatewayAttributeGenerator) -> Result<Self, Self::Error> {
+        Self::try_from(&generator)
+    }
+}
+
+/// Serializes the event as the flat map of attribute keys to values that it emits.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        OsGatewayAttributeGenerator::from(self.clone()).serialize(serializer)
+    }
+}
+/// Deserializes the event from a flat map of attribute keys to values, rejecting maps that do not
+/// describe a complete event of a recognized type.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayEvent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_typed(deserializer)
+    }
+}
+
+/// Deserializes a flat map of attribute keys to values into a typed event.
+#[cfg(feature = "serde")]
+fn deserialize_typed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: TryFrom<OsGatewayAttributeGenerator, Error = OsGatewayError>,
+{
+    let attributes = <BTreeMap<String, String> as serde::Deserialize>::deserialize(deserializer)?;
+    OsGatewayAttributeGenerator::from_attributes_lenient(attributes)
+        .and_then(T::try_from)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Verifies that the generator holds every required attribute for the expected event type,
+/// producing its scope address and target account address.
+fn typed_fields<'a>(
+    generator: &'a OsGatewayAttributeGenerator,
+    expected_event_type: &str,
+) -> Result<(&'a str, &'a str), OsGatewayError> {
+    generator.verify_required_attributes()?;
+    match (
+        generator.get(OS_GATEWAY_KEYS.event_type),
+        generator.get(OS_GATEWAY_KEYS.scope_address),
+        generator.get(OS_GATEWAY_KEYS.target_account),
+    ) {
+        (Some(event_type), Some(scope_address), Some(target_account_address))
+            if event_type == expected_event_type =>
+        {
+            Ok((scope_address, target_account_address))
+        }
+        (event_type, _, _) => Err(OsGatewayError::UnexpectedEventType {
+            expected: expected_event_type.to_string(),
+            actual: event_type.unwrap_or_default().to_string(),
+        }),
+    }
+}
+
+/// Every key modeled by [AccessGrant](self::AccessGrant).  The payload version describes the
+/// attributes rather than the grant, so it is retained as an extra attribute.
+pub(crate) const GRANT_KEYS: [&str; 6] = [
+    REQUIRED_KEYS[0],
+    REQUIRED_KEYS[1],
+    REQUIRED_KEYS[2],
+    OS_GATEWAY_KEYS.access_grant_id,
+    OS_GATEWAY_KEYS.expiration,
+    OS_GATEWAY_KEYS.access_level,
+];
+
+/// Every key modeled by [AccessRevoke](self::AccessRevoke).  Expirations only apply to grants, so
+/// a revoke retains them as extra attributes.
+pub(crate) const REVOKE_KEYS: [&str; 4] = [
+    REQUIRED_KEYS[0],
+    REQUIRED_KEYS[1],
+    REQUIRED_KEYS[2],
+    OS_GATEWAY_KEYS.access_grant_id,
+];
+
+/// Collects every attribute whose key is not modeled by a typed event.
+fn extra_attributes(
+    generator: &OsGatewayAttributeGenerator,
+    modeled_keys: &[&str],
+) -> BTreeMap<String, String> {
+    generator
+        .as_map()
+        .iter()
+        .filter(|(key, _)| !modeled_keys.contains(&key.as_str()))
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .collect()
+}
+
+/// Adds extra attributes to the generator, skipping any that hold a key modeled by the typed event,
+/// such as one inserted directly into its public extra attributes.  Otherwise, an absent modeled
+/// field could be filled from the extra attributes, and would no longer be absent once converted
+/// back.
+fn with_extra_attributes(
+    mut generator: OsGatewayAttributeGenerator,
+    extra_attributes: BTreeMap<String, String>,
+    modeled_keys: &[&str],
+) -> OsGatewayAttributeGenerator {
+    for (key, value) in extra_attributes {
+        if !modeled_keys.contains(&key.as_str()) {
+            generator.set_attribute(key, value);
+        }
+    }
+    generator
+}
+
+/// Inserts an extra attribute unless its key is modeled by the typed event.
+fn insert_extra_attribute<S1: Into<String>, S2: Into<String>>(
+    extra_attributes: &mut BTreeMap<String, String>,
+    modeled_keys: &[&str],
+    key: S1,
+    value: S2,
+) {
+    let key = key.into();
+    if !modeled_keys.contains(&key.as_str()) {
+        extra_attributes.insert(key, value.into());
+    }
+}
+
+/// Replaces the target account address and the target kind extra attribute with those of the
+/// target.
+fn set_target(
+    target_account_address: &mut String,
+    extra_attributes: &mut BTreeMap<String, String>,
+    target: GrantTarget,
+) {
+    match target.kind_attribute() {
+        Some(kind) => {
+            extra_attributes.insert(OS_GATEWAY_KEYS.target_kind.to_string(), kind.to_string())
+        }
+        None => extra_attributes.remove(OS_GATEWAY_KEYS.target_kind),
+    };
+    *target_account_address = match target {
+        GrantTarget::Account(address)
+        | GrantTarget::Marker(address)
+        | GrantTarget::ScopeSpec(address) => address,
+    };
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use crate::{
+        dedup_events, parse_gateway_event, parse_gateway_events, sort_events, AccessGrant,
+        AccessLevel, AccessRevoke, GrantExpiration, OsGatewayAttributeGenerator, OsGatewayError,
+        OsGatewayEvent, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_access_grant_matches_fluent_generator() {
+        let generator = OsGatewayAttributeGenerator::from(AccessGrant {
+            scope_address: "scope".to_string(),
+            target_account_address: "target".to_string(),
+            access_grant_id: Some("id".to_string()),
+            expiration: None,
+            access_level: None,
+            extra_attributes: BTreeMap::new(),
+        });
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id"),
+            generator,
+            "the converted grant should match the fluently constructed generator",
+        );
+    }
+
+    #[test]
+    fn test_access_revoke_matches_fluent_generator() {
+        let generator = OsGatewayAttributeGenerator::from(AccessRevoke {
+            scope_address: "scope".to_string(),
+            target_account_address: "target".to_string(),
+            access_grant_id: None,
+            extra_attributes: BTreeMap::new(),
+        });
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke("scope", "target"),
+            generator,
+            "the converted revoke should match the fluently constructed generator",
+        );
+    }
+
+    #[test]
+    fn test_constructors_match_struct_literals() {
+        assert_eq!(
+            AccessGrant {
+                scope_address: "scope".to_string(),
+                target_account_address: "target".to_string(),
+                access_grant_id: Some("id".to_string()),
+                expiration: None,
+                access_level: None,
+                extra_attributes: BTreeMap::new(),
+            },
+            AccessGrant::new("scope", "target").with_access_grant_id("id"),
+            "the grant constructor and setter should populate every field",
+        );
+        assert_eq!(
+            AccessRevoke {
+                scope_address: "scope".to_string(),
+                target_account_address: "target".to_string(),
+                access_grant_id: None,
+                extra_attributes: BTreeMap::new(),
+            },
+            AccessRevoke::new("scope", "target").with_optional_access_grant_id(None::<String>),
+            "an absent optional access grant id should leave the field empty",
+        );
+    }
+
+    #[test]
+    fn test_typed_structs_round_trip_through_generators() {
+        let grant = AccessGrant::new("scope", "target").with_access_grant_id("id");
+        assert_eq!(
+            Ok(grant.clone()),
+            AccessGrant::try_from(OsGatewayAttributeGenerator::from(grant)),
+            "a grant should survive conversion into a generator and back",
+        );
+        let revoke = AccessRevoke::new("scope", "target");
+        assert_eq!(
+            Ok(revoke.clone()),
+            AccessRevoke::try_from(&OsGatewayAttributeGenerator::from(revoke)),
+            "a revoke should survive conversion into a generator and back",
+        );
+    }
+
+    #[test]
+    fn test_typed_structs_from_parsed_events() {
+        let events = [
+            OsGatewayAttributeGenerator::access_grant("scope", "first").to_event(),
+            OsGatewayAttributeGenerator::access_grant("scope", "second")
+                .with_access_grant_id("id")
+                .to_event(),
+        ];
+        let grants = parse_gateway_events(&events)
+            .into_iter()
+            .collect::<Result<Vec<OsGatewayEvent>, OsGatewayError>>();
+        assert_eq!(
+            Ok(vec![
+                OsGatewayEvent::AccessGrant(AccessGrant::new("scope", "first")),
+                OsGatewayEvent::AccessGrant(
+                    AccessGrant::new("scope", "second").with_access_grant_id("id"),
+                ),
+            ]),
+            grants,
+            "parsed events should produce typed grants",
+        );
+    }
+
+    #[test]
+    fn test_typed_conversion_rejects_other_event_types() {
+        assert_eq!(
+            Err(OsGatewayError::UnexpectedEventType {
+                expected: OS_GATEWAY_EVENT_TYPES.access_grant.to_string(),
+                actual: OS_GATEWAY_EVENT_TYPES.access_revoke.to_string(),
+            }),
+            AccessGrant::try_from(OsGatewayAttributeGenerator::access_revoke(
+                "scope", "target"
+            )),
+            "a revoke should not convert into a grant",
+        );
+        assert_eq!(
+            Err(OsGatewayError::UnexpectedEventType {
+                expected: OS_GATEWAY_EVENT_TYPES.access_revoke.to_string(),
+                actual: OS_GATEWAY_EVENT_TYPES.access_grant.to_string(),
+            }),
+            AccessRevoke::try_from(OsGatewayAttributeGenerator::access_grant("scope", "target")),
+            "a grant should not convert into a revoke",
+        );
+    }
+
+    #[test]
+    fn test_typed_conversion_requires_all_required_keys() {
+        assert_eq!(
+            Err(OsGatewayError::MissingRequiredAttributes {
+                keys: vec![OS_GATEWAY_KEYS.target_account.to_string()],
+            }),
+            AccessGrant::try_from(
+                OsGatewayAttributeGenerator::new()
+                    .with_event_type(OS_GATEWAY_EVENT_TYPES.access_grant)
+                    .with_scope_address("scope"),
+            ),
+            "attributes without a target account should not convert into a grant",
+        );
+    }
+
+    #[test]
+    fn test_event_accessors_abstract_over_variants() {
+        let grant = OsGatewayEvent::from(AccessGrant::new("scope", "target"));
+        let revoke = OsGatewayEvent::from(
+            AccessRevoke::new("other_scope", "other").with_access_grant_id("id"),
+        );
+        assert_eq!(
+            (OS_GATEWAY_EVENT_TYPES.access_grant, "scope", "target", None),
+            (
+                grant.event_type(),
+                grant.scope_address(),
+                grant.target_account(),
+                grant.access_grant_id(),
+            ),
+            "the accessors should expose the grant's fields",
+        );
+        assert_eq!(
+            (
+                OS_GATEWAY_EVENT_TYPES.access_revoke,
+                "other_scope",
+                "other",
+                Some("id"),
+            ),
+            (
+                revoke.event_type(),
+                revoke.scope_address(),
+                revoke.target_account(),
+                revoke.access_grant_id(),
+            ),
+            "the accessors should expose the revoke's fields",
+        );
+    }
+
+    #[test]
+    fn test_event_converts_to_and_from_generators() {
+        for generator in [
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id"),
+            OsGatewayAttributeGenerator::access_revoke("scope", "target"),
+        ] {
+            let event = OsGatewayEvent::try_from(&generator)
+                .expect("a complete generator should convert into an event");
+            assert_eq!(
+                generator,
+                OsGatewayAttributeGenerator::from(event),
+                "the event should convert back into the original generator",
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_rejects_unknown_event_types() {
+        assert_eq!(
+            Err(OsGatewayError::UnknownEventType {
+                event_type: "access_audit".to_string(),
+            }),
+            OsGatewayEvent::try_from(
+                OsGatewayAttributeGenerator::access_grant("scope", "target")
+                    .with_event_type("access_audit"),
+            ),
+            "an unrecognized event type should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_target_account_parsed() {
+        let event = OsGatewayEvent::from(AccessGrant::new(
+            "scope",
+            "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+        ));
+        assert_eq!(
+            Ok("tp"),
+            event
+                .target_account_parsed()
+                .as_ref()
+                .map(|address| address.hrp()),
+            "a valid target account should be parsed into an account address",
+        );
+        assert!(
+            matches!(
+                AccessRevoke::new("scope", "target").target_account_parsed(),
+                Err(OsGatewayError::InvalidAddress { .. }),
+            ),
+            "an invalid target account should produce an error",
+        );
+    }
+
+    #[test]
+    fn test_typed_structs_retain_extra_attributes() {
+        let mut generator = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        generator.set_attribute("object_store_gateway_source_contract", "contract");
+        let grant = AccessGrant::try_from(&generator)
+            .expect("a complete generator should convert into a grant");
+        assert_eq!(
+            AccessGrant::new("scope", "target")
+                .with_extra_attribute("object_store_gateway_source_contract", "contract"),
+            grant,
+            "unmodeled attributes should be retained as extra attributes",
+        );
+        assert_eq!(
+            generator,
+            OsGatewayAttributeGenerator::from(grant),
+            "extra attributes should be restored when converting back into a generator",
+        );
+    }
+
+    #[test]
+    fn test_extra_attributes_never_hold_modeled_fields() {
+        let revoke = AccessRevoke::new("scope", "target")
+            .with_extra_attribute(OS_GATEWAY_KEYS.scope_address, "other_scope")
+            .with_extra_attribute(OS_GATEWAY_KEYS.event_type, "access_audit");
+        assert!(
+            revoke.extra_attributes.is_empty(),
+            "modeled keys should not be added as extra attributes, but found: {:?}",
+            revoke.extra_attributes,
+        );
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke("scope", "target"),
+            OsGatewayAttributeGenerator::from(revoke),
+            "extra attributes should not replace the revoke's modeled fields",
+        );
+        let mut grant = AccessGrant::new("scope", "target");
+        grant.extra_attributes.insert(
+            OS_GATEWAY_KEYS.access_grant_id.to_string(),
+            "id".to_string(),
+        );
+        let generator = OsGatewayAttributeGenerator::from(grant);
+        assert_eq!(
+            None,
+            generator.get(OS_GATEWAY_KEYS.access_grant_id),
+            "a modeled key inserted directly into the extra attributes should not be emitted",
+        );
+        assert_eq!(
+            Ok(AccessGrant::new("scope", "target")),
+            AccessGrant::try_from(generator),
+            "the grant should convert back without the modeled extra attribute",
+        );
+    }
+
+    #[test]
+    fn test_parsing_retains_prefixed_extra_attributes() {
+        let event = OsGatewayAttributeGenerator::from(
+            AccessGrant::new("scope", "target")
+                .with_extra_attribute("object_store_gateway_source_contract", "contract"),
+        )
+        .to_event()
+        .add_attribute("unrelated", "value");
+        assert_eq!(
+            vec![Ok(OsGatewayEvent::from(
+                AccessGrant::new("scope", "target")
+                    .with_extra_attribute("object_store_gateway_source_contract", "contract"),
+            ))],
+            parse_gateway_event(&event),
+            "prefixed extra attributes should survive parsing, while unprefixed ones are skipped",
+        );
+    }
+
+    #[test]
+    fn test_grant_expiration_round_trips_through_generators() {
+        let grant =
+            AccessGrant::new("scope", "target").with_expiration(GrantExpiration::AtHeight(5));
+        assert_eq!(
+            Ok(grant.clone()),
+            AccessGrant::try_from(OsGatewayAttributeGenerator::from(grant)),
+            "a grant's expiration should survive conversion into a generator and back",
+        );
+        let mut generator = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        generator.set_attribute(OS_GATEWAY_KEYS.expiration, "soon");
+        assert!(
+            matches!(
+                AccessGrant::try_from(generator),
+                Err(OsGatewayError::InvalidExpiration { .. }),
+            ),
+            "an unparseable expiration should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_grant_access_level_round_trips_through_generators() {
+        for access_level in AccessLevel::ALL {
+            let grant = AccessGrant::new("scope", "target").with_access_level(access_level);
+            let generator = OsGatewayAttributeGenerator::from(grant.clone());
+            assert_eq!(
+                Some(access_level.as_str()),
+                generator.get(OS_GATEWAY_KEYS.access_level),
+                "the grant's access level should be emitted as its attribute value",
+            );
+            assert_eq!(
+                Ok(grant),
+                AccessGrant::try_from(generator),
+                "a grant's access level should survive conversion into a generator and back",
+            );
+        }
+        let mut generator = OsGatewayAttributeGenerator::access_grant("scope", "target");
+        generator.set_attribute(OS_GATEWAY_KEYS.access_level, "admin");
+        assert_eq!(
+            Err(OsGatewayError::UnknownAccessLevel {
+                access_level: "admin".to_string(),
+            }),
+            AccessGrant::try_from(generator),
+            "an unrecognized access level should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_revokes_retain_expirations_as_extra_attributes() {
+        let mut generator = OsGatewayAttributeGenerator::access_revoke("scope", "target");
+        generator.set_attribute(
+            OS_GATEWAY_KEYS.expiration,
+            GrantExpiration::Never.to_string(),
+        );
+        let revoke = AccessRevoke::try_from(&generator)
+            .expect("a complete generator should convert into a revoke");
+        assert_eq!(
+            AccessRevoke::new("scope", "target")
+                .with_extra_attribute(OS_GATEWAY_KEYS.expiration, "never"),
+            revoke,
+            "a revoke does not model expirations, so it should retain them as extra attributes",
+        );
+        assert_eq!(
+            generator,
+            OsGatewayAttributeGenerator::from(revoke),
+            "the retained expiration should be restored when converting back into a generator",
+        );
+    }
+
+    #[test]
+    fn test_sort_events_pins_ordering() {
+        let mut events = vec![
+            OsGatewayEvent::from(AccessGrant::new("scope_b", "target_a")),
+            OsGatewayEvent::from(AccessGrant::new("scope_a", "target_b")),
+            OsGatewayEvent::from(AccessGrant::new("scope_a", "target_a").with_access_grant_id("b")),
+            OsGatewayEvent::from(AccessGrant::new("scope_a", "target_a").with_access_grant_id("a")),
+            OsGatewayEvent::from(AccessRevoke::new("scope_a", "target_a")),
+            OsGatewayEvent::from(AccessGrant::new("scope_a", "target_a")),
+        ];
+        sort_events(&mut events);
+        assert_eq!(
+            vec![
+                OsGatewayEvent::from(AccessGrant::new("scope_a", "target_a")),
+                OsGatewayEvent::from(AccessRevoke::new("scope_a", "target_a")),
+                OsGatewayEvent::from(
+                    AccessGrant::new("scope_a", "target_a").with_access_grant_id("a"),
+                ),
+                OsGatewayEvent::from(
+                    AccessGrant::new("scope_a", "target_a").with_access_grant_id("b"),
+                ),
+                OsGatewayEvent::from(AccessGrant::new("scope_a", "target_b")),
+                OsGatewayEvent::from(AccessGrant::new("scope_b", "target_a")),
+            ],
+            events,
+            "events should sort by scope, then target, then grant id, then event type",
+        );
+    }
+
+    #[test]
+    fn test_ordering_is_consistent_with_equality() {
+        let grant = AccessGrant::new("scope", "target");
+        let expiring = grant.clone().with_expiration(GrantExpiration::AtHeight(5));
+        let annotated = grant
+            .clone()
+            .with_extra_attribute("object_store_gateway_memo", "memo");
+        for other in [&expiring, &annotated] {
+            assert_ne!(
+                std::cmp::Ordering::Equal,
+                grant.cmp(other),
+                "grants that differ only in unsorted fields should not compare as equal",
+            );
+        }
+        assert_eq!(
+            std::cmp::Ordering::Equal,
+            grant.cmp(&grant.clone()),
+            "equal grants should compare as equal",
+        );
+    }
+
+    #[test]
+    fn test_dedup_events_keeps_first_occurrences() {
+        let first = OsGatewayEvent::from(AccessRevoke::new("scope", "target"));
+        let second = OsGatewayEvent::from(AccessGrant::new("scope", "target"));
+        let mut events = vec![
+            first.clone(),
+            second.clone(),
+            first.clone(),
+            OsGatewayEvent::from(AccessGrant::new("scope", "target").with_access_grant_id("id")),
+            second.clone(),
+        ];
+        dedup_events(&mut events);
+        assert_eq!(
+            vec![
+                first,
+                second,
+                OsGatewayEvent::from(
+                    AccessGrant::new("scope", "target").with_access_grant_id("id"),
+                ),
+            ],
+            events,
+            "only exact duplicates should be removed, retaining the original order",
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    mod properties {
+        use crate::gateway_event::{GRANT_KEYS, REVOKE_KEYS};
+        use crate::strategies::{access_level, grant_expiration};
+        use crate::{
+            parse_gateway_event, AccessGrant, AccessRevoke, OsGatewayAttributeGenerator,
+            OsGatewayEvent, OsGatewayKey, OS_GATEWAY_KEYS, OS_GATEWAY_PAYLOAD_KEY,
+        };
+        use proptest::collection::btree_map;
+        use proptest::option;
+        use proptest::prelude::*;
+        use proptest::sample::select;
+        use std::collections::BTreeMap;
+
+        /// Generates extra attributes keyed by a mix of the given modeled keys, prefixed keys that
+        /// the gateway does not recognize, and unprefixed keys.
+        fn extra_attributes(
+            modeled_keys: &'static [&'static str],
+        ) -> impl Strategy<Value = BTreeMap<String, String>> {
+            let key = prop_oneof![
+                select(modeled_keys).prop_map(str::to_string),
+                "object_store_gateway_[a-z_]{1,16}".prop_filter(
+                    "the key should not be recognized by the gateway",
+                    |key| {
+                        key != OS_GATEWAY_PAYLOAD_KEY
+                            && OsGatewayKey::ALL.iter().all(|known| key != known.as_str())
+                    },
+                ),
+                "[a-z][a-z_]{0,15}".prop_filter("the key should not be prefixed", |key| {
+                    !key.starts_with("object_store_gateway_")
+                }),
+            ];
+            btree_map(key, any::<String>(), 0..4)
+        }
+
+        /// Removes the modeled keys, which typed events never retain as extra attributes.
+        fn unmodeled(
+            extra_attributes: &BTreeMap<String, String>,
+            modeled_keys: &[&str],
+        ) -> BTreeMap<String, String> {
+            extra_attributes
+                .iter()
+                .filter(|(key, _)| !modeled_keys.contains(&key.as_str()))
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect()
+        }
+
+        /// Removes the unprefixed keys, which parsing skips.
+        fn prefixed(extra_attributes: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+            extra_attributes
+                .iter()
+                .filter(|(key, _)| key.starts_with("object_store_gateway_"))
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect()
+        }
+
+        proptest! {
+            #[test]
+            fn test_access_grant_round_trips(
+                scope_address in any::<String>(),
+                target_account_address in any::<String>(),
+                access_grant_id in option::of(any::<String>()),
+                expiration in option::of(grant_expiration()),
+                access_level in option::of(access_level()),
+                extra_attributes in extra_attributes(&GRANT_KEYS),
+            ) {
+                let grant = AccessGrant {
+                    scope_address,
+                    target_account_address,
+                    access_grant_id,
+                    expiration,
+                    access_level,
+                    extra_attributes,
+                };
+                let expected = AccessGrant {
+                    extra_attributes: unmodeled(&grant.extra_attributes, &GRANT_KEYS),
+                    ..grant.clone()
+                };
+                let generator = OsGatewayAttributeGenerator::from(grant);
+                prop_assert_eq!(
+                    expiration.map(|expiration| expiration.to_string()),
+                    generator.get(OS_GATEWAY_KEYS.expiration).map(str::to_string),
+                    "the grant's expiration should be emitted in its wire encoding",
+                );
+                prop_assert_eq!(
+                    Ok(expected.clone()),
+                    AccessGrant::try_from(&generator),
+                    "the grant should survive conversion into a generator and back",
+                );
+                let parsed = AccessGrant {
+                    extra_attributes: prefixed(&expected.extra_attributes),
+                    ..expected
+                };
+                prop_assert_eq!(
+                    vec![Ok(OsGatewayEvent::from(parsed))],
+                    parse_gateway_event(&generator.to_event()),
+                    "the emitted grant should parse back, retaining its prefixed extra attributes",
+                );
+            }
+
+            #[test]
+            fn test_access_revoke_round_trips(
+                scope_address in any::<String>(),
+                target_account_address in any::<String>(),
+                access_grant_id in option::of(any::<String>()),
+                extra_attributes in extra_attributes(&REVOKE_KEYS),
+            ) {
+                let revoke = AccessRevoke {
+                    scope_address,
+                    target_account_address,
+                    access_grant_id,
+                    extra_attributes,
+                };
+                let expected = AccessRevoke {
+                    extra_attributes: unmodeled(&revoke.extra_attributes, &REVOKE_KEYS),
+                    ..revoke.clone()
+                };
+                let generator = OsGatewayAttributeGenerator::from(revoke);
+                prop_assert_eq!(
+                    Ok(expected.clone()),
+                    AccessRevoke::try_from(&generator),
+                    "the revoke should survive conversion into a generator and back",
+                );
+                let parsed = AccessRevoke {
+                    extra_attributes: prefixed(&expected.extra_attributes),
+                    ..expected
+                };
+                prop_assert_eq!(
+                    vec![Ok(OsGatewayEvent::from(parsed))],
+                    parse_gateway_event(&generator.to_event()),
+                    "the emitted revoke should parse back, retaining its prefixed extra attributes",
+                );
+            }
+        }
+    }
+}
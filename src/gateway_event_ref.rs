@@ -0,0 +1,460 @@
+use crate::attribute_keys::REQUIRED_KEYS;
+use crate::gateway_event::{GRANT_KEYS, REVOKE_KEYS};
+use crate::{
+    AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, GrantTarget, OsGatewayError,
+    OsGatewayEvent, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+};
+use std::collections::BTreeMap;
+
+/// The fields shared by every typed gateway event, whether it owns its values or borrows them.
+/// Generic code that only reads events can accept any implementor, such as both an
+/// [OsGatewayEvent](crate::OsGatewayEvent) and an [OsGatewayEventRef](crate::OsGatewayEventRef).
+///
+/// ```
+/// use os_gateway_contract_attributes::{parse_gateway_event_ref, GatewayEventFields, OsGatewayAttributeGenerator};
+///
+/// fn describe<E: GatewayEventFields>(event: &E) -> String {
+///     format!("{} of {} for {}", event.event_type(), event.scope_address(), event.target_account())
+/// }
+///
+/// let event = OsGatewayAttributeGenerator::access_grant("scope", "target").to_event();
+/// for parsed in parse_gateway_event_ref(&event) {
+///     assert_eq!("access_grant of scope for target", describe(&parsed.unwrap()));
+/// }
+/// ```
+pub trait GatewayEventFields {
+    /// The value of the [event type attribute](crate::OsGatewayKeys) that denotes this kind of
+    /// event.
+    fn event_type(&self) -> &str;
+
+    /// The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which this event refers.
+    fn scope_address(&self) -> &str;
+
+    /// The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// upon which this event takes action.
+    fn target_account(&self) -> &str;
+
+    /// The access grant id included with this event, if any.
+    fn access_grant_id(&self) -> Option<&str>;
+
+    /// The target of this event.  See [GrantTarget](crate::GrantTarget).  Defaults to an account
+    /// target, and is overridden by the typed events to honor their target kind attribute.
+    fn target(&self) -> GrantTarget {
+        GrantTarget::Account(self.target_account().to_owned())
+    }
+
+    /// Whether or not this event is an access grant.
+    fn is_access_grant(&self) -> bool {
+        self.event_type() == OS_GATEWAY_EVENT_TYPES.access_grant
+    }
+
+    /// Whether or not this event is an access revoke.
+    fn is_access_revoke(&self) -> bool {
+        self.event_type() == OS_GATEWAY_EVENT_TYPES.access_revoke
+    }
+}
+
+/// A borrowed view of an [AccessGrant](crate::AccessGrant), whose values are borrowed from the
+/// attributes from which it was parsed rather than copied.
+///
+/// # Parameters
+///
+/// * `scope_address` See [AccessGrant](crate::AccessGrant).
+/// * `target_account_address` See [AccessGrant](crate::AccessGrant).
+/// * `access_grant_id` See [AccessGrant](crate::AccessGrant).
+/// * `expiration` See [AccessGrant](crate::AccessGrant).
+/// * `access_level` See [AccessGrant](crate::AccessGrant).
+/// * `extra_attributes` See [AccessGrant](crate::AccessGrant).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AccessGrantRef<'a> {
+    pub scope_address: &'a str,
+    pub target_account_address: &'a str,
+    pub access_grant_id: Option<&'a str>,
+    pub expiration: Option<GrantExpiration>,
+    pub access_level: Option<AccessLevel>,
+    pub extra_attributes: BTreeMap<&'a str, &'a str>,
+}
+impl AccessGrantRef<'_> {
+    /// Copies every borrowed value, producing the equivalent owned [AccessGrant](crate::AccessGrant).
+    pub fn to_owned(&self) -> AccessGrant {
+        AccessGrant {
+            scope_address: self.scope_address.to_owned(),
+            target_account_address: self.target_account_address.to_owned(),
+            access_grant_id: self.access_grant_id.map(str::to_owned),
+            expiration: self.expiration,
+            access_level: self.access_level,
+            extra_attributes: owned_extra_attributes(&self.extra_attributes),
+        }
+    }
+}
+
+/// A borrowed view of an [AccessRevoke](crate::AccessRevoke), whose values are borrowed from the
+/// attributes from which it was parsed rather than copied.
+///
+/// # Parameters
+///
+/// * `scope_address` See [AccessRevoke](crate::AccessRevoke).
+/// * `target_account_address` See [AccessRevoke](crate::AccessRevoke).
+/// * `access_grant_id` See [AccessRevoke](crate::AccessRevoke).
+/// * `extra_attributes` See [AccessRevoke](crate::AccessRevoke).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AccessRevokeRef<'a> {
+    pub scope_address: &'a str,
+    pub target_account_address: &'a str,
+    pub access_grant_id: Option<&'a str>,
+    pub extra_attributes: BTreeMap<&'a str, &'a str>,
+}
+impl AccessRevokeRef<'_> {
+    /// Copies every borrowed value, producing the equivalent owned [AccessRevoke](crate::AccessRevoke).
+    pub fn to_owned(&self) -> AccessRevoke {
+        AccessRevoke {
+            scope_address: self.scope_address.to_owned(),
+            target_account_address: self.target_account_address.to_owned(),
+            access_grant_id: self.access_grant_id.map(str::to_owned),
+            extra_attributes: owned_extra_attributes(&self.extra_attributes),
+        }
+    }
+}
+
+/// A borrowed view of an [OsGatewayEvent](crate::OsGatewayEvent), produced by [parse_gateway_events_ref](crate::parse_gateway_events_ref).
+/// New variants will be added alongside those of [OsGatewayEvent](crate::OsGatewayEvent), so
+/// matches on this enum must include a wildcard arm.
+///
+/// # Variants
+///
+/// * `AccessGrant` A borrowed view of an access grant.
+///
+/// * `AccessRevoke` A borrowed view of an access revoke.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OsGatewayEventRef<'a> {
+    AccessGrant(AccessGrantRef<'a>),
+    AccessRevoke(AccessRevokeRef<'a>),
+}
+impl<'a> OsGatewayEventRef<'a> {
+    /// Copies every borrowed value, producing the equivalent owned [OsGatewayEvent](crate::OsGatewayEvent).
+    pub fn to_owned(&self) -> OsGatewayEvent {
+        match self {
+            Self::AccessGrant(grant) => OsGatewayEvent::AccessGrant(grant.to_owned()),
+            Self::AccessRevoke(revoke) => OsGatewayEvent::AccessRevoke(revoke.to_owned()),
+        }
+    }
+
+    /// Interprets a single gateway event's attribute key and value pairs without copying them.
+    /// When a key is provided more than once, the last value provided for it is used.  Produces the
+    /// same errors as converting an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator)
+    /// into an [OsGatewayEvent](crate::OsGatewayEvent).
+    pub(crate) fn from_pairs<I: IntoIterator<Item = (&'a str, &'a str)>>(
+        pairs: I,
+    ) -> Result<Self, OsGatewayError> {
+        let attributes = pairs.into_iter().collect::<BTreeMap<&str, &str>>();
+        let missing_keys = REQUIRED_KEYS
+            .into_iter()
+            .filter(|key| !attributes.contains_key(key))
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>();
+        if !missing_keys.is_empty() {
+            return Err(OsGatewayError::MissingRequiredAttributes { keys: missing_keys });
+        }
+        let event_type = attributes[OS_GATEWAY_KEYS.event_type];
+        let scope_address = attributes[OS_GATEWAY_KEYS.scope_address];
+        let target_account_address = attributes[OS_GATEWAY_KEYS.target_account];
+        let access_grant_id = attributes.get(OS_GATEWAY_KEYS.access_grant_id).copied();
+        if event_type == OS_GATEWAY_EVENT_TYPES.access_grant {
+            Ok(Self::AccessGrant(AccessGrantRef {
+                scope_address,
+                target_account_address,
+                access_grant_id,
+                expiration: attributes
+                    .get(OS_GATEWAY_KEYS.expiration)
+                    .map(|expiration| expiration.parse::<GrantExpiration>())
+                    .transpose()?,
+                access_level: attributes
+                    .get(OS_GATEWAY_KEYS.access_level)
+                    .map(|access_level| access_level.parse::<AccessLevel>())
+                    .transpose()?,
+                extra_attributes: borrowed_extra_attributes(&attributes, &GRANT_KEYS),
+            }))
+        } else if event_type == OS_GATEWAY_EVENT_TYPES.access_revoke {
+            Ok(Self::AccessRevoke(AccessRevokeRef {
+                scope_address,
+                target_account_address,
+                access_grant_id,
+                extra_attributes: borrowed_extra_attributes(&attributes, &REVOKE_KEYS),
+            }))
+        } else {
+            Err(OsGatewayError::UnknownEventType {
+                event_type: event_type.to_string(),
+            })
+        }
+    }
+}
+
+impl GatewayEventFields for AccessGrant {
+    fn event_type(&self) -> &str {
+        OS_GATEWAY_EVENT_TYPES.access_grant
+    }
+
+    fn scope_address(&self) -> &str {
+        &self.scope_address
+    }
+
+    fn target_account(&self) -> &str {
+        &self.target_account_address
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        self.access_grant_id.as_deref()
+    }
+
+    fn target(&self) -> GrantTarget {
+        AccessGrant::target(self)
+    }
+}
+impl GatewayEventFields for AccessRevoke {
+    fn event_type(&self) -> &str {
+        OS_GATEWAY_EVENT_TYPES.access_revoke
+    }
+
+    fn scope_address(&self) -> &str {
+        &self.scope_address
+    }
+
+    fn target_account(&self) -> &str {
+        &self.target_account_address
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        self.access_grant_id.as_deref()
+    }
+
+    fn target(&self) -> GrantTarget {
+        AccessRevoke::target(self)
+    }
+}
+impl GatewayEventFields for OsGatewayEvent {
+    fn event_type(&self) -> &str {
+        OsGatewayEvent::event_type(self)
+    }
+
+    fn scope_address(&self) -> &str {
+        OsGatewayEvent::scope_address(self)
+    }
+
+    fn target_account(&self) -> &str {
+        OsGatewayEvent::target_account(self)
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        OsGatewayEvent::access_grant_id(self)
+    }
+
+    fn target(&self) -> GrantTarget {
+        OsGatewayEvent::target(self)
+    }
+}
+impl GatewayEventFields for AccessGrantRef<'_> {
+    fn event_type(&self) -> &str {
+        OS_GATEWAY_EVENT_TYPES.access_grant
+    }
+
+    fn scope_address(&self) -> &str {
+        self.scope_address
+    }
+
+    fn target_account(&self) -> &str {
+        self.target_account_address
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        self.access_grant_id
+    }
+
+    fn target(&self) -> GrantTarget {
+        GrantTarget::from_kind(
+            self.extra_attributes
+                .get(OS_GATEWAY_KEYS.target_kind)
+                .copied(),
+            self.target_account_address,
+        )
+    }
+}
+impl GatewayEventFields for AccessRevokeRef<'_> {
+    fn event_type(&self) -> &str {
+        OS_GATEWAY_EVENT_TYPES.access_revoke
+    }
+
+    fn scope_address(&self) -> &str {
+        self.scope_address
+    }
+
+    fn target_account(&self) -> &str {
+        self.target_account_address
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        self.access_grant_id
+    }
+
+    fn target(&self) -> GrantTarget {
+        GrantTarget::from_kind(
+            self.extra_attributes
+                .get(OS_GATEWAY_KEYS.target_kind)
+                .copied(),
+            self.target_account_address,
+        )
+    }
+}
+impl GatewayEventFields for OsGatewayEventRef<'_> {
+    fn event_type(&self) -> &str {
+        match self {
+            Self::AccessGrant(grant) => grant.event_type(),
+            Self::AccessRevoke(revoke) => revoke.event_type(),
+        }
+    }
+
+    fn scope_address(&self) -> &str {
+        match self {
+            Self::AccessGrant(grant) => grant.scope_address,
+            Self::AccessRevoke(revoke) => revoke.scope_address,
+        }
+    }
+
+    fn target_account(&self) -> &str {
+        match self {
+            Self::AccessGrant(grant) => grant.target_account_address,
+            Self::AccessRevoke(revoke) => revoke.target_account_address,
+        }
+    }
+
+    fn access_grant_id(&self) -> Option<&str> {
+        match self {
+            Self::AccessGrant(grant) => grant.access_grant_id,
+            Self::AccessRevoke(revoke) => revoke.access_grant_id,
+        }
+    }
+
+    fn target(&self) -> GrantTarget {
+        match self {
+            Self::AccessGrant(grant) => grant.target(),
+            Self::AccessRevoke(revoke) => revoke.target(),
+        }
+    }
+}
+
+fn borrowed_extra_attributes<'a>(
+    attributes: &BTreeMap<&'a str, &'a str>,
+    modeled_keys: &[&str],
+) -> BTreeMap<&'a str, &'a str> {
+    attributes
+        .iter()
+        .filter(|(key, _)| !modeled_keys.contains(key))
+        .map(|(key, value)| (*key, *value))
+        .collect()
+}
+
+fn owned_extra_attributes(extra_attributes: &BTreeMap<&str, &str>) -> BTreeMap<String, String> {
+    extra_attributes
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_gateway_event, parse_gateway_event_ref, parse_gateway_events_ref, AccessGrant,
+        AccessRevoke, GatewayEventFields, GrantExpiration, GrantTarget,
+        OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, OS_GATEWAY_EVENT_TYPES,
+    };
+    use cosmwasm_std::Event;
+
+    fn fields<E: GatewayEventFields>(event: &E) -> (String, String, String, Option<String>) {
+        (
+            event.event_type().to_string(),
+            event.scope_address().to_string(),
+            event.target_account().to_string(),
+            event.access_grant_id().map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_borrowed_parse_matches_owned_parse() {
+        let event = Event::new("wasm")
+            .add_attributes(
+                OsGatewayAttributeGenerator::grant("scope", "target")
+                    .with_access_grant_id("id")
+                    .with_expiration(GrantExpiration::AtHeight(5)),
+            )
+            .add_attributes(OsGatewayAttributeGenerator::access_revoke("scope", "other"))
+            .add_attribute("object_store_gateway_memo", "memo");
+        let owned = parse_gateway_event(&event);
+        let borrowed = parse_gateway_event_ref(&event);
+        assert_eq!(2, borrowed.len(), "both gateway events should be parsed");
+        for (owned, borrowed) in owned.iter().zip(borrowed.iter()) {
+            let owned = owned.as_ref().expect("the owned event should parse");
+            let borrowed = borrowed.as_ref().expect("the borrowed event should parse");
+            assert_eq!(
+                owned,
+                &borrowed.to_owned(),
+                "the borrowed event should convert into the owned event",
+            );
+            assert_eq!(
+                fields(owned),
+                fields(borrowed),
+                "the owned and borrowed events should expose the same fields",
+            );
+        }
+    }
+
+    #[test]
+    fn test_borrowed_parse_reports_errors() {
+        let events = [
+            Event::new("wasm").add_attribute("object_store_gateway_scope_address", "scope"),
+            Event::new("wasm").add_attributes(
+                OsGatewayAttributeGenerator::access_grant("scope", "target")
+                    .with_event_type("access_audit"),
+            ),
+        ];
+        assert_eq!(
+            vec![
+                Err(OsGatewayError::MissingRequiredAttributes {
+                    keys: vec![
+                        "object_store_gateway_event_type".to_string(),
+                        "object_store_gateway_target_account_address".to_string(),
+                    ],
+                }),
+                Err(OsGatewayError::UnknownEventType {
+                    event_type: "access_audit".to_string(),
+                }),
+            ],
+            parse_gateway_events_ref(&events),
+            "the borrowed parser should report the same errors as the owned parser",
+        );
+    }
+
+    #[test]
+    fn test_predicates_are_shared() {
+        let grant = AccessGrant::new("scope", "target");
+        let revoke = OsGatewayEvent::from(AccessRevoke::new("scope", "target"));
+        assert!(
+            grant.is_access_grant() && !grant.is_access_revoke(),
+            "a grant should be identified as a grant",
+        );
+        assert!(
+            revoke.is_access_revoke() && !revoke.is_access_grant(),
+            "a revoke should be identified as a revoke",
+        );
+        assert_eq!(
+            OS_GATEWAY_EVENT_TYPES.access_grant,
+            GatewayEventFields::event_type(&grant),
+            "the grant's event type should be exposed through the trait",
+        );
+        assert_eq!(
+            GrantTarget::Account("target".to_string()),
+            GatewayEventFields::target(&revoke),
+            "the target should be exposed through the trait",
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::OsGatewayError;
+use alloc::string::ToString;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+use cosmwasm_std::{Env, Timestamp};
+
+/// The encoded value of a grant that never expires.
+const NEVER: &str = "never";
+
+/// The prefix of an encoded expiration at a block height.
+const HEIGHT_PREFIX: &str = "height:";
+
+/// Describes the recognized encodings when a value matches none of them.
+const EXPECTED_ENCODING: &str =
+    "expected never, seconds since the unix epoch, or a height-prefixed block height";
+
+/// The point at which an access grant stops being honored by [Object Store Gateway](https://github.com/provenance-io/object-store-gateway).
+///
+/// The wire encoding used for the [expiration attribute](crate::OsGatewayKeys) lives entirely in
+/// this type's [Display] and [FromStr] implementations:
+///
+/// * `never` for grants that never expire.
+/// * Plain seconds since the unix epoch, such as `1700000000`, for grants that expire at a block
+///   time.
+/// * `height:` followed by a block height, such as `height:12345`, for grants that expire at a
+///   block height.
+///
+/// ```
+/// # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+/// use cosmwasm_std::Timestamp;
+/// use os_gateway_contract_attributes::{GrantExpiration, OsGatewayAttributeGenerator};
+///
+/// let generator = OsGatewayAttributeGenerator::grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .with_expiration(Timestamp::from_seconds(1700000000).into())
+/// .build();
+/// assert_eq!(Some("1700000000"), generator.get("object_store_gateway_expiration"));
+/// # }
+/// ```
+///
+/// # Variants
+///
+/// * `Never` The grant remains in effect until it is revoked.
+///
+/// * `AtSeconds` The grant expires once the block time reaches the contained number of seconds
+///   since the unix epoch.
+///
+/// * `AtHeight` The grant expires once the block height reaches the contained value.
+///
+/// Expirations are ordered by variant, in declaration order, and then by their contained value.
+/// Block times and block heights are not comparable with one another, so this ordering is only
+/// meant to provide a stable sort.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GrantExpiration {
+    Never,
+    AtSeconds(u64),
+    AtHeight(u64),
+}
+impl GrantExpiration {
+    /// Whether or not the expiration has been reached by the block described in the environment.
+    /// A grant is considered expired from the block at which its expiration is reached onward.
+    ///
+    /// # Parameters
+    ///
+    /// * `env` The environment of the currently executing contract.
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    pub fn is_expired(&self, env: &Env) -> bool {
+        match self {
+            Self::Never => false,
+            Self::AtSeconds(seconds) => env.block.time.seconds() >= *seconds,
+            Self::AtHeight(height) => env.block.height >= *height,
+        }
+    }
+}
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+impl From<Timestamp> for GrantExpiration {
+    fn from(timestamp: Timestamp) -> Self {
+        Self::AtSeconds(timestamp.seconds())
+    }
+}
+impl Display for GrantExpiration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Never => f.write_str(NEVER),
+            Self::AtSeconds(seconds) => write!(f, "{seconds}"),
+            Self::AtHeight(height) => write!(f, "{HEIGHT_PREFIX}{height}"),
+        }
+    }
+}
+/// Parses the wire encoding of an expiration, producing an [OsGatewayError::InvalidExpiration](crate::OsGatewayError::InvalidExpiration)
+/// if the value is not a recognized encoding.
+impl FromStr for GrantExpiration {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| OsGatewayError::InvalidExpiration {
+            expiration: s.to_string(),
+            reason: reason.to_string(),
+        };
+        if s == NEVER {
+            return Ok(Self::Never);
+        }
+        match s.strip_prefix(HEIGHT_PREFIX) {
+            Some(height) => parse_u64(height)
+                .map(Self::AtHeight)
+                .ok_or_else(|| invalid("expected a block height after the height prefix")),
+            None => parse_u64(s)
+                .map(Self::AtSeconds)
+                .ok_or_else(|| invalid(EXPECTED_ENCODING)),
+        }
+    }
+}
+/// Serializes the expiration as its wire encoding.
+#[cfg(feature = "serde")]
+impl serde::Serialize for GrantExpiration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+/// Deserializes the expiration from its wire encoding.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GrantExpiration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parses only plain decimal digits, rejecting the signs and whitespace that [u64::from_str]
+/// would otherwise allow, so that every value has exactly one encoding.
+fn parse_u64(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GrantExpiration, OsGatewayError};
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use cosmwasm_std::testing::mock_env;
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use cosmwasm_std::Timestamp;
+
+    #[test]
+    fn test_wire_encoding_round_trips() {
+        for (expiration, encoded) in [
+            (GrantExpiration::Never, "never"),
+            (GrantExpiration::AtSeconds(1700000000), "1700000000"),
+            (GrantExpiration::AtHeight(12345), "height:12345"),
+            (GrantExpiration::AtSeconds(0), "0"),
+        ] {
+            assert_eq!(
+                encoded,
+                expiration.to_string(),
+                "the expiration [{expiration:?}] should display as its wire encoding",
+            );
+            assert_eq!(
+                Ok(expiration),
+                encoded.parse::<GrantExpiration>(),
+                "the encoding [{encoded}] should parse back into the same expiration",
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_encodings_are_rejected() {
+        for encoded in [
+            "",
+            "Never",
+            "+5",
+            " 5",
+            "height:",
+            "height:-1",
+            "at:5",
+            "1.5",
+        ] {
+            assert!(
+                matches!(
+                    encoded.parse::<GrantExpiration>(),
+                    Err(OsGatewayError::InvalidExpiration { .. }),
+                ),
+                "the encoding [{encoded}] should be rejected",
+            );
+        }
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_timestamp_conversion() {
+        assert_eq!(
+            GrantExpiration::AtSeconds(1700000000),
+            GrantExpiration::from(Timestamp::from_nanos(1_700_000_000_999_999_999)),
+            "a timestamp should convert into whole seconds",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_is_expired() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let height = env.block.height;
+        for (expiration, expected) in [
+            (GrantExpiration::Never, false),
+            (GrantExpiration::AtSeconds(now + 1), false),
+            (GrantExpiration::AtSeconds(now), true),
+            (GrantExpiration::AtHeight(height + 1), false),
+            (GrantExpiration::AtHeight(height), true),
+        ] {
+            assert_eq!(
+                expected,
+                expiration.is_expired(&env),
+                "the expiration [{expiration}] should report expired as [{expected}]",
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_wire_encoding() {
+        let json = cosmwasm_std::to_json_vec(&GrantExpiration::AtHeight(12345)).unwrap();
+        assert_eq!(
+            b"\"height:12345\"".to_vec(),
+            json,
+            "the expiration should serialize as its wire encoding",
+        );
+        assert_eq!(
+            GrantExpiration::AtHeight(12345),
+            cosmwasm_std::from_json::<GrantExpiration>(&json).unwrap(),
+            "the expiration should survive a serde round trip",
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{AccessGrant, AccessRevoke};
+use std::fmt::{Display, Formatter};
+
+/// The separator placed between the components of a rendered key.
+const KEY_SEPARATOR: char = '/';
+
+/// The identity of an access grant: the scope it refers to, the account it targets, and its access
+/// grant id, if any.  Grants with equal keys describe the same grant, so the key is suitable for
+/// storing emitted grants in maps and sets.
+///
+/// When the `storage` feature is enabled, the key can be used directly as a
+/// [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus) `Map` key, which is prefixable by
+/// scope address, and then by scope address and target account, or as the prefix of a composite
+/// key.  A missing access grant id is stored as an empty key component, so an empty id, which is
+/// never a valid [AccessGrantId](crate::AccessGrantId), is loaded back as a missing id.
+///
+/// Keys render as their components separated by slashes, omitting a missing access grant id, such
+/// as `scope1qzn7jghj8puprmdcvunm3330jutsj803zz/tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr/my_unique_id`.
+///
+/// # Parameters
+///
+/// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+///   to which the grant refers.
+/// * `target_account` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   to which the grant refers.
+/// * `access_grant_id` The unique identifier of the grant, if any.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GrantKey {
+    pub scope_address: String,
+    pub target_account: String,
+    pub access_grant_id: Option<String>,
+}
+impl GrantKey {
+    /// Creates a key from its components.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope to which the grant refers.
+    /// * `target_account` The bech32 address of the account to which the grant refers.
+    /// * `access_grant_id` The unique identifier of the grant, if any.
+    pub fn new<S1: Into<String>, S2: Into<String>, S3: Into<String>>(
+        scope_address: S1,
+        target_account: S2,
+        access_grant_id: Option<S3>,
+    ) -> Self {
+        Self {
+            scope_address: scope_address.into(),
+            target_account: target_account.into(),
+            access_grant_id: access_grant_id.map(|access_grant_id| access_grant_id.into()),
+        }
+    }
+}
+impl From<&AccessGrant> for GrantKey {
+    fn from(grant: &AccessGrant) -> Self {
+        Self::new(
+            &grant.scope_address,
+            &grant.target_account_address,
+            grant.access_grant_id.as_ref(),
+        )
+    }
+}
+/// Identifies the grant that a revoke removes.  A revoke without an access grant id removes every
+/// grant for its scope and target account, so its key only matches grants that also lack an id.
+impl From<&AccessRevoke> for GrantKey {
+    fn from(revoke: &AccessRevoke) -> Self {
+        Self::new(
+            &revoke.scope_address,
+            &revoke.target_account_address,
+            revoke.access_grant_id.as_ref(),
+        )
+    }
+}
+impl Display for GrantKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{KEY_SEPARATOR}{}",
+            self.scope_address, self.target_account,
+        )?;
+        match &self.access_grant_id {
+            Some(access_grant_id) => write!(f, "{KEY_SEPARATOR}{access_grant_id}"),
+            None => Ok(()),
+        }
+    }
+}
+/// Stores the key as three components: the scope address, the target account, and the access
+/// grant id, which is empty when missing.
+#[cfg(feature = "storage")]
+impl<'a> cw_storage_plus::PrimaryKey<'a> for GrantKey {
+    type Prefix = (&'a str, &'a str);
+    type SubPrefix = &'a str;
+    type Suffix = String;
+    type SuperSuffix = (String, String);
+
+    fn key(&self) -> Vec<cw_storage_plus::Key<'_>> {
+        vec![
+            cw_storage_plus::Key::Ref(self.scope_address.as_bytes()),
+            cw_storage_plus::Key::Ref(self.target_account.as_bytes()),
+            cw_storage_plus::Key::Ref(
+                self.access_grant_id
+                    .as_deref()
+                    .unwrap_or_default()
+                    .as_bytes(),
+            ),
+        ]
+    }
+}
+/// Prefixes other key components with the key's three components, so that entries stored beneath a
+/// grant can be iterated together.  Read those entries through the map's `prefix`, as
+/// cw-storage-plus cannot load a whole composite key whose first element spans several components.
+#[cfg(feature = "storage")]
+impl<'a> cw_storage_plus::Prefixer<'a> for GrantKey {
+    fn prefix(&self) -> Vec<cw_storage_plus::Key<'_>> {
+        cw_storage_plus::PrimaryKey::key(self)
+    }
+}
+/// Loads the key from its three stored components, treating an empty access grant id as missing.
+#[cfg(feature = "storage")]
+impl cw_storage_plus::KeyDeserialize for GrantKey {
+    type Output = Self;
+
+    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+        let (scope_address, target_account, access_grant_id) =
+            <(String, String, String)>::from_vec(value)?;
+        Ok(Self::new(
+            scope_address,
+            target_account,
+            Some(access_grant_id).filter(|access_grant_id| !access_grant_id.is_empty()),
+        ))
+    }
+}
+#[cfg(feature = "storage")]
+impl cw_storage_plus::KeyDeserialize for &GrantKey {
+    type Output = GrantKey;
+
+    fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+        <GrantKey as cw_storage_plus::KeyDeserialize>::from_vec(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrant, AccessRevoke, GrantExpiration, GrantKey};
+    use std::collections::BTreeSet;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    #[test]
+    fn test_keys_identify_grants() {
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("id");
+        assert_eq!(
+            GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("id")),
+            GrantKey::from(&grant),
+            "the key should contain the grant's identifying fields",
+        );
+        assert_eq!(
+            GrantKey::from(&grant),
+            GrantKey::from(
+                &AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("id")
+            ),
+            "a revoke by id should produce the key of the grant it removes",
+        );
+        let keys = [
+            GrantKey::from(&grant),
+            GrantKey::from(&grant.clone().with_expiration(GrantExpiration::Never)),
+            GrantKey::from(&AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<GrantKey>>();
+        assert_eq!(
+            2,
+            keys.len(),
+            "grants that differ only in non-identifying fields should share a key",
+        );
+    }
+
+    #[test]
+    fn test_display_joins_components() {
+        assert_eq!(
+            format!("{SCOPE_ADDRESS}/{ACCOUNT_ADDRESS}/id"),
+            GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("id")).to_string(),
+            "the key should render every component",
+        );
+        assert_eq!(
+            format!("{SCOPE_ADDRESS}/{ACCOUNT_ADDRESS}"),
+            GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None::<String>).to_string(),
+            "a missing access grant id should be omitted",
+        );
+    }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn test_keys_store_and_load_through_maps() {
+        use cosmwasm_std::testing::MockStorage;
+        use cosmwasm_std::Order;
+        use cw_storage_plus::Map;
+
+        const GRANTS: Map<GrantKey, u64> = Map::new("grants");
+        let mut storage = MockStorage::new();
+        let keys = [
+            GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first")),
+            GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None::<String>),
+            GrantKey::new(
+                SCOPE_ADDRESS,
+                "pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p",
+                Some("second"),
+            ),
+            GrantKey::new(
+                "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+                ACCOUNT_ADDRESS,
+                Some("first"),
+            ),
+        ];
+        for (height, key) in keys.iter().enumerate() {
+            GRANTS
+                .save(&mut storage, key.clone(), &(height as u64))
+                .unwrap();
+        }
+        for (height, key) in keys.iter().enumerate() {
+            assert_eq!(
+                Some(height as u64),
+                GRANTS.may_load(&storage, key.clone()).unwrap(),
+                "the value stored under [{key}] should be loaded",
+            );
+        }
+        let mut expected = keys.to_vec();
+        expected.sort();
+        assert_eq!(
+            expected,
+            GRANTS
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<GrantKey>, _>>()
+                .unwrap(),
+            "every key should be loaded back from storage, including missing access grant ids",
+        );
+        assert_eq!(
+            vec![
+                (
+                    "pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p".to_string(),
+                    "second".to_string(),
+                ),
+                (ACCOUNT_ADDRESS.to_string(), String::new()),
+                (ACCOUNT_ADDRESS.to_string(), "first".to_string()),
+            ],
+            GRANTS
+                .sub_prefix(SCOPE_ADDRESS)
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<(String, String)>, _>>()
+                .unwrap(),
+            "keys should be prefixable by scope address",
+        );
+        assert_eq!(
+            vec![String::new(), "first".to_string()],
+            GRANTS
+                .prefix((SCOPE_ADDRESS, ACCOUNT_ADDRESS))
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<String>, _>>()
+                .unwrap(),
+            "keys should be prefixable by scope address and target account",
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use crate::{AccessGrant, AccessRevoke, GrantKey, OsGatewayAttributeGenerator};
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex};
+
+/// The secondary indexes of a [GrantLedger](self::GrantLedger).
+struct GrantIndexes<'a> {
+    scope: MultiIndex<'a, String, AccessGrant, GrantKey>,
+    target: MultiIndex<'a, String, AccessGrant, GrantKey>,
+}
+impl IndexList<AccessGrant> for GrantIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AccessGrant>> + '_> {
+        let indexes: Vec<&dyn Index<AccessGrant>> = vec![&self.scope, &self.target];
+        Box::new(indexes.into_iter())
+    }
+}
+
+/// A page of a [GrantLedger](self::GrantLedger) query, following the `start_after` and `limit`
+/// conventions of cw-plus and cw-paginate contracts.
+///
+/// # Parameters
+///
+/// * `start_after` The key of the last grant of the previous page, after which this page begins.
+///   The first page is produced when it is missing.
+///
+/// * `limit` The maximum number of grants in the page.  Defaults to
+///   [DEFAULT_LIMIT](self::GrantPagination::DEFAULT_LIMIT) when missing, and is capped at
+///   [MAX_LIMIT](self::GrantPagination::MAX_LIMIT).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrantPagination {
+    pub start_after: Option<GrantKey>,
+    pub limit: Option<u32>,
+}
+impl GrantPagination {
+    /// The number of grants in a page whose limit is missing.
+    pub const DEFAULT_LIMIT: u32 = 10;
+
+    /// The greatest number of grants in a page, regardless of its limit.
+    pub const MAX_LIMIT: u32 = 30;
+
+    /// Creates a pagination from its components.
+    ///
+    /// # Parameters
+    ///
+    /// * `start_after` The key of the last grant of the previous page, if any.
+    /// * `limit` The maximum number of grants in the page, if any.
+    pub fn new(start_after: Option<GrantKey>, limit: Option<u32>) -> Self {
+        Self { start_after, limit }
+    }
+
+    fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .min(Self::MAX_LIMIT) as usize
+    }
+}
+
+/// A record, in contract storage, of the access grants that a contract has emitted and not since
+/// revoked, so that the contract can answer queries about them without an off-chain indexer.
+///
+/// Grants are keyed by their [GrantKey](crate::GrantKey), and indexed by scope address and by
+/// target account.  Revokes are recorded with the gateway's rules: a revoke with an access grant id
+/// removes that grant, and a revoke without one removes every grant of its scope to its target
+/// account.  Use [emit_grant](self::GrantLedger::emit_grant) and
+/// [emit_revoke](self::GrantLedger::emit_revoke) to produce the attributes of an event in the same
+/// step that records it, so that the ledger never diverges from what was emitted:
+///
+/// ```
+/// use cosmwasm_std::testing::MockStorage;
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::storage::{GrantLedger, GrantPagination};
+/// use os_gateway_contract_attributes::{AccessGrant, OsGatewayResponseExt};
+///
+/// const GRANTS: GrantLedger = GrantLedger::new("grants", "grants__scope", "grants__target");
+///
+/// let mut storage = MockStorage::new();
+/// let grant = AccessGrant::new(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// let response: Response = Response::new()
+///     .add_os_gateway_event(GRANTS.emit_grant(&mut storage, grant.clone()).unwrap());
+/// assert_eq!(
+///     vec![grant],
+///     GRANTS
+///         .grants_by_scope(
+///             &storage,
+///             "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///             GrantPagination::default(),
+///         )
+///         .unwrap(),
+/// );
+/// ```
+pub struct GrantLedger<'a>(IndexedMap<'a, GrantKey, AccessGrant, GrantIndexes<'a>>);
+impl<'a> GrantLedger<'a> {
+    /// Creates a ledger stored under the given namespaces.  This is a const function, so ledgers
+    /// can be declared as constants alongside a contract's other storage items.  Each namespace
+    /// must be distinct, and must not collide with any other storage item of the contract.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace` The storage namespace of the grants.
+    /// * `scope_index_namespace` The storage namespace of the index by scope address.
+    /// * `target_index_namespace` The storage namespace of the index by target account.
+    pub const fn new(
+        namespace: &'a str,
+        scope_index_namespace: &'a str,
+        target_index_namespace: &'a str,
+    ) -> Self {
+        Self(IndexedMap::new(
+            namespace,
+            GrantIndexes {
+                scope: MultiIndex::new(
+                    |_, grant| grant.scope_address.clone(),
+                    namespace,
+                    scope_index_namespace,
+                ),
+                target: MultiIndex::new(
+                    |_, grant| grant.target_account_address.clone(),
+                    namespace,
+                    target_index_namespace,
+                ),
+            },
+        ))
+    }
+
+    /// Records an emitted grant, replacing any recorded grant with the same key.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `grant` The emitted grant.
+    pub fn record(&self, storage: &mut dyn Storage, grant: &AccessGrant) -> StdResult<()> {
+        self.0.save(storage, GrantKey::from(grant), grant)
+    }
+
+    /// Removes every recorded grant that an emitted revoke applies to, producing the number of
+    /// grants removed.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `revoke` The emitted revoke.
+    pub fn record_revoke(
+        &self,
+        storage: &mut dyn Storage,
+        revoke: &AccessRevoke,
+    ) -> StdResult<usize> {
+        let keys = match &revoke.access_grant_id {
+            Some(_) => vec![GrantKey::from(revoke)],
+            None => self
+                .0
+                .prefix((
+                    revoke.scope_address.as_str(),
+                    revoke.target_account_address.as_str(),
+                ))
+                .keys(storage, None, None, Order::Ascending)
+                .map(|access_grant_id| {
+                    access_grant_id.map(|access_grant_id| {
+                        GrantKey::new(
+                            &revoke.scope_address,
+                            &revoke.target_account_address,
+                            Some(access_grant_id).filter(|id| !id.is_empty()),
+                        )
+                    })
+                })
+                .collect::<StdResult<Vec<GrantKey>>>()?,
+        };
+        let mut removed = 0;
+        for key in keys {
+            if self.0.may_load(storage, key.clone())?.is_some() {
+                self.0.remove(storage, key)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Records a grant and produces the generator of its attributes, to be appended to the
+    /// response that emits it.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `grant` The grant to emit.
+    pub fn emit_grant(
+        &self,
+        storage: &mut dyn Storage,
+        grant: AccessGrant,
+    ) -> StdResult<OsGatewayAttributeGenerator> {
+        self.record(storage, &grant)?;
+        Ok(grant.into())
+    }
+
+    /// Records a revoke and produces the generator of its attributes, to be appended to the
+    /// response that emits it.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `revoke` The revoke to emit.
+    pub fn emit_revoke(
+        &self,
+        storage: &mut dyn Storage,
+        revoke: AccessRevoke,
+    ) -> StdResult<OsGatewayAttributeGenerator> {
+        self.record_revoke(storage, &revoke)?;
+        Ok(revoke.into())
+    }
+
+    /// Loads the recorded grant with the given key, if any.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `key` The key of the grant.
+    pub fn grant(&self, storage: &dyn Storage, key: GrantKey) -> StdResult<Option<AccessGrant>> {
+        self.0.may_load(storage, key)
+    }
+
+    /// A page of the recorded grants of a scope, ordered by their keys.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `pagination` The page to produce.
+    pub fn grants_by_scope(
+        &self,
+        storage: &dyn Storage,
+        scope_address: &str,
+        pagination: GrantPagination,
+    ) -> StdResult<Vec<AccessGrant>> {
+        Self::page(&self.0.idx.scope, storage, scope_address, pagination)
+    }
+
+    /// A page of the recorded grants to a target account, ordered by their keys.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger.
+    /// * `target_account_address` The bech32 address of the target account.
+    /// * `pagination` The page to produce.
+    pub fn grants_by_target(
+        &self,
+        storage: &dyn Storage,
+        target_account_address: &str,
+        pagination: GrantPagination,
+    ) -> StdResult<Vec<AccessGrant>> {
+        Self::page(
+            &self.0.idx.target,
+            storage,
+            target_account_address,
+            pagination,
+        )
+    }
+
+    /// Up to `limit` recorded grants, in key order, beginning after the given key.
+    pub(crate) fn grants_after(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<GrantKey>,
+        limit: usize,
+    ) -> StdResult<Vec<AccessGrant>> {
+        self.0
+            .range(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|entry| entry.map(|(_, grant)| grant))
+            .collect()
+    }
+
+    fn page(
+        index: &MultiIndex<'a, String, AccessGrant, GrantKey>,
+        storage: &dyn Storage,
+        value: &str,
+        pagination: GrantPagination,
+    ) -> StdResult<Vec<AccessGrant>> {
+        let limit = pagination.limit();
+        index
+            .prefix(value.to_string())
+            .range(
+                storage,
+                pagination.start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|entry| entry.map(|(_, grant)| grant))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{GrantLedger, GrantPagination};
+    use crate::{AccessGrant, AccessRevoke, GrantKey, OsGatewayAttributeGenerator};
+    use cosmwasm_std::testing::MockStorage;
+
+    const GRANTS: GrantLedger = GrantLedger::new("grants", "grants__scope", "grants__target");
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const OTHER_ACCOUNT_ADDRESS: &str =
+        "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+
+    fn grant(scope_address: &str, account_address: &str, id: Option<&str>) -> AccessGrant {
+        AccessGrant::new(scope_address, account_address).with_optional_access_grant_id(id)
+    }
+
+    fn ledger() -> MockStorage {
+        let mut storage = MockStorage::new();
+        for grant in [
+            grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None),
+            grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first")),
+            grant(SCOPE_ADDRESS, OTHER_ACCOUNT_ADDRESS, Some("first")),
+            grant(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("second")),
+        ] {
+            GRANTS.record(&mut storage, &grant).unwrap();
+        }
+        storage
+    }
+
+    fn all_pages(
+        query: impl Fn(GrantPagination) -> Vec<AccessGrant>,
+        limit: u32,
+    ) -> Vec<Vec<AccessGrant>> {
+        let mut pages = vec![];
+        let mut pagination = GrantPagination::new(None, Some(limit));
+        loop {
+            let page = query(pagination.clone());
+            if page.is_empty() {
+                return pages;
+            }
+            pagination.start_after = page.last().map(GrantKey::from);
+            pages.push(page);
+        }
+    }
+
+    #[test]
+    fn test_grants_are_queryable_by_scope_and_target() {
+        let storage = ledger();
+        assert_eq!(
+            vec![
+                vec![
+                    grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None),
+                    grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first")),
+                ],
+                vec![grant(SCOPE_ADDRESS, OTHER_ACCOUNT_ADDRESS, Some("first"))],
+            ],
+            all_pages(
+                |pagination| {
+                    GRANTS
+                        .grants_by_scope(&storage, SCOPE_ADDRESS, pagination)
+                        .unwrap()
+                },
+                2,
+            ),
+            "the scope's grants should be paged in key order",
+        );
+        assert_eq!(
+            vec![vec![
+                grant(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("second")),
+                grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None),
+                grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first")),
+            ]],
+            all_pages(
+                |pagination| {
+                    GRANTS
+                        .grants_by_target(&storage, ACCOUNT_ADDRESS, pagination)
+                        .unwrap()
+                },
+                10,
+            ),
+            "the target's grants should span every scope",
+        );
+    }
+
+    #[test]
+    fn test_limits_default_and_are_capped() {
+        let mut storage = MockStorage::new();
+        for index in 0..40 {
+            GRANTS
+                .record(
+                    &mut storage,
+                    &grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some(&format!("{index:02}"))),
+                )
+                .unwrap();
+        }
+        for (limit, expected) in [
+            (None, GrantPagination::DEFAULT_LIMIT),
+            (Some(5), 5),
+            (Some(100), GrantPagination::MAX_LIMIT),
+        ] {
+            assert_eq!(
+                expected as usize,
+                GRANTS
+                    .grants_by_scope(&storage, SCOPE_ADDRESS, GrantPagination::new(None, limit))
+                    .unwrap()
+                    .len(),
+                "a limit of [{limit:?}] should produce [{expected}] grants",
+            );
+        }
+    }
+
+    #[test]
+    fn test_revokes_follow_the_gateway_rules() {
+        let mut storage = ledger();
+        assert_eq!(
+            1,
+            GRANTS
+                .record_revoke(
+                    &mut storage,
+                    &AccessRevoke::new(SCOPE_ADDRESS, OTHER_ACCOUNT_ADDRESS)
+                        .with_access_grant_id("first"),
+                )
+                .unwrap(),
+            "a revoke with an id should remove that grant",
+        );
+        assert_eq!(
+            2,
+            GRANTS
+                .record_revoke(
+                    &mut storage,
+                    &AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                )
+                .unwrap(),
+            "a revoke without an id should remove every grant of the scope to the target",
+        );
+        assert_eq!(
+            0,
+            GRANTS
+                .record_revoke(
+                    &mut storage,
+                    &AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                )
+                .unwrap(),
+            "revoking again should remove nothing",
+        );
+        assert!(
+            GRANTS
+                .grants_by_scope(&storage, SCOPE_ADDRESS, GrantPagination::default())
+                .unwrap()
+                .is_empty(),
+            "the scope should have no grants left",
+        );
+        assert_eq!(
+            vec![grant(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("second"))],
+            GRANTS
+                .grants_by_target(&storage, ACCOUNT_ADDRESS, GrantPagination::default())
+                .unwrap(),
+            "the index by target should no longer list the revoked grants",
+        );
+    }
+
+    #[test]
+    fn test_emission_records_the_event() {
+        let mut storage = MockStorage::new();
+        let grant = grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first"));
+        assert_eq!(
+            OsGatewayAttributeGenerator::from(grant.clone()),
+            GRANTS.emit_grant(&mut storage, grant.clone()).unwrap(),
+            "the grant's attributes should be produced",
+        );
+        assert_eq!(
+            Some(grant.clone()),
+            GRANTS.grant(&storage, GrantKey::from(&grant)).unwrap(),
+            "the emitted grant should be recorded",
+        );
+        let revoke = AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        assert_eq!(
+            OsGatewayAttributeGenerator::from(revoke.clone()),
+            GRANTS.emit_revoke(&mut storage, revoke).unwrap(),
+            "the revoke's attributes should be produced",
+        );
+        assert_eq!(
+            None,
+            GRANTS.grant(&storage, GrantKey::from(&grant)).unwrap(),
+            "the emitted revoke should be recorded",
+        );
+    }
+}
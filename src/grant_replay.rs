@@ -0,0 +1,366 @@
+use crate::storage::GrantLedger;
+use crate::{GrantKey, OsGatewayAttributeGenerator};
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::Item;
+
+/// The progress of a [GrantReplay](self::GrantReplay), persisted in contract storage between the
+/// calls that advance it.
+///
+/// With the `serde` feature enabled, the cursor serializes as a
+/// `[start_after, replayed, complete]` array, where `start_after` is either null or a
+/// `[scope_address, target_account, access_grant_id]` array.
+///
+/// # Parameters
+///
+/// * `start_after` The key of the last grant replayed, after which the next batch begins.  Missing
+///   before the first batch.
+///
+/// * `replayed` The number of grants replayed so far.
+///
+/// * `complete` Whether or not every recorded grant has been replayed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayCursor {
+    pub start_after: Option<GrantKey>,
+    pub replayed: u64,
+    pub complete: bool,
+}
+/// Serializes the cursor as a `[start_after, replayed, complete]` array.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ReplayCursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (
+            self.start_after.as_ref().map(|key| {
+                (
+                    &key.scope_address,
+                    &key.target_account,
+                    &key.access_grant_id,
+                )
+            }),
+            self.replayed,
+            self.complete,
+        )
+            .serialize(serializer)
+    }
+}
+/// Deserializes the cursor from the array produced by its serialization.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ReplayCursor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (start_after, replayed, complete) =
+            <(Option<(String, String, Option<String>)>, u64, bool)>::deserialize(deserializer)?;
+        Ok(Self {
+            start_after: start_after.map(|(scope_address, target_account, access_grant_id)| {
+                GrantKey::new(scope_address, target_account, access_grant_id)
+            }),
+            replayed,
+            complete,
+        })
+    }
+}
+
+/// Re-emits every grant recorded in a [GrantLedger](crate::storage::GrantLedger) as a fresh event,
+/// such as when a contract migrates to a new gateway instance.  The replay proceeds in batches,
+/// with its [ReplayCursor](self::ReplayCursor) persisted in contract storage, so that it can be
+/// spread across as many migrate and execute calls as its gas requires:
+///
+/// ```
+/// use cosmwasm_std::testing::MockStorage;
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::storage::{GrantLedger, GrantReplay};
+/// use os_gateway_contract_attributes::{AccessGrant, OsGatewayResponseExt};
+///
+/// const GRANTS: GrantLedger = GrantLedger::new("grants", "grants__scope", "grants__target");
+/// const REPLAY: GrantReplay = GrantReplay::new("grant_replay");
+///
+/// let mut storage = MockStorage::new();
+/// GRANTS
+///     .record(
+///         &mut storage,
+///         &AccessGrant::new(
+///             "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///             "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///         ),
+///     )
+///     .unwrap();
+/// // In each call that advances the replay
+/// let response: Response = REPLAY
+///     .with_target_gateway("tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9")
+///     .replay_all(&mut storage, &GRANTS, 50)
+///     .unwrap()
+///     .into_iter()
+///     .fold(Response::new(), |response, event| {
+///         response.add_os_gateway_event(event)
+///     });
+/// assert_eq!(4, response.attributes.len());
+/// assert!(REPLAY.is_complete(&storage).unwrap());
+/// ```
+///
+/// Grants are replayed in key order.  Grants recorded or revoked during the replay are only
+/// reflected if their keys sort after the cursor, so contracts should pause grant changes until the
+/// replay is complete.
+pub struct GrantReplay<'a> {
+    cursor: Item<'a, ReplayCursor>,
+    target_gateway: Option<&'a str>,
+}
+impl<'a> GrantReplay<'a> {
+    /// The key of the attribute that [with_target_gateway](self::GrantReplay::with_target_gateway)
+    /// stamps on every replayed event.
+    pub const TARGET_GATEWAY_KEY: &'static str = "object_store_gateway_target_gateway";
+
+    /// Creates a replay whose cursor is stored under the given namespace.  This is a const
+    /// function, so replays can be declared as constants alongside a contract's other storage
+    /// items.  The namespace must not collide with any other storage item of the contract.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace` The storage namespace of the cursor.
+    pub const fn new(namespace: &'a str) -> Self {
+        Self {
+            cursor: Item::new(namespace),
+            target_gateway: None,
+        }
+    }
+
+    /// Stamps every replayed event with the gateway that it is intended for, under the
+    /// [TARGET_GATEWAY_KEY](self::GrantReplay::TARGET_GATEWAY_KEY) attribute.  The stamp never
+    /// overrides any of the grant's own attributes.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_gateway` The address or other identifier of the gateway instance.
+    pub const fn with_target_gateway(self, target_gateway: &'a str) -> Self {
+        Self {
+            cursor: self.cursor,
+            target_gateway: Some(target_gateway),
+        }
+    }
+
+    /// Produces the events of the next batch of recorded grants, advancing the persisted cursor
+    /// past them.  Produces no events once the replay is complete.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the ledger and the cursor.
+    /// * `ledger` The ledger whose grants are replayed.
+    /// * `batch_size` The maximum number of events to produce.  A batch size of zero is raised to
+    ///   one, so that every call makes progress.
+    pub fn replay_all(
+        &self,
+        storage: &mut dyn Storage,
+        ledger: &GrantLedger,
+        batch_size: u32,
+    ) -> StdResult<Vec<OsGatewayAttributeGenerator>> {
+        let mut cursor = self.cursor(storage)?;
+        if cursor.complete {
+            return Ok(vec![]);
+        }
+        let batch_size = batch_size.max(1) as usize;
+        let mut grants =
+            ledger.grants_after(storage, cursor.start_after.clone(), batch_size + 1)?;
+        cursor.complete = grants.len() <= batch_size;
+        grants.truncate(batch_size);
+        if let Some(last) = grants.last() {
+            cursor.start_after = Some(GrantKey::from(last));
+        }
+        cursor.replayed += grants.len() as u64;
+        self.cursor.save(storage, &cursor)?;
+        Ok(grants
+            .into_iter()
+            .map(|grant| match self.target_gateway {
+                Some(target_gateway) => grant
+                    .with_extra_attribute(Self::TARGET_GATEWAY_KEY, target_gateway)
+                    .into(),
+                None => grant.into(),
+            })
+            .collect())
+    }
+
+    /// The progress of the replay, which is the default cursor before the first batch.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the cursor.
+    pub fn cursor(&self, storage: &dyn Storage) -> StdResult<ReplayCursor> {
+        Ok(self.cursor.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Whether or not every recorded grant has been replayed.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the cursor.
+    pub fn is_complete(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.cursor(storage)?.complete)
+    }
+
+    /// Discards the progress of the replay, so that the next batch begins from the first grant.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the cursor.
+    pub fn reset(&self, storage: &mut dyn Storage) {
+        self.cursor.remove(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{GrantLedger, GrantReplay, ReplayCursor};
+    use crate::{AccessGrant, GrantKey, OsGatewayAttributeGenerator};
+    use cosmwasm_std::testing::MockStorage;
+    use std::collections::BTreeSet;
+
+    const GRANTS: GrantLedger = GrantLedger::new("grants", "grants__scope", "grants__target");
+    const REPLAY: GrantReplay = GrantReplay::new("grant_replay");
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const GATEWAY_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+
+    fn ledger(grants: usize) -> MockStorage {
+        let mut storage = MockStorage::new();
+        for index in 0..grants {
+            let scope_address = if index % 2 == 0 {
+                SCOPE_ADDRESS
+            } else {
+                OTHER_SCOPE_ADDRESS
+            };
+            GRANTS
+                .record(
+                    &mut storage,
+                    &AccessGrant::new(scope_address, ACCOUNT_ADDRESS)
+                        .with_access_grant_id(format!("grant-{index}")),
+                )
+                .unwrap();
+        }
+        storage
+    }
+
+    fn key(event: OsGatewayAttributeGenerator) -> GrantKey {
+        GrantKey::from(
+            &AccessGrant::try_from(event).expect("the replayed event should be a valid grant"),
+        )
+    }
+
+    #[test]
+    fn test_every_grant_is_replayed_exactly_once() {
+        let mut storage = ledger(10_000);
+        let mut replayed = BTreeSet::new();
+        let mut calls = 0;
+        while !REPLAY.is_complete(&storage).unwrap() {
+            calls += 1;
+            for event in REPLAY.replay_all(&mut storage, &GRANTS, 300).unwrap() {
+                let key = key(event);
+                assert!(
+                    replayed.insert(key.clone()),
+                    "the grant [{key:?}] should not be replayed twice",
+                );
+            }
+        }
+        assert_eq!(34, calls, "the replay should take one call per batch");
+        assert_eq!(10_000, replayed.len(), "no grant should be skipped");
+        assert_eq!(
+            10_000,
+            REPLAY.cursor(&storage).unwrap().replayed,
+            "the cursor should count every replayed grant",
+        );
+        assert!(
+            REPLAY
+                .replay_all(&mut storage, &GRANTS, 300)
+                .unwrap()
+                .is_empty(),
+            "a complete replay should produce no events",
+        );
+    }
+
+    #[test]
+    fn test_completion_is_detected_on_an_exact_batch() {
+        let mut storage = ledger(4);
+        assert_eq!(
+            4,
+            REPLAY.replay_all(&mut storage, &GRANTS, 4).unwrap().len(),
+            "the whole ledger should fit in one batch",
+        );
+        assert!(
+            REPLAY.is_complete(&storage).unwrap(),
+            "the replay should be complete without an empty trailing batch",
+        );
+        REPLAY.reset(&mut storage);
+        assert_eq!(
+            ReplayCursor::default(),
+            REPLAY.cursor(&storage).unwrap(),
+            "a reset should discard the progress",
+        );
+        let mut empty = MockStorage::new();
+        assert!(
+            REPLAY
+                .replay_all(&mut empty, &GRANTS, 4)
+                .unwrap()
+                .is_empty()
+                && REPLAY.is_complete(&empty).unwrap(),
+            "an empty ledger should complete immediately",
+        );
+    }
+
+    #[test]
+    fn test_target_gateway_is_stamped_on_every_event() {
+        let mut storage = ledger(3);
+        let events = REPLAY
+            .with_target_gateway(GATEWAY_ADDRESS)
+            .replay_all(&mut storage, &GRANTS, 10)
+            .unwrap();
+        assert!(
+            events
+                .iter()
+                .all(|event| event.get(GrantReplay::TARGET_GATEWAY_KEY) == Some(GATEWAY_ADDRESS)),
+            "every event should name the target gateway",
+        );
+    }
+
+    #[test]
+    fn test_cursor_serde_round_trip() {
+        let cursor = ReplayCursor {
+            start_after: Some(GrantKey::new(
+                SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS,
+                None::<String>,
+            )),
+            replayed: 12,
+            complete: false,
+        };
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(
+            format!(r#"[["{SCOPE_ADDRESS}","{ACCOUNT_ADDRESS}",null],12,false]"#),
+            json,
+            "the cursor should serialize as an array",
+        );
+        assert_eq!(
+            cursor,
+            serde_json::from_str(&json).unwrap(),
+            "the cursor should survive a serde round trip",
+        );
+    }
+    #[test]
+    fn test_zero_batch_size_still_makes_progress() {
+        let mut storage = ledger(3);
+        let mut calls = 0;
+        while !REPLAY.is_complete(&storage).unwrap() {
+            calls += 1;
+            assert!(
+                calls <= 3,
+                "an empty batch size should not stall the replay"
+            );
+            assert_eq!(
+                1,
+                REPLAY.replay_all(&mut storage, &GRANTS, 0).unwrap().len(),
+                "an empty batch size should replay one grant per call",
+            );
+        }
+        assert_eq!(
+            3,
+            REPLAY.cursor(&storage).unwrap().replayed,
+            "every grant should be replayed",
+        );
+    }
+}
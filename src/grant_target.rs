@@ -0,0 +1,381 @@
+use crate::address::{validate_bech32, SCOPE_SPEC_HRP};
+use crate::{AccountAddress, OsGatewayError};
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// The kind prefix of a rendered account target.
+const ACCOUNT_KIND: &str = "account";
+
+/// The kind prefix of a rendered marker target.
+const MARKER_KIND: &str = "marker";
+
+/// The kind prefix of a rendered scope specification target.
+pub(crate) const SCOPE_SPEC_KIND: &str = "scope_spec";
+
+/// Every kind of target, in declaration order.
+pub(crate) const TARGET_KINDS: [&str; 3] = [ACCOUNT_KIND, MARKER_KIND, SCOPE_SPEC_KIND];
+
+/// The separator between the kind prefix and the address of a rendered target.
+const KIND_SEPARATOR: char = ':';
+
+/// Describes who or what an access grant is given to, so that downstream code can match on the kind
+/// of target rather than inspecting which attributes are present.
+///
+/// Events carry the target's address in the [target account](crate::OsGatewayKeys) attribute, and
+/// its kind in the optional [target kind](crate::OsGatewayKeys) attribute.  Events without a kind
+/// target an account, which is the only kind that [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// currently recognizes, so existing events always produce an `Account` target.
+///
+/// Targets render as their kind followed by a colon and their bech32 address, such as
+/// `account:tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr`, which is also their serialized form.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrant, GrantTarget};
+///
+/// let grant = AccessGrant::new(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// match grant.target() {
+///     GrantTarget::Account(address) => assert_eq!("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr", address),
+///     other => panic!("unexpected target: {other}"),
+/// }
+/// ```
+///
+/// # Variants
+///
+/// * `Account` A [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts),
+///   identified by its bech32 address.
+///
+/// * `Marker` A [Provenance Blockchain Marker](https://docs.provenance.io/modules/marker-module),
+///   identified by the bech32 address of its account.
+///
+/// * `ScopeSpec` A [Provenance Blockchain Scope Specification](https://docs.provenance.io/modules/metadata-module#scope-specification),
+///   identified by its bech32 address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GrantTarget {
+    Account(String),
+    Marker(String),
+    ScopeSpec(String),
+}
+impl GrantTarget {
+    /// Creates an account target, validating the value as a mainnet or testnet [AccountAddress](crate::AccountAddress).
+    ///
+    /// # Parameters
+    ///
+    /// * `account_address` The bech32 address of the account.
+    pub fn account<S: Into<String>>(account_address: S) -> Result<Self, OsGatewayError> {
+        AccountAddress::new(account_address).map(|address| Self::Account(address.into_string()))
+    }
+
+    /// Creates a marker target, validating the value as a mainnet or testnet [AccountAddress](crate::AccountAddress),
+    /// because markers are identified by the address of their account.
+    ///
+    /// # Parameters
+    ///
+    /// * `marker_address` The bech32 address of the marker's account.
+    pub fn marker<S: Into<String>>(marker_address: S) -> Result<Self, OsGatewayError> {
+        AccountAddress::new(marker_address).map(|address| Self::Marker(address.into_string()))
+    }
+
+    /// Creates a scope specification target, validating the value as a bech32 address with the
+    /// `scopespec` human-readable part.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_spec_address` The bech32 address of the scope specification.
+    pub fn scope_spec<S: Into<String>>(scope_spec_address: S) -> Result<Self, OsGatewayError> {
+        let scope_spec_address = scope_spec_address.into();
+        validate_bech32(&scope_spec_address, &[SCOPE_SPEC_HRP])?;
+        Ok(Self::ScopeSpec(scope_spec_address))
+    }
+
+    /// The kind of target, as rendered before its address: `account`, `marker`, or `scope_spec`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Account(_) => ACCOUNT_KIND,
+            Self::Marker(_) => MARKER_KIND,
+            Self::ScopeSpec(_) => SCOPE_SPEC_KIND,
+        }
+    }
+
+    /// The bech32 address that identifies the target.
+    pub fn address(&self) -> &str {
+        match self {
+            Self::Account(address) | Self::Marker(address) | Self::ScopeSpec(address) => address,
+        }
+    }
+
+    /// The target identified by an event's target account address and its optional target kind
+    /// attribute.  Events without a kind, or with a kind that is not recognized, target an account,
+    /// as that is how the gateway interprets them.  Unrecognized kinds are reported by validation.
+    pub(crate) fn from_kind(kind: Option<&str>, address: &str) -> Self {
+        let address = address.to_string();
+        match kind {
+            Some(MARKER_KIND) => Self::Marker(address),
+            Some(SCOPE_SPEC_KIND) => Self::ScopeSpec(address),
+            _ => Self::Account(address),
+        }
+    }
+
+    /// The value of the target kind attribute that denotes this target, or `None` for accounts,
+    /// which events target when the attribute is omitted.
+    pub(crate) fn kind_attribute(&self) -> Option<&'static str> {
+        match self {
+            Self::Account(_) => None,
+            Self::Marker(_) | Self::ScopeSpec(_) => Some(self.kind()),
+        }
+    }
+}
+
+/// Whether or not the value is a kind of target, as rendered by [GrantTarget::kind](crate::GrantTarget::kind).
+pub(crate) fn is_target_kind(value: &str) -> bool {
+    TARGET_KINDS.contains(&value)
+}
+
+/// Whether or not the value is a valid address for a target of the given kind.  Targets without a
+/// kind are accounts.
+pub(crate) fn is_target_address(kind: Option<&str>, value: &str) -> bool {
+    match kind {
+        Some(SCOPE_SPEC_KIND) => validate_bech32(value, &[SCOPE_SPEC_HRP]).is_ok(),
+        _ => AccountAddress::new(value).is_ok(),
+    }
+}
+impl Display for GrantTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}{KIND_SEPARATOR}{}", self.kind(), self.address())
+    }
+}
+/// Parses a rendered target, validating its address with the constructor for its kind.  Produces an
+/// [OsGatewayError::InvalidAddress](crate::OsGatewayError::InvalidAddress) if the kind is not
+/// recognized or the address is not valid for its kind.
+impl FromStr for GrantTarget {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(KIND_SEPARATOR) {
+            Some((ACCOUNT_KIND, address)) => Self::account(address),
+            Some((MARKER_KIND, address)) => Self::marker(address),
+            Some((SCOPE_SPEC_KIND, address)) => Self::scope_spec(address),
+            _ => Err(OsGatewayError::InvalidAddress {
+                address: s.to_string(),
+                reason: format!(
+                    "expected a target prefixed with [{ACCOUNT_KIND}{KIND_SEPARATOR}], [{MARKER_KIND}{KIND_SEPARATOR}], or [{SCOPE_SPEC_KIND}{KIND_SEPARATOR}]",
+                ),
+            }),
+        }
+    }
+}
+/// Serializes the target in its rendered form.
+#[cfg(feature = "serde")]
+impl serde::Serialize for GrantTarget {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+/// Deserializes the target from its rendered form, rejecting unrecognized kinds and invalid
+/// addresses.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GrantTarget {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "parsing")]
+    use crate::{parse_gateway_event, parse_gateway_event_ref, AccessGrant, GatewayEventFields};
+    use crate::{
+        AccessRevoke, FieldError, FieldErrorKind, GrantTarget, OsGatewayAttributeGenerator,
+        OsGatewayError, OsGatewayEvent, OsGatewayKey, OS_GATEWAY_KEYS,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const SCOPE_SPEC_ADDRESS: &str = "scopespec1qjge0zaztu65tx5x5llv5xc9ztsqge0q72";
+
+    #[test]
+    fn test_constructors_validate_addresses() {
+        assert_eq!(
+            Ok(GrantTarget::Account(ACCOUNT_ADDRESS.to_string())),
+            GrantTarget::account(ACCOUNT_ADDRESS),
+            "a valid account address should produce an account target",
+        );
+        assert_eq!(
+            Ok(GrantTarget::Marker(ACCOUNT_ADDRESS.to_string())),
+            GrantTarget::marker(ACCOUNT_ADDRESS),
+            "a valid account address should produce a marker target",
+        );
+        assert_eq!(
+            Ok(GrantTarget::ScopeSpec(SCOPE_SPEC_ADDRESS.to_string())),
+            GrantTarget::scope_spec(SCOPE_SPEC_ADDRESS),
+            "a valid scope specification address should produce a scope specification target",
+        );
+        for target in [
+            GrantTarget::account(SCOPE_SPEC_ADDRESS),
+            GrantTarget::marker("target"),
+            GrantTarget::scope_spec(ACCOUNT_ADDRESS),
+        ] {
+            assert!(
+                matches!(target, Err(OsGatewayError::InvalidAddress { .. })),
+                "an address of the wrong kind should be rejected: {target:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_rendered_targets_round_trip() {
+        for (target, rendered) in [
+            (
+                GrantTarget::Account(ACCOUNT_ADDRESS.to_string()),
+                format!("account:{ACCOUNT_ADDRESS}"),
+            ),
+            (
+                GrantTarget::Marker(ACCOUNT_ADDRESS.to_string()),
+                format!("marker:{ACCOUNT_ADDRESS}"),
+            ),
+            (
+                GrantTarget::ScopeSpec(SCOPE_SPEC_ADDRESS.to_string()),
+                format!("scope_spec:{SCOPE_SPEC_ADDRESS}"),
+            ),
+        ] {
+            assert_eq!(
+                rendered,
+                target.to_string(),
+                "the target should render as its kind and address",
+            );
+            assert_eq!(
+                Ok(target),
+                rendered.parse::<GrantTarget>(),
+                "the rendered target [{rendered}] should parse back into the same target",
+            );
+        }
+        assert!(
+            matches!(
+                ACCOUNT_ADDRESS.parse::<GrantTarget>(),
+                Err(OsGatewayError::InvalidAddress { .. }),
+            ),
+            "a target without a kind should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_typed_events_expose_account_targets() {
+        let event = OsGatewayEvent::from(AccessRevoke::new("scope", "target"));
+        assert_eq!(
+            GrantTarget::Account("target".to_string()),
+            event.target(),
+            "the gateway's target account should be exposed as an account target",
+        );
+        let retargeted = AccessRevoke::new("scope", "target")
+            .with_target(GrantTarget::Marker(ACCOUNT_ADDRESS.to_string()))
+            .with_target(GrantTarget::Account(ACCOUNT_ADDRESS.to_string()));
+        assert_eq!(
+            AccessRevoke::new("scope", ACCOUNT_ADDRESS),
+            retargeted,
+            "an account target should replace the address and clear any previous target kind",
+        );
+    }
+
+    #[cfg(feature = "parsing")]
+    #[test]
+    fn test_parsed_events_expose_every_kind_of_target() {
+        for target in [
+            GrantTarget::Account(ACCOUNT_ADDRESS.to_string()),
+            GrantTarget::Marker(ACCOUNT_ADDRESS.to_string()),
+            GrantTarget::ScopeSpec(SCOPE_SPEC_ADDRESS.to_string()),
+        ] {
+            let generator = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, "target")
+                .with_target(target.clone());
+            assert_eq!(
+                Ok(()),
+                generator.validate(),
+                "the [{target}] target should pass validation",
+            );
+            let results = parse_gateway_event(&generator.to_event());
+            let event = match results.as_slice() {
+                [Ok(event)] => event,
+                other => panic!("the [{target}] grant should parse, but produced: {other:?}"),
+            };
+            assert_eq!(
+                target,
+                event.target(),
+                "the parsed grant should expose its [{}] target",
+                target.kind(),
+            );
+            assert_eq!(
+                target.address(),
+                event.target_account(),
+                "the target's address should be carried by the target account attribute",
+            );
+            assert_eq!(
+                target,
+                OsGatewayEvent::AccessGrant(
+                    AccessGrant::new(SCOPE_ADDRESS, "target").with_target(target.clone())
+                )
+                .target(),
+                "a typed grant should expose the [{}] target that it was given",
+                target.kind(),
+            );
+            for parsed in parse_gateway_event_ref(&generator.to_event()) {
+                assert_eq!(
+                    target,
+                    parsed.unwrap().target(),
+                    "the borrowed grant should expose the same target",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_target_kinds_are_validated() {
+        let mut generator = OsGatewayAttributeGenerator::access_revoke(SCOPE_ADDRESS, "target")
+            .with_target(GrantTarget::ScopeSpec(ACCOUNT_ADDRESS.to_string()));
+        assert_eq!(
+            Err(OsGatewayError::InvalidFields {
+                errors: vec![FieldError::new(
+                    OsGatewayKey::TargetAccount,
+                    FieldErrorKind::InvalidScopeSpecAddress,
+                    ACCOUNT_ADDRESS,
+                )],
+            }),
+            generator.validate(),
+            "a scope specification target should be validated as a scope specification address",
+        );
+        generator.set_target(GrantTarget::Account(ACCOUNT_ADDRESS.to_string()));
+        generator.set_attribute(OS_GATEWAY_KEYS.target_kind, "group");
+        assert_eq!(
+            Err(OsGatewayError::InvalidFields {
+                errors: vec![FieldError::new(
+                    OsGatewayKey::TargetKind,
+                    FieldErrorKind::UnknownTargetKind,
+                    "group",
+                )],
+            }),
+            generator.validate(),
+            "an unrecognized target kind should be rejected",
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_uses_rendered_form() {
+        let target = GrantTarget::Marker(ACCOUNT_ADDRESS.to_string());
+        let json = cosmwasm_std::to_json_vec(&target).unwrap();
+        assert_eq!(
+            format!("\"marker:{ACCOUNT_ADDRESS}\"").into_bytes(),
+            json,
+            "the target should serialize in its rendered form",
+        );
+        assert_eq!(
+            target,
+            cosmwasm_std::from_json::<GrantTarget>(&json).unwrap(),
+            "the target should survive a serde round trip",
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{OsGatewayAttributeGenerator, OsGatewayError};
+use std::collections::BTreeSet;
+
+type AccessGrantIdFn = Box<dyn Fn(&str) -> String>;
+
+/// A reusable template for granting access to a single [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+/// to many [Provenance Blockchain Accounts](https://docs.provenance.io/blockchain/basics/accounts).
+///
+/// Each target account provided to the template produces one complete [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator)
+/// for an access grant, optionally including an access grant id derived from the target account.
+///
+/// ```
+/// use os_gateway_contract_attributes::OsGatewayGrantTemplate;
+///
+/// let generators = OsGatewayGrantTemplate::new("scope1qzn7jghj8puprmdcvunm3330jutsj803zz")
+///     .with_access_grant_id_fn(|target| format!("bulk_grant_{target}"))
+///     .try_for_targets(["tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr", "tp1s2rqsddftsqvcdwd5qdg9v8mvcfmc0aqg4trcp"])
+///     .expect("the targets should be unique");
+/// ```
+pub struct OsGatewayGrantTemplate {
+    scope_address: String,
+    access_grant_id_fn: Option<AccessGrantIdFn>,
+}
+impl OsGatewayGrantTemplate {
+    /// Creates a template that grants access to the given scope.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which every produced access grant refers.
+    pub fn new<S: Into<String>>(scope_address: S) -> Self {
+        Self {
+            scope_address: scope_address.into(),
+            access_grant_id_fn: None,
+        }
+    }
+
+    /// Derives an access grant id for each produced access grant from its target account address.
+    /// Replaces any previously provided function.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id_fn` A function that receives the bech32 target account address of each
+    ///   access grant and produces the access grant id to include with it.
+    pub fn with_access_grant_id_fn<F: Fn(&str) -> String + 'static>(
+        mut self,
+        access_grant_id_fn: F,
+    ) -> Self {
+        self.access_grant_id_fn = Some(Box::new(access_grant_id_fn));
+        self
+    }
+
+    /// Produces one access grant generator per target account, in the order the target accounts
+    /// are provided.  No validation is performed across the produced grants.  Use
+    /// [try_for_targets](self::OsGatewayGrantTemplate::try_for_targets) to reject duplicates.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_account_addresses` The bech32 addresses of the [Provenance Blockchain Accounts](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which access will be granted.
+    pub fn for_targets<'a, I, S>(
+        &'a self,
+        target_account_addresses: I,
+    ) -> impl Iterator<Item = OsGatewayAttributeGenerator> + 'a
+    where
+        I: IntoIterator<Item = S>,
+        I::IntoIter: 'a,
+        S: Into<String>,
+    {
+        target_account_addresses
+            .into_iter()
+            .map(move |target_account_address| self.for_target(target_account_address))
+    }
+
+    /// Produces one access grant generator per target account, in the order the target accounts
+    /// are provided, after verifying that the batch is free of duplicates.
+    ///
+    /// Returns an [OsGatewayError::DuplicateTargetAccounts](crate::OsGatewayError::DuplicateTargetAccounts)
+    /// if any target account is provided more than once, or an [OsGatewayError::DuplicateAccessGrantIds](crate::OsGatewayError::DuplicateAccessGrantIds)
+    /// if the access grant id function produces the same id for more than one target account.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_account_addresses` The bech32 addresses of the [Provenance Blockchain Accounts](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which access will be granted.
+    pub fn try_for_targets<I, S>(
+        &self,
+        target_account_addresses: I,
+    ) -> Result<Vec<OsGatewayAttributeGenerator>, OsGatewayError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let target_account_addresses = target_account_addresses
+            .into_iter()
+            .map(|target_account_address| target_account_address.into())
+            .collect::<Vec<String>>();
+        let duplicate_targets = find_duplicates(target_account_addresses.iter().cloned());
+        if !duplicate_targets.is_empty() {
+            return Err(OsGatewayError::DuplicateTargetAccounts {
+                target_accounts: duplicate_targets,
+            });
+        }
+        if let Some(access_grant_id_fn) = &self.access_grant_id_fn {
+            let duplicate_ids = find_duplicates(
+                target_account_addresses
+                    .iter()
+                    .map(|target_account_address| access_grant_id_fn(target_account_address)),
+            );
+            if !duplicate_ids.is_empty() {
+                return Err(OsGatewayError::DuplicateAccessGrantIds {
+                    access_grant_ids: duplicate_ids,
+                });
+            }
+        }
+        Ok(self.for_targets(target_account_addresses).collect())
+    }
+
+    fn for_target<S: Into<String>>(
+        &self,
+        target_account_address: S,
+    ) -> OsGatewayAttributeGenerator {
+        let target_account_address = target_account_address.into();
+        let access_grant_id = self
+            .access_grant_id_fn
+            .as_ref()
+            .map(|access_grant_id_fn| access_grant_id_fn(&target_account_address));
+        let generator =
+            OsGatewayAttributeGenerator::access_grant(&self.scope_address, target_account_address);
+        match access_grant_id {
+            Some(access_grant_id) => generator.with_access_grant_id(access_grant_id),
+            None => generator,
+        }
+    }
+}
+
+fn find_duplicates<I: Iterator<Item = String>>(values: I) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut duplicates = BTreeSet::new();
+    for value in values {
+        if !seen.insert(value.clone()) {
+            duplicates.insert(value);
+        }
+    }
+    duplicates.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OsGatewayAttributeGenerator, OsGatewayError, OsGatewayGrantTemplate};
+
+    const SCOPE_ADDRESS: &str = "scope_address";
+
+    #[test]
+    fn test_for_targets_produces_one_grant_per_target() {
+        let generators = OsGatewayGrantTemplate::new(SCOPE_ADDRESS)
+            .for_targets(["first", "second"])
+            .collect::<Vec<OsGatewayAttributeGenerator>>();
+        assert_eq!(
+            vec![
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, "first"),
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, "second"),
+            ],
+            generators,
+            "one access grant should be produced per target, in order",
+        );
+    }
+
+    #[test]
+    fn test_access_grant_id_fn_is_applied_per_target() {
+        let generators = OsGatewayGrantTemplate::new(SCOPE_ADDRESS)
+            .with_access_grant_id_fn(|target| format!("grant_{target}"))
+            .try_for_targets(vec!["first".to_string(), "second".to_string()])
+            .expect("unique targets and ids should be accepted");
+        assert_eq!(
+            vec![
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, "first")
+                    .with_access_grant_id("grant_first"),
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, "second")
+                    .with_access_grant_id("grant_second"),
+            ],
+            generators,
+            "each access grant should include the id derived from its target",
+        );
+    }
+
+    #[test]
+    fn test_try_for_targets_rejects_duplicate_targets() {
+        let error = OsGatewayGrantTemplate::new(SCOPE_ADDRESS)
+            .try_for_targets(["first", "second", "first"])
+            .unwrap_err();
+        assert_eq!(
+            OsGatewayError::DuplicateTargetAccounts {
+                target_accounts: vec!["first".to_string()],
+            },
+            error,
+            "duplicate targets should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_try_for_targets_rejects_duplicate_access_grant_ids() {
+        let error = OsGatewayGrantTemplate::new(SCOPE_ADDRESS)
+            .with_access_grant_id_fn(|_| "same_id".to_string())
+            .try_for_targets(["first", "second"])
+            .unwrap_err();
+        assert_eq!(
+            OsGatewayError::DuplicateAccessGrantIds {
+                access_grant_ids: vec!["same_id".to_string()],
+            },
+            error,
+            "duplicate access grant ids should be rejected",
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{OsGatewayError, OsGatewayEvent};
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use std::fmt::Formatter;
+
+/// The key of the memo of an ICS-20 transfer under which a gateway event is relayed to Provenance,
+/// as in `{"os_gateway": {...}}`.  Other keys of the memo, such as those of wasm hooks or packet
+/// forwarding middleware, are left for their own consumers.
+pub const OS_GATEWAY_IBC_MEMO_KEY: &str = "os_gateway";
+
+/// The longest memo, in bytes, from which a gateway event is [decoded](crate::OsGatewayEvent::from_ibc_memo).
+/// Matches the longest memo that ibc-go accepts on a transfer, so that an oversized memo is
+/// rejected before it is decoded.
+pub const OS_GATEWAY_MAX_IBC_MEMO_BYTES: usize = 32 * 1024;
+
+/// The name of the memo encoding, as reported in errors.
+const IBC_MEMO: &str = "ibc memo";
+
+impl OsGatewayEvent {
+    /// Produces the memo of an ICS-20 transfer that relays this event to Provenance, for a
+    /// middleware contract to interpret.  The memo is a JSON object holding the flat map of the
+    /// event's attribute keys to values produced by [OsGatewayJson](crate::OsGatewayJson) under
+    /// [OS_GATEWAY_IBC_MEMO_KEY](crate::OS_GATEWAY_IBC_MEMO_KEY).
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessGrant, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(AccessGrant::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// let memo = event.to_ibc_memo();
+    /// assert!(memo.starts_with(r#"{"os_gateway":{"#));
+    /// assert_eq!(Ok(Some(event)), OsGatewayEvent::from_ibc_memo(&memo));
+    /// ```
+    pub fn to_ibc_memo(&self) -> String {
+        // Serializing a map of strings cannot fail
+        cosmwasm_std::to_json_string(&IbcMemo(Some(self.clone())))
+            .expect("the event should serialize")
+    }
+
+    /// Decodes the event relayed by the memo of an ICS-20 transfer, producing `None` if the memo
+    /// relays no event.  A memo relays no event if it is not a JSON object, as with plain text
+    /// memos, or if it has no [OS_GATEWAY_IBC_MEMO_KEY](crate::OS_GATEWAY_IBC_MEMO_KEY).  Every
+    /// other key of the memo is ignored.  Produces an [OsGatewayError::InvalidEncodedEvent](crate::OsGatewayError::InvalidEncodedEvent)
+    /// if the memo exceeds [OS_GATEWAY_MAX_IBC_MEMO_BYTES](crate::OS_GATEWAY_MAX_IBC_MEMO_BYTES),
+    /// is a malformed JSON object, holds the key more than once, or does not hold a complete event
+    /// of a recognized type under it.
+    ///
+    /// # Parameters
+    ///
+    /// * `memo` The memo of the transfer.
+    pub fn from_ibc_memo(memo: &str) -> Result<Option<Self>, OsGatewayError> {
+        if memo.len() > OS_GATEWAY_MAX_IBC_MEMO_BYTES {
+            return Err(invalid(format!(
+                "the memo exceeds {OS_GATEWAY_MAX_IBC_MEMO_BYTES} bytes"
+            )));
+        }
+        if !memo.trim_start().starts_with('{') {
+            return Ok(None);
+        }
+        cosmwasm_std::from_json::<IbcMemo>(memo.trim())
+            .map(|memo| memo.0)
+            .map_err(|error| invalid(error.to_string()))
+    }
+}
+
+/// A memo, and the gateway event that it relays, if any.
+struct IbcMemo(Option<OsGatewayEvent>);
+impl serde::Serialize for IbcMemo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.iter().len()))?;
+        if let Some(event) = &self.0 {
+            map.serialize_entry(OS_GATEWAY_IBC_MEMO_KEY, event)?;
+        }
+        map.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for IbcMemo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(IbcMemoVisitor)
+    }
+}
+
+struct IbcMemoVisitor;
+impl<'de> Visitor<'de> for IbcMemoVisitor {
+    type Value = IbcMemo;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a memo object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut event = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != OS_GATEWAY_IBC_MEMO_KEY {
+                map.next_value::<IgnoredAny>()?;
+            } else if event.is_some() {
+                return Err(serde::de::Error::duplicate_field(OS_GATEWAY_IBC_MEMO_KEY));
+            } else {
+                event = Some(map.next_value::<OsGatewayEvent>()?);
+            }
+        }
+        Ok(IbcMemo(event))
+    }
+}
+
+fn invalid(reason: String) -> OsGatewayError {
+    OsGatewayError::InvalidEncodedEvent {
+        encoding: IBC_MEMO.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError, OsGatewayEvent,
+        OS_GATEWAY_MAX_IBC_MEMO_BYTES,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const CONTRACT_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+
+    fn grant() -> OsGatewayEvent {
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::Read)
+            .into()
+    }
+
+    fn assert_rejected(memo: &str, case: &str) {
+        let result = OsGatewayEvent::from_ibc_memo(memo);
+        assert!(
+            matches!(
+                &result,
+                Err(OsGatewayError::InvalidEncodedEvent { encoding, .. }) if encoding == "ibc memo",
+            ),
+            "{case} should be rejected, but produced: {result:?}",
+        );
+    }
+
+    #[test]
+    fn test_events_round_trip_through_the_envelope() {
+        let revoke = OsGatewayEvent::from(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+        assert_eq!(
+            format!(
+                concat!(
+                    r#"{{"os_gateway":{{"object_store_gateway_event_type":"access_revoke","#,
+                    r#""object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                    r#""object_store_gateway_target_account_address":"{ACCOUNT_ADDRESS}"}}}}"#,
+                ),
+                SCOPE_ADDRESS = SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+            ),
+            revoke.to_ibc_memo(),
+            "the memo should hold the event's attribute map under the envelope key",
+        );
+        for event in [grant(), revoke] {
+            assert_eq!(
+                Ok(Some(event.clone())),
+                OsGatewayEvent::from_ibc_memo(&event.to_ibc_memo()),
+                "the event should survive a memo round trip",
+            );
+        }
+    }
+
+    #[test]
+    fn test_unrelated_memo_keys_are_ignored() {
+        let event_json = cosmwasm_std::to_json_string(&grant()).unwrap();
+        let wasm_hook = format!(
+            r#""wasm":{{"contract":"{CONTRACT_ADDRESS}","msg":{{"relay":{{"os_gateway":1}}}}}}"#
+        );
+        let forward = format!(
+            r#""forward":{{"receiver":"{ACCOUNT_ADDRESS}","port":"transfer","channel":"channel-0","next":{{"forward":{{"channel":"channel-1"}}}}}}"#
+        );
+        for memo in [
+            format!(r#"{{{wasm_hook},"os_gateway":{event_json}}}"#),
+            format!(r#"{{"os_gateway":{event_json},{forward}}}"#),
+            format!(" {{{forward}, \"os_gateway\": {event_json}, {wasm_hook}}}\n"),
+        ] {
+            assert_eq!(
+                Ok(Some(grant())),
+                OsGatewayEvent::from_ibc_memo(&memo),
+                "the event should be decoded alongside other keys from [{memo}]",
+            );
+        }
+        for memo in [
+            format!("{{{wasm_hook}}}"),
+            format!("{{{forward}}}"),
+            "{}".to_string(),
+            String::new(),
+            "payment for invoice 42".to_string(),
+            "[1, 2]".to_string(),
+        ] {
+            assert_eq!(
+                Ok(None),
+                OsGatewayEvent::from_ibc_memo(&memo),
+                "no event should be decoded from [{memo}]",
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_memos_are_rejected() {
+        let event_json = cosmwasm_std::to_json_string(&grant()).unwrap();
+        for (memo, case) in [
+            (
+                format!(r#"{{"os_gateway":{event_json}"#),
+                "an unterminated object",
+            ),
+            (
+                format!(r#"{{"os_gateway":{event_json}}} trailing"#),
+                "an object followed by garbage",
+            ),
+            (
+                format!(r#"{{"os_gateway":{event_json},"os_gateway":{event_json}}}"#),
+                "a duplicate envelope key",
+            ),
+            (
+                r#"{"os_gateway":"grant"}"#.to_string(),
+                "a non-object event",
+            ),
+            (
+                r#"{"os_gateway":{"object_store_gateway_event_type":"access_grant"}}"#.to_string(),
+                "an incomplete event",
+            ),
+            (
+                format!(
+                    r#"{{"os_gateway":{}}}"#,
+                    event_json.replace("access_grant\"", "unknown\"")
+                ),
+                "an unrecognized event type",
+            ),
+        ] {
+            assert_rejected(&memo, case);
+        }
+    }
+
+    #[test]
+    fn test_oversized_memos_are_rejected() {
+        let event_json = cosmwasm_std::to_json_string(&grant()).unwrap();
+        let padding = "x".repeat(OS_GATEWAY_MAX_IBC_MEMO_BYTES);
+        assert_eq!(
+            Err(OsGatewayError::InvalidEncodedEvent {
+                encoding: "ibc memo".to_string(),
+                reason: format!("the memo exceeds {OS_GATEWAY_MAX_IBC_MEMO_BYTES} bytes"),
+            }),
+            OsGatewayEvent::from_ibc_memo(&format!(
+                r#"{{"os_gateway":{event_json},"note":"{padding}"}}"#
+            )),
+            "an oversized memo should be rejected before it is decoded",
+        );
+        assert_rejected(&format!("{padding}x"), "an oversized plain text memo");
+    }
+}
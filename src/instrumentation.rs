@@ -0,0 +1,111 @@
+//! [tracing](https://docs.rs/tracing) spans and events emitted while parsing and validating
+//! gateway events, for off-chain services that want visibility into malformed events without
+//! writing their own wrappers.
+//!
+//! With the `tracing` feature enabled, the crate emits:
+//!
+//! * An `os_gateway_parse` span around each gateway event parsed by
+//!   [parse_gateway_events](crate::parse_gateway_events), recording the `event_type` and
+//!   `scope_address` once they are known, with a `debug` event describing the outcome.
+//! * A `warn` event within that span whenever a gateway event is parsed despite recoverable
+//!   issues, such as an invalid scope address or an unrecognized `object_store_gateway_` key.
+//!   Its `field_errors` field holds the [FieldErrors](crate::FieldError) found, and its
+//!   `unknown_keys` field holds the unrecognized keys.
+//! * An `os_gateway_validate` span around each call to
+//!   [validate](crate::OsGatewayAttributeGenerator::validate), with the same fields.
+//!
+//! Transaction context is supplied by parsing within a [tx_span](crate::instrumentation::tx_span),
+//! which `parse_tx_response` and the `listener` module do on their own:
+//!
+//! ```
+//! use cosmwasm_std::Event;
+//! use os_gateway_contract_attributes::instrumentation::tx_span;
+//! use os_gateway_contract_attributes::parse_gateway_events;
+//!
+//! let events: Vec<Event> = vec![];
+//! let tx_hash = "A0E5C99C7AB8CB6FA6932434F1B7363141106DB699E39D64A34899B5CBAA2953";
+//! let parsed = tx_span(tx_hash, Some(100)).in_scope(|| parse_gateway_events(&events));
+//! assert!(parsed.is_empty());
+//! ```
+
+use crate::attribute_keys::ALL_KEYS;
+use crate::{OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, OsGatewayKey};
+use tracing::field::Empty;
+use tracing::{debug, debug_span, info_span, warn, Level, Span};
+
+/// Creates the span that supplies transaction context to everything parsed within it.
+///
+/// # Parameters
+///
+/// * `tx_hash` The hash of the transaction whose events are parsed.
+/// * `height` The height of the block that contains the transaction, if known.
+pub fn tx_span(tx_hash: &str, height: Option<i64>) -> Span {
+    let span = info_span!("os_gateway_tx", tx_hash, height = Empty);
+    if let Some(height) = height {
+        span.record("height", height);
+    }
+    span
+}
+
+/// Creates the span around parsing a single gateway event, whose fields are recorded by
+/// [record_parsed](self::record_parsed).
+pub(crate) fn parse_span() -> Span {
+    debug_span!(
+        "os_gateway_parse",
+        event_type = Empty,
+        scope_address = Empty
+    )
+}
+
+/// Records the fields of a leniently parsed gateway event on the current parse span, warning of
+/// any recoverable issues.
+pub(crate) fn record_parsed(span: &Span, generator: &OsGatewayAttributeGenerator) {
+    record_fields(span, generator);
+    if !tracing::enabled!(Level::WARN) {
+        return;
+    }
+    let field_errors = match generator.validate() {
+        Err(OsGatewayError::InvalidFields { errors }) => errors,
+        _ => vec![],
+    };
+    let unknown_keys = generator
+        .as_map()
+        .keys()
+        .filter(|key| !ALL_KEYS.contains(&key.as_str()))
+        .collect::<Vec<&String>>();
+    if !field_errors.is_empty() || !unknown_keys.is_empty() {
+        warn!(
+            ?field_errors,
+            ?unknown_keys,
+            "parsed a gateway event with recoverable issues"
+        );
+    }
+}
+
+/// Describes the outcome of parsing a single gateway event.
+pub(crate) fn record_outcome(result: &Result<OsGatewayEvent, OsGatewayError>) {
+    match result {
+        Ok(_) => debug!("parsed a gateway event"),
+        Err(error) => debug!(%error, "rejected a gateway event"),
+    }
+}
+
+/// Creates the span around validating a generator.
+pub(crate) fn validate_span(generator: &OsGatewayAttributeGenerator) -> Span {
+    let span = debug_span!(
+        "os_gateway_validate",
+        event_type = Empty,
+        scope_address = Empty
+    );
+    record_fields(&span, generator);
+    span
+}
+
+fn record_fields(span: &Span, generator: &OsGatewayAttributeGenerator) {
+    if let Some(event_type) = generator.get_key(OsGatewayKey::EventType) {
+        span.record("event_type", event_type);
+    }
+    if let Some(scope_address) = generator.get_key(OsGatewayKey::ScopeAddress) {
+        span.record("scope_address", scope_address);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{AccessLevel, GrantExpiration, OsGatewayAttributeGenerator};
+use wasm_bindgen::prelude::*;
+
+/// Exposes an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) to JavaScript
+/// through [wasm-bindgen](https://github.com/rustwasm/wasm-bindgen), so that web frontends can
+/// construct and validate gateway attributes with the same logic that contracts use, rather than
+/// duplicating it.  The class is exported to JavaScript as `OsGatewayAttributeGenerator`.
+///
+/// Optional values are provided in their attribute encodings, such as `height:100` for an
+/// expiration and `read_write` for an access level, and every method that can fail throws an
+/// `Error` whose message describes the problem.
+///
+/// ```js
+/// const { OsGatewayAttributeGenerator } = require("os-gateway-contract-attributes");
+///
+/// const generator = OsGatewayAttributeGenerator.accessGrant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// generator.setAccessGrantId("my_unique_id");
+/// generator.setExpiration("height:100");
+/// generator.validate();
+/// // [{ key: "object_store_gateway_event_type", value: "access_grant" }, ...]
+/// const attributes = generator.toAttributesJson();
+/// ```
+#[wasm_bindgen(js_name = OsGatewayAttributeGenerator)]
+pub struct JsAttributeGenerator {
+    generator: OsGatewayAttributeGenerator,
+}
+
+#[wasm_bindgen(js_class = OsGatewayAttributeGenerator)]
+impl JsAttributeGenerator {
+    /// Constructs a generator for an access grant.  See [OsGatewayAttributeGenerator::access_grant](crate::OsGatewayAttributeGenerator::access_grant).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope to which access is granted.
+    /// * `target_account_address` The bech32 address of the account to which access is granted.
+    #[wasm_bindgen(js_name = accessGrant)]
+    pub fn access_grant(scope_address: String, target_account_address: String) -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::access_grant(
+                scope_address,
+                target_account_address,
+            ),
+        }
+    }
+
+    /// Constructs a generator for an access revoke.  See [OsGatewayAttributeGenerator::access_revoke](crate::OsGatewayAttributeGenerator::access_revoke).
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope from which access is revoked.
+    /// * `target_account_address` The bech32 address of the account whose access is revoked.
+    #[wasm_bindgen(js_name = accessRevoke)]
+    pub fn access_revoke(scope_address: String, target_account_address: String) -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::access_revoke(
+                scope_address,
+                target_account_address,
+            ),
+        }
+    }
+
+    /// Sets the access grant id, replacing any previously set.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant.
+    #[wasm_bindgen(js_name = setAccessGrantId)]
+    pub fn set_access_grant_id(&mut self, access_grant_id: String) {
+        self.generator.set_access_grant_id(access_grant_id);
+    }
+
+    /// Sets the expiration, replacing any previously set.  Throws if the value is not a valid
+    /// [GrantExpiration](crate::GrantExpiration) encoding.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The expiration, as `never`, `<unix seconds>`, or `height:<block height>`.
+    #[wasm_bindgen(js_name = setExpiration)]
+    pub fn set_expiration(&mut self, expiration: &str) -> Result<(), JsError> {
+        let expiration = expiration
+            .parse::<GrantExpiration>()
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        self.generator.insert_expiration(expiration);
+        Ok(())
+    }
+
+    /// Sets the access level, replacing any previously set.  Throws if the value is not a
+    /// recognized [AccessLevel](crate::AccessLevel).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The access level, as `read` or `read_write`.
+    #[wasm_bindgen(js_name = setAccessLevel)]
+    pub fn set_access_level(&mut self, access_level: &str) -> Result<(), JsError> {
+        let access_level = access_level
+            .parse::<AccessLevel>()
+            .map_err(|error| JsError::new(&error.to_string()))?;
+        self.generator.insert_access_level(access_level);
+        Ok(())
+    }
+
+    /// Stamps the attributes with the current payload version.  See [OsGatewayAttributeGenerator::set_payload_version](crate::OsGatewayAttributeGenerator::set_payload_version).
+    #[wasm_bindgen(js_name = setPayloadVersion)]
+    pub fn set_payload_version(&mut self) {
+        self.generator.set_payload_version();
+    }
+
+    /// Stamps the attributes with the name and version of the contract that produces them.  See
+    /// [OsGatewayAttributeGenerator::set_source_contract_version](crate::OsGatewayAttributeGenerator::set_source_contract_version).
+    ///
+    /// # Parameters
+    ///
+    /// * `contract` The name of the contract.
+    /// * `version` The version of the contract.
+    #[wasm_bindgen(js_name = setSourceContractVersion)]
+    pub fn set_source_contract_version(&mut self, contract: &str, version: &str) {
+        self.generator
+            .set_source_contract_version(contract, version);
+    }
+
+    /// Validates the value of every recognized attribute, throwing an error that lists every
+    /// problem found.  See [OsGatewayAttributeGenerator::validate](crate::OsGatewayAttributeGenerator::validate).
+    pub fn validate(&self) -> Result<(), JsError> {
+        self.generator
+            .validate()
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    /// The attributes held in the generator, as an array of `{ key, value }` objects matching the
+    /// JSON layout of a [Cosmwasm Attribute](cosmwasm_std::Attribute), in the same order as
+    /// [iter](crate::OsGatewayAttributeGenerator::iter).
+    #[wasm_bindgen(js_name = toAttributesJson)]
+    pub fn to_attributes_json(&self) -> js_sys::Array {
+        self.generator
+            .iter()
+            .map(|(key, value)| {
+                let attribute = js_sys::Object::new();
+                // Setting a property on a freshly created plain object cannot fail
+                js_sys::Reflect::set(&attribute, &"key".into(), &key.into())
+                    .expect("the key should be set on a plain object");
+                js_sys::Reflect::set(&attribute, &"value".into(), &value.into())
+                    .expect("the value should be set on a plain object");
+                attribute
+            })
+            .collect()
+    }
+}
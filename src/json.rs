@@ -0,0 +1,316 @@
+use cosmwasm_std::{Binary, StdResult};
+
+/// Converts the crate's serializable types to and from the JSON [Binary](cosmwasm_std::Binary)
+/// that contracts embed in their messages, such as a gateway payload carried in an `ExecuteMsg`
+/// field.  The conversions use the same serializer as [cosmwasm_std::to_json_binary] and
+/// [cosmwasm_std::from_json], so the output matches what contracts produce.
+///
+/// Events and generators serialize as flat maps of their attribute keys to values.  Access grants
+/// and revokes serialize as structures of their short field names, such as `scope_address`, but
+/// also accept their attribute keys, or any mix of the two, when deserialized.  The validated
+/// newtypes serialize as their attribute values.  Every encoding is stable.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrant, OsGatewayJson};
+///
+/// let grant = AccessGrant::new(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// let binary = grant.to_json_binary().expect("the grant should serialize");
+/// assert_eq!(grant, AccessGrant::from_json(&binary).expect("the grant should deserialize"));
+/// ```
+pub trait OsGatewayJson: serde::Serialize + serde::de::DeserializeOwned {
+    /// Serializes the value as JSON.
+    fn to_json_binary(&self) -> StdResult<Binary> {
+        cosmwasm_std::to_json_binary(self)
+    }
+
+    /// Deserializes a value from JSON, rejecting JSON that does not describe a valid value.
+    ///
+    /// # Parameters
+    ///
+    /// * `binary` The JSON to deserialize.
+    fn from_json(binary: &Binary) -> StdResult<Self> {
+        cosmwasm_std::from_json(binary)
+    }
+}
+impl OsGatewayJson for crate::AccessGrant {}
+impl OsGatewayJson for crate::AccessGrantId {}
+impl OsGatewayJson for crate::AccessLevel {}
+impl OsGatewayJson for crate::AccessRevoke {}
+impl OsGatewayJson for crate::AccountAddress {}
+impl OsGatewayJson for crate::FieldError {}
+impl OsGatewayJson for crate::GrantExpiration {}
+impl OsGatewayJson for crate::GrantTarget {}
+impl OsGatewayJson for crate::OsGatewayAttributeGenerator {}
+impl OsGatewayJson for crate::OsGatewayAttributeConflict {}
+impl OsGatewayJson for crate::OsGatewayDefaults {}
+impl OsGatewayJson for crate::OsGatewayError {}
+impl OsGatewayJson for crate::OsGatewayEvent {}
+impl OsGatewayJson for crate::OsGatewayEventType {}
+impl OsGatewayJson for crate::OsGatewayPayloadVersion {}
+impl OsGatewayJson for crate::ScopeAddress {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessGrantId, AccessLevel, AccessRevoke, AccountAddress, FieldError,
+        FieldErrorKind, GrantExpiration, GrantTarget, OsGatewayAttributeGenerator,
+        OsGatewayDefaults, OsGatewayEvent, OsGatewayEventType, OsGatewayJson, OsGatewayKey,
+        OsGatewayPayloadVersion, ScopeAddress,
+    };
+    use cosmwasm_std::Binary;
+    use std::fmt::Debug;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    /// Verifies that the value serializes to exactly the expected JSON, and back into itself.
+    fn assert_snapshot<T: OsGatewayJson + Debug + PartialEq>(value: T, expected: &str) {
+        let binary = value.to_json_binary().expect("the value should serialize");
+        assert_eq!(
+            expected,
+            String::from_utf8(binary.to_vec()).unwrap(),
+            "the value [{value:?}] should serialize to its pinned JSON",
+        );
+        assert_eq!(
+            value,
+            T::from_json(&binary).expect("the value should deserialize"),
+            "the value should survive a JSON round trip",
+        );
+        assert_eq!(
+            cosmwasm_std::to_json_binary(&value).unwrap(),
+            binary,
+            "the output should match the cosmwasm serializer",
+        );
+    }
+
+    #[test]
+    fn test_events_serialize_as_attribute_maps() {
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::Read);
+        let grant_json = format!(
+            concat!(
+                r#"{{"object_store_gateway_access_grant_id":"my_unique_id","#,
+                r#""object_store_gateway_access_level":"read","#,
+                r#""object_store_gateway_event_type":"access_grant","#,
+                r#""object_store_gateway_expiration":"height:100","#,
+                r#""object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                r#""object_store_gateway_target_account_address":"{ACCOUNT_ADDRESS}"}}"#,
+            ),
+            SCOPE_ADDRESS = SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+        );
+        assert_snapshot(OsGatewayEvent::from(grant.clone()), &grant_json);
+        assert_snapshot(OsGatewayAttributeGenerator::from(grant), &grant_json);
+        let revoke_json = format!(
+            concat!(
+                r#"{{"object_store_gateway_event_type":"access_revoke","#,
+                r#""object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                r#""object_store_gateway_target_account_address":"{ACCOUNT_ADDRESS}"}}"#,
+            ),
+            SCOPE_ADDRESS = SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+        );
+        assert_snapshot(
+            OsGatewayEvent::from(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+            &revoke_json,
+        );
+        assert_snapshot(
+            OsGatewayDefaults::new().with_attribute("team", "records"),
+            r#"{"team":"records"}"#,
+        );
+    }
+
+    #[test]
+    fn test_typed_events_serialize_with_short_field_names() {
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::Read)
+            .with_extra_attribute("object_store_gateway_note", "shared");
+        assert_snapshot(
+            grant,
+            &format!(
+                concat!(
+                    r#"{{"schema_version":2,"scope_address":"{SCOPE_ADDRESS}","#,
+                    r#""target_account_address":"{ACCOUNT_ADDRESS}","#,
+                    r#""access_grant_id":"my_unique_id","#,
+                    r#""expiration":"height:100","#,
+                    r#""access_level":"read","#,
+                    r#""extra_attributes":{{"object_store_gateway_note":"shared"}}}}"#,
+                ),
+                SCOPE_ADDRESS = SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+            ),
+        );
+        assert_snapshot(
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            &format!(
+                r#"{{"schema_version":2,"scope_address":"{SCOPE_ADDRESS}","target_account_address":"{ACCOUNT_ADDRESS}"}}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn test_typed_events_accept_attribute_keys() {
+        let expected = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::Read)
+            .with_extra_attribute("object_store_gateway_note", "shared");
+        let fixtures = [
+            (
+                "short field names",
+                format!(
+                    concat!(
+                        r#"{{"scope_address":"{SCOPE_ADDRESS}","#,
+                        r#""target_account_address":"{ACCOUNT_ADDRESS}","#,
+                        r#""access_grant_id":"my_unique_id","#,
+                        r#""expiration":"height:100","#,
+                        r#""access_level":"read","#,
+                        r#""extra_attributes":{{"object_store_gateway_note":"shared"}}}}"#,
+                    ),
+                    SCOPE_ADDRESS = SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+                ),
+            ),
+            (
+                "attribute keys",
+                format!(
+                    concat!(
+                        r#"{{"object_store_gateway_event_type":"access_grant","#,
+                        r#""object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                        r#""object_store_gateway_target_account_address":"{ACCOUNT_ADDRESS}","#,
+                        r#""object_store_gateway_access_grant_id":"my_unique_id","#,
+                        r#""object_store_gateway_expiration":"height:100","#,
+                        r#""object_store_gateway_access_level":"read","#,
+                        r#""object_store_gateway_note":"shared"}}"#,
+                    ),
+                    SCOPE_ADDRESS = SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+                ),
+            ),
+            (
+                "mixed spellings",
+                format!(
+                    concat!(
+                        r#"{{"scope_address":"{SCOPE_ADDRESS}","#,
+                        r#""object_store_gateway_target_account_address":"{ACCOUNT_ADDRESS}","#,
+                        r#""access_grant_id":"my_unique_id","#,
+                        r#""object_store_gateway_expiration":"height:100","#,
+                        r#""access_level":"read","#,
+                        r#""object_store_gateway_note":"shared"}}"#,
+                    ),
+                    SCOPE_ADDRESS = SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+                ),
+            ),
+        ];
+        for (format, json) in fixtures {
+            assert_eq!(
+                expected,
+                AccessGrant::from_json(&Binary::from(json.as_bytes())).unwrap(),
+                "a grant keyed by {format} should deserialize",
+            );
+        }
+        assert_eq!(
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("my_unique_id"),
+            AccessRevoke::from_json(&Binary::from(
+                format!(
+                    concat!(
+                        r#"{{"object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                        r#""target_account_address":"{ACCOUNT_ADDRESS}","#,
+                        r#""object_store_gateway_access_grant_id":"my_unique_id"}}"#,
+                    ),
+                    SCOPE_ADDRESS = SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+                )
+                .as_bytes()
+            ))
+            .unwrap(),
+            "a revoke keyed by mixed spellings should deserialize",
+        );
+    }
+
+    #[test]
+    fn test_typed_events_reject_conflicting_spellings() {
+        let json = format!(
+            concat!(
+                r#"{{"scope_address":"{SCOPE_ADDRESS}","#,
+                r#""object_store_gateway_scope_address":"{SCOPE_ADDRESS}","#,
+                r#""target_account_address":"{ACCOUNT_ADDRESS}"}}"#,
+            ),
+            SCOPE_ADDRESS = SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+        );
+        assert!(
+            AccessGrant::from_json(&Binary::from(json.as_bytes())).is_err(),
+            "a field provided under both spellings should be rejected",
+        );
+        let json = format!(
+            concat!(
+                r#"{{"object_store_gateway_event_type":"access_revoke","#,
+                r#""scope_address":"{SCOPE_ADDRESS}","#,
+                r#""target_account_address":"{ACCOUNT_ADDRESS}"}}"#,
+            ),
+            SCOPE_ADDRESS = SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS = ACCOUNT_ADDRESS,
+        );
+        assert!(
+            AccessGrant::from_json(&Binary::from(json.as_bytes())).is_err(),
+            "an explicit event type of a different kind should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_newtypes_serialize_as_attribute_values() {
+        assert_snapshot(
+            ScopeAddress::new(SCOPE_ADDRESS).unwrap(),
+            &format!(r#""{SCOPE_ADDRESS}""#),
+        );
+        assert_snapshot(
+            AccountAddress::new(ACCOUNT_ADDRESS).unwrap(),
+            &format!(r#""{ACCOUNT_ADDRESS}""#),
+        );
+        assert_snapshot(
+            AccessGrantId::new("my_unique_id").unwrap(),
+            r#""my_unique_id""#,
+        );
+        assert_snapshot(AccessLevel::ReadWrite, r#""read_write""#);
+        assert_snapshot(OsGatewayEventType::AccessRevoke, r#""access_revoke""#);
+        assert_snapshot(OsGatewayPayloadVersion::CURRENT, r#""1""#);
+        assert_snapshot(GrantExpiration::AtHeight(100), r#""height:100""#);
+        assert_snapshot(
+            GrantTarget::Account(ACCOUNT_ADDRESS.to_string()),
+            &format!(r#""account:{ACCOUNT_ADDRESS}""#),
+        );
+        assert_snapshot(
+            FieldError::new(
+                OsGatewayKey::TargetAccount,
+                FieldErrorKind::InvalidAccountAddress,
+                "target",
+            ),
+            r#"{"key":"object_store_gateway_target_account_address","kind":"invalid_account_address","value_preview":"target"}"#,
+        );
+    }
+
+    #[test]
+    fn test_invalid_json_is_rejected() {
+        assert!(
+            AccessGrant::from_json(&Binary::from(
+                br#"{"object_store_gateway_event_type":"access_grant"}"#.as_slice()
+            ))
+            .is_err(),
+            "an incomplete grant should be rejected",
+        );
+        assert!(
+            OsGatewayEventType::from_json(&Binary::from(br#""access_request""#.as_slice()))
+                .is_err(),
+            "an unrecognized event type should be rejected",
+        );
+    }
+}
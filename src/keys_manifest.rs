@@ -0,0 +1,343 @@
+use crate::attribute_keys::REQUIRED_KEYS;
+use crate::{OsGatewayEventType, OsGatewayKey, OsGatewayPayloadVersion};
+use serde::ser::SerializeStruct;
+
+/// The version of the manifest document's own layout, incremented whenever a field is renamed or
+/// removed.
+const MANIFEST_VERSION: u32 = 1;
+
+/// Produces a JSON document describing every attribute key and event type value recognized by
+/// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway), so that services
+/// written in other languages can load them rather than hardcoding the strings.  The document is
+/// generated from the same constants that this crate emits, so the two can never drift apart.
+///
+/// The document has the following fields:
+///
+/// * `manifest_version` The version of the document's layout, incremented whenever a field is
+///   renamed or removed.
+/// * `payload_version` The [payload version](crate::OsGatewayPayloadVersion) of the attribute
+///   layout described.
+/// * `keys` Every attribute key, in emission order, as an object containing its field `name` in
+///   [OsGatewayKeys](crate::OsGatewayKeys), its exact `key`, a description of its `purpose`,
+///   whether or not it is `required`, and the `event_types` to which it applies.
+/// * `event_types` Every event type, as an object containing its exact `value` and a description
+///   of its `purpose`.
+///
+/// ```
+/// use os_gateway_contract_attributes::{keys_manifest, OS_GATEWAY_KEYS};
+///
+/// let manifest = keys_manifest();
+/// assert!(manifest.contains(OS_GATEWAY_KEYS.scope_address));
+/// ```
+pub fn keys_manifest() -> String {
+    // Serializing the manifest cannot fail, as it contains only strings, numbers, and lists
+    cosmwasm_std::to_json_string(&Manifest).expect("the keys manifest should always serialize")
+}
+
+pub(crate) fn key_purpose(key: OsGatewayKey) -> &'static str {
+    match key {
+        OsGatewayKey::EventType => "Denotes which functionality the gateway invokes upon digesting the event.",
+        OsGatewayKey::ScopeAddress => "The bech32 address of the scope to which the event refers.",
+        OsGatewayKey::TargetAccount => "The bech32 address of the account upon which the event takes action.",
+        OsGatewayKey::AccessGrantId => "The unique identifier of the grant that the event creates or removes.",
+        OsGatewayKey::Expiration => "When the resulting grant stops being honored, as never, <unix seconds>, or height:<block height>.",
+        OsGatewayKey::AccessLevel => "The level of access that the resulting grant gives, as read or read_write.",
+        OsGatewayKey::PayloadVersion => "The version of the attribute layout, used to detect events produced by an incompatible release.",
+        OsGatewayKey::SourceContractVersion => "The name and version of the contract that produced the event, as <contract name>/<version>.",
+        OsGatewayKey::TargetKind => "The kind of target that the target account address identifies, as account, marker, or scope_spec.",
+    }
+}
+
+/// The event types to which the key applies.  Expirations and access levels only apply to grants.
+pub(crate) fn key_event_types(key: OsGatewayKey) -> &'static [OsGatewayEventType] {
+    match key {
+        OsGatewayKey::EventType
+        | OsGatewayKey::ScopeAddress
+        | OsGatewayKey::TargetAccount
+        | OsGatewayKey::AccessGrantId
+        | OsGatewayKey::PayloadVersion
+        | OsGatewayKey::SourceContractVersion
+        | OsGatewayKey::TargetKind => &OsGatewayEventType::ALL,
+        OsGatewayKey::Expiration | OsGatewayKey::AccessLevel => &[OsGatewayEventType::AccessGrant],
+    }
+}
+
+pub(crate) fn event_type_purpose(event_type: OsGatewayEventType) -> &'static str {
+    match event_type {
+        OsGatewayEventType::AccessGrant => "Grants the target account access to the records of the scope.",
+        OsGatewayEventType::AccessRevoke => "Revokes the target account's access to the records of the scope, limited to the grant with the access grant id if one is provided.",
+    }
+}
+
+struct Manifest;
+impl serde::Serialize for Manifest {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut manifest = serializer.serialize_struct("Manifest", 4)?;
+        manifest.serialize_field("manifest_version", &MANIFEST_VERSION)?;
+        manifest.serialize_field("payload_version", &OsGatewayPayloadVersion::CURRENT)?;
+        manifest.serialize_field("keys", &OsGatewayKey::ALL.map(KeyEntry))?;
+        manifest.serialize_field("event_types", &OsGatewayEventType::ALL.map(EventTypeEntry))?;
+        manifest.end()
+    }
+}
+
+struct KeyEntry(OsGatewayKey);
+impl serde::Serialize for KeyEntry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entry = serializer.serialize_struct("KeyEntry", 5)?;
+        entry.serialize_field("name", self.0.field_name())?;
+        entry.serialize_field("key", self.0.as_str())?;
+        entry.serialize_field("purpose", key_purpose(self.0))?;
+        entry.serialize_field("required", &REQUIRED_KEYS.contains(&self.0.as_str()))?;
+        entry.serialize_field(
+            "event_types",
+            &key_event_types(self.0)
+                .iter()
+                .map(|event_type| event_type.as_str())
+                .collect::<Vec<&str>>(),
+        )?;
+        entry.end()
+    }
+}
+
+struct EventTypeEntry(OsGatewayEventType);
+impl serde::Serialize for EventTypeEntry {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entry = serializer.serialize_struct("EventTypeEntry", 2)?;
+        entry.serialize_field("value", self.0.as_str())?;
+        entry.serialize_field("purpose", event_type_purpose(self.0))?;
+        entry.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::key_purpose;
+    use crate::{
+        keys_manifest, GrantExpiration, OsGatewayEventTypes, OsGatewayKey, OsGatewayKeys,
+        OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+    };
+    use std::collections::BTreeMap;
+
+    /// A parsed JSON value, so that the manifest can be inspected without a typed model.
+    #[derive(Debug, PartialEq)]
+    enum Json {
+        Bool(bool),
+        Number(u64),
+        String(String),
+        Array(Vec<Json>),
+        Object(BTreeMap<String, Json>),
+    }
+    impl Json {
+        fn field(&self, name: &str) -> &Json {
+            match self {
+                Json::Object(fields) => fields
+                    .get(name)
+                    .unwrap_or_else(|| panic!("expected the field [{name}]")),
+                other => panic!("expected an object but found {other:?}"),
+            }
+        }
+
+        fn items(&self) -> &[Json] {
+            match self {
+                Json::Array(items) => items,
+                other => panic!("expected an array but found {other:?}"),
+            }
+        }
+
+        fn str(&self) -> &str {
+            match self {
+                Json::String(value) => value,
+                other => panic!("expected a string but found {other:?}"),
+            }
+        }
+    }
+    impl<'de> serde::Deserialize<'de> for Json {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct JsonVisitor;
+            impl<'de> serde::de::Visitor<'de> for JsonVisitor {
+                type Value = Json;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("a JSON value")
+                }
+
+                fn visit_bool<E>(self, value: bool) -> Result<Json, E> {
+                    Ok(Json::Bool(value))
+                }
+
+                fn visit_u64<E>(self, value: u64) -> Result<Json, E> {
+                    Ok(Json::Number(value))
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Json, E> {
+                    Ok(Json::String(value.to_string()))
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: A,
+                ) -> Result<Json, A::Error> {
+                    let mut items = vec![];
+                    while let Some(item) = seq.next_element()? {
+                        items.push(item);
+                    }
+                    Ok(Json::Array(items))
+                }
+
+                fn visit_map<A: serde::de::MapAccess<'de>>(
+                    self,
+                    mut map: A,
+                ) -> Result<Json, A::Error> {
+                    let mut fields = BTreeMap::new();
+                    while let Some((name, value)) = map.next_entry()? {
+                        fields.insert(name, value);
+                    }
+                    Ok(Json::Object(fields))
+                }
+            }
+            deserializer.deserialize_any(JsonVisitor)
+        }
+    }
+
+    fn parse_manifest() -> Json {
+        cosmwasm_std::from_json(keys_manifest().as_bytes()).expect("the manifest should parse")
+    }
+
+    #[test]
+    fn test_manifest_lists_every_key() {
+        // Exhaustively destructure the keys so that adding a field without updating this test
+        // fails to compile
+        let OsGatewayKeys {
+            event_type,
+            scope_address,
+            target_account,
+            access_grant_id,
+            expiration,
+            access_level,
+            payload_version,
+            source_contract_version,
+            target_kind,
+        } = OS_GATEWAY_KEYS;
+        let expected = [
+            ("event_type", event_type, true),
+            ("scope_address", scope_address, true),
+            ("target_account", target_account, true),
+            ("access_grant_id", access_grant_id, false),
+            ("expiration", expiration, false),
+            ("access_level", access_level, false),
+            ("payload_version", payload_version, false),
+            ("source_contract_version", source_contract_version, false),
+            ("target_kind", target_kind, false),
+        ];
+        let manifest = parse_manifest();
+        let keys = manifest.field("keys").items();
+        assert_eq!(
+            expected.len(),
+            keys.len(),
+            "the manifest should list every key"
+        );
+        for ((name, key, required), entry) in expected.into_iter().zip(keys) {
+            assert_eq!(
+                name,
+                entry.field("name").str(),
+                "the key names should match"
+            );
+            assert_eq!(
+                key,
+                entry.field("key").str(),
+                "the key [{name}] should match"
+            );
+            assert_eq!(
+                &Json::Bool(required),
+                entry.field("required"),
+                "the key [{name}] should be required only if the gateway requires it",
+            );
+            assert!(
+                !entry.field("purpose").str().is_empty(),
+                "the key [{name}] should describe its purpose",
+            );
+            assert!(
+                !entry.field("event_types").items().is_empty(),
+                "the key [{name}] should apply to at least one event type",
+            );
+        }
+    }
+
+    #[test]
+    fn test_manifest_lists_every_event_type() {
+        let OsGatewayEventTypes {
+            access_grant,
+            access_revoke,
+        } = OS_GATEWAY_EVENT_TYPES;
+        let manifest = parse_manifest();
+        assert_eq!(
+            vec![access_grant, access_revoke],
+            manifest
+                .field("event_types")
+                .items()
+                .iter()
+                .map(|entry| entry.field("value").str())
+                .collect::<Vec<&str>>(),
+            "the manifest should list every event type, in order",
+        );
+        for entry in manifest.field("keys").items() {
+            for event_type in entry.field("event_types").items() {
+                assert!(
+                    [access_grant, access_revoke].contains(&event_type.str()),
+                    "the key [{}] should only apply to recognized event types",
+                    entry.field("name").str(),
+                );
+            }
+        }
+        assert_eq!(
+            vec![access_grant],
+            manifest
+                .field("keys")
+                .items()
+                .iter()
+                .find(|entry| entry.field("key").str() == OS_GATEWAY_KEYS.expiration)
+                .unwrap()
+                .field("event_types")
+                .items()
+                .iter()
+                .map(Json::str)
+                .collect::<Vec<&str>>(),
+            "expirations should only apply to grants",
+        );
+    }
+
+    #[test]
+    fn test_manifest_is_versioned() {
+        let manifest = parse_manifest();
+        assert_eq!(
+            &Json::Number(1),
+            manifest.field("manifest_version"),
+            "the manifest should declare its layout version",
+        );
+        assert_eq!(
+            "1",
+            manifest.field("payload_version").str(),
+            "the manifest should declare the payload version it describes",
+        );
+    }
+
+    #[test]
+    fn test_expiration_purpose_describes_wire_encoding() {
+        let purpose = key_purpose(OsGatewayKey::Expiration);
+        for (expiration, described_as) in [
+            (GrantExpiration::Never, "never"),
+            (GrantExpiration::AtSeconds(1700000000), "<unix seconds>"),
+            (GrantExpiration::AtHeight(12345), "height:<block height>"),
+        ] {
+            assert!(
+                purpose.contains(described_as),
+                "the expiration [{expiration}] should be described as [{described_as}] in: {purpose}",
+            );
+        }
+        assert!(
+            !purpose.contains("time:"),
+            "expirations in seconds are not prefixed, but found: {purpose}",
+        );
+    }
+}
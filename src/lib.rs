@@ -6,11 +6,12 @@
 //! To use it, simply append the required object store gateway action as attributes in the
 //! Response's fluent builder:
 //! ```
+//! # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
 //!  mod some_mod {
 //!     use cosmwasm_std::Response;
 //!     use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
 //!
-//!     fn gen_grant_response() -> Response<String> {
+//!     fn gen_grant_response() -> Response {
 //!         Response::new()
 //!             .add_attributes(
 //!                 OsGatewayAttributeGenerator::access_grant(
@@ -27,7 +28,7 @@
 //!             )
 //!     }
 //!
-//!     fn gen_revoke_response() -> Response<String> {
+//!     fn gen_revoke_response() -> Response {
 //!         Response::new()
 //!             .add_attributes(
 //!                 OsGatewayAttributeGenerator::access_revoke(
@@ -39,6 +40,7 @@
 //!             )
 //!     }
 //!  }
+//! # }
 //! ```
 //!
 //! Also provided are the actual attribute keys used in the [OsGatewayAttributeGenerator](self::OsGatewayAttributeGenerator),
@@ -62,11 +64,173 @@
 //!         println!("Access revoke event type: {}", OS_GATEWAY_EVENT_TYPES.access_revoke);
 //!     }
 //! }
+//! ```
+//!
+//! ## Custom Events
+//!
+//! By default, attributes are appended directly to the Response, where the chain places them in
+//! the generic `wasm` event alongside every other attribute the contract emits.  This is the form
+//! that all [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) deployments
+//! recognize.  To instead emit the attributes in a dedicated custom event, which the chain will
+//! observe as `wasm-object_store_gateway`, convert the generator into an event:
+//!
+//! ```
+//! # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+//! mod some_mod {
+//!     use cosmwasm_std::Response;
+//!     use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+//!
+//!     fn gen_grant_event_response() -> Response {
+//!         Response::new().add_event(
+//!             OsGatewayAttributeGenerator::access_grant(
+//!                 // Scope Address
+//!                 "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!                 // Grantee Address
+//!                 "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!             )
+//!             .to_event()
+//!         )
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! Emitted events in either form can be parsed back into typed [OsGatewayEvents](self::OsGatewayEvent)
+//! with [parse_gateway_events](self::parse_gateway_events).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+compile_error!(
+    "the `cosmwasm_1` and `cosmwasm_2` features are mutually exclusive; the `offchain` feature \
+     enables `cosmwasm_1`, so enable its individual features instead to build against cosmwasm-std \
+     2.x"
+);
+#[cfg(all(
+    not(any(feature = "cosmwasm_1", feature = "cosmwasm_2")),
+    any(feature = "parsing", feature = "serde", feature = "fixtures")
+))]
+compile_error!(
+    "the `parsing`, `serde`, and `fixtures` features, and every feature built on them, use \
+     cosmwasm-std types, and require one of the `cosmwasm_1` or `cosmwasm_2` features to select a \
+     cosmwasm-std version"
+);
+#[cfg(all(
+    not(feature = "cosmwasm_1"),
+    any(
+        feature = "storage",
+        feature = "multi-test",
+        feature = "provwasm",
+        feature = "cw2"
+    )
+))]
+compile_error!(
+    "the `storage`, `multi-test`, `provwasm`, and `cw2` features depend on crates built against \
+     cosmwasm-std 1.x, and require the `cosmwasm_1` feature"
+);
+#[cfg(all(feature = "cosmwasm_2", not(feature = "cosmwasm_1")))]
+extern crate cosmwasm_std_2 as cosmwasm_std;
+
+pub use access_grant_id::AccessGrantId;
+pub use access_level::{AccessLevel, LenientAccessLevel};
+#[cfg(all(feature = "test-utils", not(target_arch = "wasm32")))]
+pub use access_simulation::{simulate_access_state, AccessState, SimulatedGrant, SimulationError};
+pub use address::{AccountAddress, ScopeAddress};
+pub use attribute_event_types::{
+    OsGatewayEventType, OsGatewayEventTypes, OS_GATEWAY_CUSTOM_EVENT_TYPE, OS_GATEWAY_EVENT_TYPES,
+    OS_GATEWAY_EVENT_TYPE_STRS,
+};
+pub use attribute_generator::{OsGatewayAttributeGenerator, OsGatewayAttributeIter};
+pub use attribute_keys::{
+    OsGatewayKey, OsGatewayKeys, OS_GATEWAY_ALL_KEYS, OS_GATEWAY_KEYS, OS_GATEWAY_KEY_STRS,
+};
+#[cfg(feature = "std")]
+pub use canonical_bytes::OS_GATEWAY_SIGNATURE_KEY_PREFIX;
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+pub use compact_payload::OS_GATEWAY_PAYLOAD_KEY;
+pub use defaults::OsGatewayDefaults;
+#[cfg(feature = "serde")]
+pub use encoded_event::OS_GATEWAY_MAX_ENCODED_EVENT_BYTES;
+pub use error::{OsGatewayAttributeConflict, OsGatewayError};
+#[cfg(feature = "parsing")]
+pub use event_batch::GatewayEventBatch;
+pub use event_builder::{Missing, OsGatewayEventBuilder, Present, Unchecked};
+#[cfg(feature = "std")]
+pub use event_category::EventCategory;
+pub use event_encoder::EventEncoder;
+#[cfg(feature = "parsing")]
+pub use event_ext::OsGatewayEventExt;
+#[cfg(feature = "parsing")]
+pub use event_normalization::normalize_wasm_events;
+#[cfg(feature = "parsing")]
+pub use event_parser::{
+    parse_gateway_event, parse_gateway_event_ref, parse_gateway_events,
+    parse_gateway_events_from_reply, parse_gateway_events_ref, strip_wasm_prefix, wasm_event_type,
+};
+#[cfg(all(feature = "schema", not(target_arch = "wasm32")))]
+pub use events_schema::{gateway_events_schema, OS_GATEWAY_EVENTS_SCHEMA_KEY};
+#[cfg(feature = "parsing")]
+pub use expected_event::{
+    ExpectedGatewayEvent, ExpectedValue, FieldMismatch, MatchResult, UnmatchedExpectation,
+};
+pub use field_error::{FieldError, FieldErrorKind};
+#[cfg(feature = "std")]
+pub use gateway_attribute::OsGatewayAttribute;
+pub use gateway_event::{dedup_events, sort_events, AccessGrant, AccessRevoke, OsGatewayEvent};
+#[cfg(feature = "parsing")]
+pub use gateway_event_ref::{
+    AccessGrantRef, AccessRevokeRef, GatewayEventFields, OsGatewayEventRef,
+};
+pub use grant_expiration::GrantExpiration;
+#[cfg(feature = "std")]
+pub use grant_key::GrantKey;
+pub use grant_target::GrantTarget;
+#[cfg(feature = "std")]
+pub use grant_template::OsGatewayGrantTemplate;
+#[cfg(feature = "serde")]
+pub use ibc_memo::{OS_GATEWAY_IBC_MEMO_KEY, OS_GATEWAY_MAX_IBC_MEMO_BYTES};
+#[cfg(feature = "js")]
+pub use js::JsAttributeGenerator;
+#[cfg(feature = "serde")]
+pub use json::OsGatewayJson;
+#[cfg(feature = "json")]
+pub use keys_manifest::keys_manifest;
+#[cfg(feature = "metadata-address")]
+pub use metadata_address::{scope_address_from_uuid, scope_uuid_from_address};
+#[cfg(feature = "test-utils")]
+pub use mock_gateway::{MockGateway, ProcessingContext, ProcessingOutcome};
+#[cfg(feature = "msgpack")]
+pub use msgpack::OsGatewayMsgpack;
+pub use payload_version::OsGatewayPayloadVersion;
+#[cfg(feature = "provenance-proto")]
+pub use provenance_proto::parse_proto_event;
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+pub use response_ext::OsGatewayResponseExt;
+pub use scoped_builder::{GrantBuilder, RevokeBuilder};
+#[cfg(feature = "provenance-proto")]
+pub use tx_service::{
+    parse_get_tx_response, parse_get_txs_event_response, parse_tx_response, TxGatewayEvent,
+};
+#[cfg(feature = "std")]
+pub use typed_builder::{AccessGrantBuilder, AccessRevokeBuilder};
 
-pub use attribute_event_types::{OsGatewayEventTypes, OS_GATEWAY_EVENT_TYPES};
-pub use attribute_generator::OsGatewayAttributeGenerator;
-pub use attribute_keys::{OsGatewayKeys, OS_GATEWAY_KEYS};
+#[cfg(feature = "ffi")]
+uniffi::setup_scaffolding!();
 
+/// A validated access grant id with generation helpers.
+mod access_grant_id;
+/// Levels of access that an access grant gives its target account.
+mod access_level;
+/// Simulation of the net access produced by a sequence of contract responses.
+#[cfg(all(feature = "test-utils", not(target_arch = "wasm32")))]
+mod access_simulation;
+/// Validated bech32 address types.
+mod address;
+/// Arbitrary values of the typed events and generators, for fuzzing.
+#[cfg(feature = "arbitrary")]
+mod arbitrary_events;
 /// Attribute qualifiers that drive the values generated for the object_store_gateway_event_type
 /// attribute.
 mod attribute_event_types;
@@ -74,3 +238,183 @@ mod attribute_event_types;
 mod attribute_generator;
 /// Attribute qualifiers that drive the event keys that are generated.
 mod attribute_keys;
+/// CSV export and import of event batches.
+#[cfg(all(feature = "csv", not(target_arch = "wasm32")))]
+mod batch_csv;
+/// Borsh serialization of the typed events and newtypes, with pinned byte layouts.
+#[cfg(feature = "borsh")]
+mod borsh;
+/// The bytes that are signed to authorize gateway attributes off chain.
+#[cfg(feature = "std")]
+mod canonical_bytes;
+/// A REST client that confirms the gateway has processed access grants.
+#[cfg(all(feature = "client", not(target_arch = "wasm32")))]
+pub mod client;
+/// A single-attribute encoding of an entire gateway event.
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+mod compact_payload;
+/// A one-line textual rendering of typed gateway events.
+#[cfg(feature = "std")]
+mod compact_string;
+/// Deterministic digests of gateway event content, for deduplication.
+#[cfg(feature = "digest")]
+mod content_digest;
+/// Constructors for the cosmwasm-std types whose fields differ between supported versions.
+#[cfg(all(test, feature = "parsing"))]
+mod cosmwasm_compat;
+/// Stamping of the contract version stored by cw2 onto the attributes.
+#[cfg(feature = "cw2")]
+mod cw2_version;
+/// Attribute profiles that are applied to every event a contract emits.
+mod defaults;
+/// URL-safe base64 and hex encodings of gateway events for transport.
+#[cfg(feature = "serde")]
+mod encoded_event;
+/// Errors produced when attributes cannot be assembled.
+mod error;
+/// Structured serialization of errors.
+#[cfg(feature = "serde")]
+mod error_serde;
+/// Ordered collections of gateway events and the grants that survive them.
+#[cfg(feature = "parsing")]
+mod event_batch;
+/// A typestate builder that proves all required attributes are provided at compile time.
+mod event_builder;
+/// Broad classifications of gateway events by their effect.
+#[cfg(feature = "std")]
+mod event_category;
+/// A visitor over the attributes of gateway events in canonical order, for custom formats.
+mod event_encoder;
+/// An extension trait that inspects cosmwasm Events for gateway events.
+#[cfg(feature = "parsing")]
+mod event_ext;
+/// Normalization of nested contract events into plain wasm events.
+#[cfg(feature = "parsing")]
+mod event_normalization;
+/// Parsing of gateway events from emitted cosmwasm Events.
+#[cfg(feature = "parsing")]
+mod event_parser;
+/// A description of the emitted events for merging into a contract's generated API document.
+#[cfg(all(feature = "schema", not(target_arch = "wasm32")))]
+mod events_schema;
+/// Expectations that off-chain clients verify against the events of a transaction.
+#[cfg(feature = "parsing")]
+mod expected_event;
+/// Bindings that share parsing and validation with services written in other languages.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+/// Field-level details of attribute validation failures.
+mod field_error;
+/// Canonical rendering and hashing of generator fingerprints.
+mod fingerprint;
+/// A curated corpus of gateway attribute sets and their expected parse results.
+#[cfg(any(all(test, feature = "parsing"), feature = "fixtures"))]
+pub mod fixtures;
+/// A single typed attribute recognized by the gateway.
+#[cfg(feature = "std")]
+mod gateway_attribute;
+/// Typed representations of the events consumed by the gateway.
+mod gateway_event;
+/// Borrowed views of the typed gateway events, and the fields shared with their owned forms.
+#[cfg(feature = "parsing")]
+mod gateway_event_ref;
+/// Lifetimes that limit how long an access grant is honored.
+mod grant_expiration;
+/// Identities of access grants, suitable for map keys.
+#[cfg(feature = "std")]
+mod grant_key;
+/// A record in contract storage of the access grants that a contract has emitted.
+#[cfg(feature = "storage")]
+mod grant_ledger;
+/// Re-emission of the grants recorded in contract storage, in batches.
+#[cfg(feature = "storage")]
+mod grant_replay;
+/// Descriptions of who or what an access grant is given to.
+mod grant_target;
+/// A template that produces access grants for one scope across many target accounts.
+#[cfg(feature = "std")]
+mod grant_template;
+/// Encoding of gateway events in the memos of ICS-20 transfers, for relaying grants across chains.
+#[cfg(feature = "serde")]
+mod ibc_memo;
+/// Tracing spans and events emitted while parsing and validating gateway events.
+#[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+pub mod instrumentation;
+/// JavaScript bindings for constructing and validating attributes in web frontends.
+#[cfg(feature = "js")]
+mod js;
+/// Conversions between the serializable types and the JSON that contracts embed in messages.
+#[cfg(feature = "serde")]
+mod json;
+/// A machine-readable manifest of the recognized keys and event types.
+#[cfg(feature = "json")]
+mod keys_manifest;
+/// Tendermint RPC subscriptions that stream the gateway events of committed transactions.
+#[cfg(all(feature = "listener", not(target_arch = "wasm32")))]
+pub mod listener;
+/// Declarative macros that expand inline field declarations into builder calls.
+mod macros;
+/// Conversions between scope UUIDs and bech32 scope addresses.
+#[cfg(feature = "metadata-address")]
+mod metadata_address;
+/// Metrics hooks that count parsed gateway events under consistent names.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+/// An in-memory simulation of how the gateway applies events, for end-to-end tests.
+#[cfg(feature = "test-utils")]
+mod mock_gateway;
+/// Conversions between the serializable types and MessagePack.
+#[cfg(feature = "msgpack")]
+mod msgpack;
+/// Assertion helpers for cw-multi-test integration tests of contracts that emit gateway events.
+#[cfg(all(feature = "multi-test", not(target_arch = "wasm32")))]
+pub mod multi_test;
+/// Versions of the attribute layout and the rules for their compatibility.
+mod payload_version;
+/// Prometheus counters for the metrics hooks.
+#[cfg(all(feature = "prometheus", not(target_arch = "wasm32")))]
+mod prometheus_metrics;
+/// Protobuf messages for the typed events.
+#[cfg(feature = "proto")]
+pub mod proto;
+/// Conversions between gateway events and Tendermint protobuf events.
+#[cfg(feature = "provenance-proto")]
+mod provenance_proto;
+/// Correlation of expected access grants with the gateway events in submessage replies.
+#[cfg(feature = "storage")]
+mod reply_tracker;
+/// An extension trait that appends gateway attributes directly to a cosmwasm Response.
+#[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+mod response_ext;
+/// JSON schemas for the typed events and validated newtypes.
+#[cfg(feature = "schema")]
+mod schema;
+/// Fluent builders that only expose the options honored by each event type.
+mod scoped_builder;
+/// The name and version of the contract that produced an event.
+mod source_contract_version;
+/// Storage helpers for queueing gateway events until they can be emitted.
+#[cfg(feature = "storage")]
+pub mod storage;
+/// cw-storage-plus key implementations for the validated newtypes.
+#[cfg(feature = "storage")]
+mod storage_keys;
+/// Proptest strategies that generate realistic gateway values.
+#[cfg(feature = "proptest")]
+pub mod strategies;
+/// Assertion helpers for unit tests of contracts that emit gateway events in a Response.  The
+/// crate's own unit tests share them whenever parsing is enabled.
+#[cfg(any(all(test, feature = "parsing"), feature = "test-utils"))]
+pub mod test_utils;
+/// Parsing of gateway events from the responses of the cosmos tx service.
+#[cfg(feature = "provenance-proto")]
+mod tx_service;
+/// Builders for the typed events that verify required fields when built.
+#[cfg(feature = "std")]
+mod typed_builder;
+/// Versioned serialization of the typed events.
+#[cfg(feature = "serde")]
+mod typed_event_serde;
+/// YAML rendering of gateway events and batches, for human review.
+#[cfg(all(feature = "yaml", not(target_arch = "wasm32")))]
+mod yaml;
@@ -0,0 +1,364 @@
+//! [Tendermint RPC](https://docs.cometbft.com/v0.37/rpc/) subscriptions that stream the gateway
+//! events of committed transactions, for off-chain services that react to access grants and
+//! revokes as they happen.
+//!
+//! Each item of the stream pairs a gateway event with the hash of the transaction that emitted
+//! it.  Gateway events that cannot be decoded, and errors reported by the subscription, are
+//! surfaced as individual items, so a single malformed event never ends the stream.  Reconnecting
+//! after the websocket closes is left to the caller, who can resubscribe with the same query.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use os_gateway_contract_attributes::listener::{gateway_event_query, subscribe_gateway_events};
+//! use tendermint_rpc::WebSocketClient;
+//!
+//! # async fn listen() -> Result<(), Box<dyn std::error::Error>> {
+//! let (client, driver) = WebSocketClient::new("ws://localhost:26657/websocket").await?;
+//! tokio::spawn(async move { driver.run().await });
+//! let mut events = subscribe_gateway_events(&client, gateway_event_query()).await?;
+//! while let Some(item) = events.next().await {
+//!     match item {
+//!         Ok((tx_hash, event)) => println!("{tx_hash}: {event:?}"),
+//!         Err(error) => eprintln!("{error}"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    parse_gateway_event, wasm_event_type, OsGatewayError, OsGatewayEvent,
+    OS_GATEWAY_CUSTOM_EVENT_TYPE, OS_GATEWAY_KEYS,
+};
+use cosmwasm_std::{Attribute, Event};
+use futures::{stream, Stream, StreamExt};
+use std::fmt::{Display, Formatter};
+use tendermint::Hash;
+use tendermint_rpc::event::{Event as RpcEvent, EventData};
+use tendermint_rpc::query::{EventType, Query};
+use tendermint_rpc::SubscriptionClient;
+
+/// The hash of a committed transaction.
+pub type TxHash = Hash;
+
+/// The key under which Tendermint lists the hash of the transaction that produced an event.
+const TX_HASH_KEY: &str = "tx.hash";
+
+/// The errors produced by the items of a gateway event stream.
+///
+/// # Variants
+///
+/// * `Rpc` Denotes that the subscription reported an error, such as a websocket frame that could
+///   not be deserialized.
+///
+/// * `MissingTxHash` Denotes that a transaction containing gateway events did not list its hash.
+///
+/// * `InvalidTxHash` Denotes that a transaction containing gateway events listed a hash that is
+///   not an uppercase hexadecimal SHA-256 hash.  The `value` field contains the listed hash.
+///
+/// * `InvalidEvent` Denotes that a transaction contained a gateway event that could not be
+///   parsed.  The `tx_hash` field contains the hash of the transaction, and the `error` field
+///   contains the reason the event was rejected.
+#[derive(Debug)]
+pub enum ListenError {
+    Rpc(tendermint_rpc::Error),
+    MissingTxHash,
+    InvalidTxHash {
+        value: String,
+    },
+    InvalidEvent {
+        tx_hash: TxHash,
+        error: OsGatewayError,
+    },
+}
+impl Display for ListenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rpc(error) => write!(f, "the subscription reported an error: {error}"),
+            Self::MissingTxHash => f.write_str("a transaction with gateway events had no hash"),
+            Self::InvalidTxHash { value } => {
+                write!(
+                    f,
+                    "a transaction with gateway events had an invalid hash [{value}]"
+                )
+            }
+            Self::InvalidEvent { tx_hash, error } => {
+                write!(
+                    f,
+                    "transaction [{tx_hash}] contained an invalid gateway event: {error}"
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for ListenError {}
+impl From<tendermint_rpc::Error> for ListenError {
+    fn from(error: tendermint_rpc::Error) -> Self {
+        Self::Rpc(error)
+    }
+}
+
+/// The subscription query that matches every transaction in which a contract appended gateway
+/// attributes to its response, which the chain emits within `wasm` events.
+pub fn gateway_event_query() -> Query {
+    Query::from(EventType::Tx).and_exists(format!("wasm.{}", OS_GATEWAY_KEYS.event_type))
+}
+
+/// The subscription query that matches every transaction in which a contract emitted a custom
+/// gateway event, as produced by [to_event](crate::OsGatewayAttributeGenerator::to_event).
+/// Tendermint queries cannot match either form at once, so services that must observe both should
+/// subscribe with this query alongside [gateway_event_query](self::gateway_event_query).
+pub fn gateway_custom_event_query() -> Query {
+    Query::from(EventType::Tx).and_exists(format!(
+        "{}.{}",
+        wasm_event_type(OS_GATEWAY_CUSTOM_EVENT_TYPE),
+        OS_GATEWAY_KEYS.event_type,
+    ))
+}
+
+/// Subscribes to the query and streams every gateway event of each matching transaction, paired
+/// with the transaction's hash.  Fails only if the subscription itself cannot be created.
+///
+/// # Parameters
+///
+/// * `client` The client with which to subscribe, such as a
+///   [WebSocketClient](tendermint_rpc::WebSocketClient) whose driver is running.
+/// * `query` The subscription query, usually [gateway_event_query](self::gateway_event_query) or
+///   [gateway_custom_event_query](self::gateway_custom_event_query).
+pub async fn subscribe_gateway_events<C: SubscriptionClient + ?Sized>(
+    client: &C,
+    query: Query,
+) -> Result<impl Stream<Item = Result<(TxHash, OsGatewayEvent), ListenError>>, ListenError> {
+    Ok(gateway_event_stream(client.subscribe(query).await?))
+}
+
+/// Streams every gateway event of each transaction in a stream of subscription events, paired with
+/// the transaction's hash.  Use this to decode subscriptions created by other means, such as
+/// several subscriptions merged with [select_all](futures::stream::select_all).
+///
+/// # Parameters
+///
+/// * `events` The subscription events, such as a [Subscription](tendermint_rpc::Subscription).
+pub fn gateway_event_stream<S: Stream<Item = Result<RpcEvent, tendermint_rpc::Error>>>(
+    events: S,
+) -> impl Stream<Item = Result<(TxHash, OsGatewayEvent), ListenError>> {
+    events.flat_map(|event| {
+        stream::iter(match event {
+            Ok(event) => gateway_events_from_rpc_event(&event),
+            Err(error) => vec![Err(ListenError::Rpc(error))],
+        })
+    })
+}
+
+/// Parses every gateway event of the transaction described by a subscription event, in the order
+/// in which they were emitted, paired with the transaction's hash.  Produces nothing for events
+/// that do not describe a transaction.
+///
+/// # Parameters
+///
+/// * `event` A single event received from a subscription.
+pub fn gateway_events_from_rpc_event(
+    event: &RpcEvent,
+) -> Vec<Result<(TxHash, OsGatewayEvent), ListenError>> {
+    let EventData::Tx { tx_result } = &event.data else {
+        return vec![];
+    };
+    let tx_hash = tx_hash(event);
+    #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+    let _span = tx_hash.as_ref().ok().map(|tx_hash| {
+        crate::instrumentation::tx_span(&tx_hash.to_string(), Some(tx_result.height)).entered()
+    });
+    let parsed = tx_result
+        .result
+        .events
+        .iter()
+        .flat_map(|event| {
+            parse_gateway_event(&Event::new(&event.kind).add_attributes(
+                event.attributes.iter().map(|attribute| Attribute {
+                    key: attribute.key.to_owned(),
+                    value: attribute.value.to_owned(),
+                }),
+            ))
+        })
+        .collect::<Vec<Result<OsGatewayEvent, OsGatewayError>>>();
+    if parsed.is_empty() {
+        return vec![];
+    }
+    let tx_hash = match tx_hash {
+        Ok(tx_hash) => tx_hash,
+        Err(error) => return vec![Err(error)],
+    };
+    parsed
+        .into_iter()
+        .map(|event| {
+            event
+                .map(|event| (tx_hash, event))
+                .map_err(|error| ListenError::InvalidEvent { tx_hash, error })
+        })
+        .collect()
+}
+
+fn tx_hash(event: &RpcEvent) -> Result<TxHash, ListenError> {
+    let value = event
+        .events
+        .as_ref()
+        .and_then(|events| events.get(TX_HASH_KEY))
+        .and_then(|values| values.first())
+        .ok_or(ListenError::MissingTxHash)?;
+    value.parse().map_err(|_| ListenError::InvalidTxHash {
+        value: value.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::listener::{
+        gateway_custom_event_query, gateway_event_query, gateway_events_from_rpc_event, ListenError,
+    };
+    use crate::{AccessGrant, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent};
+    use std::collections::BTreeMap;
+    use tendermint::abci;
+    use tendermint_rpc::event::{Event, EventData, TxInfo, TxResult};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const TX_HASH: &str = "FCB86F71C4EFF43E13C51FA12791F6DD1DDB8600A51131BE2289614D6882F6BE";
+
+    fn tx_event(tx_hash: Option<&str>, events: Vec<abci::Event>) -> Event {
+        Event {
+            query: gateway_event_query().to_string(),
+            data: EventData::Tx {
+                tx_result: TxInfo {
+                    height: 1,
+                    index: None,
+                    tx: vec![],
+                    result: TxResult {
+                        log: None,
+                        gas_wanted: None,
+                        gas_used: None,
+                        events,
+                    },
+                },
+            },
+            events: tx_hash.map(|tx_hash| {
+                BTreeMap::from([("tx.hash".to_string(), vec![tx_hash.to_string()])])
+            }),
+        }
+    }
+
+    fn wasm_event(generator: OsGatewayAttributeGenerator) -> abci::Event {
+        abci::Event::new(
+            "wasm",
+            generator
+                .iter()
+                .map(|(key, value)| (key.to_owned(), value.to_owned())),
+        )
+    }
+
+    #[test]
+    fn test_queries_name_the_event_type_key() {
+        assert_eq!(
+            "tm.event = 'Tx' AND wasm.object_store_gateway_event_type EXISTS",
+            gateway_event_query().to_string(),
+            "the query should match flat gateway attributes",
+        );
+        assert_eq!(
+            "tm.event = 'Tx' AND wasm-object_store_gateway.object_store_gateway_event_type EXISTS",
+            gateway_custom_event_query().to_string(),
+            "the query should match custom gateway events",
+        );
+    }
+
+    #[test]
+    fn test_events_are_paired_with_their_tx_hash() {
+        let events = gateway_events_from_rpc_event(&tx_event(
+            Some(TX_HASH),
+            vec![
+                abci::Event::new(
+                    "message",
+                    [("action", "/cosmwasm.wasm.v1.MsgExecuteContract")],
+                ),
+                wasm_event(OsGatewayAttributeGenerator::access_grant(
+                    SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS,
+                )),
+            ],
+        ));
+        assert_eq!(
+            1,
+            events.len(),
+            "the single gateway event should be produced"
+        );
+        let (tx_hash, event) = events.into_iter().next().unwrap().unwrap();
+        assert_eq!(
+            TX_HASH,
+            tx_hash.to_string(),
+            "the tx hash should be produced"
+        );
+        assert_eq!(
+            OsGatewayEvent::from(AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+            event,
+            "the gateway event should be parsed",
+        );
+    }
+
+    #[test]
+    fn test_transactions_without_gateway_events_produce_nothing() {
+        assert!(
+            gateway_events_from_rpc_event(&tx_event(
+                None,
+                vec![abci::Event::new("transfer", [("amount", "1nhash")])],
+            ))
+            .is_empty(),
+            "a transaction without gateway events should not require a hash",
+        );
+    }
+
+    #[test]
+    fn test_errors_are_surfaced_per_item() {
+        let events = gateway_events_from_rpc_event(&tx_event(
+            Some(TX_HASH),
+            vec![
+                abci::Event::new(
+                    "wasm",
+                    [("object_store_gateway_event_type", "access_grant")],
+                ),
+                wasm_event(OsGatewayAttributeGenerator::access_revoke(
+                    SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS,
+                )),
+            ],
+        ));
+        assert!(
+            matches!(
+                &events[0],
+                Err(ListenError::InvalidEvent {
+                    error: OsGatewayError::MissingRequiredAttributes { .. },
+                    ..
+                }),
+            ),
+            "the malformed event should be surfaced with its error",
+        );
+        assert!(
+            events[1].is_ok(),
+            "the valid event should still be produced"
+        );
+        for (tx_hash, expected) in [(None, "no hash"), (Some("not a hash"), "invalid hash")] {
+            let events = gateway_events_from_rpc_event(&tx_event(
+                tx_hash,
+                vec![wasm_event(OsGatewayAttributeGenerator::access_revoke(
+                    SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS,
+                ))],
+            ));
+            assert!(
+                events[0]
+                    .as_ref()
+                    .unwrap_err()
+                    .to_string()
+                    .contains(expected),
+                "a transaction with hash [{tx_hash:?}] should report [{expected}]",
+            );
+        }
+    }
+}
@@ -0,0 +1,114 @@
+/// Generates an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) for an access
+/// grant from inline field declarations, expanding to the fluent [OsGatewayEventBuilder](crate::OsGatewayEventBuilder)
+/// calls.
+///
+/// # Fields
+///
+/// * `scope` (required) The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+///   to which this access grant refers.
+/// * `target` (required) The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+///   to which this access grant refers.
+/// * `grant_id` (optional) A custom access grant unique identifier.
+///
+/// Fields may be declared in any order.  Unknown field names, duplicate fields, and omitted
+/// required fields all produce compile errors.
+///
+/// ```
+/// use os_gateway_contract_attributes::os_gateway_grant;
+///
+/// let generator = os_gateway_grant! {
+///     scope: "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     target: "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     grant_id: "my_unique_id",
+/// };
+/// ```
+#[macro_export]
+macro_rules! os_gateway_grant {
+    ($($fields:tt)*) => {
+        $crate::__os_gateway_builder_fields!($crate::OsGatewayEventBuilder::grant(); $($fields)*)
+    };
+}
+
+/// Generates an [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator) for an access
+/// revoke from inline field declarations, expanding to the fluent [OsGatewayEventBuilder](crate::OsGatewayEventBuilder)
+/// calls.
+///
+/// Accepts the same fields as [os_gateway_grant](crate::os_gateway_grant).
+///
+/// ```
+/// use os_gateway_contract_attributes::os_gateway_revoke;
+///
+/// let generator = os_gateway_revoke! {
+///     scope: "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     target: "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// };
+/// ```
+#[macro_export]
+macro_rules! os_gateway_revoke {
+    ($($fields:tt)*) => {
+        $crate::__os_gateway_builder_fields!($crate::OsGatewayEventBuilder::revoke(); $($fields)*)
+    };
+}
+
+/// Internal field muncher shared by the event macros.  Not public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __os_gateway_builder_fields {
+    ($builder:expr;) => {
+        $builder.build()
+    };
+    ($builder:expr; scope: $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__os_gateway_builder_fields!($builder.scope($value); $($($rest)*)?)
+    };
+    ($builder:expr; target: $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__os_gateway_builder_fields!($builder.target($value); $($($rest)*)?)
+    };
+    ($builder:expr; grant_id: $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__os_gateway_builder_fields!($builder.access_grant_id($value); $($($rest)*)?)
+    };
+    ($builder:expr; $field:ident $($rest:tt)*) => {
+        compile_error!(concat!(
+            "unknown object store gateway field `",
+            stringify!($field),
+            "`: expected one of `scope`, `target`, or `grant_id`",
+        ))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::OsGatewayAttributeGenerator;
+
+    #[test]
+    fn test_grant_macro_matches_fluent_constructor() {
+        let expected = OsGatewayAttributeGenerator::access_grant("scope", "target")
+            .with_access_grant_id("grant_id")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let from_macro = os_gateway_grant! {
+            grant_id: "grant_id",
+            target: "target",
+            scope: "scope",
+        }
+        .into_iter()
+        .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            expected, from_macro,
+            "the grant macro should produce the same attributes as the fluent constructor",
+        );
+    }
+
+    #[test]
+    fn test_revoke_macro_matches_fluent_constructor() {
+        let expected = OsGatewayAttributeGenerator::access_revoke("scope", "target")
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        let from_macro = os_gateway_revoke! { scope: "scope", target: String::from("target") }
+            .into_iter()
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(
+            expected, from_macro,
+            "the revoke macro should produce the same attributes as the fluent constructor",
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::address::SCOPE_HRP;
+use crate::OsGatewayError;
+use bech32::{Bech32, Hrp};
+
+/// The leading byte of every [Provenance Blockchain Metadata Address](https://docs.provenance.io/modules/metadata-module#metadata-addresses)
+/// that denotes a scope.
+const SCOPE_KEY_PREFIX: u8 = 0x00;
+
+/// The number of bytes in a UUID.
+const UUID_LENGTH: usize = 16;
+
+/// The positions of the hyphens in a hyphenated UUID string.
+const UUID_HYPHEN_POSITIONS: [usize; 4] = [8, 13, 18, 23];
+
+/// Converts a scope UUID into the bech32 address of its [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope),
+/// following the [Metadata Address](https://docs.provenance.io/modules/metadata-module#metadata-addresses)
+/// encoding: a scope type byte followed by the UUID's sixteen bytes, encoded as bech32 with the
+/// `scope` human-readable part.
+///
+/// Both hyphenated (`91978ba2-5f35-459a-86a7-feca1b0512e0`) and unhyphenated
+/// (`91978ba25f35459a86a7feca1b0512e0`) UUIDs are accepted, in either case.  Produces an
+/// [OsGatewayError::InvalidScopeUuid](crate::OsGatewayError::InvalidScopeUuid) if the value is not
+/// a UUID.
+///
+/// ```
+/// use os_gateway_contract_attributes::scope_address_from_uuid;
+///
+/// assert_eq!(
+///     "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+///     scope_address_from_uuid("91978ba2-5f35-459a-86a7-feca1b0512e0").unwrap(),
+/// );
+/// ```
+///
+/// # Parameters
+///
+/// * `uuid` The UUID of the scope.
+pub fn scope_address_from_uuid(uuid: &str) -> Result<String, OsGatewayError> {
+    let mut bytes = vec![SCOPE_KEY_PREFIX];
+    bytes.extend(parse_uuid(uuid)?);
+    bech32::encode::<Bech32>(Hrp::parse_unchecked(SCOPE_HRP), &bytes).map_err(|error| {
+        OsGatewayError::InvalidScopeUuid {
+            uuid: uuid.to_string(),
+            reason: error.to_string(),
+        }
+    })
+}
+
+/// Converts the bech32 address of a [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+/// into its lowercase, hyphenated UUID.  This is the inverse of [scope_address_from_uuid](crate::scope_address_from_uuid).
+/// Produces an [OsGatewayError::InvalidAddress](crate::OsGatewayError::InvalidAddress) if the
+/// value is not a scope address.
+///
+/// # Parameters
+///
+/// * `scope_address` The bech32 address of the scope.
+pub fn scope_uuid_from_address(scope_address: &str) -> Result<String, OsGatewayError> {
+    let invalid = |reason: String| OsGatewayError::InvalidAddress {
+        address: scope_address.to_string(),
+        reason,
+    };
+    let (hrp, bytes) = bech32::decode(scope_address).map_err(|error| invalid(error.to_string()))?;
+    if hrp.as_str() != SCOPE_HRP {
+        return Err(invalid(format!(
+            "expected a prefix of [{SCOPE_HRP}] but found [{hrp}]",
+        )));
+    }
+    match bytes.split_first() {
+        Some((&SCOPE_KEY_PREFIX, uuid_bytes)) if uuid_bytes.len() == UUID_LENGTH => {
+            Ok(format_uuid(uuid_bytes))
+        }
+        _ => Err(invalid(
+            "the address does not contain a scope metadata key".to_string(),
+        )),
+    }
+}
+
+fn parse_uuid(uuid: &str) -> Result<Vec<u8>, OsGatewayError> {
+    let invalid = |reason: &str| OsGatewayError::InvalidScopeUuid {
+        uuid: uuid.to_string(),
+        reason: reason.to_string(),
+    };
+    let format_error =
+        || invalid("expected 32 hexadecimal digits, optionally hyphenated as 8-4-4-4-12");
+    // Compare bytes rather than slicing the string, so that no input can split a character
+    let characters = uuid.as_bytes();
+    let digits = match characters.len() {
+        32 => characters.to_vec(),
+        36 if UUID_HYPHEN_POSITIONS
+            .iter()
+            .all(|position| characters[*position] == b'-') =>
+        {
+            characters
+                .iter()
+                .enumerate()
+                .filter(|(position, _)| !UUID_HYPHEN_POSITIONS.contains(position))
+                .map(|(_, character)| *character)
+                .collect()
+        }
+        _ => return Err(format_error()),
+    };
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = char::from(pair[0]).to_digit(16);
+            let low = char::from(pair[1]).to_digit(16);
+            match (high, low) {
+                (Some(high), Some(low)) => Ok((high << 4 | low) as u8),
+                _ => Err(invalid("the uuid contains a non-hexadecimal character")),
+            }
+        })
+        .collect()
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let mut uuid = String::with_capacity(36);
+    for (index, byte) in bytes.iter().enumerate() {
+        if [4, 6, 8, 10].contains(&index) {
+            uuid.push('-');
+        }
+        uuid.push_str(&format!("{byte:02x}"));
+    }
+    uuid
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{scope_address_from_uuid, scope_uuid_from_address, OsGatewayError};
+
+    /// Known pairs of scope UUIDs and bech32 scope addresses.
+    const FIXTURES: [(&str, &str); 2] = [
+        (
+            "91978ba2-5f35-459a-86a7-feca1b0512e0",
+            "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+        ),
+        (
+            "a7e922f2-3878-11ed-b867-27b8c62f9717",
+            "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+        ),
+    ];
+
+    #[test]
+    fn test_scope_address_from_uuid_matches_fixtures() {
+        for (uuid, scope_address) in FIXTURES {
+            assert_eq!(
+                Ok(scope_address.to_string()),
+                scope_address_from_uuid(uuid),
+                "the uuid [{uuid}] should produce the known scope address",
+            );
+            assert_eq!(
+                Ok(scope_address.to_string()),
+                scope_address_from_uuid(&uuid.replace('-', "").to_uppercase()),
+                "the unhyphenated uppercase uuid [{uuid}] should produce the same scope address",
+            );
+        }
+    }
+
+    #[test]
+    fn test_scope_uuid_from_address_matches_fixtures() {
+        for (uuid, scope_address) in FIXTURES {
+            assert_eq!(
+                Ok(uuid.to_string()),
+                scope_uuid_from_address(scope_address),
+                "the scope address [{scope_address}] should produce the known uuid",
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_uuids_are_rejected() {
+        for uuid in [
+            "",
+            "91978ba2-5f35-459a-86a7-feca1b0512e",
+            "91978ba25-f35-459a-86a7-feca1b0512e0",
+            "91978ba2-5f35-459a-86a7-feca1b0512eg",
+            // Hyphenated lengths with a misplaced, missing, or extra hyphen
+            "91978ba2-5f35-459a-86a7feca-1b0512e0",
+            "91978ba2-5f35-459a-86a7-feca1b05-2e0",
+            "91978ba2-5f35-459a-86a7--eca1b0512e0",
+            "-91978ba2-5f35-459a-86a7-feca1b0512e",
+            "91978ba2-5f35-459a-86a7-feca1b0512e-",
+            "91978ba2-5f35-459a-86a7-------------",
+            "91978ba25f35459a86a7feca1b0512e0----",
+            // Unhyphenated lengths containing hyphens or multi-byte characters
+            "91978ba25f35459a86a7feca1b05-2e0",
+            "91978ba25f35459a86a7feca1b0512\u{e9}",
+            "91978ba2-5f35-459a-86a7-feca1b0512\u{e9}",
+        ] {
+            assert!(
+                matches!(
+                    scope_address_from_uuid(uuid),
+                    Err(OsGatewayError::InvalidScopeUuid { .. }),
+                ),
+                "the value [{uuid}] should be rejected as a uuid",
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_scope_addresses_are_rejected() {
+        for address in [
+            "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+            "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwea",
+            "not an address",
+        ] {
+            assert!(
+                matches!(
+                    scope_uuid_from_address(address),
+                    Err(OsGatewayError::InvalidAddress { .. }),
+                ),
+                "the value [{address}] should be rejected as a scope address",
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Metrics hooks for services that ingest gateway events, so that every consumer counts parsed
+//! events and parse failures under the same names and labels.
+//!
+//! Implement [GatewayMetricsSink] over any metrics backend, or enable the `prometheus` feature for
+//! the ready-made `PrometheusGatewayMetrics`, and parse with
+//! [parse_gateway_events_with_metrics]:
+//!
+//! ```
+//! use cosmwasm_std::Event;
+//! use os_gateway_contract_attributes::metrics::{
+//!     parse_gateway_events_with_metrics, GatewayMetricsSink,
+//! };
+//! use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayEventType};
+//! use std::cell::Cell;
+//!
+//! #[derive(Default)]
+//! struct Counts {
+//!     parsed: Cell<u64>,
+//!     failed: Cell<u64>,
+//! }
+//! impl GatewayMetricsSink for Counts {
+//!     fn event_parsed(&self, _: &OsGatewayEventType) {
+//!         self.parsed.set(self.parsed.get() + 1);
+//!     }
+//!
+//!     fn parse_failed(&self, _: &str) {
+//!         self.failed.set(self.failed.get() + 1);
+//!     }
+//! }
+//!
+//! let counts = Counts::default();
+//! let events = vec![OsGatewayAttributeGenerator::access_grant(
+//!     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//! )
+//! .to_event()];
+//! parse_gateway_events_with_metrics(&events, &counts);
+//! assert_eq!(1, counts.parsed.get());
+//! ```
+
+#[cfg(all(feature = "prometheus", not(target_arch = "wasm32")))]
+pub use crate::prometheus_metrics::PrometheusGatewayMetrics;
+use crate::{parse_gateway_event, OsGatewayError, OsGatewayEvent, OsGatewayEventType};
+use cosmwasm_std::Event;
+
+/// The name of the counter of gateway events parsed, labeled by [EVENT_TYPE_LABEL].
+pub const EVENTS_PARSED_METRIC: &str = "os_gateway_events_parsed_total";
+
+/// The name of the counter of gateway events that could not be parsed, labeled by [CODE_LABEL].
+pub const PARSE_FAILURES_METRIC: &str = "os_gateway_parse_failures_total";
+
+/// The label that holds the [event type](crate::OsGatewayEventType) of a parsed gateway event.
+pub const EVENT_TYPE_LABEL: &str = "event_type";
+
+/// The label that holds the [code](crate::OsGatewayError::code) of a parse failure.
+pub const CODE_LABEL: &str = "code";
+
+/// A destination for the metrics of parsing gateway events.  Implementations should count each
+/// call under [EVENTS_PARSED_METRIC] and [PARSE_FAILURES_METRIC] respectively, so that metrics
+/// remain consistent across consumers.
+pub trait GatewayMetricsSink {
+    /// Counts a gateway event that was parsed successfully.
+    ///
+    /// # Parameters
+    ///
+    /// * `ty` The type of the parsed event.
+    fn event_parsed(&self, ty: &OsGatewayEventType);
+
+    /// Counts a gateway event that could not be parsed.
+    ///
+    /// # Parameters
+    ///
+    /// * `code` The [code](crate::OsGatewayError::code) of the error produced in the event's place.
+    fn parse_failed(&self, code: &str);
+}
+
+/// Parses every gateway event in the given events exactly as
+/// [parse_gateway_events](crate::parse_gateway_events), counting each result in the sink.
+///
+/// # Parameters
+///
+/// * `events` The events from which to parse gateway events.
+/// * `sink` The destination of the metrics.
+pub fn parse_gateway_events_with_metrics(
+    events: &[Event],
+    sink: &dyn GatewayMetricsSink,
+) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    events
+        .iter()
+        .flat_map(|event| parse_gateway_event_with_metrics(event, sink))
+        .collect()
+}
+
+/// Parses every gateway event in a single event exactly as
+/// [parse_gateway_event](crate::parse_gateway_event), counting each result in the sink.
+///
+/// # Parameters
+///
+/// * `event` The event from which to parse gateway events.
+/// * `sink` The destination of the metrics.
+pub fn parse_gateway_event_with_metrics(
+    event: &Event,
+    sink: &dyn GatewayMetricsSink,
+) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    let results = parse_gateway_event(event);
+    for result in &results {
+        match result {
+            Ok(event) => sink.event_parsed(&event.typed_event_type()),
+            Err(error) => sink.parse_failed(error.code()),
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::{parse_gateway_events_with_metrics, GatewayMetricsSink};
+    use crate::{OsGatewayAttributeGenerator, OsGatewayEventType};
+    use cosmwasm_std::Event;
+    use std::cell::RefCell;
+
+    /// Records every call, in order.
+    #[derive(Default)]
+    struct RecordingSink(RefCell<Vec<String>>);
+    impl GatewayMetricsSink for RecordingSink {
+        fn event_parsed(&self, ty: &OsGatewayEventType) {
+            self.0.borrow_mut().push(format!("parsed:{ty}"));
+        }
+
+        fn parse_failed(&self, code: &str) {
+            self.0.borrow_mut().push(format!("failed:{code}"));
+        }
+    }
+
+    #[test]
+    fn test_every_result_is_counted() {
+        let sink = RecordingSink::default();
+        let results = parse_gateway_events_with_metrics(
+            &[
+                Event::new("wasm")
+                    .add_attributes(OsGatewayAttributeGenerator::access_grant("scope", "target"))
+                    .add_attributes(OsGatewayAttributeGenerator::access_revoke(
+                        "scope", "target",
+                    )),
+                Event::new("transfer").add_attribute("amount", "1nhash"),
+                Event::new("wasm").add_attribute("object_store_gateway_event_type", "access_grant"),
+            ],
+            &sink,
+        );
+        assert_eq!(3, results.len(), "every gateway event should be parsed");
+        assert_eq!(
+            vec![
+                "parsed:access_grant",
+                "parsed:access_revoke",
+                "failed:missing_required_attributes",
+            ],
+            sink.0.into_inner(),
+            "each result should be counted by type or error code",
+        );
+    }
+}
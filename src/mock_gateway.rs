@@ -0,0 +1,430 @@
+use crate::{
+    parse_gateway_event, AccessGrant, AccessRevoke, GrantKey, OsGatewayError, OsGatewayEvent,
+};
+use cosmwasm_std::{Env, Event};
+use std::collections::BTreeMap;
+
+/// The circumstances in which a [MockGateway](crate::MockGateway) observes an event.
+///
+/// # Parameters
+///
+/// * `signer` The bech32 address of the account that signed the transaction that emitted the
+///   event.
+///
+/// * `env` The environment of the block in which the event was emitted, against which grant
+///   expirations are evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessingContext {
+    pub signer: String,
+    pub env: Env,
+}
+impl ProcessingContext {
+    /// Creates a context from its components.
+    ///
+    /// # Parameters
+    ///
+    /// * `signer` The bech32 address of the account that signed the transaction.
+    /// * `env` The environment of the block in which the event was emitted.
+    pub fn new<S: Into<String>>(signer: S, env: Env) -> Self {
+        Self {
+            signer: signer.into(),
+            env,
+        }
+    }
+}
+
+/// What a [MockGateway](crate::MockGateway) did with a single event.
+///
+/// # Variants
+///
+/// * `Granted` Denotes that the grant was created.
+///
+/// * `DuplicateGrant` Denotes that a grant with the same [GrantKey](crate::GrantKey) already
+///   existed, so nothing was created.
+///
+/// * `Revoked` Denotes that the revoke was honored.  The `count` field contains the number of
+///   grants that were removed, which is zero if none matched.
+///
+/// * `Unauthorized` Denotes that the signer was not permitted to emit the event, so it was
+///   disregarded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProcessingOutcome {
+    Granted,
+    DuplicateGrant,
+    Revoked { count: usize },
+    Unauthorized,
+}
+
+/// An in-memory simulation of how [Object Store Gateway](https://github.com/provenance-io/object-store-gateway)
+/// applies the events it observes, for end-to-end tests that check which accounts a contract's
+/// events would give access to.  The documented rules are applied:
+///
+/// * A grant is only honored if it was signed by the value owner of its scope.  It creates a grant
+///   unless one with the same [GrantKey](crate::GrantKey) already exists.
+/// * A revoke is only honored if it was signed by the value owner of its scope or by its target
+///   account.  A revoke with an access grant id removes the grant with that id, and a revoke
+///   without one removes every grant of its scope to its target account.
+///
+/// Value owners are supplied by the caller, as the mock has no view of the chain's scopes.  The
+/// requirement that the scope's records were stored with the gateway's account as an audience is
+/// not modeled.
+///
+/// ```
+/// use cosmwasm_std::testing::mock_env;
+/// use os_gateway_contract_attributes::{
+///     AccessGrant, MockGateway, ProcessingContext, ProcessingOutcome,
+/// };
+///
+/// let scope_address = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+/// let owner = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+/// let account = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+/// let mut gateway = MockGateway::new().with_value_owner(scope_address, owner);
+/// assert_eq!(
+///     ProcessingOutcome::Granted,
+///     gateway.process(
+///         AccessGrant::new(scope_address, account).into(),
+///         ProcessingContext::new(owner, mock_env()),
+///     ),
+/// );
+/// assert!(gateway.has_access(scope_address, account));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MockGateway {
+    value_owners: BTreeMap<String, String>,
+    grants: BTreeMap<GrantKey, AccessGrant>,
+    latest_env: Option<Env>,
+}
+impl MockGateway {
+    /// Creates a gateway without any grants or known value owners.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the value owner of a scope, replacing any previously recorded owner.  Grants of
+    /// scopes without a recorded owner are never honored.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `value_owner` The bech32 address of the account that owns the scope's value.
+    pub fn with_value_owner<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        scope_address: S1,
+        value_owner: S2,
+    ) -> Self {
+        self.set_value_owner(scope_address, value_owner);
+        self
+    }
+
+    /// Records the value owner of a scope, replacing any previously recorded owner, such as when a
+    /// test transfers the scope mid-flow.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `value_owner` The bech32 address of the account that owns the scope's value.
+    pub fn set_value_owner<S1: Into<String>, S2: Into<String>>(
+        &mut self,
+        scope_address: S1,
+        value_owner: S2,
+    ) {
+        self.value_owners
+            .insert(scope_address.into(), value_owner.into());
+    }
+
+    /// Applies a single event according to the gateway's rules.
+    ///
+    /// # Parameters
+    ///
+    /// * `event` The event to apply.
+    /// * `ctx` The circumstances in which the event was emitted.
+    pub fn process(&mut self, event: OsGatewayEvent, ctx: ProcessingContext) -> ProcessingOutcome {
+        let outcome = match event {
+            OsGatewayEvent::AccessGrant(grant) => self.process_grant(grant, &ctx.signer),
+            OsGatewayEvent::AccessRevoke(revoke) => self.process_revoke(&revoke, &ctx.signer),
+        };
+        self.latest_env = Some(ctx.env);
+        outcome
+    }
+
+    /// Applies every gateway event contained in a [Cosmwasm Event](cosmwasm_std::Event), as found
+    /// by [parse_gateway_event](crate::parse_gateway_event), in the order in which they were
+    /// emitted.  Gateway events that cannot be parsed are disregarded, as by the gateway, and
+    /// produce their error.
+    ///
+    /// # Parameters
+    ///
+    /// * `event` The event emitted by the contract.
+    /// * `ctx` The circumstances in which the event was emitted.
+    pub fn process_event(
+        &mut self,
+        event: &Event,
+        ctx: ProcessingContext,
+    ) -> Vec<Result<ProcessingOutcome, OsGatewayError>> {
+        parse_gateway_event(event)
+            .into_iter()
+            .map(|gateway_event| {
+                gateway_event.map(|gateway_event| self.process(gateway_event, ctx.clone()))
+            })
+            .collect()
+    }
+
+    /// Whether or not the account holds a grant of the scope that has not expired as of the block
+    /// of the most recently processed event.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    /// * `account_address` The bech32 address of the account.
+    pub fn has_access(&self, scope_address: &str, account_address: &str) -> bool {
+        self.grants.values().any(|grant| {
+            grant.scope_address == scope_address
+                && grant.target_account_address == account_address
+                && !self.is_expired(grant)
+        })
+    }
+
+    /// Every grant held by the gateway, including expired grants, ordered by scope address, then by
+    /// target account, and then by access grant id.
+    pub fn grants(&self) -> Vec<&AccessGrant> {
+        self.grants.values().collect()
+    }
+
+    /// Every grant of the scope held by the gateway, including expired grants, ordered by target
+    /// account and then by access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    pub fn grants_for_scope(&self, scope_address: &str) -> Vec<&AccessGrant> {
+        self.grants
+            .values()
+            .filter(|grant| grant.scope_address == scope_address)
+            .collect()
+    }
+
+    fn process_grant(&mut self, grant: AccessGrant, signer: &str) -> ProcessingOutcome {
+        if !self.is_value_owner(&grant.scope_address, signer) {
+            return ProcessingOutcome::Unauthorized;
+        }
+        let key = GrantKey::from(&grant);
+        if self.grants.contains_key(&key) {
+            return ProcessingOutcome::DuplicateGrant;
+        }
+        self.grants.insert(key, grant);
+        ProcessingOutcome::Granted
+    }
+
+    fn process_revoke(&mut self, revoke: &AccessRevoke, signer: &str) -> ProcessingOutcome {
+        if !self.is_value_owner(&revoke.scope_address, signer)
+            && revoke.target_account_address != signer
+        {
+            return ProcessingOutcome::Unauthorized;
+        }
+        let count = self.grants.len();
+        self.grants.retain(|key, _| {
+            key.scope_address != revoke.scope_address
+                || key.target_account != revoke.target_account_address
+                || revoke
+                    .access_grant_id
+                    .as_ref()
+                    .is_some_and(|id| key.access_grant_id.as_ref() != Some(id))
+        });
+        ProcessingOutcome::Revoked {
+            count: count - self.grants.len(),
+        }
+    }
+
+    fn is_value_owner(&self, scope_address: &str, signer: &str) -> bool {
+        self.value_owners
+            .get(scope_address)
+            .is_some_and(|owner| owner == signer)
+    }
+
+    fn is_expired(&self, grant: &AccessGrant) -> bool {
+        match (&grant.expiration, &self.latest_env) {
+            (Some(expiration), Some(env)) => expiration.is_expired(env),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures;
+    use crate::{
+        AccessGrant, AccessRevoke, GrantExpiration, MockGateway, ProcessingContext,
+        ProcessingOutcome,
+    };
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::Env;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const OWNER_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+
+    fn gateway() -> MockGateway {
+        MockGateway::new()
+            .with_value_owner(SCOPE_ADDRESS, OWNER_ADDRESS)
+            .with_value_owner(OTHER_SCOPE_ADDRESS, OWNER_ADDRESS)
+    }
+
+    fn signed_by(signer: &str) -> ProcessingContext {
+        ProcessingContext::new(signer, mock_env())
+    }
+
+    fn env_at_height(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    #[test]
+    fn test_fixture_corpus_signed_by_the_value_owner() {
+        let mut gateway = gateway();
+        let outcomes = fixtures::all()
+            .iter()
+            .map(|case| {
+                (
+                    case.description.clone(),
+                    gateway.process_event(&case.to_event(), signed_by(OWNER_ADDRESS)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [
+            Ok(ProcessingOutcome::Granted),
+            Ok(ProcessingOutcome::Granted),
+            Ok(ProcessingOutcome::Granted),
+            Ok(ProcessingOutcome::DuplicateGrant),
+            Ok(ProcessingOutcome::DuplicateGrant),
+            Ok(ProcessingOutcome::Revoked { count: 2 }),
+            Ok(ProcessingOutcome::Revoked { count: 1 }),
+        ];
+        let (applied, disregarded) = outcomes.split_at(expected.len());
+        for ((description, outcome), expected) in applied.iter().zip(expected) {
+            assert_eq!(
+                &vec![expected],
+                outcome,
+                "[{description}] should be applied by the gateway",
+            );
+        }
+        for (description, outcome) in disregarded {
+            assert!(
+                matches!(outcome.as_slice(), [Err(_)]),
+                "[{description}] should be disregarded by the gateway",
+            );
+        }
+        assert!(
+            gateway.grants_for_scope(SCOPE_ADDRESS).is_empty()
+                && gateway.grants_for_scope(OTHER_SCOPE_ADDRESS).is_empty(),
+            "the corpus's revokes should remove every grant it created",
+        );
+    }
+
+    #[test]
+    fn test_fixture_corpus_signed_by_the_target_account() {
+        let mut gateway = gateway();
+        for case in fixtures::all() {
+            let expected = match &case.expected {
+                Ok(event) if event.event_type() == "access_grant" => {
+                    Ok(ProcessingOutcome::Unauthorized)
+                }
+                Ok(_) => Ok(ProcessingOutcome::Revoked { count: 0 }),
+                Err(error) => Err(error.clone()),
+            };
+            assert_eq!(
+                vec![expected],
+                gateway.process_event(&case.to_event(), signed_by(ACCOUNT_ADDRESS)),
+                "[{}] should only be honored if the target account may emit it",
+                case.description,
+            );
+        }
+    }
+
+    #[test]
+    fn test_revokes_with_an_id_only_remove_that_grant() {
+        let mut gateway = gateway();
+        for id in [None, Some("first"), Some("second")] {
+            gateway.process(
+                AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_optional_access_grant_id(id)
+                    .into(),
+                signed_by(OWNER_ADDRESS),
+            );
+        }
+        assert_eq!(
+            ProcessingOutcome::Revoked { count: 1 },
+            gateway.process(
+                AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_access_grant_id("first")
+                    .into(),
+                signed_by(ACCOUNT_ADDRESS),
+            ),
+            "the target account should be able to revoke a single grant",
+        );
+        assert_eq!(
+            vec![None, Some("second")],
+            gateway
+                .grants_for_scope(SCOPE_ADDRESS)
+                .into_iter()
+                .map(|grant| grant.access_grant_id.as_deref())
+                .collect::<Vec<_>>(),
+            "only the grant with the revoked id should be removed",
+        );
+        assert!(
+            gateway.has_access(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            "the remaining grants should still give access",
+        );
+    }
+
+    #[test]
+    fn test_value_owner_changes_are_honored() {
+        let mut gateway = gateway();
+        gateway.set_value_owner(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        assert_eq!(
+            ProcessingOutcome::Unauthorized,
+            gateway.process(
+                AccessGrant::new(SCOPE_ADDRESS, "tp1other").into(),
+                signed_by(OWNER_ADDRESS),
+            ),
+            "the previous owner should no longer be able to grant access",
+        );
+        assert_eq!(
+            ProcessingOutcome::Unauthorized,
+            MockGateway::new().process(
+                AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+                signed_by(OWNER_ADDRESS),
+            ),
+            "grants of scopes without a known owner should never be honored",
+        );
+    }
+
+    #[test]
+    fn test_access_expires_with_the_chain() {
+        let mut gateway = gateway();
+        gateway.process(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .into(),
+            ProcessingContext::new(OWNER_ADDRESS, env_at_height(99)),
+        );
+        assert!(
+            gateway.has_access(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            "the grant should give access before it expires",
+        );
+        gateway.process(
+            AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+            ProcessingContext::new(OWNER_ADDRESS, env_at_height(100)),
+        );
+        assert!(
+            !gateway.has_access(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            "the grant should stop giving access once the chain reaches its expiration",
+        );
+        assert_eq!(
+            1,
+            gateway.grants_for_scope(SCOPE_ADDRESS).len(),
+            "expired grants should still be listed",
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Assertion helpers for [cw-multi-test](https://github.com/CosmWasm/cw-multi-test) integration
+//! tests of contracts that emit gateway events.  Every failed assertion prints each gateway
+//! attribute found in the response, grouped by the event that carried it, so that a failure can be
+//! diagnosed without re-running the test.
+//!
+//! ```
+//! use cosmwasm_std::Event;
+//! use cw_multi_test::AppResponse;
+//! use os_gateway_contract_attributes::multi_test::{assert_access_grant, assert_no_gateway_events};
+//! use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+//!
+//! let response = AppResponse {
+//!     events: vec![Event::new("wasm").add_attributes(OsGatewayAttributeGenerator::access_grant(
+//!         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!     ))],
+//!     data: None,
+//! };
+//! let grant = assert_access_grant(
+//!     &response,
+//!     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//! );
+//! assert_eq!(None, grant.access_level);
+//! assert_no_gateway_events(&AppResponse::default());
+//! ```
+
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::{parse_gateway_events, AccessGrant, AccessRevoke, OsGatewayEvent};
+use cw_multi_test::AppResponse;
+
+/// Parses every gateway event emitted in the response, in the order in which they were emitted.
+/// See [parse_gateway_events](crate::parse_gateway_events) for the recognized forms.
+///
+/// Panics if any gateway event in the response cannot be parsed, as a contract under test should
+/// never emit one.
+///
+/// # Parameters
+///
+/// * `response` The response of an executed message.
+pub fn gateway_events(response: &AppResponse) -> Vec<OsGatewayEvent> {
+    parse_gateway_events(&response.events)
+        .into_iter()
+        .map(|event| {
+            event.unwrap_or_else(|error| {
+                panic!(
+                    "a gateway event could not be parsed: {error}\n{}",
+                    describe_gateway_attributes(response),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Asserts that the response contains an access grant of the scope to the target account, and
+/// produces the first such grant so that its remaining fields can be checked.
+///
+/// # Parameters
+///
+/// * `response` The response of an executed message.
+/// * `scope_address` The expected bech32 address of the scope.
+/// * `target_account_address` The expected bech32 address of the target account.
+pub fn assert_access_grant(
+    response: &AppResponse,
+    scope_address: &str,
+    target_account_address: &str,
+) -> AccessGrant {
+    gateway_events(response)
+        .into_iter()
+        .find_map(|event| match event {
+            OsGatewayEvent::AccessGrant(grant)
+                if grant.scope_address == scope_address
+                    && grant.target_account_address == target_account_address =>
+            {
+                Some(grant)
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an access grant of [{scope_address}] to [{target_account_address}]\n{}",
+                describe_gateway_attributes(response),
+            )
+        })
+}
+
+/// Asserts that the response contains an access revoke of the target account's access to the
+/// scope, and produces the first such revoke so that its remaining fields can be checked.
+///
+/// # Parameters
+///
+/// * `response` The response of an executed message.
+/// * `scope_address` The expected bech32 address of the scope.
+/// * `target_account_address` The expected bech32 address of the target account.
+pub fn assert_access_revoke(
+    response: &AppResponse,
+    scope_address: &str,
+    target_account_address: &str,
+) -> AccessRevoke {
+    gateway_events(response)
+        .into_iter()
+        .find_map(|event| match event {
+            OsGatewayEvent::AccessRevoke(revoke)
+                if revoke.scope_address == scope_address
+                    && revoke.target_account_address == target_account_address =>
+            {
+                Some(revoke)
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an access revoke of [{target_account_address}] from [{scope_address}]\n{}",
+                describe_gateway_attributes(response),
+            )
+        })
+}
+
+/// Asserts that the response contains no gateway attributes at all, including any that do not
+/// form a complete gateway event.
+///
+/// # Parameters
+///
+/// * `response` The response of an executed message.
+pub fn assert_no_gateway_events(response: &AppResponse) {
+    assert!(
+        !response
+            .events
+            .iter()
+            .flat_map(|event| &event.attributes)
+            .any(|attribute| attribute.key.starts_with(OS_GATEWAY_KEY_PREFIX)),
+        "expected no gateway events\n{}",
+        describe_gateway_attributes(response),
+    );
+}
+
+/// Renders every gateway attribute in the response, beneath the type of the event that carried it.
+fn describe_gateway_attributes(response: &AppResponse) -> String {
+    let described = response
+        .events
+        .iter()
+        .filter_map(|event| {
+            let attributes = event
+                .attributes
+                .iter()
+                .filter(|attribute| attribute.key.starts_with(OS_GATEWAY_KEY_PREFIX))
+                .map(|attribute| format!("  {}={}\n", attribute.key, attribute.value))
+                .collect::<String>();
+            (!attributes.is_empty()).then(|| format!("{}:\n{attributes}", event.ty))
+        })
+        .collect::<String>();
+    if described.is_empty() {
+        "no gateway attributes were found in the response".to_string()
+    } else {
+        format!("gateway attributes found in the response:\n{described}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::multi_test::{
+        assert_access_grant, assert_access_revoke, assert_no_gateway_events, gateway_events,
+    };
+    use crate::{AccessGrant, AccessLevel, AccessRevoke, OsGatewayAttributeGenerator};
+    use cosmwasm_std::Event;
+    use cw_multi_test::AppResponse;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn response(events: Vec<Event>) -> AppResponse {
+        AppResponse { events, data: None }
+    }
+
+    fn panic_message<F: FnOnce() + std::panic::UnwindSafe>(assertion: F) -> String {
+        let payload = std::panic::catch_unwind(assertion).expect_err("the assertion should fail");
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .expect("the panic should carry a formatted message")
+    }
+
+    #[test]
+    fn test_events_are_found_in_every_emission_form() {
+        let grant = OsGatewayAttributeGenerator::grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_level(AccessLevel::Read);
+        let revoke = OsGatewayAttributeGenerator::access_revoke(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let response = response(vec![
+            Event::new("execute").add_attribute("_contract_address", "tp1contract"),
+            Event::new("wasm").add_attributes(grant),
+            Event::new("wasm-object_store_gateway").add_attributes(revoke),
+        ]);
+        assert_eq!(
+            2,
+            gateway_events(&response).len(),
+            "both events should be found"
+        );
+        assert_eq!(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_level(AccessLevel::Read),
+            assert_access_grant(&response, SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            "the grant should be produced",
+        );
+        assert_eq!(
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            assert_access_revoke(&response, SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            "the revoke should be produced",
+        );
+    }
+
+    #[test]
+    fn test_failures_describe_the_gateway_attributes_found() {
+        let response = response(vec![Event::new("wasm")
+            .add_attribute("action", "share")
+            .add_attributes(OsGatewayAttributeGenerator::access_revoke(
+                SCOPE_ADDRESS,
+                ACCOUNT_ADDRESS,
+            ))]);
+        assert_eq!(
+            format!(
+                concat!(
+                    "expected an access grant of [{scope}] to [{account}]\n",
+                    "gateway attributes found in the response:\n",
+                    "wasm:\n",
+                    "  object_store_gateway_event_type=access_revoke\n",
+                    "  object_store_gateway_scope_address={scope}\n",
+                    "  object_store_gateway_target_account_address={account}\n",
+                ),
+                scope = SCOPE_ADDRESS,
+                account = ACCOUNT_ADDRESS,
+            ),
+            panic_message(|| {
+                assert_access_grant(&response, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+            }),
+            "the failure should list every gateway attribute in the response",
+        );
+        assert!(
+            panic_message(|| assert_no_gateway_events(&response))
+                .starts_with("expected no gateway events\n"),
+            "gateway events should fail the assertion that there are none",
+        );
+        assert!(
+            panic_message(|| {
+                assert_access_revoke(&AppResponse::default(), SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+            })
+            .ends_with("no gateway attributes were found in the response"),
+            "an empty response should be described as such",
+        );
+    }
+
+    #[test]
+    fn test_malformed_events_fail_with_their_error() {
+        let response = response(vec![Event::new("wasm")
+            .add_attribute("object_store_gateway_event_type", "access_grant")
+            .add_attribute("object_store_gateway_scope_address", SCOPE_ADDRESS)]);
+        assert!(
+            panic_message(|| {
+                gateway_events(&response);
+            })
+            .starts_with("a gateway event could not be parsed: missing required"),
+            "a malformed gateway event should fail with its parse error",
+        );
+        assert!(
+            std::panic::catch_unwind(|| assert_no_gateway_events(&response)).is_err(),
+            "an incomplete gateway event should still fail the assertion that there are none",
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{AccessGrant, AccessRevoke, OsGatewayError, OsGatewayEvent, OS_GATEWAY_KEYS};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// The version of the layout of the attributes that describe a gateway event, as stamped in the
+/// [payload version attribute](crate::OsGatewayKeys) by [with_payload_version](crate::OsGatewayAttributeGenerator::with_payload_version).
+///
+/// The contained value is a major version: it is only incremented when the attribute layout
+/// changes in a way that older consumers would misinterpret, such as a renamed key or a changed
+/// value encoding.  Additive changes, like new optional attributes, do not increment it, and there
+/// is no separate minor version.
+///
+/// __Compatibility__: A consumer accepts a payload exactly when the payload's version has the same
+/// major version as the consumer's.  As the value is the major version alone, this means the two
+/// values are equal.  Older and newer major versions are both rejected, because either side may
+/// rely on an encoding that the other does not share.  See [is_compatible_with](self::OsGatewayPayloadVersion::is_compatible_with).
+///
+/// __Stamping__: Generators only stamp a version when asked to with [with_payload_version](crate::OsGatewayAttributeGenerator::with_payload_version),
+/// so that the default attributes stay exactly those that deployed gateways and existing consumers
+/// already recognize.  An event without a stamp uses the layout that predates the attribute, which
+/// is version `1`.
+///
+/// Versions render as their plain decimal value, such as `1`, which is also their wire encoding.
+///
+/// ```
+/// use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayPayloadVersion};
+///
+/// let generator = OsGatewayAttributeGenerator::access_grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .with_payload_version();
+/// let version = generator.payload_version().unwrap().expect("the version should be stamped");
+/// assert!(version.is_compatible_with(&OsGatewayPayloadVersion::CURRENT));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OsGatewayPayloadVersion(pub u32);
+impl OsGatewayPayloadVersion {
+    /// The version of the attribute layout produced by this release.
+    pub const CURRENT: Self = Self(1);
+
+    /// Whether or not a consumer that understands this version can interpret a payload stamped
+    /// with the other version, which is the case when both share the same major version.  Since
+    /// the value is the major version, this holds exactly when the two versions are equal.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` The version stamped on the payload being interpreted.
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Display for OsGatewayPayloadVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+/// Parses the wire encoding of a version, producing an [OsGatewayError::InvalidPayloadVersion](crate::OsGatewayError::InvalidPayloadVersion)
+/// if the value is not plain decimal digits that fit in a `u32`.
+impl FromStr for OsGatewayPayloadVersion {
+    type Err = OsGatewayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| OsGatewayError::InvalidPayloadVersion {
+            version: s.to_string(),
+            reason: reason.to_string(),
+        };
+        // Only plain decimal digits are accepted, rejecting the signs and whitespace that u32
+        // parsing would otherwise allow, so that every version has exactly one encoding
+        if s.is_empty() || !s.chars().all(|character| character.is_ascii_digit()) {
+            return Err(invalid("expected a plain decimal version number"));
+        }
+        s.parse()
+            .map(Self)
+            .map_err(|_| invalid("expected a version number that fits in 32 bits"))
+    }
+}
+/// Serializes the version as its wire encoding.
+#[cfg(feature = "serde")]
+impl serde::Serialize for OsGatewayPayloadVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+/// Deserializes the version from its wire encoding.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OsGatewayPayloadVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl OsGatewayEvent {
+    /// The payload version stamped on the event, or `None` if it was emitted without one.
+    /// Produces an [OsGatewayError::InvalidPayloadVersion](crate::OsGatewayError::InvalidPayloadVersion)
+    /// if the stamped value cannot be parsed.
+    pub fn payload_version(&self) -> Result<Option<OsGatewayPayloadVersion>, OsGatewayError> {
+        match self {
+            Self::AccessGrant(grant) => grant.payload_version(),
+            Self::AccessRevoke(revoke) => revoke.payload_version(),
+        }
+    }
+}
+
+impl AccessGrant {
+    /// The payload version retained in the grant's extra attributes.  See
+    /// [OsGatewayEvent::payload_version](crate::OsGatewayEvent::payload_version).
+    pub fn payload_version(&self) -> Result<Option<OsGatewayPayloadVersion>, OsGatewayError> {
+        stamped_version(&self.extra_attributes)
+    }
+}
+
+impl AccessRevoke {
+    /// The payload version retained in the revoke's extra attributes.  See
+    /// [OsGatewayEvent::payload_version](crate::OsGatewayEvent::payload_version).
+    pub fn payload_version(&self) -> Result<Option<OsGatewayPayloadVersion>, OsGatewayError> {
+        stamped_version(&self.extra_attributes)
+    }
+}
+
+fn stamped_version(
+    extra_attributes: &BTreeMap<String, String>,
+) -> Result<Option<OsGatewayPayloadVersion>, OsGatewayError> {
+    extra_attributes
+        .get(OS_GATEWAY_KEYS.payload_version)
+        .map(|version| version.parse())
+        .transpose()
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use crate::{
+        parse_gateway_event, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+        OsGatewayPayloadVersion, OS_GATEWAY_KEYS,
+    };
+
+    fn parse_single(generator: OsGatewayAttributeGenerator) -> OsGatewayEvent {
+        let mut results = parse_gateway_event(&generator.to_event());
+        assert_eq!(1, results.len(), "a single event should be parsed");
+        results.remove(0).expect("the event should parse")
+    }
+
+    #[test]
+    fn test_wire_encoding_round_trips() {
+        for (version, encoded) in [
+            (OsGatewayPayloadVersion::CURRENT, "1"),
+            (OsGatewayPayloadVersion(0), "0"),
+            (OsGatewayPayloadVersion(u32::MAX), "4294967295"),
+        ] {
+            assert_eq!(
+                encoded,
+                version.to_string(),
+                "the version [{version:?}] should display as its wire encoding",
+            );
+            assert_eq!(
+                Ok(version),
+                encoded.parse::<OsGatewayPayloadVersion>(),
+                "the encoding [{encoded}] should parse back into the same version",
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_versions_are_rejected() {
+        for encoded in ["", "v1", "+1", " 1", "1.0", "-1", "4294967296"] {
+            assert!(
+                matches!(
+                    encoded.parse::<OsGatewayPayloadVersion>(),
+                    Err(OsGatewayError::InvalidPayloadVersion { .. }),
+                ),
+                "the encoding [{encoded}] should be rejected",
+            );
+        }
+    }
+
+    #[test]
+    fn test_compatibility_requires_the_same_major_version() {
+        let current = OsGatewayPayloadVersion::CURRENT;
+        assert!(
+            current.is_compatible_with(&OsGatewayPayloadVersion(current.0)),
+            "the same major version should be compatible",
+        );
+        assert!(
+            !current.is_compatible_with(&OsGatewayPayloadVersion(current.0 + 1)),
+            "a future major version should not be compatible",
+        );
+        assert!(
+            !OsGatewayPayloadVersion(current.0 + 1).is_compatible_with(&current),
+            "an older major version should not be compatible",
+        );
+        assert!(
+            OsGatewayPayloadVersion(current.0 + 1) > current,
+            "versions should be ordered by their major version",
+        );
+    }
+
+    #[test]
+    fn test_generator_stamps_current_version() {
+        let generator = OsGatewayAttributeGenerator::access_revoke("scope", "target");
+        assert_eq!(
+            Ok(None),
+            generator.payload_version(),
+            "the version should only be stamped on request",
+        );
+        assert_eq!(
+            Ok(Some(OsGatewayPayloadVersion::CURRENT)),
+            generator.with_payload_version().payload_version(),
+            "the current version should be stamped",
+        );
+    }
+
+    #[test]
+    fn test_parsed_events_expose_payload_version() {
+        let unversioned =
+            parse_single(OsGatewayAttributeGenerator::access_grant("scope", "target"));
+        assert_eq!(
+            Ok(None),
+            unversioned.payload_version(),
+            "an event without a version should report no version",
+        );
+        let current = parse_single(
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_payload_version(),
+        );
+        assert_eq!(
+            Ok(Some(OsGatewayPayloadVersion::CURRENT)),
+            current.payload_version(),
+            "a stamped grant should expose its version",
+        );
+        let mut future = OsGatewayAttributeGenerator::access_revoke("scope", "target");
+        future.set_attribute(OS_GATEWAY_KEYS.payload_version, "2");
+        let future = parse_single(future)
+            .payload_version()
+            .unwrap()
+            .expect("the future version should be exposed");
+        assert!(
+            !OsGatewayPayloadVersion::CURRENT.is_compatible_with(&future),
+            "a future version should be detected as incompatible",
+        );
+        let mut malformed = OsGatewayAttributeGenerator::access_revoke("scope", "target");
+        malformed.set_attribute(OS_GATEWAY_KEYS.payload_version, "two");
+        assert!(
+            matches!(
+                parse_single(malformed).payload_version(),
+                Err(OsGatewayError::InvalidPayloadVersion { .. }),
+            ),
+            "a malformed version should be reported without rejecting the event",
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::metrics::{
+    GatewayMetricsSink, CODE_LABEL, EVENTS_PARSED_METRIC, EVENT_TYPE_LABEL, PARSE_FAILURES_METRIC,
+};
+use crate::OsGatewayEventType;
+use prometheus::{IntCounterVec, Opts, Registry};
+
+/// A [GatewayMetricsSink](crate::metrics::GatewayMetricsSink) that counts into
+/// [Prometheus](https://docs.rs/prometheus) counters named by the
+/// [metrics](crate::metrics) module's constants.
+///
+/// ```
+/// use os_gateway_contract_attributes::metrics::{
+///     parse_gateway_events_with_metrics, PrometheusGatewayMetrics,
+/// };
+/// use prometheus::Registry;
+///
+/// let registry = Registry::new();
+/// let metrics = PrometheusGatewayMetrics::new().unwrap();
+/// metrics.register(&registry).unwrap();
+/// parse_gateway_events_with_metrics(&[], &metrics);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrometheusGatewayMetrics {
+    events_parsed: IntCounterVec,
+    parse_failures: IntCounterVec,
+}
+impl PrometheusGatewayMetrics {
+    /// Creates the counters, which must then be [registered](self::PrometheusGatewayMetrics::register)
+    /// to be exported.
+    pub fn new() -> prometheus::Result<Self> {
+        Ok(Self {
+            events_parsed: IntCounterVec::new(
+                Opts::new(
+                    EVENTS_PARSED_METRIC,
+                    "Gateway events parsed, by event type.",
+                ),
+                &[EVENT_TYPE_LABEL],
+            )?,
+            parse_failures: IntCounterVec::new(
+                Opts::new(
+                    PARSE_FAILURES_METRIC,
+                    "Gateway events that could not be parsed, by error code.",
+                ),
+                &[CODE_LABEL],
+            )?,
+        })
+    }
+
+    /// Registers the counters with a registry.  Fails if counters of the same names are already
+    /// registered with it.
+    ///
+    /// # Parameters
+    ///
+    /// * `registry` The registry from which the counters are exported.
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        registry.register(Box::new(self.events_parsed.clone()))?;
+        registry.register(Box::new(self.parse_failures.clone()))
+    }
+
+    /// The counter of gateway events parsed, labeled by event type.
+    pub fn events_parsed(&self) -> &IntCounterVec {
+        &self.events_parsed
+    }
+
+    /// The counter of gateway events that could not be parsed, labeled by error code.
+    pub fn parse_failures(&self) -> &IntCounterVec {
+        &self.parse_failures
+    }
+}
+impl GatewayMetricsSink for PrometheusGatewayMetrics {
+    fn event_parsed(&self, ty: &OsGatewayEventType) {
+        self.events_parsed.with_label_values(&[ty.as_str()]).inc();
+    }
+
+    fn parse_failed(&self, code: &str) {
+        self.parse_failures.with_label_values(&[code]).inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::{
+        parse_gateway_events_with_metrics, PrometheusGatewayMetrics, EVENTS_PARSED_METRIC,
+        PARSE_FAILURES_METRIC,
+    };
+    use crate::OsGatewayAttributeGenerator;
+    use cosmwasm_std::Event;
+    use prometheus::{Encoder, Registry, TextEncoder};
+
+    #[test]
+    fn test_counts_are_exported_under_consistent_names() {
+        let registry = Registry::new();
+        let metrics = PrometheusGatewayMetrics::new().unwrap();
+        metrics.register(&registry).unwrap();
+        let grant = OsGatewayAttributeGenerator::access_grant("scope", "target").to_event();
+        parse_gateway_events_with_metrics(
+            &[
+                grant.clone(),
+                grant,
+                Event::new("wasm").add_attribute("object_store_gateway_event_type", "unknown"),
+            ],
+            &metrics,
+        );
+        let mut exported = vec![];
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut exported)
+            .unwrap();
+        let exported = String::from_utf8(exported).unwrap();
+        for expected in [
+            format!("{EVENTS_PARSED_METRIC}{{event_type=\"access_grant\"}} 2"),
+            format!("{PARSE_FAILURES_METRIC}{{code=\"missing_required_attributes\"}} 1"),
+        ] {
+            assert!(
+                exported.contains(&expected),
+                "the export should contain [{expected}], but was [{exported}]",
+            );
+        }
+        assert!(
+            metrics.register(&registry).is_err(),
+            "the counters should not be registered twice",
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Protobuf messages for the typed gateway events, generated by [prost](https://github.com/tokio-rs/prost)
+//! from the `proto/object_store_gateway.proto` definitions that ship with this crate, along with
+//! conversions to and from the typed events.
+//!
+//! Optional values are carried in their attribute encodings, and every attribute that a typed event
+//! does not model is carried in its `extra_attributes` map, so converting an event to its message
+//! and back is lossless.  Protobuf cannot distinguish an empty string from an absent one, so a
+//! message with an empty scope address or target account address is rejected as missing it.
+//!
+//! ```
+//! use os_gateway_contract_attributes::{proto, AccessGrant, OsGatewayEvent};
+//! use prost::Message;
+//!
+//! let event = OsGatewayEvent::from(
+//!     AccessGrant::new(
+//!         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!     )
+//!     .with_access_grant_id("my_unique_id"),
+//! );
+//! let bytes = proto::OsGatewayEvent::from(event.clone()).encode_to_vec();
+//! let decoded = proto::OsGatewayEvent::decode(bytes.as_slice()).expect("the message should decode");
+//! assert_eq!(event, OsGatewayEvent::try_from(decoded).expect("the message should convert"));
+//! ```
+
+use crate::OsGatewayError;
+
+include!(concat!(env!("OUT_DIR"), "/object_store_gateway.rs"));
+
+impl From<crate::AccessGrant> for AccessGrant {
+    fn from(grant: crate::AccessGrant) -> Self {
+        Self {
+            scope_address: grant.scope_address,
+            target_account_address: grant.target_account_address,
+            access_grant_id: grant.access_grant_id,
+            expiration: grant.expiration.map(|expiration| expiration.to_string()),
+            access_level: grant
+                .access_level
+                .map(|access_level| access_level.as_str().to_string()),
+            extra_attributes: grant.extra_attributes,
+        }
+    }
+}
+/// Produces an [OsGatewayError::MissingRequiredFields](crate::OsGatewayError::MissingRequiredFields)
+/// if the message holds an empty scope address or target account address, which protobuf cannot
+/// distinguish from an absent one.  Produces an [OsGatewayError::InvalidExpiration](crate::OsGatewayError::InvalidExpiration)
+/// or an [OsGatewayError::UnknownAccessLevel](crate::OsGatewayError::UnknownAccessLevel) if the
+/// message holds a value that cannot be parsed.
+impl TryFrom<AccessGrant> for crate::AccessGrant {
+    type Error = OsGatewayError;
+
+    fn try_from(grant: AccessGrant) -> Result<Self, Self::Error> {
+        require_addresses(&grant.scope_address, &grant.target_account_address)?;
+        Ok(Self {
+            scope_address: grant.scope_address,
+            target_account_address: grant.target_account_address,
+            access_grant_id: grant.access_grant_id,
+            expiration: grant
+                .expiration
+                .map(|expiration| expiration.parse())
+                .transpose()?,
+            access_level: grant
+                .access_level
+                .map(|access_level| access_level.parse())
+                .transpose()?,
+            extra_attributes: grant.extra_attributes,
+        })
+    }
+}
+
+impl From<crate::AccessRevoke> for AccessRevoke {
+    fn from(revoke: crate::AccessRevoke) -> Self {
+        Self {
+            scope_address: revoke.scope_address,
+            target_account_address: revoke.target_account_address,
+            access_grant_id: revoke.access_grant_id,
+            extra_attributes: revoke.extra_attributes,
+        }
+    }
+}
+/// Produces an [OsGatewayError::MissingRequiredFields](crate::OsGatewayError::MissingRequiredFields)
+/// if the message holds an empty scope address or target account address, which protobuf cannot
+/// distinguish from an absent one.
+impl TryFrom<AccessRevoke> for crate::AccessRevoke {
+    type Error = OsGatewayError;
+
+    fn try_from(revoke: AccessRevoke) -> Result<Self, Self::Error> {
+        require_addresses(&revoke.scope_address, &revoke.target_account_address)?;
+        Ok(Self {
+            scope_address: revoke.scope_address,
+            target_account_address: revoke.target_account_address,
+            access_grant_id: revoke.access_grant_id,
+            extra_attributes: revoke.extra_attributes,
+        })
+    }
+}
+
+impl From<crate::OsGatewayEvent> for OsGatewayEvent {
+    fn from(event: crate::OsGatewayEvent) -> Self {
+        Self {
+            event: Some(match event {
+                crate::OsGatewayEvent::AccessGrant(grant) => {
+                    os_gateway_event::Event::AccessGrant(grant.into())
+                }
+                crate::OsGatewayEvent::AccessRevoke(revoke) => {
+                    os_gateway_event::Event::AccessRevoke(revoke.into())
+                }
+            }),
+        }
+    }
+}
+/// Produces an [OsGatewayError::MissingRequiredFields](crate::OsGatewayError::MissingRequiredFields)
+/// if the message holds no event, or any error produced when converting the event it holds.
+impl TryFrom<OsGatewayEvent> for crate::OsGatewayEvent {
+    type Error = OsGatewayError;
+
+    fn try_from(event: OsGatewayEvent) -> Result<Self, Self::Error> {
+        match event.event {
+            Some(os_gateway_event::Event::AccessGrant(grant)) => {
+                Ok(Self::AccessGrant(grant.try_into()?))
+            }
+            Some(os_gateway_event::Event::AccessRevoke(revoke)) => {
+                Ok(Self::AccessRevoke(revoke.try_into()?))
+            }
+            None => Err(OsGatewayError::MissingRequiredFields {
+                fields: vec!["event".to_string()],
+            }),
+        }
+    }
+}
+
+/// Names every required address that a message left empty.
+fn require_addresses(
+    scope_address: &str,
+    target_account_address: &str,
+) -> Result<(), OsGatewayError> {
+    let fields = [
+        ("scope_address", scope_address),
+        ("target_account_address", target_account_address),
+    ]
+    .into_iter()
+    .filter(|(_, address)| address.is_empty())
+    .map(|(field, _)| field.to_string())
+    .collect::<Vec<String>>();
+    if fields.is_empty() {
+        Ok(())
+    } else {
+        Err(OsGatewayError::MissingRequiredFields { fields })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{proto, AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError};
+    use prost::Message;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn round_trip(event: crate::OsGatewayEvent) -> crate::OsGatewayEvent {
+        let bytes = proto::OsGatewayEvent::from(event).encode_to_vec();
+        proto::OsGatewayEvent::decode(bytes.as_slice())
+            .expect("the message should decode")
+            .try_into()
+            .expect("the message should convert into an event")
+    }
+
+    #[test]
+    fn test_events_round_trip_through_bytes() {
+        let events: [crate::OsGatewayEvent; 4] = [
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::ReadWrite)
+                .with_extra_attribute("object_store_gateway_custom", "value")
+                .into(),
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_extra_attribute("object_store_gateway_custom", "value")
+                .into(),
+        ];
+        for event in events {
+            assert_eq!(
+                event.clone(),
+                round_trip(event),
+                "the event should be unchanged after a protobuf round trip",
+            );
+        }
+    }
+
+    #[test]
+    fn test_messages_carry_attribute_encodings() {
+        let message = proto::AccessGrant::from(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::Read),
+        );
+        assert_eq!(
+            Some("height:100"),
+            message.expiration.as_deref(),
+            "the expiration should be carried in its attribute encoding",
+        );
+        assert_eq!(
+            Some("read"),
+            message.access_level.as_deref(),
+            "the access level should be carried in its attribute encoding",
+        );
+    }
+
+    #[test]
+    fn test_invalid_messages_are_rejected() {
+        assert_eq!(
+            Err(OsGatewayError::MissingRequiredFields {
+                fields: vec!["event".to_string()],
+            }),
+            crate::OsGatewayEvent::try_from(proto::OsGatewayEvent::default()),
+            "a message without an event should be rejected",
+        );
+        let message = proto::AccessGrant {
+            access_level: Some("admin".to_string()),
+            ..AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()
+        };
+        assert_eq!(
+            Err(OsGatewayError::UnknownAccessLevel {
+                access_level: "admin".to_string(),
+            }),
+            AccessGrant::try_from(message),
+            "a message holding an unrecognized access level should be rejected",
+        );
+        let message = proto::AccessRevoke {
+            target_account_address: String::new(),
+            ..AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()
+        };
+        assert_eq!(
+            Err(OsGatewayError::MissingRequiredFields {
+                fields: vec!["target_account_address".to_string()],
+            }),
+            AccessRevoke::try_from(message),
+            "a message holding an empty target account address should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_empty_messages_are_rejected() {
+        for bytes in [
+            proto::AccessGrant::default().encode_to_vec(),
+            proto::AccessRevoke::default().encode_to_vec(),
+        ] {
+            assert!(
+                bytes.is_empty(),
+                "a message holding only default values should encode to no bytes",
+            );
+        }
+        let missing_addresses = || OsGatewayError::MissingRequiredFields {
+            fields: vec![
+                "scope_address".to_string(),
+                "target_account_address".to_string(),
+            ],
+        };
+        assert_eq!(
+            Err(missing_addresses()),
+            AccessGrant::try_from(proto::AccessGrant::decode([].as_slice()).unwrap()),
+            "a grant decoded from empty bytes should be rejected",
+        );
+        assert_eq!(
+            Err(missing_addresses()),
+            AccessRevoke::try_from(proto::AccessRevoke::decode([].as_slice()).unwrap()),
+            "a revoke decoded from empty bytes should be rejected",
+        );
+        let message = proto::OsGatewayEvent {
+            event: Some(proto::os_gateway_event::Event::AccessRevoke(
+                proto::AccessRevoke::default(),
+            )),
+        };
+        assert_eq!(
+            Err(missing_addresses()),
+            crate::OsGatewayEvent::try_from(
+                proto::OsGatewayEvent::decode(message.encode_to_vec().as_slice()).unwrap(),
+            ),
+            "an event holding an empty revoke should be rejected",
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{parse_gateway_event, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent};
+use cosmos_sdk_proto::tendermint::abci::{Event as ProtoEvent, EventAttribute};
+use cosmwasm_std::{Attribute, Event};
+
+/// The type of the event under which the chain places all flat attributes appended to a contract's
+/// response.
+const WASM_EVENT_TYPE: &str = "wasm";
+
+impl OsGatewayEvent {
+    /// Produces the Tendermint protobuf [Event](cosmos_sdk_proto::tendermint::abci::Event) that the
+    /// chain records when a contract appends this event's attributes to its response, for tooling
+    /// that replays events into systems that only read protobuf structures.
+    ///
+    /// The event is of type `wasm` and holds the event's attributes in their documented emission
+    /// order.  Keys and values are plain UTF-8 strings, as in Tendermint 0.37 and later, rather than
+    /// the base64 encoding of older releases.  No attribute is marked for indexing.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{parse_proto_event, AccessRevoke, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(AccessRevoke::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// let proto_event = event.to_proto_event();
+    /// assert_eq!("wasm", proto_event.r#type);
+    /// assert_eq!(vec![Ok(event)], parse_proto_event(&proto_event));
+    /// ```
+    pub fn to_proto_event(&self) -> ProtoEvent {
+        ProtoEvent {
+            r#type: WASM_EVENT_TYPE.to_string(),
+            attributes: OsGatewayAttributeGenerator::from(self.clone())
+                .into_attributes()
+                .into_iter()
+                .map(|attribute| EventAttribute {
+                    key: attribute.key,
+                    value: attribute.value,
+                    index: false,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Parses every gateway event contained in a Tendermint protobuf [Event](cosmos_sdk_proto::tendermint::abci::Event),
+/// such as one produced by [to_proto_event](crate::OsGatewayEvent::to_proto_event), in the order in
+/// which they were emitted.  Events and attributes are recognized exactly as by [parse_gateway_event](crate::parse_gateway_event),
+/// and each attribute's index flag is ignored.
+///
+/// # Parameters
+///
+/// * `event` The protobuf event from which to parse gateway events, whose keys and values are plain
+///   UTF-8 strings.
+pub fn parse_proto_event(event: &ProtoEvent) -> Vec<Result<OsGatewayEvent, OsGatewayError>> {
+    parse_gateway_event(
+        &Event::new(&event.r#type).add_attributes(event.attributes.iter().map(|attribute| {
+            Attribute {
+                // Constructed directly, as chain-added keys like _contract_address are reserved
+                key: attribute.key.clone(),
+                value: attribute.value.clone(),
+            }
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_proto_event, AccessGrant, AccessLevel, AccessRevoke, GrantExpiration,
+        OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent,
+    };
+    use cosmos_sdk_proto::tendermint::abci::{Event as ProtoEvent, EventAttribute};
+    use cosmos_sdk_proto::traits::Message;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn attribute(key: &str, value: &str) -> EventAttribute {
+        EventAttribute {
+            key: key.to_string(),
+            value: value.to_string(),
+            index: false,
+        }
+    }
+
+    fn events() -> Vec<OsGatewayEvent> {
+        vec![
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("my_unique_id")
+                .with_expiration(GrantExpiration::AtHeight(100))
+                .with_access_level(AccessLevel::Read)
+                .with_extra_attribute("object_store_gateway_note", "shared")
+                .into(),
+            AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into(),
+        ]
+    }
+
+    #[test]
+    fn test_proto_events_hold_plain_attributes_in_emission_order() {
+        for event in events() {
+            let proto_event = event.to_proto_event();
+            assert_eq!(
+                "wasm", proto_event.r#type,
+                "the event should be a wasm event"
+            );
+            assert_eq!(
+                OsGatewayAttributeGenerator::from(event.clone())
+                    .to_attributes()
+                    .into_iter()
+                    .map(|cosmwasm| attribute(&cosmwasm.key, &cosmwasm.value))
+                    .collect::<Vec<EventAttribute>>(),
+                proto_event.attributes,
+                "the attributes should be plain strings in emission order",
+            );
+        }
+    }
+
+    #[test]
+    fn test_proto_events_round_trip() {
+        for event in events() {
+            let encoded = event.to_proto_event().encode_to_vec();
+            assert_eq!(
+                vec![Ok(event)],
+                parse_proto_event(&ProtoEvent::decode(encoded.as_slice()).unwrap()),
+                "the event should survive an encoded protobuf round trip",
+            );
+        }
+    }
+
+    #[test]
+    fn test_replayed_events_are_parsed_like_cosmwasm_events() {
+        let mut attributes = vec![attribute("_contract_address", "tp1contract")];
+        for event in events() {
+            attributes.extend(event.to_proto_event().attributes);
+        }
+        attributes[1].index = true;
+        assert_eq!(
+            events().into_iter().map(Ok).collect::<Vec<_>>(),
+            parse_proto_event(&ProtoEvent {
+                r#type: "wasm".to_string(),
+                attributes: attributes.clone(),
+            }),
+            "every gateway event should be parsed from a combined event",
+        );
+        assert!(
+            parse_proto_event(&ProtoEvent {
+                r#type: "transfer".to_string(),
+                attributes,
+            })
+            .is_empty(),
+            "events of unrelated types should be skipped",
+        );
+        assert_eq!(
+            vec![Err(OsGatewayError::MissingRequiredAttributes {
+                keys: vec!["object_store_gateway_target_account_address".to_string()],
+            })],
+            parse_proto_event(&ProtoEvent {
+                r#type: "wasm".to_string(),
+                attributes: vec![
+                    attribute("object_store_gateway_event_type", "access_revoke"),
+                    attribute("object_store_gateway_scope_address", SCOPE_ADDRESS),
+                ],
+            }),
+            "an incomplete gateway event should produce an error",
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{parse_gateway_events_from_reply, AccessGrant, OsGatewayError, OsGatewayEvent};
+use cosmwasm_std::Reply;
+
+/// An access grant that a contract expects a submessage to emit, as recorded by
+/// [expect_grant](self::GatewayReplyTracker::expect_grant).
+///
+/// With the `serde` feature enabled, the expectation serializes as a
+/// `[submsg_id, scope_address, target_account_address]` array.
+///
+/// # Parameters
+///
+/// * `submsg_id` The id of the submessage expected to emit the grant.
+///
+/// * `scope_address` The bech32 address of the scope that the grant is expected to refer to.
+///
+/// * `target_account_address` The bech32 address of the account that the grant is expected to
+///   refer to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExpectedGrant {
+    pub submsg_id: u64,
+    pub scope_address: String,
+    pub target_account_address: String,
+}
+impl ExpectedGrant {
+    fn is_fulfilled_by(&self, grant: &AccessGrant) -> bool {
+        grant.scope_address == self.scope_address
+            && grant.target_account_address == self.target_account_address
+    }
+}
+/// Serializes the expectation as a `[submsg_id, scope_address, target_account_address]` array.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExpectedGrant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (
+            self.submsg_id,
+            &self.scope_address,
+            &self.target_account_address,
+        )
+            .serialize(serializer)
+    }
+}
+/// Deserializes the expectation from the array produced by its serialization.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExpectedGrant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (submsg_id, scope_address, target_account_address) =
+            <(u64, String, String)>::deserialize(deserializer)?;
+        Ok(Self {
+            submsg_id,
+            scope_address,
+            target_account_address,
+        })
+    }
+}
+
+/// The outcome of correlating a submessage's reply with the grants expected of it, produced by
+/// [record_reply](self::GatewayReplyTracker::record_reply).
+///
+/// # Parameters
+///
+/// * `fulfilled` Every expectation that the reply fulfilled, paired with the grant that fulfilled
+///   it, in the order in which the grants were emitted.
+///
+/// * `unmatched` Every expectation of the submessage that the reply did not fulfill, in the order
+///   in which they were recorded.
+///
+/// * `unexpected` Every gateway event in the reply that did not fulfill an expectation, including
+///   events that could not be parsed, in the order in which they were emitted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GatewayReplyOutcome {
+    pub fulfilled: Vec<(ExpectedGrant, AccessGrant)>,
+    pub unmatched: Vec<ExpectedGrant>,
+    pub unexpected: Vec<Result<OsGatewayEvent, OsGatewayError>>,
+}
+impl GatewayReplyOutcome {
+    /// Whether or not every expectation was fulfilled and no other gateway events were emitted.
+    pub fn is_exact(&self) -> bool {
+        self.unmatched.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Correlates the access grants that a contract expects its submessages to emit with the gateway
+/// events found in their replies, for contracts that dispatch several submessages at once and must
+/// know which grant belongs to which submessage.
+///
+/// The tracker is kept in contract state, such as a cw-storage-plus [Item](cw_storage_plus::Item),
+/// between dispatching the submessages and handling their replies.  Expectations are recorded with
+/// [expect_grant](self::GatewayReplyTracker::expect_grant) before dispatch, and each reply is passed
+/// to [record_reply](self::GatewayReplyTracker::record_reply), which consumes the expectations of
+/// that submessage.
+///
+/// ```
+/// use cosmwasm_std::{Event, Reply, SubMsgResponse, SubMsgResult};
+/// use os_gateway_contract_attributes::storage::GatewayReplyTracker;
+/// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+///
+/// let scope_address = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+/// let target_account_address = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+/// let mut tracker = GatewayReplyTracker::new();
+/// tracker.expect_grant(7, scope_address, target_account_address);
+///
+/// let reply = Reply {
+///     id: 7,
+///     result: SubMsgResult::Ok(SubMsgResponse {
+///         events: vec![Event::new("wasm").add_attributes(
+///             OsGatewayAttributeGenerator::access_grant(scope_address, target_account_address),
+///         )],
+///         data: None,
+///     }),
+/// };
+/// let outcome = tracker.record_reply(&reply);
+/// assert!(outcome.is_exact());
+/// assert!(tracker.is_empty());
+/// ```
+///
+/// With the `serde` feature enabled, the tracker serializes as the array of its pending
+/// expectations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GatewayReplyTracker {
+    expectations: Vec<ExpectedGrant>,
+}
+impl GatewayReplyTracker {
+    /// Creates a tracker without any expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the submessage is expected to emit an access grant of the scope to the target
+    /// account.  Any number of grants may be expected of the same submessage.
+    ///
+    /// # Parameters
+    ///
+    /// * `submsg_id` The id of the submessage, as provided to [SubMsg](cosmwasm_std::SubMsg).
+    /// * `scope_address` The bech32 address of the scope that the grant is expected to refer to.
+    /// * `target_account_address` The bech32 address of the account that the grant is expected to
+    ///   refer to.
+    pub fn expect_grant<S1: Into<String>, S2: Into<String>>(
+        &mut self,
+        submsg_id: u64,
+        scope_address: S1,
+        target_account_address: S2,
+    ) {
+        self.expectations.push(ExpectedGrant {
+            submsg_id,
+            scope_address: scope_address.into(),
+            target_account_address: target_account_address.into(),
+        });
+    }
+
+    /// Correlates the gateway events of a submessage's reply with the grants expected of that
+    /// submessage, removing every expectation of the submessage from the tracker whether or not it
+    /// was fulfilled.  Each event fulfills at most one expectation, and expectations are fulfilled
+    /// in the order in which they were recorded.  Replies to failed submessages contain no events,
+    /// so leave every expectation of the submessage unmatched.
+    ///
+    /// # Parameters
+    ///
+    /// * `reply` The reply received by the contract's `reply` entry point.
+    pub fn record_reply(&mut self, reply: &Reply) -> GatewayReplyOutcome {
+        let (mut expected, pending) = std::mem::take(&mut self.expectations)
+            .into_iter()
+            .partition::<Vec<ExpectedGrant>, _>(|expectation| expectation.submsg_id == reply.id);
+        self.expectations = pending;
+        let mut fulfilled = vec![];
+        let mut unexpected = vec![];
+        for event in parse_gateway_events_from_reply(reply) {
+            let position = match &event {
+                Ok(OsGatewayEvent::AccessGrant(grant)) => expected
+                    .iter()
+                    .position(|expectation| expectation.is_fulfilled_by(grant)),
+                _ => None,
+            };
+            match (position, event) {
+                (Some(position), Ok(OsGatewayEvent::AccessGrant(grant))) => {
+                    fulfilled.push((expected.remove(position), grant))
+                }
+                (_, event) => unexpected.push(event),
+            }
+        }
+        GatewayReplyOutcome {
+            fulfilled,
+            unmatched: expected,
+            unexpected,
+        }
+    }
+
+    /// Every expectation that has not yet been consumed by a reply, in the order in which they were
+    /// recorded.
+    pub fn pending(&self) -> &[ExpectedGrant] {
+        &self.expectations
+    }
+
+    /// Whether or not every expectation has been consumed by a reply.
+    pub fn is_empty(&self) -> bool {
+        self.expectations.is_empty()
+    }
+}
+/// Serializes the tracker as the array of its pending expectations.
+#[cfg(feature = "serde")]
+impl serde::Serialize for GatewayReplyTracker {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.expectations.serialize(serializer)
+    }
+}
+/// Deserializes the tracker from the array produced by its serialization.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GatewayReplyTracker {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            expectations: Vec::deserialize(deserializer)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosmwasm_compat;
+    use crate::storage::{ExpectedGrant, GatewayReplyTracker};
+    use crate::{AccessGrant, AccessRevoke, OsGatewayAttributeGenerator, OsGatewayEvent};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Event, Reply, SubMsgResult};
+    use cw_storage_plus::Item;
+
+    const TRACKER: Item<GatewayReplyTracker> = Item::new("gateway_reply_tracker");
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn reply(id: u64, events: Vec<OsGatewayEvent>) -> Reply {
+        cosmwasm_compat::reply(
+            id,
+            SubMsgResult::Ok(cosmwasm_compat::sub_msg_response(vec![events
+                .into_iter()
+                .fold(
+                    Event::new("wasm").add_attribute("action", "grant"),
+                    |event, gateway_event| {
+                        event.add_attributes(OsGatewayAttributeGenerator::from(gateway_event))
+                    },
+                )])),
+        )
+    }
+
+    fn expectation(submsg_id: u64, scope_address: &str) -> ExpectedGrant {
+        ExpectedGrant {
+            submsg_id,
+            scope_address: scope_address.to_string(),
+            target_account_address: ACCOUNT_ADDRESS.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_and_reply_flow() {
+        let mut storage = MockStorage::new();
+        // Dispatching the submessages
+        let mut tracker = GatewayReplyTracker::new();
+        tracker.expect_grant(1, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        tracker.expect_grant(2, OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        TRACKER.save(&mut storage, &tracker).unwrap();
+
+        // Handling the reply to the second submessage first
+        let mut tracker = TRACKER.load(&storage).unwrap();
+        let grant = AccessGrant::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let outcome = tracker.record_reply(&reply(2, vec![grant.clone().into()]));
+        TRACKER.save(&mut storage, &tracker).unwrap();
+        assert_eq!(
+            vec![(expectation(2, OTHER_SCOPE_ADDRESS), grant)],
+            outcome.fulfilled,
+            "the grant should fulfill the expectation of its submessage",
+        );
+        assert!(outcome.is_exact(), "nothing else should be reported");
+        assert_eq!(
+            vec![expectation(1, SCOPE_ADDRESS)],
+            TRACKER.load(&storage).unwrap().pending(),
+            "the expectation of the other submessage should remain pending",
+        );
+
+        // Handling the reply to the first submessage
+        let mut tracker = TRACKER.load(&storage).unwrap();
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("id");
+        let outcome = tracker.record_reply(&reply(1, vec![grant.clone().into()]));
+        TRACKER.save(&mut storage, &tracker).unwrap();
+        assert_eq!(
+            vec![(expectation(1, SCOPE_ADDRESS), grant)],
+            outcome.fulfilled,
+            "the grant should fulfill the expectation of its submessage",
+        );
+        assert!(
+            TRACKER.load(&storage).unwrap().is_empty(),
+            "every expectation should be consumed",
+        );
+    }
+
+    #[test]
+    fn test_grants_are_correlated_to_their_own_submessage() {
+        let mut tracker = GatewayReplyTracker::new();
+        tracker.expect_grant(1, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        tracker.expect_grant(2, OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let outcome = tracker.record_reply(&reply(
+            1,
+            vec![AccessGrant::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()],
+        ));
+        assert!(
+            outcome.fulfilled.is_empty(),
+            "a grant expected of another submessage should not fulfill this one",
+        );
+        assert_eq!(
+            vec![expectation(1, SCOPE_ADDRESS)],
+            outcome.unmatched,
+            "the submessage's own expectation should be unmatched",
+        );
+        assert_eq!(
+            vec![Ok(
+                AccessGrant::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()
+            )],
+            outcome.unexpected,
+            "the grant should be reported as unexpected",
+        );
+        assert_eq!(
+            vec![expectation(2, OTHER_SCOPE_ADDRESS)],
+            tracker.pending(),
+            "only the replied submessage's expectations should be consumed",
+        );
+    }
+
+    #[test]
+    fn test_each_event_fulfills_one_expectation() {
+        let mut tracker = GatewayReplyTracker::new();
+        tracker.expect_grant(1, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        tracker.expect_grant(1, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let revoke = AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let outcome =
+            tracker.record_reply(&reply(1, vec![grant.clone().into(), revoke.clone().into()]));
+        assert_eq!(
+            vec![(expectation(1, SCOPE_ADDRESS), grant)],
+            outcome.fulfilled,
+            "a single grant should fulfill a single expectation",
+        );
+        assert_eq!(
+            vec![expectation(1, SCOPE_ADDRESS)],
+            outcome.unmatched,
+            "the duplicate expectation should be unmatched",
+        );
+        assert_eq!(
+            vec![Ok(revoke.into())],
+            outcome.unexpected,
+            "events other than grants should be reported as unexpected",
+        );
+    }
+
+    #[test]
+    fn test_failed_submessages_leave_expectations_unmatched() {
+        let mut tracker = GatewayReplyTracker::new();
+        tracker.expect_grant(1, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let outcome = tracker.record_reply(&cosmwasm_compat::reply(
+            1,
+            SubMsgResult::Err("out of gas".to_string()),
+        ));
+        assert_eq!(
+            vec![expectation(1, SCOPE_ADDRESS)],
+            outcome.unmatched,
+            "the expectation of a failed submessage should be unmatched",
+        );
+        assert!(
+            tracker.is_empty(),
+            "the expectation should be consumed by the reply",
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut tracker = GatewayReplyTracker::new();
+        tracker.expect_grant(3, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let json = cosmwasm_std::to_json_string(&tracker).unwrap();
+        assert_eq!(
+            format!(r#"[[3,"{SCOPE_ADDRESS}","{ACCOUNT_ADDRESS}"]]"#),
+            json,
+            "the tracker should serialize as its pending expectations",
+        );
+        assert_eq!(
+            tracker,
+            cosmwasm_std::from_json::<GatewayReplyTracker>(json.as_bytes()).unwrap(),
+            "the tracker should survive a serde round trip",
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::OsGatewayAttributeGenerator;
+use cosmwasm_std::Response;
+
+/// Extends the [Cosmwasm Response](cosmwasm_std::Response) with functions that append
+/// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) attributes
+/// directly in the response's fluent builder:
+///
+/// ```
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayResponseExt};
+///
+/// let grant_response: Response = Response::new().add_os_gateway_grant(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// );
+/// // Optional attributes are included by providing a pre-built generator
+/// let grant_with_id_response: Response = Response::new().add_os_gateway_event(
+///     OsGatewayAttributeGenerator::access_grant(
+///         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     )
+///     .with_access_grant_id("my_unique_id"),
+/// );
+/// ```
+///
+/// The extension, like every other function in this crate that accepts a response, is generic over
+/// the response's custom message type without further bounds, so it applies equally to contracts
+/// that return custom messages alongside their gateway attributes.
+#[cfg_attr(
+    feature = "provwasm",
+    doc = r#"
+For example, a [provwasm](https://github.com/provenance-io/provwasm) contract returning
+`Response<ProvenanceMsg>`:
+
+```
+use cosmwasm_std::{Addr, Response, StdResult};
+use os_gateway_contract_attributes::OsGatewayResponseExt;
+use provwasm_std::{bind_name, NameBinding, ProvenanceMsg};
+
+fn execute() -> StdResult<Response<ProvenanceMsg>> {
+    Ok(Response::new()
+        .add_message(bind_name(
+            "gateway.pb",
+            Addr::unchecked("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr"),
+            NameBinding::Restricted,
+        )?)
+        .add_os_gateway_grant(
+            "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+            "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+        ))
+}
+
+assert_eq!(3, execute().unwrap().attributes.len());
+```
+"#
+)]
+pub trait OsGatewayResponseExt<T> {
+    /// Appends the attributes produced by [access_grant](crate::OsGatewayAttributeGenerator::access_grant)
+    /// to the response.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    fn add_os_gateway_grant<S1: Into<String>, S2: Into<String>>(
+        self,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self;
+
+    /// Appends the attributes produced by [access_revoke](crate::OsGatewayAttributeGenerator::access_revoke)
+    /// to the response.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    fn add_os_gateway_revoke<S1: Into<String>, S2: Into<String>>(
+        self,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self;
+
+    /// Appends all attributes held in a pre-built [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator)
+    /// to the response.  Use this when optional attributes are required.
+    ///
+    /// # Parameters
+    ///
+    /// * `generator` The generator whose attributes will be appended to the response.
+    fn add_os_gateway_event(self, generator: OsGatewayAttributeGenerator) -> Self;
+}
+impl<T> OsGatewayResponseExt<T> for Response<T> {
+    fn add_os_gateway_grant<S1: Into<String>, S2: Into<String>>(
+        self,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        self.add_os_gateway_event(OsGatewayAttributeGenerator::access_grant(
+            scope_address,
+            target_account_address,
+        ))
+    }
+
+    fn add_os_gateway_revoke<S1: Into<String>, S2: Into<String>>(
+        self,
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        self.add_os_gateway_event(OsGatewayAttributeGenerator::access_revoke(
+            scope_address,
+            target_account_address,
+        ))
+    }
+
+    fn add_os_gateway_event(self, generator: OsGatewayAttributeGenerator) -> Self {
+        generator.add_to_response(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OsGatewayAttributeGenerator, OsGatewayResponseExt};
+    use cosmwasm_std::Response;
+
+    #[test]
+    fn test_grant_shortcut_matches_generator() {
+        let response: Response<String> = Response::new().add_os_gateway_grant("scope", "target");
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_grant("scope", "target").to_attributes(),
+            response.attributes,
+            "the grant shortcut should append the access grant attributes",
+        );
+    }
+
+    #[test]
+    fn test_revoke_shortcut_matches_generator() {
+        let response: Response<String> = Response::new().add_os_gateway_revoke("scope", "target");
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke("scope", "target").to_attributes(),
+            response.attributes,
+            "the revoke shortcut should append the access revoke attributes",
+        );
+    }
+
+    #[test]
+    fn test_event_retains_existing_attributes() {
+        let generator =
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id");
+        let response: Response<String> = Response::new()
+            .add_attribute("action", "grant")
+            .add_os_gateway_event(generator.clone());
+        assert_eq!(
+            "action", response.attributes[0].key,
+            "existing attributes should be retained ahead of the gateway attributes",
+        );
+        assert_eq!(
+            generator.to_attributes(),
+            response.attributes[1..],
+            "the pre-built generator's attributes should be appended to the response",
+        );
+    }
+}
@@ -0,0 +1,528 @@
+use crate::grant_target::TARGET_KINDS;
+use crate::typed_event_serde::{SCHEMA_VERSION, SCHEMA_VERSION_FIELD};
+use crate::{
+    AccessGrant, AccessGrantId, AccessLevel, AccessRevoke, AccountAddress, GrantExpiration,
+    GrantTarget, OsGatewayEvent, OsGatewayPayloadVersion, ScopeAddress, OS_GATEWAY_EVENT_TYPES,
+    OS_GATEWAY_KEYS,
+};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, ObjectValidation, Schema, SchemaObject, StringValidation,
+    SubschemaValidation,
+};
+use schemars::JsonSchema;
+
+/// Describes the structure of short field names that an access grant serializes into.
+impl JsonSchema for AccessGrant {
+    fn schema_name() -> String {
+        "AccessGrant".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let expiration_schema = gen.subschema_for::<GrantExpiration>();
+        let access_level_schema = gen.subschema_for::<AccessLevel>();
+        typed_event_schema(
+            gen,
+            "AccessGrant",
+            "An access grant, which instructs Object Store Gateway to grant an account access to the records of a scope.",
+            vec![
+                ("expiration", expiration_schema),
+                ("access_level", access_level_schema),
+            ],
+        )
+    }
+}
+
+/// Describes the structure of short field names that an access revoke serializes into.
+impl JsonSchema for AccessRevoke {
+    fn schema_name() -> String {
+        "AccessRevoke".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        typed_event_schema(
+            gen,
+            "AccessRevoke",
+            "An access revoke, which instructs Object Store Gateway to revoke an account's access to the records of a scope.",
+            vec![],
+        )
+    }
+}
+
+/// Describes the flat map of attribute keys to values that an event serializes into, as exactly
+/// one of the attribute maps of its variants.
+impl JsonSchema for OsGatewayEvent {
+    fn schema_name() -> String {
+        "OsGatewayEvent".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let expiration_schema = gen.subschema_for::<GrantExpiration>();
+        let access_level_schema = gen.subschema_for::<AccessLevel>();
+        let grant_schema = event_schema(
+            gen,
+            "AccessGrant",
+            "The attributes of an access grant event, which instructs Object Store Gateway to grant an account access to the records of a scope.",
+            OS_GATEWAY_EVENT_TYPES.access_grant,
+            vec![
+                (OS_GATEWAY_KEYS.expiration, expiration_schema),
+                (OS_GATEWAY_KEYS.access_level, access_level_schema),
+            ],
+        );
+        let revoke_schema = event_schema(
+            gen,
+            "AccessRevoke",
+            "The attributes of an access revoke event, which instructs Object Store Gateway to revoke an account's access to the records of a scope.",
+            OS_GATEWAY_EVENT_TYPES.access_revoke,
+            vec![],
+        );
+        Schema::Object(SchemaObject {
+            metadata: metadata(
+                "OsGatewayEvent",
+                "Any event consumed by Object Store Gateway, distinguished by its event type attribute.",
+            ),
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(vec![grant_schema, revoke_schema]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        })
+    }
+}
+
+/// Describes a scope address as a plain string.
+impl JsonSchema for ScopeAddress {
+    fn schema_name() -> String {
+        "ScopeAddress".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "ScopeAddress",
+            "The bech32 address of a Provenance Blockchain Scope, with the scope human-readable part.",
+            None,
+        )
+    }
+}
+
+/// Describes an account address as a plain string.
+impl JsonSchema for AccountAddress {
+    fn schema_name() -> String {
+        "AccountAddress".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "AccountAddress",
+            "The bech32 address of a Provenance Blockchain Account.",
+            None,
+        )
+    }
+}
+
+/// Describes an access grant id as a plain string, including its length and character
+/// restrictions.
+impl JsonSchema for AccessGrantId {
+    fn schema_name() -> String {
+        "AccessGrantId".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "AccessGrantId",
+            "A unique identifier for an access grant, which enables referral to the grant after the event is processed.",
+            Some(StringValidation {
+                max_length: Some(128),
+                min_length: Some(1),
+                pattern: Some("^[A-Za-z0-9._:-]+$".to_string()),
+            }),
+        )
+    }
+}
+
+/// Describes an expiration as its wire encoding.
+impl JsonSchema for GrantExpiration {
+    fn schema_name() -> String {
+        "GrantExpiration".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "GrantExpiration",
+            "The point at which an access grant stops being honored: never, seconds since the unix epoch, or a block height prefixed with height:.",
+            Some(StringValidation {
+                pattern: Some("^(never|[0-9]+|height:[0-9]+)$".to_string()),
+                ..Default::default()
+            }),
+        )
+    }
+}
+
+/// Describes a target in its rendered form.
+impl JsonSchema for GrantTarget {
+    fn schema_name() -> String {
+        "GrantTarget".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "GrantTarget",
+            "Who or what an access grant is given to: a kind of account, marker, or scope_spec, followed by a colon and a bech32 address.",
+            Some(StringValidation {
+                pattern: Some("^(account|marker|scope_spec):[a-z0-9]+$".to_string()),
+                ..Default::default()
+            }),
+        )
+    }
+}
+
+/// Describes an access level as one of its recognized attribute values.
+impl JsonSchema for AccessLevel {
+    fn schema_name() -> String {
+        "AccessLevel".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            metadata: metadata(
+                "AccessLevel",
+                "The level of access that an access grant gives its target account.",
+            ),
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(
+                AccessLevel::ALL
+                    .into_iter()
+                    .map(|access_level| access_level.as_str().into())
+                    .collect(),
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+/// Describes a payload version as its wire encoding.
+impl JsonSchema for OsGatewayPayloadVersion {
+    fn schema_name() -> String {
+        "OsGatewayPayloadVersion".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "OsGatewayPayloadVersion",
+            "The major version of the layout of an event's attributes, as a plain decimal number.",
+            Some(StringValidation {
+                pattern: Some("^[0-9]+$".to_string()),
+                ..Default::default()
+            }),
+        )
+    }
+}
+
+/// Produces the schema of a stamped [source contract version](crate::OsGatewayAttributeGenerator::with_source_contract_version).
+pub(crate) fn source_contract_version_schema() -> Schema {
+    string_schema(
+        "SourceContractVersion",
+        "The name and version of the contract that produced an event, as <contract name>/<version>.",
+        Some(StringValidation {
+            pattern: Some("^.+/[^/]+$".to_string()),
+            ..Default::default()
+        }),
+    )
+}
+
+/// Produces the schema of the [target kind](crate::OsGatewayKeys) attribute.
+pub(crate) fn target_kind_schema() -> Schema {
+    Schema::Object(SchemaObject {
+        metadata: metadata(
+            "TargetKind",
+            "The kind of target that the target account address identifies, which is an account when omitted.",
+        ),
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(TARGET_KINDS.into_iter().map(|kind| kind.into()).collect()),
+        ..Default::default()
+    })
+}
+
+fn metadata(title: &str, description: &str) -> Option<Box<Metadata>> {
+    Some(Box::new(Metadata {
+        title: Some(title.to_string()),
+        description: Some(description.to_string()),
+        ..Default::default()
+    }))
+}
+
+fn string_schema(title: &str, description: &str, string: Option<StringValidation>) -> Schema {
+    Schema::Object(SchemaObject {
+        metadata: metadata(title, description),
+        instance_type: Some(InstanceType::String.into()),
+        string: string.map(Box::new),
+        ..Default::default()
+    })
+}
+
+/// Produces the schema of a typed event's short field names: its schema version, its addresses,
+/// its optional access grant id, and its extra attributes as a map of strings.  Fields serialized
+/// by only one kind of event are provided alongside their schemas.
+fn typed_event_schema(
+    gen: &mut SchemaGenerator,
+    title: &str,
+    description: &str,
+    event_specific_properties: Vec<(&str, Schema)>,
+) -> Schema {
+    let extra_attributes_schema = Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        object: Some(Box::new(ObjectValidation {
+            additional_properties: Some(Box::new(gen.subschema_for::<String>())),
+            ..Default::default()
+        })),
+        ..Default::default()
+    });
+    let schema_version_schema = Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::Integer.into()),
+        enum_values: Some(vec![SCHEMA_VERSION.into()]),
+        ..Default::default()
+    });
+    let mut object = ObjectValidation::default();
+    object
+        .properties
+        .insert(SCHEMA_VERSION_FIELD.to_string(), schema_version_schema);
+    for field in ["scope_address", "target_account_address", "access_grant_id"] {
+        object
+            .properties
+            .insert(field.to_string(), gen.subschema_for::<String>());
+    }
+    for (field, schema) in event_specific_properties {
+        object.properties.insert(field.to_string(), schema);
+    }
+    object
+        .properties
+        .insert("extra_attributes".to_string(), extra_attributes_schema);
+    for field in [
+        SCHEMA_VERSION_FIELD,
+        "scope_address",
+        "target_account_address",
+    ] {
+        object.required.insert(field.to_string());
+    }
+    Schema::Object(SchemaObject {
+        metadata: metadata(title, description),
+        instance_type: Some(InstanceType::Object.into()),
+        object: Some(Box::new(object)),
+        ..Default::default()
+    })
+}
+
+/// Produces the schema of a typed event's attributes: its event type pinned to a single value, its
+/// modeled attributes, and any number of extra string attributes.  Attributes modeled by only one
+/// kind of event are provided alongside their schemas.
+fn event_schema(
+    gen: &mut SchemaGenerator,
+    title: &str,
+    description: &str,
+    event_type: &str,
+    event_specific_properties: Vec<(&str, Schema)>,
+) -> Schema {
+    let event_type_schema = Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(vec![event_type.into()]),
+        ..Default::default()
+    });
+    let mut object = ObjectValidation {
+        additional_properties: Some(Box::new(gen.subschema_for::<String>())),
+        ..Default::default()
+    };
+    object
+        .properties
+        .insert(OS_GATEWAY_KEYS.event_type.to_string(), event_type_schema);
+    object.properties.insert(
+        OS_GATEWAY_KEYS.scope_address.to_string(),
+        gen.subschema_for::<String>(),
+    );
+    object.properties.insert(
+        OS_GATEWAY_KEYS.target_account.to_string(),
+        gen.subschema_for::<String>(),
+    );
+    object.properties.insert(
+        OS_GATEWAY_KEYS.access_grant_id.to_string(),
+        gen.subschema_for::<String>(),
+    );
+    object.properties.insert(
+        OS_GATEWAY_KEYS.payload_version.to_string(),
+        gen.subschema_for::<OsGatewayPayloadVersion>(),
+    );
+    object.properties.insert(
+        OS_GATEWAY_KEYS.source_contract_version.to_string(),
+        source_contract_version_schema(),
+    );
+    object.properties.insert(
+        OS_GATEWAY_KEYS.target_kind.to_string(),
+        target_kind_schema(),
+    );
+    for (key, schema) in event_specific_properties {
+        object.properties.insert(key.to_string(), schema);
+    }
+    for key in [
+        OS_GATEWAY_KEYS.event_type,
+        OS_GATEWAY_KEYS.scope_address,
+        OS_GATEWAY_KEYS.target_account,
+    ] {
+        object.required.insert(key.to_string());
+    }
+    Schema::Object(SchemaObject {
+        metadata: metadata(title, description),
+        instance_type: Some(InstanceType::Object.into()),
+        object: Some(Box::new(object)),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessGrantId, AccessRevoke, GrantExpiration, GrantTarget, OsGatewayEvent,
+        OsGatewayPayloadVersion, ScopeAddress, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS,
+    };
+    use schemars::schema::{RootSchema, Schema};
+    use schemars::schema_for;
+
+    fn property_names(schema: &RootSchema) -> Vec<&str> {
+        schema
+            .schema
+            .object
+            .as_ref()
+            .expect("the schema should describe an object")
+            .properties
+            .keys()
+            .map(|key| key.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_typed_event_schemas_name_every_field() {
+        for (schema, title, event_specific_fields) in [
+            (
+                schema_for!(AccessGrant),
+                "AccessGrant",
+                vec!["access_level", "expiration"],
+            ),
+            (schema_for!(AccessRevoke), "AccessRevoke", vec![]),
+        ] {
+            let mut expected = vec![
+                "access_grant_id",
+                "extra_attributes",
+                "schema_version",
+                "scope_address",
+                "target_account_address",
+            ];
+            expected.extend(event_specific_fields);
+            expected.sort_unstable();
+            assert_eq!(
+                expected,
+                property_names(&schema),
+                "the [{title}] schema should name every field",
+            );
+            assert_eq!(
+                Some(title),
+                schema
+                    .schema
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.title.as_deref()),
+                "the schema should be titled after its type",
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_schema_names_every_attribute_of_each_variant() {
+        let schema = schema_for!(OsGatewayEvent);
+        let variants = schema
+            .schema
+            .subschemas
+            .as_ref()
+            .and_then(|subschemas| subschemas.one_of.as_ref())
+            .expect("the event schema should be one of its variants");
+        for (variant, (event_type, event_specific_keys)) in variants.iter().zip([
+            (
+                OS_GATEWAY_EVENT_TYPES.access_grant,
+                vec![OS_GATEWAY_KEYS.expiration, OS_GATEWAY_KEYS.access_level],
+            ),
+            (OS_GATEWAY_EVENT_TYPES.access_revoke, vec![]),
+        ]) {
+            let Schema::Object(variant) = variant else {
+                panic!("the [{event_type}] variant should be an object schema");
+            };
+            let mut expected = vec![
+                OS_GATEWAY_KEYS.access_grant_id,
+                OS_GATEWAY_KEYS.event_type,
+                OS_GATEWAY_KEYS.scope_address,
+                OS_GATEWAY_KEYS.target_account,
+                OS_GATEWAY_KEYS.payload_version,
+                OS_GATEWAY_KEYS.source_contract_version,
+                OS_GATEWAY_KEYS.target_kind,
+            ];
+            expected.extend(event_specific_keys);
+            expected.sort_unstable();
+            assert_eq!(
+                expected,
+                variant
+                    .object
+                    .as_ref()
+                    .unwrap()
+                    .properties
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<&str>>(),
+                "the [{event_type}] variant should name every attribute key",
+            );
+            let json = cosmwasm_std::to_json_string(variant).unwrap();
+            assert!(
+                json.contains(&format!("\"enum\":[\"{event_type}\"]")),
+                "the [{event_type}] variant should pin the event type: {json}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_newtype_schemas_are_strings() {
+        for json in [
+            cosmwasm_std::to_json_string(&schema_for!(ScopeAddress)).unwrap(),
+            cosmwasm_std::to_json_string(&schema_for!(AccessGrantId)).unwrap(),
+            cosmwasm_std::to_json_string(&schema_for!(GrantExpiration)).unwrap(),
+            cosmwasm_std::to_json_string(&schema_for!(GrantTarget)).unwrap(),
+            cosmwasm_std::to_json_string(&schema_for!(OsGatewayPayloadVersion)).unwrap(),
+        ] {
+            assert!(
+                json.contains("\"type\":\"string\""),
+                "the newtype schema should describe a string: {json}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialized_events_match_schema_keys() {
+        let grant = AccessGrant::new("scope", "target").with_access_grant_id("id");
+        let json = cosmwasm_std::to_json_vec(&grant).unwrap();
+        assert_eq!(
+            br#"{"schema_version":2,"scope_address":"scope","target_account_address":"target","access_grant_id":"id"}"#
+                .as_slice(),
+            json,
+            "the grant should serialize with the field names of its schema",
+        );
+        assert_eq!(
+            grant,
+            cosmwasm_std::from_json::<AccessGrant>(&json).unwrap(),
+            "the grant should survive a serde round trip",
+        );
+        let json = cosmwasm_std::to_json_vec(&OsGatewayEvent::from(grant.clone())).unwrap();
+        assert_eq!(
+            OsGatewayEvent::from(grant),
+            cosmwasm_std::from_json::<OsGatewayEvent>(&json).unwrap(),
+            "the serialized event should deserialize as an event",
+        );
+        assert!(
+            cosmwasm_std::from_json::<AccessRevoke>(&json).is_err(),
+            "the attributes of a grant should not deserialize as a revoke",
+        );
+    }
+}
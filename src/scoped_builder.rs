@@ -0,0 +1,369 @@
+use crate::{AccessLevel, GrantExpiration, OsGatewayAttributeGenerator};
+use alloc::string::String;
+use alloc::vec::IntoIter;
+
+/// A fluent builder for access grant events that only exposes the options that
+/// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) honors for access
+/// grants.  Options that only apply to access revokes do not exist on this type, so they cannot be
+/// provided to a grant and then silently ignored by the gateway.
+///
+/// The builder is produced by [OsGatewayAttributeGenerator::grant](crate::OsGatewayAttributeGenerator::grant),
+/// converts into a common [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator), and
+/// can be appended to a response directly:
+///
+/// ```
+/// # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::{AccessLevel, OsGatewayAttributeGenerator};
+///
+/// let response: Response<String> = Response::new().add_attributes(
+///     OsGatewayAttributeGenerator::grant(
+///         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     )
+///     .with_access_grant_id("my_unique_id")
+///     .with_access_level(AccessLevel::Read),
+/// );
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use os_gateway_contract_attributes::{AccessLevel, OsGatewayAttributeGenerator};
+///
+/// // Access levels only apply to grants, so a revoke cannot be given one
+/// let revoke = OsGatewayAttributeGenerator::revoke(
+///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+/// )
+/// .with_access_level(AccessLevel::Read);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrantBuilder {
+    generator: OsGatewayAttributeGenerator,
+}
+impl GrantBuilder {
+    /// Creates a builder for an access grant event.  See [access_grant](crate::OsGatewayAttributeGenerator::access_grant)
+    /// for the criteria the gateway requires in order to honor the grant.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access grant refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access grant refers.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::access_grant(
+                scope_address,
+                target_account_address,
+            ),
+        }
+    }
+
+    /// Appends an access grant id to the grant.  See [with_access_grant_id](crate::OsGatewayAttributeGenerator::with_access_grant_id).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier with which the resulting grant will be created.
+    pub fn with_access_grant_id<S: Into<String>>(self, access_grant_id: S) -> Self {
+        Self {
+            generator: self.generator.with_access_grant_id(access_grant_id),
+        }
+    }
+
+    /// Appends an access grant id to the grant only if one is provided.  See [with_optional_access_grant_id](crate::OsGatewayAttributeGenerator::with_optional_access_grant_id).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier with which the resulting grant will be created, if
+    ///   any.
+    pub fn with_optional_access_grant_id<S: Into<String>>(
+        self,
+        access_grant_id: Option<S>,
+    ) -> Self {
+        Self {
+            generator: self
+                .generator
+                .with_optional_access_grant_id(access_grant_id),
+        }
+    }
+
+    /// Sets the point at which the grant stops being honored, encoded as described by
+    /// [GrantExpiration](crate::GrantExpiration).  Replaces any previously set expiration.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the resulting grant expires.
+    pub fn with_expiration(mut self, expiration: GrantExpiration) -> Self {
+        self.generator.insert_expiration(expiration);
+        self
+    }
+
+    /// Sets the point at which the grant stops being honored only if one is provided.  See
+    /// [with_expiration](self::GrantBuilder::with_expiration).
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the resulting grant expires, if any.
+    pub fn with_optional_expiration(mut self, expiration: Option<GrantExpiration>) -> Self {
+        if let Some(expiration) = expiration {
+            self.generator.insert_expiration(expiration);
+        }
+        self
+    }
+
+    /// Sets the level of access that the grant gives its target account.  Replaces any previously
+    /// set access level.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access that the resulting grant gives.
+    pub fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.generator.insert_access_level(access_level);
+        self
+    }
+
+    /// Sets the level of access that the grant gives only if one is provided.  See
+    /// [with_access_level](self::GrantBuilder::with_access_level).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access that the resulting grant gives, if any.
+    pub fn with_optional_access_level(mut self, access_level: Option<AccessLevel>) -> Self {
+        if let Some(access_level) = access_level {
+            self.generator.insert_access_level(access_level);
+        }
+        self
+    }
+
+    /// Exposes read-only access to the generator that holds the grant's attributes.
+    pub fn as_generator(&self) -> &OsGatewayAttributeGenerator {
+        &self.generator
+    }
+
+    /// Consumes the builder, producing the generator that holds the grant's attributes.
+    pub fn build(self) -> OsGatewayAttributeGenerator {
+        self.generator
+    }
+}
+impl From<GrantBuilder> for OsGatewayAttributeGenerator {
+    fn from(builder: GrantBuilder) -> Self {
+        builder.build()
+    }
+}
+impl IntoIterator for GrantBuilder {
+    type Item = (String, String);
+
+    type IntoIter = IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.generator.into_iter()
+    }
+}
+
+/// A fluent builder for access revoke events that only exposes the options that
+/// [Object Store Gateway](https://github.com/provenance-io/object-store-gateway) honors for access
+/// revokes.  Options that only apply to access grants do not exist on this type, so they cannot be
+/// provided to a revoke and then silently ignored by the gateway.
+///
+/// The builder is produced by [OsGatewayAttributeGenerator::revoke](crate::OsGatewayAttributeGenerator::revoke),
+/// converts into a common [OsGatewayAttributeGenerator](crate::OsGatewayAttributeGenerator), and
+/// can be appended to a response directly:
+///
+/// ```
+/// # #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))] {
+/// use cosmwasm_std::Response;
+/// use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+///
+/// let response: Response<String> = Response::new().add_attributes(
+///     OsGatewayAttributeGenerator::revoke(
+///         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///     ),
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokeBuilder {
+    generator: OsGatewayAttributeGenerator,
+}
+impl RevokeBuilder {
+    /// Creates a builder for an access revoke event.  See [access_revoke](crate::OsGatewayAttributeGenerator::access_revoke)
+    /// for the criteria the gateway requires in order to honor the revoke.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    ///   to which this access revoke refers.
+    /// * `target_account_address` The bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    ///   to which this access revoke refers.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            generator: OsGatewayAttributeGenerator::access_revoke(
+                scope_address,
+                target_account_address,
+            ),
+        }
+    }
+
+    /// Limits the revoke to the grant created with the given access grant id.  See [with_access_grant_id](crate::OsGatewayAttributeGenerator::with_access_grant_id).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant to revoke.
+    pub fn with_access_grant_id<S: Into<String>>(self, access_grant_id: S) -> Self {
+        Self {
+            generator: self.generator.with_access_grant_id(access_grant_id),
+        }
+    }
+
+    /// Limits the revoke to the grant created with the given access grant id only if one is
+    /// provided.  See [with_optional_access_grant_id](crate::OsGatewayAttributeGenerator::with_optional_access_grant_id).
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant to revoke, if any.
+    pub fn with_optional_access_grant_id<S: Into<String>>(
+        self,
+        access_grant_id: Option<S>,
+    ) -> Self {
+        Self {
+            generator: self
+                .generator
+                .with_optional_access_grant_id(access_grant_id),
+        }
+    }
+
+    /// Exposes read-only access to the generator that holds the revoke's attributes.
+    pub fn as_generator(&self) -> &OsGatewayAttributeGenerator {
+        &self.generator
+    }
+
+    /// Consumes the builder, producing the generator that holds the revoke's attributes.
+    pub fn build(self) -> OsGatewayAttributeGenerator {
+        self.generator
+    }
+}
+impl From<RevokeBuilder> for OsGatewayAttributeGenerator {
+    fn from(builder: RevokeBuilder) -> Self {
+        builder.build()
+    }
+}
+impl IntoIterator for RevokeBuilder {
+    type Item = (String, String);
+
+    type IntoIter = IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.generator.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessLevel, GrantBuilder, GrantExpiration, OsGatewayAttributeGenerator, RevokeBuilder,
+        OS_GATEWAY_KEYS,
+    };
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    use cosmwasm_std::Response;
+
+    #[test]
+    fn test_grant_builder_matches_generator() {
+        let builder = GrantBuilder::new("scope", "target")
+            .with_access_grant_id("id")
+            .with_expiration(GrantExpiration::AtHeight(5))
+            .with_access_level(AccessLevel::Read);
+        let mut expected =
+            OsGatewayAttributeGenerator::access_grant("scope", "target").with_access_grant_id("id");
+        expected.set_attribute(OS_GATEWAY_KEYS.expiration, "height:5");
+        expected.set_attribute(OS_GATEWAY_KEYS.access_level, "read");
+        assert_eq!(
+            &expected,
+            builder.as_generator(),
+            "the grant builder should hold the same attributes as the generator",
+        );
+        assert_eq!(
+            expected,
+            OsGatewayAttributeGenerator::from(builder),
+            "the grant builder should convert into the equivalent generator",
+        );
+    }
+
+    #[test]
+    fn test_grant_builder_optional_values() {
+        assert_eq!(
+            GrantBuilder::new("scope", "target"),
+            GrantBuilder::new("scope", "target")
+                .with_optional_expiration(None)
+                .with_optional_access_level(None),
+            "absent optional values should not be included",
+        );
+        assert_eq!(
+            GrantBuilder::new("scope", "target")
+                .with_expiration(GrantExpiration::Never)
+                .with_access_level(AccessLevel::ReadWrite),
+            GrantBuilder::new("scope", "target")
+                .with_optional_expiration(Some(GrantExpiration::Never))
+                .with_optional_access_level(Some(AccessLevel::ReadWrite)),
+            "present optional values should be included",
+        );
+    }
+
+    #[test]
+    fn test_generator_entry_points_produce_builders() {
+        assert_eq!(
+            GrantBuilder::new("scope", "target"),
+            OsGatewayAttributeGenerator::grant("scope", "target"),
+            "the grant entry point should produce a grant builder",
+        );
+        assert_eq!(
+            RevokeBuilder::new("scope", "target"),
+            OsGatewayAttributeGenerator::revoke("scope", "target"),
+            "the revoke entry point should produce a revoke builder",
+        );
+    }
+
+    #[test]
+    fn test_revoke_builder_matches_generator() {
+        let builder =
+            RevokeBuilder::new("scope", "target").with_optional_access_grant_id(Some("id"));
+        let expected = OsGatewayAttributeGenerator::access_revoke("scope", "target")
+            .with_access_grant_id("id");
+        assert_eq!(
+            &expected,
+            builder.as_generator(),
+            "the revoke builder should hold the same attributes as the generator",
+        );
+        assert_eq!(
+            expected,
+            builder.build(),
+            "the revoke builder should build the equivalent generator",
+        );
+    }
+
+    #[cfg(any(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+    #[test]
+    fn test_builders_append_to_responses() {
+        let grant_response: Response<String> =
+            Response::new().add_attributes(GrantBuilder::new("scope", "target"));
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_grant("scope", "target").to_attributes(),
+            grant_response.attributes,
+            "the grant builder should append the grant attributes in emission order",
+        );
+        let revoke_response: Response<String> =
+            Response::new().add_attributes(RevokeBuilder::new("scope", "target"));
+        assert_eq!(
+            OsGatewayAttributeGenerator::access_revoke("scope", "target").to_attributes(),
+            revoke_response.attributes,
+            "the revoke builder should append the revoke attributes in emission order",
+        );
+    }
+}
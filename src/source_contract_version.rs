@@ -0,0 +1,141 @@
+use crate::{AccessGrant, AccessRevoke, OsGatewayEvent, OS_GATEWAY_KEYS};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+impl OsGatewayEvent {
+    /// The contract name and version stamped on the event, as `<contract name>/<version>`, or
+    /// `None` if it was emitted without one.  See [with_source_contract_version](crate::OsGatewayAttributeGenerator::with_source_contract_version).
+    pub fn source_contract_version(&self) -> Option<&str> {
+        match self {
+            Self::AccessGrant(grant) => grant.source_contract_version(),
+            Self::AccessRevoke(revoke) => revoke.source_contract_version(),
+        }
+    }
+}
+
+impl AccessGrant {
+    /// The contract name and version retained in the grant's extra attributes.  See
+    /// [OsGatewayEvent::source_contract_version](crate::OsGatewayEvent::source_contract_version).
+    pub fn source_contract_version(&self) -> Option<&str> {
+        stamped_contract_version(&self.extra_attributes)
+    }
+}
+
+impl AccessRevoke {
+    /// The contract name and version retained in the revoke's extra attributes.  See
+    /// [OsGatewayEvent::source_contract_version](crate::OsGatewayEvent::source_contract_version).
+    pub fn source_contract_version(&self) -> Option<&str> {
+        stamped_contract_version(&self.extra_attributes)
+    }
+}
+
+fn stamped_contract_version(extra_attributes: &BTreeMap<String, String>) -> Option<&str> {
+    extra_attributes
+        .get(OS_GATEWAY_KEYS.source_contract_version)
+        .map(|version| version.as_str())
+}
+
+/// Splits a stamped value into its contract name and version, or produces `None` if either is
+/// missing.  The version follows the last `/`, so that contract names may contain one.
+pub(crate) fn split_source_contract_version(value: &str) -> Option<(&str, &str)> {
+    value
+        .rsplit_once('/')
+        .filter(|(contract, version)| !contract.is_empty() && !version.is_empty())
+}
+
+#[cfg(all(test, feature = "parsing"))]
+mod tests {
+    use super::split_source_contract_version;
+    use crate::{
+        parse_gateway_event, FieldErrorKind, OsGatewayAttributeGenerator, OsGatewayError,
+        OsGatewayEvent, OS_GATEWAY_KEYS,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn parse_single(generator: OsGatewayAttributeGenerator) -> OsGatewayEvent {
+        let mut results = parse_gateway_event(&generator.to_event());
+        assert_eq!(1, results.len(), "a single event should be parsed");
+        results.remove(0).expect("the event should parse")
+    }
+
+    #[test]
+    fn test_generator_stamps_contract_version() {
+        let generator = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        assert_eq!(
+            None,
+            generator.source_contract_version(),
+            "the contract version should only be stamped on request",
+        );
+        let generator = generator
+            .with_source_contract_version("my-contract", "1.4.1")
+            .with_source_contract_version("my-contract", "1.4.2");
+        assert_eq!(
+            Some("my-contract/1.4.2"),
+            generator.source_contract_version(),
+            "the latest contract version should replace the previous one",
+        );
+        assert_eq!(
+            Ok(()),
+            generator.validate(),
+            "a stamped contract version should pass validation",
+        );
+    }
+
+    #[test]
+    fn test_parsed_events_expose_contract_version() {
+        let unstamped = parse_single(OsGatewayAttributeGenerator::access_revoke(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+        ));
+        assert_eq!(
+            None,
+            unstamped.source_contract_version(),
+            "an event without a contract version should report none",
+        );
+        for generator in [
+            OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            OsGatewayAttributeGenerator::access_revoke(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+        ] {
+            let event = parse_single(
+                generator.with_source_contract_version("crates.io:my-contract", "1.4.2"),
+            );
+            assert_eq!(
+                Some("crates.io:my-contract/1.4.2"),
+                event.source_contract_version(),
+                "the [{}] event should expose its contract version",
+                event.event_type(),
+            );
+            assert_eq!(
+                Some("crates.io:my-contract/1.4.2"),
+                OsGatewayAttributeGenerator::from(event).source_contract_version(),
+                "the contract version should survive conversion back into attributes",
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_contract_versions_fail_validation() {
+        assert_eq!(
+            Some(("org/my-contract", "1.4.2")),
+            split_source_contract_version("org/my-contract/1.4.2"),
+            "the version should follow the last separator",
+        );
+        for value in ["my-contract", "my-contract/", "/1.4.2", "/"] {
+            let mut generator =
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+            generator.set_attribute(OS_GATEWAY_KEYS.source_contract_version, value);
+            let result = generator.validate();
+            assert!(
+                matches!(
+                    &result,
+                    Err(OsGatewayError::InvalidFields { errors })
+                        if errors.len() == 1
+                            && errors[0].kind == FieldErrorKind::InvalidSourceContractVersion,
+                ),
+                "the contract version [{value}] should be rejected, but produced: {result:?}",
+            );
+        }
+    }
+}
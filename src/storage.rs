@@ -0,0 +1,242 @@
+//! [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus) helpers for contracts that must
+//! defer emitting gateway events, such as granting access only after a submessage succeeds.
+//!
+//! Events are queued in contract storage when they are decided upon, then drained in the order
+//! they were queued and appended to the response that should carry them:
+//!
+//! ```
+//! use cosmwasm_std::testing::MockStorage;
+//! use cosmwasm_std::Response;
+//! use os_gateway_contract_attributes::storage::{emit_drained, PendingEvents};
+//! use os_gateway_contract_attributes::AccessGrant;
+//!
+//! const PENDING_EVENTS: PendingEvents = PendingEvents::new("pending_gateway_events");
+//!
+//! let mut storage = MockStorage::new();
+//! // When executing the message that dispatches the submessage
+//! PENDING_EVENTS
+//!     .queue_event(
+//!         &mut storage,
+//!         AccessGrant::new(
+//!             "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!             "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!         ),
+//!     )
+//!     .expect("the event should be queued");
+//! // When handling the successful reply
+//! let events = PENDING_EVENTS
+//!     .drain_events(&mut storage, 10)
+//!     .expect("the events should be drained");
+//! let response: Response = emit_drained(Response::new(), events);
+//! assert_eq!(3, response.attributes.len());
+//! ```
+//!
+//! Contracts that dispatch several submessages at once can instead keep a [GatewayReplyTracker] in
+//! storage to learn which of the expected grants each submessage's reply actually carried, and
+//! contracts that answer queries about the access they have granted can keep a [GrantLedger] of
+//! the grants they have emitted and not since revoked.  A [GrantReplay] re-emits a ledger's grants
+//! in batches, such as when migrating to a new gateway instance.
+
+pub use crate::grant_ledger::{GrantLedger, GrantPagination};
+pub use crate::grant_replay::{GrantReplay, ReplayCursor};
+pub use crate::reply_tracker::{ExpectedGrant, GatewayReplyOutcome, GatewayReplyTracker};
+use crate::{OsGatewayAttributeGenerator, OsGatewayEvent};
+use cosmwasm_std::{Response, StdResult, Storage};
+use cw_storage_plus::Deque;
+
+/// A first-in, first-out queue of gateway events held in contract storage under a namespace.
+/// Give each queue a distinct namespace, which must not collide with any other storage item of the
+/// contract, so that multiple queues, or multiple contracts built from shared code, never overwrite
+/// one another.
+///
+/// Events are stored as maps of the attribute keys that they emit, so queued events remain readable
+/// after upgrading this crate.
+pub struct PendingEvents<'a>(Deque<'a, OsGatewayEvent>);
+impl<'a> PendingEvents<'a> {
+    /// Creates a queue stored under the given namespace.  This is a const function, so queues can
+    /// be declared as constants alongside a contract's other storage items.
+    ///
+    /// # Parameters
+    ///
+    /// * `namespace` The storage namespace of the queue.
+    pub const fn new(namespace: &'a str) -> Self {
+        Self(Deque::new(namespace))
+    }
+
+    /// Appends an event to the end of the queue.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the queue.
+    /// * `event` The event to queue, or any typed event that converts into one.
+    pub fn queue_event<E: Into<OsGatewayEvent>>(
+        &self,
+        storage: &mut dyn Storage,
+        event: E,
+    ) -> StdResult<()> {
+        self.0.push_back(storage, &event.into())
+    }
+
+    /// Removes up to `limit` events from the front of the queue, producing them in the order they
+    /// were queued.  Events beyond the limit remain queued for a later drain, which keeps the gas
+    /// used by a single drain bounded.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the queue.
+    /// * `limit` The maximum number of events to remove.
+    pub fn drain_events(
+        &self,
+        storage: &mut dyn Storage,
+        limit: usize,
+    ) -> StdResult<Vec<OsGatewayEvent>> {
+        let mut events = Vec::new();
+        while events.len() < limit {
+            match self.0.pop_front(storage)? {
+                Some(event) => events.push(event),
+                None => break,
+            }
+        }
+        Ok(events)
+    }
+
+    /// The number of events in the queue.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the queue.
+    pub fn len(&self, storage: &dyn Storage) -> StdResult<u32> {
+        self.0.len(storage)
+    }
+
+    /// Whether or not the queue holds no events.
+    ///
+    /// # Parameters
+    ///
+    /// * `storage` The contract storage holding the queue.
+    pub fn is_empty(&self, storage: &dyn Storage) -> StdResult<bool> {
+        self.0.is_empty(storage)
+    }
+}
+
+/// Appends the attributes of every drained event to the response, in order.  The gateway reads each
+/// event's attributes separately, as a new event begins at each event type attribute.
+///
+/// # Parameters
+///
+/// * `response` The response to which the attributes will be appended.  Any existing attributes,
+///   messages, and events on the response are retained.
+/// * `events` The events produced by [drain_events](self::PendingEvents::drain_events).
+pub fn emit_drained<T, I: IntoIterator<Item = OsGatewayEvent>>(
+    response: Response<T>,
+    events: I,
+) -> Response<T> {
+    events.into_iter().fold(response, |response, event| {
+        OsGatewayAttributeGenerator::from(event).add_to_response(response)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{emit_drained, PendingEvents};
+    use crate::{parse_gateway_event, AccessGrant, AccessRevoke, OsGatewayEvent};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::{Event, Response};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn events(count: usize) -> Vec<OsGatewayEvent> {
+        (0..count)
+            .map(|index| {
+                AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_access_grant_id(format!("grant-{index}"))
+                    .into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_events_drain_in_queued_order() {
+        let queue = PendingEvents::new("pending");
+        let mut storage = MockStorage::new();
+        for event in events(3) {
+            queue.queue_event(&mut storage, event).unwrap();
+        }
+        assert_eq!(
+            events(3),
+            queue.drain_events(&mut storage, 10).unwrap(),
+            "every event should be drained in the order it was queued",
+        );
+        assert!(
+            queue.is_empty(&storage).unwrap(),
+            "a complete drain should empty the queue",
+        );
+        assert!(
+            queue.drain_events(&mut storage, 10).unwrap().is_empty(),
+            "draining an empty queue should produce no events",
+        );
+    }
+
+    #[test]
+    fn test_partial_drains_leave_remaining_events() {
+        let queue = PendingEvents::new("pending");
+        let mut storage = MockStorage::new();
+        let queued = events(5);
+        for event in queued.clone() {
+            queue.queue_event(&mut storage, event).unwrap();
+        }
+        assert_eq!(
+            queued[..2].to_vec(),
+            queue.drain_events(&mut storage, 2).unwrap(),
+            "a partial drain should produce the oldest events",
+        );
+        assert_eq!(
+            3,
+            queue.len(&storage).unwrap(),
+            "the undrained events should remain queued",
+        );
+        queue
+            .queue_event(
+                &mut storage,
+                AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            )
+            .unwrap();
+        let mut remaining = queued[2..].to_vec();
+        remaining.push(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).into());
+        assert_eq!(
+            remaining,
+            queue.drain_events(&mut storage, 10).unwrap(),
+            "events queued after a partial drain should follow the remaining events",
+        );
+        assert!(
+            queue.drain_events(&mut storage, 0).unwrap().is_empty(),
+            "a limit of zero should drain nothing",
+        );
+    }
+
+    #[test]
+    fn test_namespaces_do_not_collide() {
+        let first = PendingEvents::new("first");
+        let second = PendingEvents::new("second");
+        let mut storage = MockStorage::new();
+        first
+            .queue_event(&mut storage, events(1).remove(0))
+            .unwrap();
+        assert!(
+            second.is_empty(&storage).unwrap(),
+            "a queue in another namespace should not see the event",
+        );
+        assert_eq!(1, first.len(&storage).unwrap());
+    }
+
+    #[test]
+    fn test_drained_events_are_emitted_in_order() {
+        let response: Response = emit_drained(Response::new(), events(2));
+        assert_eq!(
+            events(2).into_iter().map(Ok).collect::<Vec<_>>(),
+            parse_gateway_event(&Event::new("wasm").add_attributes(response.attributes)),
+            "each drained event should be parsed back from the response",
+        );
+    }
+}
@@ -0,0 +1,206 @@
+/// Implements the [cw-storage-plus](https://github.com/CosmWasm/cw-storage-plus) key traits for a
+/// validated string newtype, storing it as its string value.  The owned and borrowed forms can both
+/// be used as map keys and prefixes, and both load back as the owned form.
+///
+/// Stored keys are loaded without being validated again, as they were validated before they were
+/// stored.  This matches the handling of `Addr` by cw-storage-plus, and allows keys validated with
+/// non-default rules, such as [AccountAddress::new_with_hrps](crate::AccountAddress::new_with_hrps),
+/// to load.
+///
+/// The macro must be invoked in the module that declares the newtype, which has access to its
+/// private constructor.
+macro_rules! string_storage_key {
+    ($newtype:ident) => {
+        /// Stores the value as a single key component holding its string value.
+        impl<'a> cw_storage_plus::PrimaryKey<'a> for $newtype {
+            type Prefix = ();
+            type SubPrefix = ();
+            type Suffix = Self;
+            type SuperSuffix = Self;
+
+            fn key(&self) -> Vec<cw_storage_plus::Key<'_>> {
+                vec![cw_storage_plus::Key::Ref(self.0.as_bytes())]
+            }
+        }
+        impl<'a> cw_storage_plus::Prefixer<'a> for $newtype {
+            fn prefix(&self) -> Vec<cw_storage_plus::Key<'_>> {
+                vec![cw_storage_plus::Key::Ref(self.0.as_bytes())]
+            }
+        }
+        impl cw_storage_plus::KeyDeserialize for $newtype {
+            type Output = Self;
+
+            fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+                <String as cw_storage_plus::KeyDeserialize>::from_vec(value).map(Self)
+            }
+        }
+        impl cw_storage_plus::KeyDeserialize for &$newtype {
+            type Output = $newtype;
+
+            fn from_vec(value: Vec<u8>) -> cosmwasm_std::StdResult<Self::Output> {
+                <$newtype as cw_storage_plus::KeyDeserialize>::from_vec(value)
+            }
+        }
+    };
+}
+pub(crate) use string_storage_key;
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrantId, AccountAddress, GrantKey, ScopeAddress};
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Order;
+    use cw_storage_plus::Map;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const OTHER_ACCOUNT_ADDRESS: &str = "pb1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6dt62p";
+
+    fn scope(address: &str) -> ScopeAddress {
+        ScopeAddress::new(address).unwrap()
+    }
+
+    fn account(address: &str) -> AccountAddress {
+        AccountAddress::new(address).unwrap()
+    }
+
+    fn grant_id(access_grant_id: &str) -> AccessGrantId {
+        AccessGrantId::new(access_grant_id).unwrap()
+    }
+
+    #[test]
+    fn test_newtypes_store_and_load_as_keys() {
+        const SCOPES: Map<&ScopeAddress, u64> = Map::new("scopes");
+        const ACCOUNTS: Map<AccountAddress, u64> = Map::new("accounts");
+        const GRANT_IDS: Map<&AccessGrantId, u64> = Map::new("grant_ids");
+        let mut storage = MockStorage::new();
+        SCOPES
+            .save(&mut storage, &scope(SCOPE_ADDRESS), &1)
+            .unwrap();
+        ACCOUNTS
+            .save(&mut storage, account(ACCOUNT_ADDRESS), &2)
+            .unwrap();
+        GRANT_IDS
+            .save(&mut storage, &grant_id("my_unique_id"), &3)
+            .unwrap();
+        assert_eq!(
+            vec![(scope(SCOPE_ADDRESS), 1)],
+            SCOPES
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "a borrowed scope address key should load back as the owned address",
+        );
+        assert_eq!(
+            vec![account(ACCOUNT_ADDRESS)],
+            ACCOUNTS
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "an owned account address key should load back",
+        );
+        assert_eq!(
+            Some(3),
+            GRANT_IDS
+                .may_load(&storage, &grant_id("my_unique_id"))
+                .unwrap(),
+            "the value stored under an access grant id should be loaded",
+        );
+    }
+
+    #[test]
+    fn test_keys_load_without_default_validation() {
+        const ACCOUNTS: Map<&AccountAddress, ()> = Map::new("accounts");
+        let mut storage = MockStorage::new();
+        let address = AccountAddress::new_with_hrps(
+            "cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a",
+            &["cosmos"],
+        )
+        .unwrap();
+        ACCOUNTS.save(&mut storage, &address, &()).unwrap();
+        assert_eq!(
+            vec![address],
+            ACCOUNTS
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "an address validated with custom prefixes should load back",
+        );
+    }
+
+    #[test]
+    fn test_grants_are_iterable_by_scope() {
+        const GRANTS: Map<(&ScopeAddress, &AccountAddress, &AccessGrantId), u64> =
+            Map::new("grants");
+        let mut storage = MockStorage::new();
+        let grants = [
+            (SCOPE_ADDRESS, ACCOUNT_ADDRESS, "first"),
+            (SCOPE_ADDRESS, ACCOUNT_ADDRESS, "second"),
+            (SCOPE_ADDRESS, OTHER_ACCOUNT_ADDRESS, "first"),
+            (OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS, "first"),
+        ];
+        for (height, (scope_address, account_address, access_grant_id)) in grants.iter().enumerate()
+        {
+            GRANTS
+                .save(
+                    &mut storage,
+                    (
+                        &scope(scope_address),
+                        &account(account_address),
+                        &grant_id(access_grant_id),
+                    ),
+                    &(height as u64),
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            vec![
+                ((account(OTHER_ACCOUNT_ADDRESS), grant_id("first")), 2),
+                ((account(ACCOUNT_ADDRESS), grant_id("first")), 0),
+                ((account(ACCOUNT_ADDRESS), grant_id("second")), 1),
+            ],
+            GRANTS
+                .sub_prefix(&scope(SCOPE_ADDRESS))
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "every grant for the scope, and only that scope, should be iterated",
+        );
+        assert_eq!(
+            vec![grant_id("first"), grant_id("second")],
+            GRANTS
+                .prefix((&scope(SCOPE_ADDRESS), &account(ACCOUNT_ADDRESS)))
+                .keys(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "every grant for the scope and account should be iterated",
+        );
+    }
+
+    #[test]
+    fn test_grant_keys_prefix_composite_keys() {
+        const HISTORY: Map<(&GrantKey, u64), String> = Map::new("history");
+        let mut storage = MockStorage::new();
+        let key = GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, Some("first"));
+        let other_key = GrantKey::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None::<String>);
+        for (grant_key, height, action) in [
+            (&key, 2, "revoke"),
+            (&key, 1, "grant"),
+            (&other_key, 3, "grant"),
+        ] {
+            HISTORY
+                .save(&mut storage, (grant_key, height), &action.to_string())
+                .unwrap();
+        }
+        assert_eq!(
+            vec![(1, "grant".to_string()), (2, "revoke".to_string())],
+            HISTORY
+                .prefix(&key)
+                .range(&storage, None, None, Order::Ascending)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            "a grant key should prefix the entries stored beneath it",
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! [Proptest](https://github.com/proptest-rs/proptest) strategies that generate realistic gateway
+//! values, for property testing contracts that emit gateway events.
+//!
+//! Every address is a bech32 address with a correct checksum, scope addresses encode a scope
+//! [Metadata Address](https://docs.provenance.io/modules/metadata-module#metadata-addresses), and
+//! optional fields are present roughly half of the time.  Generated events hold no extra
+//! attributes, so they always pass [validation](crate::OsGatewayEvent::validate).
+//!
+//! ```
+//! use os_gateway_contract_attributes::strategies::gateway_event;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn events_are_valid(event in gateway_event()) {
+//!         prop_assert!(event.validate().is_ok());
+//!     }
+//! }
+//! # events_are_valid();
+//! ```
+
+use crate::{
+    scope_address_from_uuid, AccessGrant, AccessLevel, AccessRevoke, GatewayEventBatch,
+    GrantExpiration, OsGatewayEvent,
+};
+use bech32::{Bech32, Hrp};
+use proptest::collection::{vec, SizeRange};
+use proptest::option;
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+
+/// Generates the bech32 address of a scope with a uniformly random UUID.
+pub fn valid_scope_address() -> impl Strategy<Value = String> {
+    any::<[u8; 16]>().prop_map(|uuid| {
+        let uuid = uuid
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        // Every sixteen byte value is a valid UUID
+        scope_address_from_uuid(&uuid).expect("every sixteen byte value should encode as a scope")
+    })
+}
+
+/// Generates bech32 account addresses with the given human-readable part, holding either twenty
+/// bytes, as for accounts, or thirty-two bytes, as for contracts.  Only the `pb` and `tp` parts are
+/// accepted as [AccountAddresses](crate::AccountAddress).
+///
+/// # Parameters
+///
+/// * `hrp` The human-readable part of the addresses, such as `tp`.  Panics if the value is not a
+///   valid bech32 human-readable part.
+pub fn valid_account_address(hrp: &str) -> impl Strategy<Value = String> {
+    let hrp = Hrp::parse(hrp).expect("the human-readable part should be valid bech32");
+    prop_oneof![vec(any::<u8>(), 20), vec(any::<u8>(), 32)].prop_map(move |bytes| {
+        // The data is far shorter than the bech32 length limit
+        bech32::encode::<Bech32>(hrp, &bytes).expect("the account address should encode")
+    })
+}
+
+/// Generates valid [access grant ids](crate::AccessGrantId), from a single character up to the
+/// maximum length.
+pub fn access_grant_id() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9._:-]{1,128}"
+}
+
+/// Generates every kind of [GrantExpiration](crate::GrantExpiration).
+pub fn grant_expiration() -> impl Strategy<Value = GrantExpiration> {
+    prop_oneof![
+        Just(GrantExpiration::Never),
+        any::<u64>().prop_map(GrantExpiration::AtSeconds),
+        any::<u64>().prop_map(GrantExpiration::AtHeight),
+    ]
+}
+
+/// Generates every [AccessLevel](crate::AccessLevel).
+pub fn access_level() -> impl Strategy<Value = AccessLevel> {
+    prop_oneof![Just(AccessLevel::Read), Just(AccessLevel::ReadWrite)]
+}
+
+/// Generates account addresses with either of the human-readable parts accepted by the gateway.
+fn gateway_account_address() -> impl Strategy<Value = String> {
+    prop_oneof![valid_account_address("pb"), valid_account_address("tp")]
+}
+
+/// Generates valid [AccessGrants](crate::AccessGrant), with each optional field present roughly
+/// half of the time.
+pub fn access_grant() -> impl Strategy<Value = AccessGrant> {
+    (
+        valid_scope_address(),
+        gateway_account_address(),
+        option::of(access_grant_id()),
+        option::of(grant_expiration()),
+        option::of(access_level()),
+    )
+        .prop_map(
+            |(scope_address, target_account_address, access_grant_id, expiration, access_level)| {
+                AccessGrant {
+                    scope_address,
+                    target_account_address,
+                    access_grant_id,
+                    expiration,
+                    access_level,
+                    extra_attributes: BTreeMap::new(),
+                }
+            },
+        )
+}
+
+/// Generates valid [AccessRevokes](crate::AccessRevoke), with an access grant id present roughly
+/// half of the time.
+pub fn access_revoke() -> impl Strategy<Value = AccessRevoke> {
+    (
+        valid_scope_address(),
+        gateway_account_address(),
+        option::of(access_grant_id()),
+    )
+        .prop_map(
+            |(scope_address, target_account_address, access_grant_id)| AccessRevoke {
+                scope_address,
+                target_account_address,
+                access_grant_id,
+                extra_attributes: BTreeMap::new(),
+            },
+        )
+}
+
+/// Generates valid grants and revokes in equal proportion.
+pub fn gateway_event() -> impl Strategy<Value = OsGatewayEvent> {
+    prop_oneof![
+        access_grant().prop_map(OsGatewayEvent::from),
+        access_revoke().prop_map(OsGatewayEvent::from),
+    ]
+}
+
+/// Generates batches of independent events.  Because every address is random, the events of a
+/// batch almost never refer to one another; see [related_event_batch](self::related_event_batch)
+/// for batches whose revokes apply to their grants.
+///
+/// # Parameters
+///
+/// * `size` The range of the number of events in each batch, such as `0..10`.
+pub fn gateway_event_batch(size: impl Into<SizeRange>) -> impl Strategy<Value = GatewayEventBatch> {
+    vec(gateway_event(), size).prop_map(GatewayEventBatch::from_iter)
+}
+
+/// Generates batches of events drawn from two scopes, two target accounts, and three access grant
+/// ids, so that revokes frequently apply to earlier grants and grants frequently reuse ids.  These
+/// batches exercise [net_grants](crate::GatewayEventBatch::net_grants) and the duplicate id checks
+/// of [validate](crate::GatewayEventBatch::validate).
+///
+/// # Parameters
+///
+/// * `size` The range of the number of events in each batch, such as `0..10`.
+pub fn related_event_batch(size: impl Into<SizeRange>) -> impl Strategy<Value = GatewayEventBatch> {
+    let size = size.into();
+    (
+        vec(valid_scope_address(), 2),
+        vec(gateway_account_address(), 2),
+        vec(access_grant_id(), 3),
+    )
+        .prop_flat_map(
+            move |(scope_addresses, account_addresses, access_grant_ids)| {
+                let event = (
+                    any::<bool>(),
+                    proptest::sample::select(scope_addresses),
+                    proptest::sample::select(account_addresses),
+                    option::of(proptest::sample::select(access_grant_ids)),
+                )
+                    .prop_map(
+                        |(is_grant, scope_address, target_account_address, access_grant_id)| {
+                            if is_grant {
+                                AccessGrant::new(scope_address, target_account_address)
+                                    .with_optional_access_grant_id(access_grant_id)
+                                    .into()
+                            } else {
+                                AccessRevoke::new(scope_address, target_account_address)
+                                    .with_optional_access_grant_id(access_grant_id)
+                                    .into()
+                            }
+                        },
+                    );
+                vec(event, size.clone()).prop_map(GatewayEventBatch::from_iter)
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategies::{
+        gateway_event, gateway_event_batch, related_event_batch, valid_account_address,
+        valid_scope_address,
+    };
+    use crate::{
+        parse_gateway_event, parse_gateway_events, scope_uuid_from_address, AccountAddress,
+        GatewayEventBatch, OsGatewayAttributeGenerator, OsGatewayEvent, ScopeAddress,
+    };
+    use cosmwasm_std::Event;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_addresses_are_valid(
+            scope_address in valid_scope_address(),
+            account_address in valid_account_address("tp"),
+        ) {
+            prop_assert!(ScopeAddress::new(&scope_address).is_ok());
+            prop_assert!(scope_uuid_from_address(&scope_address).is_ok());
+            prop_assert!(AccountAddress::new(&account_address).is_ok());
+        }
+
+        #[test]
+        fn test_events_are_valid(event in gateway_event()) {
+            prop_assert_eq!(Ok(()), event.validate());
+        }
+
+        #[test]
+        fn test_events_round_trip_through_attributes(event in gateway_event()) {
+            let generator = OsGatewayAttributeGenerator::from(event.clone());
+            prop_assert_eq!(
+                vec![Ok(event.clone())],
+                parse_gateway_event(&Event::new("wasm").add_attributes(&generator)),
+            );
+            prop_assert_eq!(vec![Ok(event)], parse_gateway_event(&generator.to_event()));
+        }
+
+        #[test]
+        fn test_events_round_trip_through_compact_payloads(event in gateway_event()) {
+            let attribute = OsGatewayAttributeGenerator::from(event.clone()).to_compact_attribute();
+            prop_assert_eq!(
+                vec![Ok(event)],
+                parse_gateway_event(&Event::new("wasm").add_attributes([attribute])),
+            );
+        }
+
+        #[test]
+        fn test_events_round_trip_through_compact_strings(event in gateway_event()) {
+            prop_assert_eq!(
+                Ok(event.clone()),
+                OsGatewayEvent::from_compact_string(&event.to_compact_string()),
+            );
+        }
+
+        #[test]
+        fn test_batches_round_trip_through_events(batch in gateway_event_batch(0..8)) {
+            let events = batch
+                .events()
+                .iter()
+                .map(|event| OsGatewayAttributeGenerator::from(event.clone()).to_event())
+                .collect::<Vec<Event>>();
+            prop_assert_eq!(
+                Ok(batch),
+                parse_gateway_events(&events)
+                    .into_iter()
+                    .collect::<Result<GatewayEventBatch, _>>(),
+            );
+        }
+
+        #[test]
+        fn test_netting_is_idempotent(batch in related_event_batch(0..12)) {
+            let netted = batch.net_grants();
+            let renetted = netted
+                .iter()
+                .cloned()
+                .map(OsGatewayEvent::from)
+                .collect::<GatewayEventBatch>()
+                .net_grants();
+            prop_assert_eq!(netted, renetted);
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! Assertion helpers for unit tests of contracts that emit gateway events, which inspect the
+//! [Response](cosmwasm_std::Response) produced by an entry point rather than digging through its
+//! attributes by hand.  Every failed assertion prints each gateway attribute found in the
+//! response, so that a failure can be diagnosed without re-running the test.
+//!
+//! ```
+//! use cosmwasm_std::Response;
+//! use os_gateway_contract_attributes::test_utils::{assert_access_grant, ExpectedGrant};
+//! use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+//!
+//! let response: Response = Response::new().add_attributes(
+//!     OsGatewayAttributeGenerator::access_grant(
+//!         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!     )
+//!     .with_access_grant_id("generated-1"),
+//! );
+//! let grant = assert_access_grant(
+//!     &response,
+//!     ExpectedGrant::new(
+//!         "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+//!         "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+//!     )
+//!     .with_any_access_grant_id(),
+//! );
+//! assert_eq!(None, grant.access_level);
+//! ```
+
+use crate::attribute_keys::OS_GATEWAY_KEY_PREFIX;
+use crate::{parse_gateway_events, AccessGrant, AccessRevoke, ExpectedValue, OsGatewayEvent};
+use cosmwasm_std::{Attribute, Event, Response};
+
+/// The fields shared by the expectations of grants and revokes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Expectation {
+    scope_address: String,
+    target_account_address: String,
+    access_grant_id: ExpectedValue,
+}
+impl Expectation {
+    fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self {
+            scope_address: scope_address.into(),
+            target_account_address: target_account_address.into(),
+            access_grant_id: ExpectedValue::Absent,
+        }
+    }
+
+    fn matches(
+        &self,
+        scope_address: &str,
+        target_account_address: &str,
+        access_grant_id: Option<&str>,
+    ) -> bool {
+        self.scope_address == scope_address
+            && self.target_account_address == target_account_address
+            && self.access_grant_id.accepts(access_grant_id)
+    }
+}
+
+/// The access grant that [assert_access_grant](self::assert_access_grant) expects a response to
+/// contain.  By default, the grant must not hold an access grant id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedGrant(Expectation);
+impl ExpectedGrant {
+    /// Expects an access grant of the scope to the target account, without an access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope that the grant must refer to.
+    /// * `target_account_address` The bech32 address of the account that the grant must refer to.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self(Expectation::new(scope_address, target_account_address))
+    }
+
+    /// Expects the grant to hold exactly this access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The expected access grant id.
+    pub fn with_access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.0.access_grant_id = ExpectedValue::Value(access_grant_id.into());
+        self
+    }
+
+    /// Expects the grant to hold an access grant id, without regard to its value, such as an id
+    /// generated by the contract.
+    pub fn with_any_access_grant_id(mut self) -> Self {
+        self.0.access_grant_id = ExpectedValue::Any;
+        self
+    }
+}
+
+/// The access revoke that [assert_access_revoke](self::assert_access_revoke) expects a response
+/// to contain.  By default, the revoke must not hold an access grant id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedRevoke(Expectation);
+impl ExpectedRevoke {
+    /// Expects an access revoke of the target account's access to the scope, without an access
+    /// grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope that the revoke must refer to.
+    /// * `target_account_address` The bech32 address of the account that the revoke must refer to.
+    pub fn new<S1: Into<String>, S2: Into<String>>(
+        scope_address: S1,
+        target_account_address: S2,
+    ) -> Self {
+        Self(Expectation::new(scope_address, target_account_address))
+    }
+
+    /// Expects the revoke to hold exactly this access grant id.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The expected access grant id.
+    pub fn with_access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.0.access_grant_id = ExpectedValue::Value(access_grant_id.into());
+        self
+    }
+
+    /// Expects the revoke to hold an access grant id, without regard to its value.
+    pub fn with_any_access_grant_id(mut self) -> Self {
+        self.0.access_grant_id = ExpectedValue::Any;
+        self
+    }
+}
+
+/// Asserts that the response contains the expected access grant, whether its attributes were
+/// appended to the response or emitted in a custom event, and produces the first such grant so
+/// that its remaining fields can be checked.
+///
+/// Panics if any gateway event in the response cannot be parsed, as a contract under test should
+/// never emit one.
+///
+/// # Parameters
+///
+/// * `response` The response produced by the contract.
+/// * `expected` The expected grant.
+pub fn assert_access_grant<T>(response: &Response<T>, expected: ExpectedGrant) -> AccessGrant {
+    let ExpectedGrant(expected) = expected;
+    gateway_events(response)
+        .into_iter()
+        .find_map(|event| match event {
+            OsGatewayEvent::AccessGrant(grant)
+                if expected.matches(
+                    &grant.scope_address,
+                    &grant.target_account_address,
+                    grant.access_grant_id.as_deref(),
+                ) =>
+            {
+                Some(grant)
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an access grant of [{}] to [{}] with access grant id [{}]\n{}",
+                expected.scope_address,
+                expected.target_account_address,
+                expected.access_grant_id,
+                describe_gateway_attributes(response),
+            )
+        })
+}
+
+/// Asserts that the response contains the expected access revoke, whether its attributes were
+/// appended to the response or emitted in a custom event, and produces the first such revoke so
+/// that its remaining fields can be checked.
+///
+/// Panics if any gateway event in the response cannot be parsed, as a contract under test should
+/// never emit one.
+///
+/// # Parameters
+///
+/// * `response` The response produced by the contract.
+/// * `expected` The expected revoke.
+pub fn assert_access_revoke<T>(response: &Response<T>, expected: ExpectedRevoke) -> AccessRevoke {
+    let ExpectedRevoke(expected) = expected;
+    gateway_events(response)
+        .into_iter()
+        .find_map(|event| match event {
+            OsGatewayEvent::AccessRevoke(revoke)
+                if expected.matches(
+                    &revoke.scope_address,
+                    &revoke.target_account_address,
+                    revoke.access_grant_id.as_deref(),
+                ) =>
+            {
+                Some(revoke)
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "expected an access revoke of [{}] from [{}] with access grant id [{}]\n{}",
+                expected.target_account_address,
+                expected.scope_address,
+                expected.access_grant_id,
+                describe_gateway_attributes(response),
+            )
+        })
+}
+
+/// Produces the value of the only attribute with the given key, searching both the attributes
+/// appended to the response and those of its events.
+///
+/// Panics if the response holds no attribute with the key, or more than one.
+///
+/// # Parameters
+///
+/// * `response` The response produced by the contract.
+/// * `key` The full key of the attribute, such as a field of [OS_GATEWAY_KEYS](crate::OS_GATEWAY_KEYS).
+pub fn single_attribute_for_key<'a, T>(response: &'a Response<T>, key: &str) -> &'a str {
+    let values = all_attributes(response)
+        .filter(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.as_str())
+        .collect::<Vec<&str>>();
+    match values.as_slice() {
+        [value] => value,
+        _ => panic!(
+            "expected exactly one attribute with the key [{key}], but found {}\n{}",
+            values.len(),
+            describe_gateway_attributes(response),
+        ),
+    }
+}
+
+/// Parses every gateway event in the response, with the appended attributes first, followed by the
+/// events in the order in which they were added.
+fn gateway_events<T>(response: &Response<T>) -> Vec<OsGatewayEvent> {
+    let mut events = vec![Event::new("wasm").add_attributes(response.attributes.clone())];
+    events.extend(response.events.iter().cloned());
+    parse_gateway_events(&events)
+        .into_iter()
+        .map(|event| {
+            event.unwrap_or_else(|error| {
+                panic!(
+                    "a gateway event could not be parsed: {error}\n{}",
+                    describe_gateway_attributes(response),
+                )
+            })
+        })
+        .collect()
+}
+
+fn all_attributes<T>(response: &Response<T>) -> impl Iterator<Item = &Attribute> {
+    response
+        .attributes
+        .iter()
+        .chain(response.events.iter().flat_map(|event| &event.attributes))
+}
+
+/// Renders every gateway attribute in the response, beneath `attributes` for those appended to the
+/// response, or the type of the event that carried it.
+fn describe_gateway_attributes<T>(response: &Response<T>) -> String {
+    let describe = |attributes: &[Attribute]| {
+        attributes
+            .iter()
+            .filter(|attribute| attribute.key.starts_with(OS_GATEWAY_KEY_PREFIX))
+            .map(|attribute| format!("  {}={}\n", attribute.key, attribute.value))
+            .collect::<String>()
+    };
+    let described = [("attributes", describe(&response.attributes))]
+        .into_iter()
+        .chain(
+            response
+                .events
+                .iter()
+                .map(|event| (event.ty.as_str(), describe(&event.attributes))),
+        )
+        .filter(|(_, attributes)| !attributes.is_empty())
+        .map(|(source, attributes)| format!("{source}:\n{attributes}"))
+        .collect::<String>();
+    if described.is_empty() {
+        "no gateway attributes were found in the response".to_string()
+    } else {
+        format!("gateway attributes found in the response:\n{described}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{
+        assert_access_grant, assert_access_revoke, single_attribute_for_key, ExpectedGrant,
+        ExpectedRevoke,
+    };
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, OsGatewayAttributeGenerator, OS_GATEWAY_KEYS,
+    };
+    use cosmwasm_std::Response;
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn panic_message<F: FnOnce() + std::panic::UnwindSafe>(assertion: F) -> String {
+        let payload = std::panic::catch_unwind(assertion).expect_err("the assertion should fail");
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .expect("the panic should carry a formatted message")
+    }
+
+    #[test]
+    fn test_events_are_found_in_every_emission_form() {
+        let grant = OsGatewayAttributeGenerator::grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("generated-1")
+            .with_access_level(AccessLevel::Read);
+        let revoke =
+            OsGatewayAttributeGenerator::access_revoke(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        let response: Response = Response::new()
+            .add_attribute("action", "share")
+            .add_attributes(grant)
+            .add_event(revoke.to_event());
+        assert_eq!(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                .with_access_grant_id("generated-1")
+                .with_access_level(AccessLevel::Read),
+            assert_access_grant(
+                &response,
+                ExpectedGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_any_access_grant_id(),
+            ),
+            "the appended grant should be found with any access grant id",
+        );
+        assert_access_grant(
+            &response,
+            ExpectedGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("generated-1"),
+        );
+        assert_eq!(
+            AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            assert_access_revoke(
+                &response,
+                ExpectedRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+            ),
+            "the revoke emitted in a custom event should be found",
+        );
+    }
+
+    #[test]
+    fn test_single_attribute_for_key_searches_every_attribute() {
+        let response: Response = Response::new().add_attribute("action", "share").add_event(
+            OsGatewayAttributeGenerator::access_revoke(SCOPE_ADDRESS, ACCOUNT_ADDRESS).to_event(),
+        );
+        assert_eq!(
+            "share",
+            single_attribute_for_key(&response, "action"),
+            "an appended attribute should be found",
+        );
+        assert_eq!(
+            SCOPE_ADDRESS,
+            single_attribute_for_key(&response, OS_GATEWAY_KEYS.scope_address),
+            "an attribute of an event should be found",
+        );
+        let duplicated =
+            response
+                .clone()
+                .add_attributes(OsGatewayAttributeGenerator::access_grant(
+                    OTHER_SCOPE_ADDRESS,
+                    ACCOUNT_ADDRESS,
+                ));
+        assert!(
+            panic_message(|| {
+                single_attribute_for_key(&duplicated, OS_GATEWAY_KEYS.scope_address);
+            })
+            .starts_with(&format!(
+                "expected exactly one attribute with the key [{}], but found 2\n",
+                OS_GATEWAY_KEYS.scope_address,
+            )),
+            "a duplicated attribute should fail the assertion",
+        );
+        assert!(
+            panic_message(|| {
+                single_attribute_for_key(&response, OS_GATEWAY_KEYS.access_grant_id);
+            })
+            .contains("but found 0\n"),
+            "a missing attribute should fail the assertion",
+        );
+    }
+
+    #[test]
+    fn test_failures_describe_the_gateway_attributes_found() {
+        let response: Response = Response::new()
+            .add_attribute("action", "share")
+            .add_attributes(
+                OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .with_access_grant_id("generated-1"),
+            )
+            .add_event(
+                OsGatewayAttributeGenerator::access_revoke(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                    .to_event(),
+            );
+        assert_eq!(
+            format!(
+                concat!(
+                    "expected an access grant of [{scope}] to [{account}] with access grant id [<absent>]\n",
+                    "gateway attributes found in the response:\n",
+                    "attributes:\n",
+                    "  object_store_gateway_event_type=access_grant\n",
+                    "  object_store_gateway_scope_address={scope}\n",
+                    "  object_store_gateway_target_account_address={account}\n",
+                    "  object_store_gateway_access_grant_id=generated-1\n",
+                    "object_store_gateway:\n",
+                    "  object_store_gateway_event_type=access_revoke\n",
+                    "  object_store_gateway_scope_address={other_scope}\n",
+                    "  object_store_gateway_target_account_address={account}\n",
+                ),
+                scope = SCOPE_ADDRESS,
+                other_scope = OTHER_SCOPE_ADDRESS,
+                account = ACCOUNT_ADDRESS,
+            ),
+            panic_message(|| {
+                assert_access_grant(&response, ExpectedGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+            }),
+            "the failure should list every gateway attribute in the response",
+        );
+        assert!(
+            panic_message(|| {
+                assert_access_revoke(
+                    &response,
+                    ExpectedRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+                        .with_access_grant_id("generated-1"),
+                );
+            })
+            .starts_with(&format!(
+                "expected an access revoke of [{ACCOUNT_ADDRESS}] from [{OTHER_SCOPE_ADDRESS}] with access grant id [generated-1]\n"
+            )),
+            "a mismatched access grant id should fail the assertion",
+        );
+        assert!(
+            panic_message(|| {
+                assert_access_revoke(
+                    &Response::<cosmwasm_std::Empty>::new(),
+                    ExpectedRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+                );
+            })
+            .ends_with("no gateway attributes were found in the response"),
+            "an empty response should be described as such",
+        );
+    }
+
+    #[test]
+    fn test_malformed_events_fail_with_their_error() {
+        let response: Response = Response::new()
+            .add_attribute(OS_GATEWAY_KEYS.event_type, "access_grant")
+            .add_attribute(OS_GATEWAY_KEYS.scope_address, SCOPE_ADDRESS);
+        assert!(
+            panic_message(|| {
+                assert_access_grant(
+                    &response,
+                    ExpectedGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+                );
+            })
+            .starts_with("a gateway event could not be parsed: missing required"),
+            "a malformed gateway event should fail with its parse error",
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{parse_gateway_event, OsGatewayError, OsGatewayEvent};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{GetTxResponse, GetTxsEventResponse};
+use cosmwasm_std::{Attribute, Event};
+
+/// The attribute with which Cosmos SDK 0.50 and later tag each top-level transaction event with
+/// the index of the message that emitted it.
+const MSG_INDEX_KEY: &str = "msg_index";
+
+/// A gateway event found in a transaction's results, along with where it was found.
+///
+/// # Parameters
+///
+/// * `tx_hash` The uppercase hexadecimal hash of the transaction that emitted the event.
+///
+/// * `msg_index` The index of the message within the transaction that emitted the event.
+///
+/// * `event` The parsed gateway event, or the reason it could not be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxGatewayEvent {
+    pub tx_hash: String,
+    pub msg_index: u32,
+    pub event: Result<OsGatewayEvent, OsGatewayError>,
+}
+
+/// Parses every gateway event in the results of a transaction, as returned by the cosmos tx
+/// service, for backfilling history from gRPC.
+///
+/// Cosmos SDK releases before 0.50 group each message's events in the `logs` field, while later
+/// releases leave it empty and instead tag each top-level event with a `msg_index` attribute.  If
+/// any top-level event is tagged, the top-level events are used and the logs are ignored, so that
+/// a response populating both never produces an event twice.  Otherwise the logs are used, and the
+/// untagged top-level events, which repeat them, are ignored.  Events are produced in the order in
+/// which they were emitted.
+///
+/// ```
+/// use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{
+///     AbciMessageLog, Attribute, StringEvent, TxResponse,
+/// };
+/// use os_gateway_contract_attributes::{parse_tx_response, OsGatewayAttributeGenerator};
+///
+/// let tx_response = TxResponse {
+///     txhash: "A0E5C99C7AB8CB6FA6932434F1B7363141106DB699E39D64A34899B5CBAA2953".to_string(),
+///     logs: vec![AbciMessageLog {
+///         msg_index: 0,
+///         log: String::new(),
+///         events: vec![StringEvent {
+///             r#type: "wasm".to_string(),
+///             attributes: OsGatewayAttributeGenerator::access_grant(
+///                 "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+///                 "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+///             )
+///             .iter()
+///             .map(|(key, value)| Attribute { key: key.to_string(), value: value.to_string() })
+///             .collect(),
+///         }],
+///     }],
+///     ..TxResponse::default()
+/// };
+/// let events = parse_tx_response(&tx_response);
+/// assert_eq!(1, events.len());
+/// assert_eq!(0, events[0].msg_index);
+/// assert!(events[0].event.is_ok());
+/// ```
+///
+/// # Parameters
+///
+/// * `tx_response` The results of a single transaction.
+pub fn parse_tx_response(tx_response: &TxResponse) -> Vec<TxGatewayEvent> {
+    #[cfg(all(feature = "tracing", not(target_arch = "wasm32")))]
+    let _span =
+        crate::instrumentation::tx_span(&tx_response.txhash, Some(tx_response.height)).entered();
+    let indexed_events = tx_response
+        .events
+        .iter()
+        .filter_map(|event| {
+            let event = Event::new(&event.r#type).add_attributes(event.attributes.iter().map(
+                |attribute| Attribute {
+                    // Constructed directly, as chain-added keys like _contract_address are reserved
+                    key: String::from_utf8_lossy(&attribute.key).into_owned(),
+                    value: String::from_utf8_lossy(&attribute.value).into_owned(),
+                },
+            ));
+            let msg_index = event
+                .attributes
+                .iter()
+                .find(|attribute| attribute.key == MSG_INDEX_KEY)?
+                .value
+                .parse::<u32>()
+                .ok()?;
+            Some((msg_index, event))
+        })
+        .collect::<Vec<(u32, Event)>>();
+    let indexed_events = if indexed_events.is_empty() {
+        tx_response
+            .logs
+            .iter()
+            .flat_map(|log| {
+                log.events.iter().map(|event| {
+                    (
+                        log.msg_index,
+                        Event::new(&event.r#type).add_attributes(event.attributes.iter().map(
+                            |attribute| Attribute {
+                                key: attribute.key.clone(),
+                                value: attribute.value.clone(),
+                            },
+                        )),
+                    )
+                })
+            })
+            .collect()
+    } else {
+        indexed_events
+    };
+    indexed_events
+        .into_iter()
+        .flat_map(|(msg_index, event)| {
+            parse_gateway_event(&event)
+                .into_iter()
+                .map(move |event| TxGatewayEvent {
+                    tx_hash: tx_response.txhash.clone(),
+                    msg_index,
+                    event,
+                })
+        })
+        .collect()
+}
+
+/// Parses every gateway event in the response of the cosmos tx service's `GetTx` endpoint.  See
+/// [parse_tx_response](crate::parse_tx_response) for how the transaction's results are read.
+///
+/// # Parameters
+///
+/// * `response` The response of a `GetTx` request.
+pub fn parse_get_tx_response(response: &GetTxResponse) -> Vec<TxGatewayEvent> {
+    response
+        .tx_response
+        .as_ref()
+        .map(parse_tx_response)
+        .unwrap_or_default()
+}
+
+/// Parses every gateway event in the response of the cosmos tx service's `GetTxsEvent` endpoint,
+/// in the order of its transactions.  Query it with `tx.height=<height>` to backfill a whole block,
+/// as the `GetBlockWithTxs` endpoint returns the block's transactions without their results.  See
+/// [parse_tx_response](crate::parse_tx_response) for how each transaction's results are read.
+///
+/// # Parameters
+///
+/// * `response` The response of a `GetTxsEvent` request.
+pub fn parse_get_txs_event_response(response: &GetTxsEventResponse) -> Vec<TxGatewayEvent> {
+    response
+        .tx_responses
+        .iter()
+        .flat_map(parse_tx_response)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_get_tx_response, parse_get_txs_event_response, parse_tx_response, AccessGrant,
+        AccessRevoke, OsGatewayAttributeGenerator, OsGatewayError, OsGatewayEvent, TxGatewayEvent,
+    };
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{
+        AbciMessageLog, Attribute, StringEvent, TxResponse,
+    };
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{GetTxResponse, GetTxsEventResponse};
+    use cosmos_sdk_proto::tendermint::v0_34::abci::{Event, EventAttribute};
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+    const CONTRACT_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+    const TX_HASH: &str = "A0E5C99C7AB8CB6FA6932434F1B7363141106DB699E39D64A34899B5CBAA2953";
+    const OTHER_TX_HASH: &str = "EA9F2ED08D8CE1EA555DE3B1E6C47C7C43F659F4D6D6F3AF52CF74B4A24950D1";
+
+    fn grant() -> OsGatewayEvent {
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .into()
+    }
+
+    fn revoke() -> OsGatewayEvent {
+        AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS).into()
+    }
+
+    /// The attributes of the `wasm` event emitted by executing a contract that emits the event.
+    fn wasm_attributes(event: OsGatewayEvent) -> Vec<(String, String)> {
+        [("_contract_address", CONTRACT_ADDRESS), ("action", "share")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .chain(
+                OsGatewayAttributeGenerator::from(event)
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string())),
+            )
+            .collect()
+    }
+
+    fn string_event(ty: &str, attributes: Vec<(String, String)>) -> StringEvent {
+        StringEvent {
+            r#type: ty.to_string(),
+            attributes: attributes
+                .into_iter()
+                .map(|(key, value)| Attribute { key, value })
+                .collect(),
+        }
+    }
+
+    fn abci_event(ty: &str, attributes: Vec<(String, String)>, msg_index: Option<u32>) -> Event {
+        Event {
+            r#type: ty.to_string(),
+            attributes: attributes
+                .into_iter()
+                .chain(msg_index.map(|index| ("msg_index".to_string(), index.to_string())))
+                .map(|(key, value)| EventAttribute {
+                    key: key.into(),
+                    value: value.into(),
+                    index: true,
+                })
+                .collect(),
+        }
+    }
+
+    fn fee_event() -> Event {
+        abci_event(
+            "tx",
+            vec![("fee".to_string(), "1905000000nhash".to_string())],
+            None,
+        )
+    }
+
+    /// A transaction of two messages as returned by Cosmos SDK 0.47, which populates the logs and
+    /// repeats their events, untagged, at the top level.
+    fn sdk_047_response() -> TxResponse {
+        TxResponse {
+            txhash: TX_HASH.to_string(),
+            logs: vec![
+                AbciMessageLog {
+                    msg_index: 0,
+                    log: String::new(),
+                    events: vec![string_event("wasm", wasm_attributes(grant()))],
+                },
+                AbciMessageLog {
+                    msg_index: 1,
+                    log: String::new(),
+                    events: vec![string_event("wasm", wasm_attributes(revoke()))],
+                },
+            ],
+            events: vec![
+                fee_event(),
+                abci_event("wasm", wasm_attributes(grant()), None),
+                abci_event("wasm", wasm_attributes(revoke()), None),
+            ],
+            ..TxResponse::default()
+        }
+    }
+
+    /// The same transaction as returned by Cosmos SDK 0.50, which leaves the logs empty and tags the
+    /// top-level events of each message with its index.
+    fn sdk_050_response() -> TxResponse {
+        TxResponse {
+            txhash: TX_HASH.to_string(),
+            logs: vec![],
+            events: vec![
+                fee_event(),
+                abci_event("wasm", wasm_attributes(grant()), Some(0)),
+                abci_event("wasm", wasm_attributes(revoke()), Some(1)),
+            ],
+            ..TxResponse::default()
+        }
+    }
+
+    fn expected() -> Vec<TxGatewayEvent> {
+        vec![
+            TxGatewayEvent {
+                tx_hash: TX_HASH.to_string(),
+                msg_index: 0,
+                event: Ok(grant()),
+            },
+            TxGatewayEvent {
+                tx_hash: TX_HASH.to_string(),
+                msg_index: 1,
+                event: Ok(revoke()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_legacy_logs_are_parsed() {
+        assert_eq!(
+            expected(),
+            parse_tx_response(&sdk_047_response()),
+            "the events should be read from the logs, without the untagged top-level repeats",
+        );
+    }
+
+    #[test]
+    fn test_tagged_top_level_events_are_parsed() {
+        assert_eq!(
+            expected(),
+            parse_tx_response(&sdk_050_response()),
+            "the events should be read from the tagged top-level events",
+        );
+    }
+
+    #[test]
+    fn test_responses_populating_both_are_deduplicated() {
+        let response = TxResponse {
+            logs: sdk_047_response().logs,
+            ..sdk_050_response()
+        };
+        assert_eq!(
+            expected(),
+            parse_tx_response(&response),
+            "each event should be produced once when both fields are populated",
+        );
+    }
+
+    #[test]
+    fn test_malformed_events_keep_their_location() {
+        let response = TxResponse {
+            txhash: TX_HASH.to_string(),
+            events: vec![abci_event(
+                "wasm",
+                vec![(
+                    "object_store_gateway_event_type".to_string(),
+                    "access_grant".to_string(),
+                )],
+                Some(3),
+            )],
+            ..TxResponse::default()
+        };
+        let events = parse_tx_response(&response);
+        assert_eq!(1, events.len(), "the malformed event should be produced");
+        assert_eq!(
+            (TX_HASH, 3),
+            (events[0].tx_hash.as_str(), events[0].msg_index),
+            "the malformed event should retain its location",
+        );
+        assert!(
+            matches!(
+                events[0].event,
+                Err(OsGatewayError::MissingRequiredAttributes { .. }),
+            ),
+            "the malformed event should carry its error",
+        );
+    }
+
+    #[test]
+    fn test_service_responses_are_parsed() {
+        assert_eq!(
+            expected(),
+            parse_get_tx_response(&GetTxResponse {
+                tx: None,
+                tx_response: Some(sdk_050_response()),
+            }),
+            "the transaction's events should be parsed",
+        );
+        assert!(
+            parse_get_tx_response(&GetTxResponse::default()).is_empty(),
+            "a response without results should produce nothing",
+        );
+        let other = TxResponse {
+            txhash: OTHER_TX_HASH.to_string(),
+            ..sdk_047_response()
+        };
+        let events = parse_get_txs_event_response(&GetTxsEventResponse {
+            tx_responses: vec![sdk_050_response(), other],
+            ..GetTxsEventResponse::default()
+        });
+        assert_eq!(
+            vec![TX_HASH, TX_HASH, OTHER_TX_HASH, OTHER_TX_HASH],
+            events
+                .iter()
+                .map(|event| event.tx_hash.as_str())
+                .collect::<Vec<&str>>(),
+            "every transaction's events should be produced in order",
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayError};
+use std::collections::BTreeMap;
+
+/// A builder for [AccessGrant](crate::AccessGrant) values, for callers who prefer not to write
+/// struct literals.  Every field may be provided in any order, and the required scope address and
+/// target account are verified when the grant is built.
+///
+/// Unlike the [GrantBuilder](crate::GrantBuilder), which assembles attributes, this builder only
+/// produces the typed grant.
+///
+/// ```
+/// use os_gateway_contract_attributes::{AccessGrant, GrantExpiration};
+///
+/// let grant = AccessGrant::builder()
+///     .scope_address("scope1qzn7jghj8puprmdcvunm3330jutsj803zz")
+///     .target_account("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr")
+///     .access_grant_id("my_unique_id")
+///     .expiration(GrantExpiration::AtHeight(12345))
+///     .build()
+///     .expect("every required field should be provided");
+/// assert_eq!(Some("my_unique_id".to_string()), grant.access_grant_id);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessGrantBuilder {
+    scope_address: Option<String>,
+    target_account_address: Option<String>,
+    access_grant_id: Option<String>,
+    expiration: Option<GrantExpiration>,
+    access_level: Option<AccessLevel>,
+    extra_attributes: BTreeMap<String, String>,
+}
+impl AccessGrantBuilder {
+    /// Sets the bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which the grant refers.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    pub fn scope_address<S: Into<String>>(mut self, scope_address: S) -> Self {
+        self.scope_address = Some(scope_address.into());
+        self
+    }
+
+    /// Sets the bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// to which the grant refers.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_account_address` The bech32 address of the account.
+    pub fn target_account<S: Into<String>>(mut self, target_account_address: S) -> Self {
+        self.target_account_address = Some(target_account_address.into());
+        self
+    }
+
+    /// Sets the unique identifier with which the grant will be created.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant.
+    pub fn access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.access_grant_id = Some(access_grant_id.into());
+        self
+    }
+
+    /// Sets the point at which the grant stops being honored.
+    ///
+    /// # Parameters
+    ///
+    /// * `expiration` The point at which the grant expires.
+    pub fn expiration(mut self, expiration: GrantExpiration) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Sets the level of access that the grant gives.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_level` The level of access to grant.
+    pub fn access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = Some(access_level);
+        self
+    }
+
+    /// Adds an attribute that is not modeled by [AccessGrant](crate::AccessGrant).  See
+    /// [with_extra_attribute](crate::AccessGrant::with_extra_attribute).
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The value of the attribute.
+    pub fn extra_attribute<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        self.extra_attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Produces the grant, or an [OsGatewayError::MissingRequiredFields](crate::OsGatewayError::MissingRequiredFields)
+    /// naming every required field that was never provided.
+    pub fn build(self) -> Result<AccessGrant, OsGatewayError> {
+        match (self.scope_address, self.target_account_address) {
+            (Some(scope_address), Some(target_account_address)) => Ok(AccessGrant {
+                scope_address,
+                target_account_address,
+                access_grant_id: self.access_grant_id,
+                expiration: self.expiration,
+                access_level: self.access_level,
+                extra_attributes: self.extra_attributes,
+            }),
+            (scope_address, target_account_address) => {
+                Err(missing_fields(&scope_address, &target_account_address))
+            }
+        }
+    }
+}
+
+/// A builder for [AccessRevoke](crate::AccessRevoke) values, for callers who prefer not to write
+/// struct literals.  Every field may be provided in any order, and the required scope address and
+/// target account are verified when the revoke is built.
+///
+/// Unlike the [RevokeBuilder](crate::RevokeBuilder), which assembles attributes, this builder only
+/// produces the typed revoke.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessRevokeBuilder {
+    scope_address: Option<String>,
+    target_account_address: Option<String>,
+    access_grant_id: Option<String>,
+    extra_attributes: BTreeMap<String, String>,
+}
+impl AccessRevokeBuilder {
+    /// Sets the bech32 address of the [Provenance Blockchain Scope](https://docs.provenance.io/modules/metadata-module#metadata-scope)
+    /// to which the revoke refers.
+    ///
+    /// # Parameters
+    ///
+    /// * `scope_address` The bech32 address of the scope.
+    pub fn scope_address<S: Into<String>>(mut self, scope_address: S) -> Self {
+        self.scope_address = Some(scope_address.into());
+        self
+    }
+
+    /// Sets the bech32 address of the [Provenance Blockchain Account](https://docs.provenance.io/blockchain/basics/accounts)
+    /// to which the revoke refers.
+    ///
+    /// # Parameters
+    ///
+    /// * `target_account_address` The bech32 address of the account.
+    pub fn target_account<S: Into<String>>(mut self, target_account_address: S) -> Self {
+        self.target_account_address = Some(target_account_address.into());
+        self
+    }
+
+    /// Limits the revoke to the grant created with the given id.
+    ///
+    /// # Parameters
+    ///
+    /// * `access_grant_id` The unique identifier of the grant to revoke.
+    pub fn access_grant_id<S: Into<String>>(mut self, access_grant_id: S) -> Self {
+        self.access_grant_id = Some(access_grant_id.into());
+        self
+    }
+
+    /// Adds an attribute that is not modeled by [AccessRevoke](crate::AccessRevoke).  See
+    /// [with_extra_attribute](crate::AccessRevoke::with_extra_attribute).
+    ///
+    /// # Parameters
+    ///
+    /// * `key` The full key of the attribute.
+    /// * `value` The value of the attribute.
+    pub fn extra_attribute<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        key: S1,
+        value: S2,
+    ) -> Self {
+        self.extra_attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Produces the revoke, or an [OsGatewayError::MissingRequiredFields](crate::OsGatewayError::MissingRequiredFields)
+    /// naming every required field that was never provided.
+    pub fn build(self) -> Result<AccessRevoke, OsGatewayError> {
+        match (self.scope_address, self.target_account_address) {
+            (Some(scope_address), Some(target_account_address)) => Ok(AccessRevoke {
+                scope_address,
+                target_account_address,
+                access_grant_id: self.access_grant_id,
+                extra_attributes: self.extra_attributes,
+            }),
+            (scope_address, target_account_address) => {
+                Err(missing_fields(&scope_address, &target_account_address))
+            }
+        }
+    }
+}
+
+impl AccessGrant {
+    /// Creates an empty [AccessGrantBuilder](crate::AccessGrantBuilder).
+    pub fn builder() -> AccessGrantBuilder {
+        AccessGrantBuilder::default()
+    }
+}
+
+impl AccessRevoke {
+    /// Creates an empty [AccessRevokeBuilder](crate::AccessRevokeBuilder).
+    pub fn builder() -> AccessRevokeBuilder {
+        AccessRevokeBuilder::default()
+    }
+}
+
+fn missing_fields(
+    scope_address: &Option<String>,
+    target_account_address: &Option<String>,
+) -> OsGatewayError {
+    OsGatewayError::MissingRequiredFields {
+        fields: [
+            ("scope_address", scope_address.is_none()),
+            ("target_account_address", target_account_address.is_none()),
+        ]
+        .into_iter()
+        .filter(|(_, missing)| *missing)
+        .map(|(field, _)| field.to_string())
+        .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccessGrant, AccessRevoke, GrantExpiration, OsGatewayError};
+
+    #[test]
+    fn test_grant_builder_populates_every_field() {
+        assert_eq!(
+            Ok(AccessGrant::new("scope", "target")
+                .with_access_grant_id("id")
+                .with_expiration(GrantExpiration::Never)
+                .with_extra_attribute("object_store_gateway_memo", "memo")),
+            AccessGrant::builder()
+                .expiration(GrantExpiration::Never)
+                .target_account("target")
+                .extra_attribute("object_store_gateway_memo", "memo")
+                .access_grant_id("id")
+                .scope_address("scope")
+                .build(),
+            "the builder should populate every provided field, in any order",
+        );
+    }
+
+    #[test]
+    fn test_revoke_builder_populates_every_field() {
+        assert_eq!(
+            Ok(AccessRevoke::new("scope", "target").with_access_grant_id("id")),
+            AccessRevoke::builder()
+                .scope_address("scope")
+                .target_account("target")
+                .access_grant_id("id")
+                .build(),
+            "the builder should populate every provided field",
+        );
+    }
+
+    #[test]
+    fn test_builders_name_missing_fields() {
+        assert_eq!(
+            Err(OsGatewayError::MissingRequiredFields {
+                fields: vec![
+                    "scope_address".to_string(),
+                    "target_account_address".to_string(),
+                ],
+            }),
+            AccessGrant::builder().access_grant_id("id").build(),
+            "every missing required field should be named",
+        );
+        assert_eq!(
+            Err(OsGatewayError::MissingRequiredFields {
+                fields: vec!["target_account_address".to_string()],
+            }),
+            AccessRevoke::builder().scope_address("scope").build(),
+            "only the missing required field should be named",
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{AccessGrant, AccessRevoke, OsGatewayError, OS_GATEWAY_EVENT_TYPES, OS_GATEWAY_KEYS};
+use serde::de::{Error, MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+
+/// The schema version of the layout written by this release.
+pub(crate) const SCHEMA_VERSION: u32 = 2;
+
+/// The schema version assumed for values that do not name one.
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+pub(crate) const SCHEMA_VERSION_FIELD: &str = "schema_version";
+const SCOPE_ADDRESS_FIELD: &str = "scope_address";
+const TARGET_ACCOUNT_ADDRESS_FIELD: &str = "target_account_address";
+const ACCESS_GRANT_ID_FIELD: &str = "access_grant_id";
+const EXPIRATION_FIELD: &str = "expiration";
+const ACCESS_LEVEL_FIELD: &str = "access_level";
+const EXTRA_ATTRIBUTES_FIELD: &str = "extra_attributes";
+
+/// The short field names of the typed events, paired with the attribute keys that version 1
+/// accepted in their place.
+const FIELD_KEYS: [(&str, &str); 5] = [
+    (SCOPE_ADDRESS_FIELD, OS_GATEWAY_KEYS.scope_address),
+    (TARGET_ACCOUNT_ADDRESS_FIELD, OS_GATEWAY_KEYS.target_account),
+    (ACCESS_GRANT_ID_FIELD, OS_GATEWAY_KEYS.access_grant_id),
+    (EXPIRATION_FIELD, OS_GATEWAY_KEYS.expiration),
+    (ACCESS_LEVEL_FIELD, OS_GATEWAY_KEYS.access_level),
+];
+
+/// Every field modeled by [AccessGrant](crate::AccessGrant).
+const GRANT_FIELDS: [&str; 5] = [
+    SCOPE_ADDRESS_FIELD,
+    TARGET_ACCOUNT_ADDRESS_FIELD,
+    ACCESS_GRANT_ID_FIELD,
+    EXPIRATION_FIELD,
+    ACCESS_LEVEL_FIELD,
+];
+
+/// Every field modeled by [AccessRevoke](crate::AccessRevoke).
+const REVOKE_FIELDS: [&str; 3] = [
+    SCOPE_ADDRESS_FIELD,
+    TARGET_ACCOUNT_ADDRESS_FIELD,
+    ACCESS_GRANT_ID_FIELD,
+];
+
+/// Serializes the grant in the layout of the current schema version: a structure of its short
+/// field names, led by a `schema_version` field.
+impl serde::Serialize for AccessGrant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_typed(
+            serializer,
+            "AccessGrant",
+            &[
+                (SCOPE_ADDRESS_FIELD, Some(self.scope_address.to_owned())),
+                (
+                    TARGET_ACCOUNT_ADDRESS_FIELD,
+                    Some(self.target_account_address.to_owned()),
+                ),
+                (ACCESS_GRANT_ID_FIELD, self.access_grant_id.to_owned()),
+                (
+                    EXPIRATION_FIELD,
+                    self.expiration.map(|expiration| expiration.to_string()),
+                ),
+                (
+                    ACCESS_LEVEL_FIELD,
+                    self.access_level
+                        .map(|access_level| access_level.as_str().to_string()),
+                ),
+            ],
+            &self.extra_attributes,
+        )
+    }
+}
+/// Deserializes the grant from the layout of any schema version, rejecting values that do not
+/// describe a complete access grant.
+///
+/// Every layout written by an earlier release remains readable: a value is migrated one version at
+/// a time from the schema version that it was written with up to the current version.  Contracts
+/// may therefore persist grants and revokes in their state and continue to read them after
+/// upgrading this crate.  A value written by a newer release, with a schema version that this
+/// release does not know, is rejected rather than misread.  The schema versions are:
+///
+/// 1. Every layout written before the `schema_version` field was introduced, which is assumed
+///    when the field is absent: a flat map keyed by attribute keys, such as
+///    `object_store_gateway_scope_address`, by short field names, such as `scope_address`, or by a
+///    mix of the two.  Keys that the type does not model are read as extra attributes.
+/// 2. The current layout: `schema_version`, followed by `scope_address`, `target_account_address`,
+///    the optional `access_grant_id`, `expiration`, and `access_level` of grants, and the extra
+///    attributes nested under `extra_attributes` when there are any.  Unknown fields are rejected.
+impl<'de> serde::Deserialize<'de> for AccessGrant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = deserializer
+            .deserialize_map(SerializedFieldsVisitor)?
+            .migrate(OS_GATEWAY_EVENT_TYPES.access_grant, &GRANT_FIELDS)
+            .map_err(D::Error::custom)?;
+        let (scope_address, target_account_address) =
+            fields.required_fields().map_err(D::Error::custom)?;
+        Ok(Self {
+            access_grant_id: fields.fields.remove(ACCESS_GRANT_ID_FIELD),
+            expiration: fields
+                .fields
+                .remove(EXPIRATION_FIELD)
+                .map(|expiration| expiration.parse())
+                .transpose()
+                .map_err(D::Error::custom)?,
+            access_level: fields
+                .fields
+                .remove(ACCESS_LEVEL_FIELD)
+                .map(|access_level| access_level.parse())
+                .transpose()
+                .map_err(D::Error::custom)?,
+            extra_attributes: fields.extra_attributes,
+            ..Self::new(scope_address, target_account_address)
+        })
+    }
+}
+/// Serializes the revoke in the layout of the current schema version: a structure of its short
+/// field names, led by a `schema_version` field.
+impl serde::Serialize for AccessRevoke {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_typed(
+            serializer,
+            "AccessRevoke",
+            &[
+                (SCOPE_ADDRESS_FIELD, Some(self.scope_address.to_owned())),
+                (
+                    TARGET_ACCOUNT_ADDRESS_FIELD,
+                    Some(self.target_account_address.to_owned()),
+                ),
+                (ACCESS_GRANT_ID_FIELD, self.access_grant_id.to_owned()),
+            ],
+            &self.extra_attributes,
+        )
+    }
+}
+/// Deserializes the revoke from the layout of any schema version, rejecting values that do not
+/// describe a complete access revoke.  Layouts are migrated in the same manner as
+/// [AccessGrant](crate::AccessGrant).
+impl<'de> serde::Deserialize<'de> for AccessRevoke {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = deserializer
+            .deserialize_map(SerializedFieldsVisitor)?
+            .migrate(OS_GATEWAY_EVENT_TYPES.access_revoke, &REVOKE_FIELDS)
+            .map_err(D::Error::custom)?;
+        let (scope_address, target_account_address) =
+            fields.required_fields().map_err(D::Error::custom)?;
+        Ok(Self {
+            access_grant_id: fields.fields.remove(ACCESS_GRANT_ID_FIELD),
+            extra_attributes: fields.extra_attributes,
+            ..Self::new(scope_address, target_account_address)
+        })
+    }
+}
+
+/// Serializes a typed event in the current layout: the schema version, the present fields, and
+/// the extra attributes when there are any.
+fn serialize_typed<S: serde::Serializer>(
+    serializer: S,
+    name: &'static str,
+    fields: &[(&'static str, Option<String>)],
+    extra_attributes: &BTreeMap<String, String>,
+) -> Result<S::Ok, S::Error> {
+    // Some formats write the number of fields up front, so it must be exact
+    let len = 1
+        + fields.iter().filter(|(_, value)| value.is_some()).count()
+        + usize::from(!extra_attributes.is_empty());
+    let mut state = serializer.serialize_struct(name, len)?;
+    state.serialize_field(SCHEMA_VERSION_FIELD, &SCHEMA_VERSION)?;
+    for (field, value) in fields {
+        if let Some(value) = value {
+            state.serialize_field(field, value)?;
+        }
+    }
+    if !extra_attributes.is_empty() {
+        state.serialize_field(EXTRA_ATTRIBUTES_FIELD, extra_attributes)?;
+    }
+    state.end()
+}
+
+/// The fields of a serialized typed event, as written by any schema version.
+#[derive(Default)]
+struct SerializedFields {
+    schema_version: Option<u32>,
+    fields: BTreeMap<String, String>,
+    extra_attributes: BTreeMap<String, String>,
+}
+impl SerializedFields {
+    /// Migrates the fields one schema version at a time, from the version they were written with
+    /// to the current version, then verifies that every remaining field is modeled by the event.
+    fn migrate(mut self, event_type: &str, modeled_fields: &[&str]) -> Result<Self, String> {
+        loop {
+            self = match self.schema_version.unwrap_or(UNVERSIONED_SCHEMA_VERSION) {
+                1 => self.migrate_v1(event_type, modeled_fields)?,
+                SCHEMA_VERSION => break,
+                version => {
+                    return Err(format!(
+                        "unsupported schema version [{version}], as the newest supported version is [{SCHEMA_VERSION}]"
+                    ))
+                }
+            };
+        }
+        match self
+            .fields
+            .keys()
+            .find(|field| !modeled_fields.contains(&field.as_str()))
+        {
+            Some(field) => Err(format!("unknown field `{field}`")),
+            None => Ok(self),
+        }
+    }
+
+    /// Migrates fields written before the schema version was introduced, as schema version 1.
+    /// Attribute keys are renamed to their short field names, the event type attribute is verified
+    /// and dropped, and every key that the event does not model becomes an extra attribute, as it
+    /// did when the fields were read as attributes.
+    fn migrate_v1(self, event_type: &str, modeled_fields: &[&str]) -> Result<Self, String> {
+        let mut fields = BTreeMap::new();
+        let mut extra_attributes = BTreeMap::new();
+        for (key, value) in self.fields {
+            if key == OS_GATEWAY_KEYS.event_type {
+                if value != event_type {
+                    return Err(OsGatewayError::UnexpectedEventType {
+                        expected: event_type.to_string(),
+                        actual: value,
+                    }
+                    .to_string());
+                }
+                continue;
+            }
+            let field_key = FIELD_KEYS
+                .into_iter()
+                .find(|(field, attribute_key)| *field == key || *attribute_key == key);
+            match field_key {
+                Some((field, _)) if modeled_fields.contains(&field) => {
+                    if fields.insert(field.to_string(), value).is_some() {
+                        return Err(format!("the field [{field}] is provided more than once"));
+                    }
+                }
+                // Fields that this kind of event does not model were always attributes
+                Some((_, attribute_key)) => {
+                    extra_attributes.insert(attribute_key.to_string(), value);
+                }
+                None => {
+                    extra_attributes.insert(key, value);
+                }
+            }
+        }
+        extra_attributes.extend(self.extra_attributes);
+        Ok(Self {
+            schema_version: Some(2),
+            fields,
+            extra_attributes,
+        })
+    }
+
+    /// Removes the scope address and target account address, which every event requires.
+    fn required_fields(&mut self) -> Result<(String, String), String> {
+        let mut take = |field: &str| {
+            self.fields
+                .remove(field)
+                .ok_or_else(|| format!("missing field `{field}`"))
+        };
+        Ok((
+            take(SCOPE_ADDRESS_FIELD)?,
+            take(TARGET_ACCOUNT_ADDRESS_FIELD)?,
+        ))
+    }
+}
+
+/// Collects the fields of a serialized typed event without interpreting them, as the layout is not
+/// known until the schema version, which may appear anywhere, has been read.
+struct SerializedFieldsVisitor;
+impl<'de> Visitor<'de> for SerializedFieldsVisitor {
+    type Value = SerializedFields;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("a map describing a gateway event")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut serialized = SerializedFields::default();
+        let mut extra_attributes = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                SCHEMA_VERSION_FIELD => {
+                    if serialized.schema_version.is_some() {
+                        return Err(A::Error::duplicate_field(SCHEMA_VERSION_FIELD));
+                    }
+                    serialized.schema_version = Some(map.next_value()?);
+                }
+                EXTRA_ATTRIBUTES_FIELD => {
+                    if extra_attributes.is_some() {
+                        return Err(A::Error::duplicate_field(EXTRA_ATTRIBUTES_FIELD));
+                    }
+                    extra_attributes = Some(map.next_value()?);
+                }
+                _ => {
+                    let value = map.next_value::<String>()?;
+                    if serialized.fields.insert(key.clone(), value).is_some() {
+                        return Err(A::Error::custom(format!(
+                            "the field [{key}] is provided more than once"
+                        )));
+                    }
+                }
+            }
+        }
+        serialized.extra_attributes = extra_attributes.unwrap_or_default();
+        Ok(serialized)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{GatewayEventBatch, OsGatewayEvent};
+
+impl OsGatewayEvent {
+    /// Renders the event as YAML for human review, as a mapping of the attribute keys that it emits
+    /// to their values.  The keys are written in alphabetical order, so the same event always
+    /// renders identically, and no comments are written.
+    ///
+    /// ```
+    /// use os_gateway_contract_attributes::{AccessRevoke, OsGatewayEvent};
+    ///
+    /// let event = OsGatewayEvent::from(AccessRevoke::new(
+    ///     "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+    ///     "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    /// ));
+    /// assert_eq!(
+    ///     concat!(
+    ///         "object_store_gateway_event_type: access_revoke\n",
+    ///         "object_store_gateway_scope_address: scope1qzn7jghj8puprmdcvunm3330jutsj803zz\n",
+    ///         "object_store_gateway_target_account_address: tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr\n",
+    ///     ),
+    ///     event.to_yaml().expect("the event should render"),
+    /// );
+    /// ```
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Reads an event from the YAML produced by [to_yaml](self::OsGatewayEvent::to_yaml),
+    /// rejecting YAML that does not describe a complete event of a recognized type.
+    ///
+    /// # Parameters
+    ///
+    /// * `yaml` The YAML to read.
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
+
+impl GatewayEventBatch {
+    /// Renders the batch as YAML for human review, as a sequence of its events in order, each
+    /// rendered as by [OsGatewayEvent::to_yaml](crate::OsGatewayEvent::to_yaml).
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self.events())
+    }
+
+    /// Reads a batch from the YAML produced by [to_yaml](self::GatewayEventBatch::to_yaml),
+    /// rejecting YAML in which any event is incomplete or of an unrecognized type.
+    ///
+    /// # Parameters
+    ///
+    /// * `yaml` The YAML to read.
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str::<Vec<OsGatewayEvent>>(yaml).map(GatewayEventBatch::from_iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccessGrant, AccessLevel, AccessRevoke, GatewayEventBatch, GrantExpiration, OsGatewayEvent,
+    };
+
+    const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+    const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+    fn full_grant() -> AccessGrant {
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .with_expiration(GrantExpiration::AtHeight(100))
+            .with_access_level(AccessLevel::ReadWrite)
+            .with_extra_attribute("object_store_gateway_note", "shared: with team")
+    }
+
+    #[test]
+    fn test_event_renders_in_stable_order() {
+        let yaml = OsGatewayEvent::from(full_grant()).to_yaml().unwrap();
+        assert_eq!(
+            format!(
+                concat!(
+                    "object_store_gateway_access_grant_id: my_unique_id\n",
+                    "object_store_gateway_access_level: read_write\n",
+                    "object_store_gateway_event_type: access_grant\n",
+                    "object_store_gateway_expiration: height:100\n",
+                    "object_store_gateway_note: 'shared: with team'\n",
+                    "object_store_gateway_scope_address: {}\n",
+                    "object_store_gateway_target_account_address: {}\n",
+                ),
+                SCOPE_ADDRESS, ACCOUNT_ADDRESS,
+            ),
+            yaml,
+            "the event should render as a sorted mapping without comments",
+        );
+    }
+
+    #[test]
+    fn test_event_round_trips() {
+        for event in [
+            OsGatewayEvent::from(full_grant()),
+            OsGatewayEvent::from(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+        ] {
+            assert_eq!(
+                Ok(event.clone()),
+                OsGatewayEvent::from_yaml(&event.to_yaml().unwrap()).map_err(|e| e.to_string()),
+                "the event should survive a YAML round trip",
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_round_trips() {
+        let mut batch = GatewayEventBatch::new();
+        batch.push(full_grant());
+        batch.push(AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS));
+        let yaml = batch.to_yaml().unwrap();
+        assert!(
+            yaml.starts_with("- object_store_gateway_access_grant_id: my_unique_id\n"),
+            "the batch should render as a sequence of events, but rendered:\n{yaml}",
+        );
+        assert_eq!(
+            batch,
+            GatewayEventBatch::from_yaml(&yaml).unwrap(),
+            "the batch should survive a YAML round trip in order",
+        );
+        assert_eq!(
+            GatewayEventBatch::new(),
+            GatewayEventBatch::from_yaml(&GatewayEventBatch::new().to_yaml().unwrap()).unwrap(),
+            "an empty batch should survive a YAML round trip",
+        );
+    }
+
+    #[test]
+    fn test_incomplete_events_are_rejected() {
+        assert!(
+            OsGatewayEvent::from_yaml("object_store_gateway_event_type: access_grant\n").is_err(),
+            "an event without its required attributes should be rejected",
+        );
+        assert!(
+            GatewayEventBatch::from_yaml("- object_store_gateway_event_type: bogus\n").is_err(),
+            "a batch holding an unrecognized event type should be rejected",
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Checks canonical byte production against the vectors shared with services written in other
+//! languages, which must produce identical bytes from the same file.
+
+use os_gateway_contract_attributes::OsGatewayAttributeGenerator;
+use serde_json::Value;
+
+fn vectors() -> Value {
+    serde_json::from_str(include_str!("conformance/canonical_bytes.json"))
+        .expect("the canonical bytes vectors should parse")
+}
+
+fn string(value: &Value) -> String {
+    value
+        .as_str()
+        .unwrap_or_else(|| panic!("expected a string but found {value}"))
+        .to_string()
+}
+
+#[test]
+fn test_canonical_bytes_vectors() {
+    let vectors = vectors();
+    for vector in vectors["vectors"].as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        let generator = OsGatewayAttributeGenerator::from_attributes_lenient(
+            vector["attributes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|attribute| (string(&attribute["key"]), string(&attribute["value"]))),
+        )
+        .unwrap_or_else(|error| panic!("[{name}] the attributes should assemble: {error}"));
+        let actual = generator
+            .canonical_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        assert_eq!(
+            string(&vector["canonical_hex"]),
+            actual,
+            "[{name}] should match its vector",
+        );
+    }
+}
+
+#[test]
+fn test_canonicalization_version_matches_bytes() {
+    let vectors = vectors();
+    let prefix = format!("{}\n", string(&vectors["canonicalization"]));
+    for vector in vectors["vectors"].as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        assert!(
+            string(&vector["canonical_hex"]).starts_with(
+                &prefix
+                    .bytes()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>()
+            ),
+            "[{name}] should begin with the canonicalization version line",
+        );
+    }
+}
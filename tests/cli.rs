@@ -0,0 +1,153 @@
+#![cfg(feature = "cli")]
+
+//! Runs the `os-gateway-attrs` binary end to end, exercising the public API that it is built on.
+
+use os_gateway_contract_attributes::{AccessGrant, OsGatewayAttributeGenerator};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+/// Runs the binary with the arguments, writing the input to its standard input.
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_os-gateway-attrs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the binary should start");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().expect("the binary should finish")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn generator() -> OsGatewayAttributeGenerator {
+    OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+        .with_access_grant_id("my_unique_id")
+}
+
+#[test]
+fn test_generate_prints_every_attribute() {
+    let args = [
+        "generate",
+        "grant",
+        "--scope",
+        SCOPE_ADDRESS,
+        "--target",
+        ACCOUNT_ADDRESS,
+        "--grant-id",
+        "my_unique_id",
+    ];
+    let output = run(&args, "");
+    assert!(output.status.success(), "generation should succeed");
+    assert_eq!(
+        generator(),
+        serde_json::from_str(&stdout(&output)).unwrap(),
+        "the JSON should describe the generator's attributes",
+    );
+    let output = run(&[&args[..], &["--format", "kv"]].concat(), "");
+    assert_eq!(
+        generator()
+            .iter()
+            .map(|(key, value)| format!("{key}={value}\n"))
+            .collect::<String>(),
+        stdout(&output),
+        "the key-value lines should follow emission order",
+    );
+}
+
+#[test]
+fn test_parse_prints_typed_events_and_reports_errors() {
+    let generated = stdout(&run(
+        &[
+            "generate",
+            "grant",
+            "--scope",
+            SCOPE_ADDRESS,
+            "--target",
+            ACCOUNT_ADDRESS,
+            "--grant-id",
+            "my_unique_id",
+        ],
+        "",
+    ));
+    let output = run(&["parse"], &generated);
+    assert!(output.status.success(), "parsing should succeed");
+    assert_eq!(
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("my_unique_id"),
+        serde_json::from_str(&stdout(&output)).unwrap(),
+        "the generated attributes should parse back into the grant",
+    );
+
+    let events = serde_json::to_string(&[generator().to_event(), generator().to_event()]).unwrap();
+    let output = run(&["parse"], &events);
+    assert_eq!(
+        2,
+        stdout(&output).lines().count(),
+        "every emitted event should be parsed",
+    );
+
+    let output = run(
+        &["parse"],
+        r#"{"object_store_gateway_event_type": "access_grant"}"#,
+    );
+    assert_eq!(Some(1), output.status.code(), "a parse failure should fail");
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("missing_required_attributes: "),
+        "the failure should be reported with its code",
+    );
+}
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let valid = serde_json::to_string(&generator()).unwrap();
+    let output = run(&["validate"], &format!("[{valid}]"));
+    assert!(output.status.success(), "a valid payload should pass");
+    assert_eq!(
+        "[0] valid\n",
+        stdout(&output),
+        "the payload should be valid"
+    );
+
+    let output = run(
+        &["validate"],
+        &format!(
+            r#"{{
+                "object_store_gateway_event_type": "access_grant",
+                "object_store_gateway_scope_address": "not_a_scope",
+                "object_store_gateway_target_account_address": "{ACCOUNT_ADDRESS}",
+                "object_store_gateway_access_grant_id": "has space"
+            }}"#
+        ),
+    );
+    assert_eq!(
+        Some(1),
+        output.status.code(),
+        "an invalid payload should fail"
+    );
+    assert_eq!(
+        2,
+        stdout(&output).lines().count(),
+        "both problems should be reported, not [{}]",
+        stdout(&output),
+    );
+
+    let output = run(&["validate", "--from-json", "/nonexistent.json"], "");
+    assert_eq!(
+        Some(2),
+        output.status.code(),
+        "an unreadable input should be distinguished from an invalid payload",
+    );
+}
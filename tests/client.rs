@@ -0,0 +1,196 @@
+#![cfg(feature = "client")]
+
+//! Confirms grants against a mock gateway REST API.
+
+use os_gateway_contract_attributes::client::{ClientError, GatewayClient, GRANTS_PATH};
+use os_gateway_contract_attributes::AccessGrant;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+const GRANTER_ADDRESS: &str = "tp1a882cz6l2j0875940s87mv9ksez2vr6rj8xguztx6lrwkwcmr58qpt7cd9";
+
+/// A response listing a single grant of the scope to the account.
+fn grant_response(grant_id: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "grants": [{
+            "scopeAddress": SCOPE_ADDRESS,
+            "granteeAddress": ACCOUNT_ADDRESS,
+            "granterAddress": GRANTER_ADDRESS,
+            "grantId": grant_id,
+        }],
+    }))
+}
+
+fn client(server: &MockServer) -> GatewayClient {
+    GatewayClient::new(server.uri())
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(40))
+}
+
+#[tokio::test]
+async fn test_existing_grant_is_confirmed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .and(query_param("scopeAddress", SCOPE_ADDRESS))
+        .and(query_param("granteeAddress", ACCOUNT_ADDRESS))
+        .respond_with(grant_response("my_unique_id"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let confirmation = client(&server)
+        .confirm_grant(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+            Some("my_unique_id"),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("the grant should be confirmed");
+    assert_eq!(
+        AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("my_unique_id"),
+        confirmation.grant,
+        "the gateway's grant should be mapped into a typed grant",
+    );
+    assert_eq!(
+        Some(GRANTER_ADDRESS),
+        confirmation.granter_address.as_deref(),
+        "the granter should be reported",
+    );
+    assert_eq!(1, confirmation.attempts, "a single request should suffice");
+}
+
+#[tokio::test]
+async fn test_grant_is_polled_until_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(grant_response("other_id"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(grant_response("my_unique_id"))
+        .mount(&server)
+        .await;
+    let confirmation = client(&server)
+        .confirm_grant(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+            Some("my_unique_id"),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("the grant should be confirmed once it appears");
+    assert_eq!(
+        4, confirmation.attempts,
+        "not-found responses and other grants should be retried",
+    );
+}
+
+#[tokio::test]
+async fn test_missing_grant_times_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    match client(&server)
+        .confirm_grant(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+            None,
+            Duration::from_millis(200),
+        )
+        .await
+    {
+        Err(ClientError::Timeout {
+            attempts,
+            last_status,
+            last_body,
+        }) => {
+            assert!(
+                attempts > 1,
+                "the grant should be polled more than once before timing out, not [{attempts}] times",
+            );
+            assert_eq!(
+                (None, None),
+                (last_status, last_body),
+                "no server error should be reported when the gateway never failed",
+            );
+        }
+        other => panic!("the confirmation should time out, not produce {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_reports_last_server_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(ResponseTemplate::new(500).set_body_string("database unavailable"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(ResponseTemplate::new(503).set_body_string("overloaded"))
+        .mount(&server)
+        .await;
+    let error = client(&server)
+        .confirm_grant(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS,
+            None,
+            Duration::from_millis(200),
+        )
+        .await
+        .expect_err("the confirmation should time out");
+    assert!(
+        matches!(
+            &error,
+            ClientError::Timeout {
+                last_status: Some(503),
+                last_body: Some(body),
+                ..
+            } if body == "overloaded",
+        ),
+        "the last server error should be reported, not {error:?}",
+    );
+    assert!(
+        error.to_string().ends_with("status [503]: overloaded"),
+        "the server error should be described, not: {error}",
+    );
+}
+
+#[tokio::test]
+async fn test_client_errors_end_polling() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(GRANTS_PATH))
+        .respond_with(ResponseTemplate::new(401).set_body_string("unauthorized"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    match client(&server)
+        .confirm_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS, None, Duration::from_secs(5))
+        .await
+    {
+        Err(ClientError::UnexpectedStatus { status, body }) => {
+            assert_eq!(401, status, "the status should be reported");
+            assert_eq!("unauthorized", body, "the body should be reported");
+        }
+        other => panic!("the confirmation should fail immediately, not produce {other:?}"),
+    }
+}
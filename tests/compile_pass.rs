@@ -0,0 +1,9 @@
+#![cfg(feature = "cosmwasm_1")]
+
+#[test]
+fn test_responses_with_custom_messages_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/pass/cosmos_msg_response.rs");
+    #[cfg(feature = "provwasm")]
+    cases.pass("tests/pass/provwasm_response.rs");
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "ffi")]
+
+//! Checks the bindings against the conformance vectors shared with services written in other
+//! languages, which must produce identical results from the same file.
+
+use os_gateway_contract_attributes::ffi::{
+    parse_event, validate_grant, GatewayAttribute, GatewayError, GatewayEvent,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn vectors() -> Value {
+    serde_json::from_str(include_str!("conformance/vectors.json"))
+        .expect("the conformance vectors should parse")
+}
+
+fn string(value: &Value) -> String {
+    value
+        .as_str()
+        .unwrap_or_else(|| panic!("expected a string but found {value}"))
+        .to_string()
+}
+
+fn optional_string(value: Option<&Value>) -> Option<String> {
+    value.filter(|value| !value.is_null()).map(string)
+}
+
+fn event_json(event: GatewayEvent) -> Value {
+    let extras = |extra_attributes: HashMap<String, String>| {
+        Value::Object(
+            extra_attributes
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)))
+                .collect(),
+        )
+    };
+    match event {
+        GatewayEvent::AccessGrant {
+            scope_address,
+            target_account_address,
+            access_grant_id,
+            expiration,
+            access_level,
+            extra_attributes,
+        } => json!({
+            "type": "access_grant",
+            "scope_address": scope_address,
+            "target_account_address": target_account_address,
+            "access_grant_id": access_grant_id,
+            "expiration": expiration,
+            "access_level": access_level,
+            "extra_attributes": extras(extra_attributes),
+        }),
+        GatewayEvent::AccessRevoke {
+            scope_address,
+            target_account_address,
+            access_grant_id,
+            extra_attributes,
+        } => json!({
+            "type": "access_revoke",
+            "scope_address": scope_address,
+            "target_account_address": target_account_address,
+            "access_grant_id": access_grant_id,
+            "extra_attributes": extras(extra_attributes),
+        }),
+    }
+}
+
+#[test]
+fn test_parse_event_vectors() {
+    let vectors = vectors();
+    for vector in vectors["parse_event"].as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        let attributes = vector["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|attribute| GatewayAttribute {
+                key: string(&attribute["key"]),
+                value: string(&attribute["value"]),
+            })
+            .collect();
+        let actual = match parse_event(attributes) {
+            Ok(event) => json!({ "event": event_json(event) }),
+            Err(GatewayError::Rejected { code, .. }) => json!({ "error": code }),
+            Err(error) => {
+                panic!("[{name}] parsing should not validate fields, but produced {error}")
+            }
+        };
+        assert_eq!(
+            vector["expected"], actual,
+            "[{name}] should match its vector"
+        );
+    }
+}
+
+#[test]
+fn test_validate_grant_vectors() {
+    let vectors = vectors();
+    for vector in vectors["validate_grant"].as_array().unwrap() {
+        let name = vector["name"].as_str().unwrap();
+        let grant = &vector["grant"];
+        let field_errors = match validate_grant(
+            string(&grant["scope_address"]),
+            string(&grant["target_account_address"]),
+            optional_string(grant.get("access_grant_id")),
+            optional_string(grant.get("expiration")),
+            optional_string(grant.get("access_level")),
+        ) {
+            Ok(()) => vec![],
+            Err(GatewayError::InvalidFields { errors }) => errors
+                .into_iter()
+                .map(|error| json!({ "key": error.key, "code": error.code }))
+                .collect(),
+            Err(error) => {
+                panic!("[{name}] validation should only report fields, but produced {error}")
+            }
+        };
+        assert_eq!(
+            vector["expected"],
+            json!({ "field_errors": field_errors }),
+            "[{name}] should match its vector",
+        );
+    }
+}
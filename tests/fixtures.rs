@@ -0,0 +1,18 @@
+#![cfg(all(feature = "fixtures", feature = "serde"))]
+
+//! Checks that the exported fixture corpus shared with services written in other languages matches
+//! the corpus shipped with the crate, so that every parser runs against the same cases.
+
+use os_gateway_contract_attributes::fixtures;
+use serde_json::Value;
+
+#[test]
+fn test_exported_corpus_matches_crate() {
+    let exported: Value = serde_json::from_str(include_str!("conformance/fixtures.json"))
+        .expect("the exported fixture corpus should parse");
+    assert_eq!(
+        exported,
+        serde_json::to_value(fixtures::all()).unwrap(),
+        "conformance/fixtures.json should be regenerated from fixtures::all()",
+    );
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "listener")]
+
+//! Replays synthetic websocket frames through a mock Tendermint RPC client, exercising the gateway
+//! event stream from subscription through decoding.  The frames are hand-written in the CometBFT
+//! 0.37 subscription format rather than captured from a node, so they do not cover quirks that
+//! only appear in real traffic.
+
+use futures::StreamExt;
+use os_gateway_contract_attributes::listener::{
+    gateway_custom_event_query, gateway_event_query, subscribe_gateway_events, ListenError,
+};
+use os_gateway_contract_attributes::{AccessGrant, AccessRevoke, OsGatewayError, OsGatewayEvent};
+use tendermint_rpc::event::{v0_37, Event};
+use tendermint_rpc::{MockClient, MockRequestMethodMatcher, Response};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+/// Decodes a hand-written websocket frame in the CometBFT 0.37 subscription format.
+fn frame(name: &str) -> Event {
+    let path = format!("{}/tests/listener/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    v0_37::DeEvent::from_string(std::fs::read(path).expect("the frame should be readable"))
+        .expect("the frame should be a subscription event")
+        .into()
+}
+
+#[tokio::test]
+async fn test_synthetic_frames_are_streamed() {
+    let (client, driver) = MockClient::new(MockRequestMethodMatcher::default());
+    let driver = tokio::spawn(driver.run());
+    let flat = subscribe_gateway_events(&client, gateway_event_query())
+        .await
+        .expect("the flat subscription should be created");
+    let custom = subscribe_gateway_events(&client, gateway_custom_event_query())
+        .await
+        .expect("the custom subscription should be created");
+    for name in ["flat_grant", "flat_malformed", "custom_revoke"] {
+        client.publish(&frame(name));
+    }
+    client.close();
+    driver.await.unwrap().unwrap();
+
+    let flat = flat.collect::<Vec<_>>().await;
+    assert_eq!(3, flat.len(), "every gateway event should be streamed");
+    let (tx_hash, event) = flat[0].as_ref().expect("the grant should be decoded");
+    assert_eq!(
+        "A0E5C99C7AB8CB6FA6932434F1B7363141106DB699E39D64A34899B5CBAA2953",
+        tx_hash.to_string(),
+        "the grant should be paired with its transaction",
+    );
+    assert_eq!(
+        &OsGatewayEvent::from(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("my_unique_id"),
+        ),
+        event,
+        "the grant should be decoded",
+    );
+    match &flat[1] {
+        Err(ListenError::InvalidEvent { tx_hash, error }) => {
+            assert_eq!(
+                "38DA9EDE5CE50F16968B1AB55CA0329F6BDBBE9FD69DB208544D2A2A2CACA63E",
+                tx_hash.to_string(),
+                "the error should name the transaction that contained the event",
+            );
+            assert!(
+                matches!(error, OsGatewayError::MissingRequiredAttributes { .. }),
+                "the error should describe the malformed event",
+            );
+        }
+        other => panic!("the malformed event should be surfaced as an error, not {other:?}"),
+    }
+    assert_eq!(
+        &OsGatewayEvent::from(AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS)),
+        &flat[2].as_ref().expect("the revoke should be decoded").1,
+        "the stream should continue past the malformed event",
+    );
+
+    let custom = custom.collect::<Vec<_>>().await;
+    assert_eq!(
+        vec![OsGatewayEvent::from(AccessRevoke::new(
+            SCOPE_ADDRESS,
+            ACCOUNT_ADDRESS
+        ))],
+        custom
+            .into_iter()
+            .map(|item| item.expect("the revoke should be decoded").1)
+            .collect::<Vec<_>>(),
+        "the custom event should only be streamed to the custom subscription",
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "msgpack")]
+
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_std_2 as cosmwasm_std;
+
+use os_gateway_contract_attributes::{
+    AccessGrant, AccessLevel, AccessRevoke, FieldError, FieldErrorKind, GrantExpiration,
+    OsGatewayEvent, OsGatewayKey, OsGatewayMsgpack,
+};
+use std::collections::BTreeMap;
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+fn representative_grant() -> OsGatewayEvent {
+    AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+        .with_access_grant_id("4c1d5e3a-9d1f-4f5b-8a0e-2b6f0c7d9e1a")
+        .with_expiration(GrantExpiration::AtHeight(12_345_678))
+        .with_access_level(AccessLevel::Read)
+        .into()
+}
+
+#[test]
+fn test_events_round_trip() {
+    let events = [
+        representative_grant(),
+        AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+            .with_access_grant_id("my_unique_id")
+            .into(),
+    ];
+    for event in events {
+        assert_eq!(
+            event,
+            OsGatewayEvent::from_msgpack(&event.to_msgpack()).unwrap(),
+            "the event should be unchanged after a MessagePack round trip",
+        );
+    }
+}
+
+#[test]
+fn test_encoding_is_smaller_than_json() {
+    let event = representative_grant();
+    let msgpack = event.to_msgpack().len();
+    let json = cosmwasm_std::to_json_vec(&event).unwrap().len();
+    // The full attribute keys dominate both encodings, so MessagePack only saves the JSON
+    // punctuation, which is roughly 4% of a representative grant (376 bytes against 393)
+    assert!(
+        msgpack * 100 <= json * 96,
+        "the MessagePack encoding ({msgpack} bytes) should be at least 4% smaller than JSON ({json} bytes)",
+    );
+}
+
+#[test]
+fn test_unknown_fields_do_not_fail_decoding() {
+    let mut attributes = event_attributes(&representative_grant());
+    attributes.insert(
+        "object_store_gateway_future_field".to_string(),
+        "value".to_string(),
+    );
+    let bytes = rmp_serde::to_vec_named(&attributes).unwrap();
+    let OsGatewayEvent::AccessGrant(grant) = OsGatewayEvent::from_msgpack(&bytes).unwrap() else {
+        panic!("the event should decode as a grant");
+    };
+    assert_eq!(
+        Some("value"),
+        grant
+            .extra_attributes
+            .get("object_store_gateway_future_field")
+            .map(String::as_str),
+        "an attribute from a newer release should be retained as an extra attribute",
+    );
+    let mut fields = BTreeMap::new();
+    fields.insert("key", OsGatewayKey::ScopeAddress.as_str());
+    fields.insert("kind", "empty");
+    fields.insert("value_preview", "");
+    fields.insert("future_field", "value");
+    assert_eq!(
+        FieldError::new(OsGatewayKey::ScopeAddress, FieldErrorKind::Empty, ""),
+        FieldError::from_msgpack(&rmp_serde::to_vec_named(&fields).unwrap()).unwrap(),
+        "an unrecognized field of a structure should be skipped",
+    );
+}
+
+fn event_attributes(event: &OsGatewayEvent) -> BTreeMap<String, String> {
+    rmp_serde::from_slice(&event.to_msgpack()).unwrap()
+}
@@ -0,0 +1,115 @@
+#![cfg(all(feature = "multi-test", feature = "serde"))]
+
+//! Exercises the cw-multi-test assertion helpers end to end against a contract that emits the
+//! gateway events it is asked to.
+
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use os_gateway_contract_attributes::multi_test::{
+    assert_access_grant, assert_access_revoke, assert_no_gateway_events, gateway_events,
+};
+use os_gateway_contract_attributes::{
+    AccessGrant, AccessLevel, AccessRevoke, GrantExpiration, OsGatewayAttributeGenerator,
+    OsGatewayEvent, OsGatewayResponseExt,
+};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const OTHER_SCOPE_ADDRESS: &str = "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> Result<Response, StdError> {
+    Ok(Response::new())
+}
+
+/// Emits every requested event as flat attributes, alongside an unrelated attribute.
+fn execute(
+    _: DepsMut,
+    _: Env,
+    _: MessageInfo,
+    events: Vec<OsGatewayEvent>,
+) -> Result<Response, StdError> {
+    Ok(events.into_iter().fold(
+        Response::new().add_attribute("action", "share"),
+        |response, event| response.add_os_gateway_event(OsGatewayAttributeGenerator::from(event)),
+    ))
+}
+
+fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    Ok(Binary::default())
+}
+
+/// Stores and instantiates the contract, producing the app and the contract's address.
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "gateway emitter",
+            None,
+        )
+        .unwrap();
+    (app, contract)
+}
+
+#[test]
+fn test_helpers_find_emitted_events() {
+    let (mut app, contract) = setup();
+    let grant = AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+        .with_access_grant_id("my_unique_id")
+        .with_expiration(GrantExpiration::AtHeight(100))
+        .with_access_level(AccessLevel::Read);
+    let revoke = AccessRevoke::new(OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    let response = app
+        .execute_contract(
+            Addr::unchecked("sender"),
+            contract,
+            &vec![
+                OsGatewayEvent::from(grant.clone()),
+                OsGatewayEvent::from(revoke.clone()),
+            ],
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        grant,
+        assert_access_grant(&response, SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+        "the emitted grant should be found",
+    );
+    assert_eq!(
+        revoke,
+        assert_access_revoke(&response, OTHER_SCOPE_ADDRESS, ACCOUNT_ADDRESS),
+        "the emitted revoke should be found",
+    );
+    assert_eq!(
+        vec![OsGatewayEvent::from(grant), OsGatewayEvent::from(revoke)],
+        gateway_events(&response),
+        "every emitted event should be parsed in order",
+    );
+}
+
+#[test]
+fn test_helpers_report_missing_events() {
+    let (mut app, contract) = setup();
+    let response = app
+        .execute_contract(
+            Addr::unchecked("sender"),
+            contract,
+            &Vec::<OsGatewayEvent>::new(),
+            &[],
+        )
+        .unwrap();
+    assert_no_gateway_events(&response);
+    assert!(
+        std::panic::catch_unwind(|| {
+            assert_access_grant(&response, SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+        })
+        .is_err(),
+        "a grant that was never emitted should fail the assertion",
+    );
+}
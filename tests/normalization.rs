@@ -0,0 +1,82 @@
+#![cfg(feature = "parsing")]
+
+//! Parses the gateway events of an authz `MsgExec` transaction, whose log merges the `wasm` events
+//! of every nested contract execution into one.
+//!
+//! The `authz_exec` fixture is hand-built in the shape of a testnet `GetTx` response, and its
+//! transaction hash and contract addresses are made up.  No testnet transaction has been captured
+//! for it yet, so this test only covers the merged layout as documented, not as observed on chain.
+//! The fixture should be replaced with a captured authz-exec transaction.
+
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_std_2 as cosmwasm_std;
+
+use cosmwasm_std::Event;
+use os_gateway_contract_attributes::{
+    normalize_wasm_events, parse_gateway_events, parse_gateway_events_ref, AccessGrant,
+    OsGatewayError, OsGatewayEvent,
+};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+/// Decodes the events of the first message log of a hand-built transaction response.
+fn log_events(name: &str) -> Vec<Event> {
+    let path = format!(
+        "{}/tests/normalization/{name}.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let response: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(path).expect("the response should be readable"))
+            .expect("the response should be json");
+    serde_json::from_value(response["tx_response"]["logs"][0]["events"].clone())
+        .expect("the log should contain events")
+}
+
+#[test]
+fn test_nested_grants_are_parsed() {
+    let events = log_events("authz_exec");
+    let normalized = normalize_wasm_events(&events);
+    assert_eq!(
+        vec!["execute", "message", "wasm", "wasm"],
+        normalized
+            .iter()
+            .map(|event| event.ty.as_str())
+            .collect::<Vec<&str>>(),
+        "the merged wasm event should be split into one event per contract",
+    );
+    let results = parse_gateway_events(&events);
+    assert_eq!(
+        2,
+        results.len(),
+        "each contract should produce its own result",
+    );
+    assert!(
+        matches!(
+            results[0],
+            Err(OsGatewayError::MissingRequiredAttributes { .. })
+        ),
+        "the stray attribute should be reported on its own, not {:?}",
+        results[0],
+    );
+    assert_eq!(
+        Ok(OsGatewayEvent::AccessGrant(
+            AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS).with_access_grant_id("servicing"),
+        )),
+        results[1],
+        "the grant of the nested contract should be parsed",
+    );
+    assert_eq!(
+        results,
+        parse_gateway_events(&normalized),
+        "parsing should accept the normalized form",
+    );
+    assert_eq!(
+        results,
+        parse_gateway_events_ref(&events)
+            .into_iter()
+            .map(|result| result.map(|event| event.to_owned()))
+            .collect::<Vec<Result<OsGatewayEvent, OsGatewayError>>>(),
+        "borrowed parsing should normalize the merged event exactly as owned parsing does",
+    );
+}
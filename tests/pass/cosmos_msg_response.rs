@@ -0,0 +1,41 @@
+use cosmwasm_std::{BankMsg, CosmosMsg, Empty, Response};
+use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayResponseExt};
+
+/// A chain-specific message, which needs none of the bounds that cosmwasm places on messages it
+/// serializes.
+struct ChainMsg;
+
+fn generator() -> OsGatewayAttributeGenerator {
+    OsGatewayAttributeGenerator::access_grant(
+        "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+        "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    )
+}
+
+fn empty_response() -> Response {
+    Response::new()
+        .add_message(BankMsg::Burn { amount: vec![] })
+        .add_os_gateway_event(generator())
+}
+
+fn custom_response() -> Response<ChainMsg> {
+    let response = generator()
+        .add_to_response(Response::new())
+        .add_message(CosmosMsg::Custom(ChainMsg));
+    generator().apply_to(response).add_attributes(generator())
+}
+
+fn explicit_cosmos_msg_response() -> Response<Empty> {
+    Response::new()
+        .add_messages(vec![CosmosMsg::<Empty>::Bank(BankMsg::Burn { amount: vec![] })])
+        .add_os_gateway_revoke(
+            "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+            "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+        )
+}
+
+fn main() {
+    assert_eq!(3, empty_response().attributes.len());
+    assert_eq!(9, custom_response().attributes.len());
+    assert_eq!(3, explicit_cosmos_msg_response().attributes.len());
+}
@@ -0,0 +1,40 @@
+use cosmwasm_std::{Addr, Response, StdResult};
+use os_gateway_contract_attributes::{OsGatewayAttributeGenerator, OsGatewayResponseExt};
+use provwasm_std::{bind_name, NameBinding, ProvenanceMsg};
+
+fn generator() -> OsGatewayAttributeGenerator {
+    OsGatewayAttributeGenerator::access_grant(
+        "scope1qzn7jghj8puprmdcvunm3330jutsj803zz",
+        "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr",
+    )
+}
+
+fn bind() -> StdResult<cosmwasm_std::CosmosMsg<ProvenanceMsg>> {
+    bind_name(
+        "gateway.pb",
+        Addr::unchecked("tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr"),
+        NameBinding::Restricted,
+    )
+}
+
+fn extension_response() -> StdResult<Response<ProvenanceMsg>> {
+    Ok(Response::new()
+        .add_os_gateway_event(generator())
+        .add_message(bind()?))
+}
+
+fn generator_response() -> StdResult<Response<ProvenanceMsg>> {
+    Ok(generator().add_to_response(Response::new().add_message(bind()?)))
+}
+
+fn attributes_response() -> StdResult<Response<ProvenanceMsg>> {
+    Ok(Response::new().add_message(bind()?).add_attributes(generator()))
+}
+
+fn main() {
+    for response in [extension_response(), generator_response(), attributes_response()] {
+        let response = response.unwrap();
+        assert_eq!(1, response.messages.len());
+        assert_eq!(3, response.attributes.len());
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "serde")]
+
+//! Loads grants and revokes persisted in the layout of every historical schema version, which must
+//! remain readable after every upgrade of the crate.
+
+use os_gateway_contract_attributes::{AccessGrant, AccessLevel, AccessRevoke, GrantExpiration};
+use serde_json::{json, Value};
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+
+fn expected_grant() -> AccessGrant {
+    AccessGrant::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+        .with_access_grant_id("my_unique_id")
+        .with_expiration(GrantExpiration::AtHeight(100))
+        .with_access_level(AccessLevel::Read)
+        .with_extra_attribute("object_store_gateway_note", "shared")
+}
+
+fn expected_revoke() -> AccessRevoke {
+    AccessRevoke::new(SCOPE_ADDRESS, ACCOUNT_ADDRESS)
+        .with_access_grant_id("my_unique_id")
+        .with_extra_attribute("object_store_gateway_note", "shared")
+}
+
+#[test]
+fn test_every_grant_fixture_loads() {
+    for (name, fixture) in [
+        (
+            "v1_grant_attribute_keys",
+            include_str!("schema_versions/v1_grant_attribute_keys.json"),
+        ),
+        (
+            "v1_grant_field_names",
+            include_str!("schema_versions/v1_grant_field_names.json"),
+        ),
+        ("v2_grant", include_str!("schema_versions/v2_grant.json")),
+    ] {
+        assert_eq!(
+            expected_grant(),
+            serde_json::from_str::<AccessGrant>(fixture)
+                .unwrap_or_else(|error| panic!("[{name}] should load: {error}")),
+            "[{name}] should load as the same grant",
+        );
+    }
+}
+
+#[test]
+fn test_every_revoke_fixture_loads() {
+    for (name, fixture) in [
+        (
+            "v1_revoke_attribute_keys",
+            include_str!("schema_versions/v1_revoke_attribute_keys.json"),
+        ),
+        ("v2_revoke", include_str!("schema_versions/v2_revoke.json")),
+    ] {
+        assert_eq!(
+            expected_revoke(),
+            serde_json::from_str::<AccessRevoke>(fixture)
+                .unwrap_or_else(|error| panic!("[{name}] should load: {error}")),
+            "[{name}] should load as the same revoke",
+        );
+    }
+}
+
+#[test]
+fn test_current_layout_matches_newest_fixtures() {
+    for (actual, fixture) in [
+        (
+            serde_json::to_value(expected_grant()).unwrap(),
+            include_str!("schema_versions/v2_grant.json"),
+        ),
+        (
+            serde_json::to_value(expected_revoke()).unwrap(),
+            include_str!("schema_versions/v2_revoke.json"),
+        ),
+    ] {
+        assert_eq!(
+            serde_json::from_str::<Value>(fixture).unwrap(),
+            actual,
+            "a new schema version requires a new fixture, and the old fixtures must be kept",
+        );
+    }
+}
+
+#[test]
+fn test_unsupported_layouts_are_rejected() {
+    let mut future =
+        serde_json::from_str::<Value>(include_str!("schema_versions/v2_grant.json")).unwrap();
+    future["schema_version"] = json!(3);
+    let error = serde_json::from_value::<AccessGrant>(future)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("unsupported schema version [3]"),
+        "a grant written by a newer release should be rejected, but produced: {error}",
+    );
+    let mut unknown_field =
+        serde_json::from_str::<Value>(include_str!("schema_versions/v2_grant.json")).unwrap();
+    unknown_field["object_store_gateway_note"] = json!("shared");
+    assert!(
+        serde_json::from_value::<AccessGrant>(unknown_field).is_err(),
+        "the current layout should nest extra attributes rather than accept unknown fields",
+    );
+    assert!(
+        serde_json::from_str::<AccessRevoke>(include_str!(
+            "schema_versions/v1_grant_attribute_keys.json"
+        ))
+        .is_err(),
+        "a persisted grant should not load as a revoke",
+    );
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "tracing")]
+
+//! Captures the output of a `tracing-subscriber` formatter while parsing and validating gateway
+//! events, asserting that the structured fields reach it.
+
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_std_2 as cosmwasm_std;
+
+use cosmwasm_std::Event;
+use os_gateway_contract_attributes::instrumentation::tx_span;
+use os_gateway_contract_attributes::{parse_gateway_events, OsGatewayAttributeGenerator};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::MakeWriter;
+
+const SCOPE_ADDRESS: &str = "scope1qzn7jghj8puprmdcvunm3330jutsj803zz";
+const ACCOUNT_ADDRESS: &str = "tp12vu3ww5tfta78fl3fvehacunrud4gtqqcpfwnr";
+const TX_HASH: &str = "A0E5C99C7AB8CB6FA6932434F1B7363141106DB699E39D64A34899B5CBAA2953";
+
+/// A writer that retains everything written to it, for inspection once tracing completes.
+#[derive(Clone, Default)]
+struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+impl CapturedOutput {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .expect("the output should be utf-8")
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+}
+impl Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+impl<'a> MakeWriter<'a> for CapturedOutput {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// Runs the closure with a subscriber that formats every debug or more severe event into the
+/// produced output.
+fn capture(run: impl FnOnce()) -> CapturedOutput {
+    let output = CapturedOutput::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(output.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, run);
+    output
+}
+
+#[test]
+fn test_recoverable_issues_are_warned_with_their_fields() {
+    let events = vec![Event::new("wasm")
+        .add_attributes(OsGatewayAttributeGenerator::access_grant(
+            "not_a_scope",
+            ACCOUNT_ADDRESS,
+        ))
+        .add_attribute("object_store_gateway_note", "shared")];
+    let output = capture(|| {
+        let parsed = tx_span(TX_HASH, Some(100)).in_scope(|| parse_gateway_events(&events));
+        assert!(parsed[0].is_ok(), "the grant should be parsed leniently");
+    });
+    let lines = output.lines();
+    let warning = lines
+        .iter()
+        .find(|line| line.contains("WARN"))
+        .unwrap_or_else(|| panic!("a warning should be emitted, not {lines:?}"));
+    for expected in [
+        &format!("os_gateway_tx{{tx_hash=\"{TX_HASH}\" height=100}}"),
+        "os_gateway_parse{event_type=\"access_grant\" scope_address=\"not_a_scope\"}",
+        "field_errors=[FieldError { key: ScopeAddress, kind: InvalidScopeAddress",
+        "unknown_keys=[\"object_store_gateway_note\"]",
+    ] {
+        assert!(
+            warning.contains(expected),
+            "the warning should contain [{expected}], but was [{warning}]",
+        );
+    }
+}
+
+#[test]
+fn test_outcomes_and_validation_are_traced() {
+    let valid = OsGatewayAttributeGenerator::access_grant(SCOPE_ADDRESS, ACCOUNT_ADDRESS);
+    let output = capture(|| {
+        parse_gateway_events(&[
+            valid.to_event(),
+            Event::new("wasm").add_attribute("object_store_gateway_event_type", "access_grant"),
+        ]);
+        assert!(valid.validate().is_ok(), "the grant should be valid");
+    });
+    let lines = output.lines();
+    assert!(
+        !lines.iter().any(|line| line.contains("WARN")),
+        "a valid grant should not be warned of, but the output was {lines:?}",
+    );
+    assert!(
+        lines.iter().any(|line| line.contains(&format!(
+            "os_gateway_parse{{event_type=\"access_grant\" scope_address=\"{SCOPE_ADDRESS}\"}}: "
+        )) && line.contains("parsed a gateway event")),
+        "the parsed grant should be traced with its fields, but the output was {lines:?}",
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains("rejected a gateway event") && line.contains("error=")),
+        "the rejected event should be traced with its error, but the output was {lines:?}",
+    );
+}
@@ -0,0 +1,5 @@
+use os_gateway_contract_attributes::os_gateway_revoke;
+
+fn main() {
+    let _ = os_gateway_revoke! { scope: "scope" };
+}
@@ -0,0 +1,9 @@
+use os_gateway_contract_attributes::os_gateway_grant;
+
+fn main() {
+    let _ = os_gateway_grant! {
+        scope: "scope",
+        target: "target",
+        grant: "grant_id",
+    };
+}